@@ -1,5 +1,6 @@
 # Meta-stuff
 complete -c eza -s v -l version -d "Show version of eza"
+complete -c eza -l explain -d "Show the options eza would use and where they came from"
 complete -c eza -l help -d "Show list of command-line options"
 
 # Display options
@@ -9,55 +10,109 @@ complete -c eza -s G -l grid -d "Display entries in a grid"
 complete -c eza -s x -l across -d "Sort the grid across, rather than downwards"
 complete -c eza -s R -l recurse -d "Recurse into directories"
 complete -c eza -s T -l tree -d "Recurse into directories as a tree"
+complete -c eza -l collapse -d "Show single-directory chains on one line in a tree"
+complete -c eza -l tree-format -d "How to write a tree" -x -a "
+    tree\t'Draw the tree with lines'
+    indent\t'Indent entries so they can be read back in'
+"
+complete -c eza -l format-template -d "Write one line per file from a template" -x
+complete -c eza -l csv -d "Write the long view's columns as CSV"
+complete -c eza -l csv-delimiter -d "Separate CSV fields with a character" -x
 complete -c eza -s X -l dereference -d "Dereference symbolic links when displaying file information"
 complete -c eza -s F -l classify -d "Display type indicator by file names"
+complete -c eza -l classify-link-targets -d "Also display type indicators of symlink targets"
+complete -c eza -l classify-xattrs -d "Also display @ after files with extended attributes"
+complete -c eza -l resolve-link-targets -d "Show relative symlink targets from the current directory"
+complete -c eza -s p -l indicator-style -d "Display a slash after directory names only" -a "
+    slash\t'Display a slash after directory names'
+    classify\t'Display type indicators by all file names'
+    none\t'Display no indicators'
+"
 complete -c eza -l color \
     -l colour -d "When to use terminal colours" -x -a "
     always\t'Always use colour'
     auto\t'Use colour if standard output is a terminal'
     automatic\t'Use colour if standard output is a terminal'
     never\t'Never use colour'
+    html\t'Write an HTML listing with inline styles'
+    pango\t'Write Pango markup for GTK programs'
 "
 complete -c eza -l color-scale \
     -l colour-scale -d "Highlight levels 'field' distinctly" -x -a "
     all\t''
     age\t''
+    age:mtime\t'Shade ages by modification time'
+    age:atime\t'Shade ages by access time'
+    age:ctime\t'Shade ages by change time'
     size\t''
+    inode\t''
+    links\t''
+    none\t''
 "
 complete -c eza -l color-scale-mode \
     -l colour-scale-mode \
     -d "Use gradient or fixed colors in --color-scale" -x -a "
     fixed\t'Highlight based on fixed colors'
     gradient\t'Highlight based \'field\' in relation to other files'
+    logarithmic\t'Highlight based \'field\' in orders of magnitude'
 "
+complete -c eza -l highlight-risky -d "Highlight world-writable, setuid, and setgid files"
 complete -c eza -l icons -d "When to display icons" -x -a "
   always\t'Always display icons'
   auto\t'Display icons if standard output is a terminal'
   automatic\t'Display icons if standard output is a terminal'
   never\t'Never display icons'
+  ascii\t'Display basic icons if standard output is a terminal'
 "
+complete -c eza -l icon-spacing -d "Number of spaces between icons and file names" -x
 complete -c eza -l no-quotes -d "Don't quote file names with spaces"
-complete -c eza -l hyperlink -d "Display entries as hyperlinks"
+complete -c eza -l hyperlink -d "Display entries as hyperlinks" -x -a "
+  always\t'Always display hyperlinks'
+  auto\t'Display hyperlinks unless colours are disabled'
+  automatic\t'Display hyperlinks unless colours are disabled'
+  never\t'Never display hyperlinks'
+"
+complete -c eza -l full-paths -d "Show the path of each entry when recursing"
 complete -c eza -l absolute -d "Display entries with their absolute path" -x -a "
   on\t'Show absolute path for listed entries'
   follow\t'Show absolute path with followed symlinks'
   off\t'Do not show the absolute path'
 "
 complete -c eza -l smart-group -d "Only show group if it has a different name from owner"
+complete -c eza -l owner-format -d "How to show the user and group" -x -a "
+    separate\t'A column each'
+    combined\t'One user:group column'
+"
 
 # Filtering and sorting options
-complete -c eza -l group-directories-first -d "Sort directories before other files"
+complete -c eza -l group-directories-first -l gdf -d "Sort directories before other files"
+complete -c eza -l group-directories-last -d "Sort directories after other files"
+complete -c eza -l group-directories -d "What to group as directories" -x -a "
+    dirs\t'Group only directories'
+    with-links\t'Group symlinks to directories too'
+"
 complete -c eza -l git-ignore -d "Ignore files mentioned in '.gitignore'"
+complete -c eza -l mark-ignored -d "Dim files mentioned in '.gitignore'"
 complete -c eza -s a -l all -d "Show hidden and 'dot' files. Use this twice to also show the '.' and '..' directories"
 complete -c eza -s A -l almost-all -d "Equivalent to --all; included for compatibility with `ls -A`"
 complete -c eza -s d -l list-dirs -d "List directories like regular files"
 complete -c eza -s L -l level -d "Limit the depth of recursion" -x -a "1 2 3 4 5 6 7 8 9"
-complete -c eza -s w -l width -d "Limits column output of grid, 0 implies auto-width"
+complete -c eza -l summarize-dirs -d "Follow each directory's contents with a summary"
+complete -c eza -l skip-unreadable -d "Skip directories that can't be read when recursing"
+complete -c eza -l per-dir-limit -d "Show at most this many entries per directory when recursing" -x
+complete -c eza -l tree-summary -d "End a recursive listing with a tree of the directories"
+complete -c eza -l progress -d "Count the directories scanned so far on stderr when recursing"
+complete -c eza -l global-sort -d "Sort every entry together when recursing"
+complete -c eza -s w -l width -d "Limits column output of grid, 0, max or auto imply the full terminal width, a negative number leaves a margin"
+complete -c eza -l min-width -d "Never use fewer than this many columns" -x
+complete -c eza -l max-width -d "Never use more than this many columns" -x
+complete -c eza -l max-output -d "Stop printing after about this many bytes" -x
 complete -c eza -s r -l reverse -d "Reverse the sort order"
 complete -c eza -s s -l sort -d "Which field to sort by" -x -a "
     accessed\t'Sort by file accessed time'
     age\t'Sort by file modified time (newest first)'
     changed\t'Sort by changed time'
+    children\t'Sort directories by number of entries'
     created\t'Sort by file modified time'
     date\t'Sort by file modified time'
     ext\t'Sort by file extension'
@@ -66,6 +121,8 @@ complete -c eza -s s -l sort -d "Which field to sort by" -x -a "
     Extension\t'Sort by file extension (uppercase first)'
     filename\t'Sort by filename'
     Filename\t'Sort by filename (uppercase first)'
+    git\t'Sort by Git status'
+    group\t'Sort by file group'
     inode\t'Sort by file inode'
     modified\t'Sort by file modified time'
     name\t'Sort by filename'
@@ -73,32 +130,49 @@ complete -c eza -s s -l sort -d "Which field to sort by" -x -a "
     newest\t'Sort by file modified time (newest first)'
     none\t'Do not sort files at all'
     oldest\t'Sort by file modified time'
+    owner\t'Sort by file owner'
+    random\t'Shuffle the files'
     size\t'Sort by file size'
     time\t'Sort by file modified time'
-    type\t'Sort by file type'
+    type\t'Sort by file type: directories, links, files, then others'
+    user\t'Sort by file owner'
 "
 
 complete -c eza -s I -l ignore-glob -d "Ignore files that match these glob patterns" -r
+complete -c eza -l prune-dirs -d "Don't recurse into directories that match these glob patterns" -r
 complete -c eza -s D -l only-dirs -d "List only directories"
 complete -c eza -s f -l only-files -d "List only files"
+complete -c eza -l seed -d "Shuffle the same way every time with --sort=random" -x
+complete -c eza -l min-links -d "List only files with at least this many hard links" -x
 
 # Long view options
 complete -c eza -s b -l binary -d "List file sizes with binary prefixes"
 complete -c eza -s B -l bytes -d "List file sizes in bytes, without any prefixes"
+complete -c eza -l size-unit -d "List every file size in the same unit" -x -a "k m g t ki mi gi ti"
 complete -c eza -s g -l group -d "List each file's group"
 complete -c eza -s h -l header -d "Add a header row to each column"
+complete -c eza -l depth -d "List how many levels below the arguments each file is"
+complete -c eza -l name-width -d "Pad or truncate file names to exactly N columns" -x
+complete -c eza -l ellipsis -d "What to end truncated names with" -x
 complete -c eza -s H -l links -d "List each file's number of hard links"
 complete -c eza -s i -l inode -d "List each file's inode number"
+complete -c eza -l inode-format -d "How to list inode numbers" -x -a "
+    dec\t'Show inode numbers in decimal'
+    hex\t'Show inode numbers in hexadecimal'
+"
 complete -c eza -s S -l blocksize -d "List each file's size of allocated file system blocks"
+complete -c eza -l io-blocksize -d "List each file's preferred I/O block size"
 complete -c eza -s t -l time -d "Which timestamp field to list" -x -a "
     modified\t'Display modified time'
     changed\t'Display changed time'
     accessed\t'Display accessed time'
     created\t'Display created time'
+    all\t'Display every time'
 "
 complete -c eza -s m -l modified -d "Use the modified timestamp field"
 complete -c eza -s n -l numeric -d "List numeric user and group IDs."
 complete -c eza -l changed -d "Use the changed timestamp field"
+complete -c eza -l all-times -d "Use every timestamp field"
 complete -c eza -s u -l accessed -d "Use the accessed timestamp field"
 complete -c eza -s U -l created -d "Use the created timestamp field"
 complete -c eza -l time-style -d "How to format timestamps" -x -a "
@@ -107,21 +181,60 @@ complete -c eza -l time-style -d "How to format timestamps" -x -a "
     long-iso\t'Display longer ISO timestamps, up to the minute'
     full-iso\t'Display full ISO timestamps, up to the nanosecond'
     relative\t'Display relative timestamps'
+    relative-aligned\t'Display relative timestamps that line up'
+    combined\t'Display all timestamps in one column, compactly'
+    utc\t'Use the default time style, in UTC'
     +FORMAT\t'Use custom time style'
 "
+complete -c eza -l no-recent-style -d "Use the first line of a custom time style for recent files too"
 complete -c eza -l total-size -d "Show recursive directory size (unix only)"
+complete -c eza -l dir-size -d "What to show as the size of a directory" -x -a "
+    none\t'Show a dash'
+    inode\t'Show the size of the directory entry'
+    total\t'Show the size of everything inside'
+"
+complete -c eza -l grid-rows-threshold -d "Only use the grid-details view if it would produce this many rows" -x
+complete -c eza -l align -d "Override the alignment of the named columns" -x
+complete -c eza -l columns -d "Show exactly the named columns, in order" -x
+complete -c eza -l mime -d "List each file's MIME type"
+complete -c eza -l flags-format -d "How to list Windows attributes" -x -a "
+    long\t'A comma-separated list of descriptions'
+    short\t'A single character for each attribute'
+"
+complete -c eza -l checksum -d "List a digest of each file's contents" -x -a "
+    crc32\t'CRC32, as used by zip and gzip'
+    md5\t'MD5'
+    sha256\t'SHA-256'
+"
+complete -c eza -l peek-archives -d "List the top-level contents of .zip and .tar files"
 complete -c eza -l no-permissions -d "Suppress the permissions field"
 complete -c eza -s o -l octal-permissions -d "List each file's permission in octal format"
 complete -c eza -l no-filesize -d "Suppress the filesize field"
 complete -c eza -l no-user -d "Suppress the user field"
 complete -c eza -l no-time -d "Suppress the time field"
+complete -c eza -l no-group -d "Suppress the group field"
+complete -c eza -l no-inode -d "Suppress the inode field"
+complete -c eza -l no-links -d "Suppress the hard links field"
+complete -c eza -l no-flags -d "Suppress the file flags field"
 complete -c eza -s M -l mounts -d "Show mount details"
 complete -c eza -l stdin -d "When piping to eza. Read file names from stdin"
+complete -c eza -l from-tree -d "Draw a tree written with --tree-format=indent" -r -F
 
 # Optional extras
 complete -c eza -l git -d "List each file's Git status, if tracked"
 complete -c eza -l no-git -d "Suppress Git status"
 complete -c eza -l git-repos -d "List each git-repos status and branch name"
 complete -c eza -l git-repos-no-status -d "List each git-repos branch name (much faster)"
+complete -c eza -l git-col -d "Where to put the Git status column" -x -a "
+  default\t'After the other columns'
+  left\t'Just before the file name'
+"
+complete -c eza -l git-summary -d "Count files by Git status after the table" -x -a "
+  always\t'Always show the count'
+  auto\t'Show the count if standard output is a terminal'
+  automatic\t'Show the count if standard output is a terminal'
+  never\t'Never show the count'
+"
 complete -c eza -s '@' -l extended -d "List each file's extended attributes and sizes"
 complete -c eza -s Z -l context -d "List each file's security context"
+complete -c eza -l finder-tags -d "List each file's Finder tags"
@@ -1,5 +1,6 @@
 use crate::fs::feature::git::GitCache;
 use crate::fs::fields::GitStatus;
+use std::collections::HashSet;
 use std::fs;
 use std::io;
 use std::path::{Path, PathBuf};
@@ -235,3 +236,127 @@ impl DotFilter {
         }
     }
 }
+
+/// Identifies a directory by its device and inode numbers, which stay the
+/// same however it was reached.
+#[derive(PartialEq, Eq, Hash, Debug, Copy, Clone)]
+pub struct DirId {
+    dev: u64,
+    ino: u64,
+}
+
+impl DirId {
+    /// Gets the ID of the directory with the given metadata. Only Unix has
+    /// inode numbers, so there’s nothing to go on elsewhere.
+    #[cfg(unix)]
+    pub fn from_metadata(metadata: &fs::Metadata) -> Option<Self> {
+        use std::os::unix::fs::MetadataExt;
+
+        Some(Self {
+            dev: metadata.dev(),
+            ino: metadata.ino(),
+        })
+    }
+
+    #[cfg(not(unix))]
+    pub fn from_metadata(_metadata: &fs::Metadata) -> Option<Self> {
+        None
+    }
+
+    /// Gets the ID of the directory at the given path, following symlinks.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        fs::metadata(path)
+            .ok()
+            .as_ref()
+            .and_then(Self::from_metadata)
+    }
+}
+
+/// The directories that have been entered on the way down to the one being
+/// listed when recursing. Some filesystems allow hard links to directories,
+/// which, unlike symlinks, look just like any other directory, so a loop
+/// made of them can only be spotted by seeing the same directory again.
+#[derive(Debug, Default, Clone)]
+pub struct Ancestors(Vec<DirId>);
+
+impl Ancestors {
+    /// Whether the directory with this ID has already been entered.
+    pub fn contains(&self, id: Option<DirId>) -> bool {
+        id.is_some_and(|id| self.0.contains(&id))
+    }
+
+    /// Returns these ancestors with another directory entered.
+    pub fn with(&self, id: Option<DirId>) -> Self {
+        let mut ancestors = self.clone();
+        ancestors.0.extend(id);
+        ancestors
+    }
+
+    /// The error given for a directory that isn’t entered because it has
+    /// been entered already, further up the tree.
+    pub fn loop_error() -> io::Error {
+        io::Error::new(
+            io::ErrorKind::Other,
+            "not entering directory, as it loops back to one of its parents",
+        )
+    }
+}
+
+/// The directories that have been listed so far when recursing. Path
+/// arguments can overlap, such as `eza -R a a/b`, or a symlink argument can
+/// point into another argument’s tree, so the same directory can be reached
+/// more than once; it only gets listed the first time.
+#[derive(Debug, Default)]
+pub struct Listed(HashSet<DirId>);
+
+impl Listed {
+    /// Records the directory with this ID as listed, returning whether it
+    /// hadn’t been already. Directories without an ID can’t be told apart,
+    /// so they always get listed.
+    pub fn insert(&mut self, id: Option<DirId>) -> bool {
+        match id {
+            Some(id) => self.0.insert(id),
+            None => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod ancestors_test {
+    use super::*;
+
+    fn id(ino: u64) -> Option<DirId> {
+        Some(DirId { dev: 1, ino })
+    }
+
+    #[test]
+    fn entered() {
+        let ancestors = Ancestors::default().with(id(1)).with(id(2));
+        assert!(ancestors.contains(id(1)));
+        assert!(ancestors.contains(id(2)));
+        assert!(!ancestors.contains(id(3)));
+    }
+
+    #[test]
+    fn without_ids() {
+        let ancestors = Ancestors::default().with(None).with(id(1));
+        assert!(ancestors.contains(id(1)));
+        assert!(!ancestors.contains(None));
+    }
+
+    #[test]
+    fn different_devices() {
+        let ancestors = Ancestors::default().with(id(1));
+        assert!(!ancestors.contains(Some(DirId { dev: 2, ino: 1 })));
+    }
+
+    #[test]
+    fn listed_once() {
+        let mut listed = Listed::default();
+        assert!(listed.insert(id(1)));
+        assert!(listed.insert(id(2)));
+        assert!(!listed.insert(id(1)));
+        assert!(listed.insert(None));
+        assert!(listed.insert(None));
+    }
+}
@@ -1,5 +1,7 @@
 //! What to do when encountering a directory?
 
+use std::io;
+
 /// The action to take when trying to list a file that turns out to be a
 /// directory.
 ///
@@ -55,7 +57,7 @@ impl DirAction {
 }
 
 /// The options that determine how to recurse into a directory.
-#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+#[derive(PartialEq, Eq, Debug, Copy, Clone, Default)]
 pub struct RecurseOptions {
     /// Whether recursion should be done as a tree or as multiple individual
     /// views of files.
@@ -64,6 +66,35 @@ pub struct RecurseOptions {
     /// The maximum number of times that recursion should descend to, if one
     /// is specified.
     pub max_depth: Option<usize>,
+
+    /// Whether to print a line summarising each directory after its
+    /// contents. This does nothing when recursing as a tree.
+    pub summarize: bool,
+
+    /// Whether to silently pass over directories that can’t be read,
+    /// instead of reporting an error for each of them.
+    pub skip_unreadable: bool,
+
+    /// Whether to show a directory that holds nothing but one other
+    /// directory on the same line as it. This only works as a tree.
+    pub collapse: bool,
+
+    /// The most entries to show for each directory, with a note saying how
+    /// many more there were after them.
+    pub per_dir_limit: Option<usize>,
+
+    /// Whether to follow the listing with a tree of just the directories
+    /// under each argument. This does nothing when recursing as a tree.
+    pub tree_summary: bool,
+
+    /// Whether to keep a count of the directories scanned so far on
+    /// stderr, while they’re being read.
+    pub progress: bool,
+
+    /// Whether to list every entry under the arguments as one list, sorted
+    /// together, instead of directory by directory. This does nothing when
+    /// recursing as a tree.
+    pub global_sort: bool,
 }
 
 impl RecurseOptions {
@@ -74,4 +105,49 @@ impl RecurseOptions {
             Some(d) => d <= depth,
         }
     }
+
+    /// Returns whether an error from opening a directory to recurse into it
+    /// should be ignored, rather than reported.
+    pub fn skips_error(self, error: &io::Error) -> bool {
+        self.skip_unreadable && error.kind() == io::ErrorKind::PermissionDenied
+    }
+
+    /// Cuts a directory’s sorted and filtered entries down to the
+    /// per-directory limit, returning how many were left out.
+    pub fn limit_entries<T>(self, entries: &mut Vec<T>) -> usize {
+        match self.per_dir_limit {
+            Some(limit) if entries.len() > limit => {
+                let hidden = entries.len() - limit;
+                entries.truncate(limit);
+                hidden
+            }
+            _ => 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn recurse(skip_unreadable: bool) -> RecurseOptions {
+        RecurseOptions {
+            tree: true,
+            skip_unreadable,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn skips_permission_denied() {
+        let error = io::Error::from(io::ErrorKind::PermissionDenied);
+        assert!(recurse(true).skips_error(&error));
+        assert!(!recurse(false).skips_error(&error));
+    }
+
+    #[test]
+    fn reports_other_errors() {
+        let error = io::Error::from(io::ErrorKind::NotFound);
+        assert!(!recurse(true).skips_error(&error));
+    }
 }
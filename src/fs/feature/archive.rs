@@ -0,0 +1,383 @@
+//! Peeking inside `.zip` and `.tar` archives without extracting them.
+//!
+//! Only the names of the entries at the top level of the archive are read,
+//! which keeps the cost bounded: for zip files, this means reading the
+//! central directory at the end of the file, and for tar files, reading each
+//! entry’s header and seeking past its contents.
+
+use std::fs;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::Path;
+
+/// An entry at the top level of an archive.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct ArchiveEntry {
+    /// The entry’s name, without any path separators.
+    pub name: String,
+
+    /// Whether this entry is a directory, either because the archive says so
+    /// or because other entries are stored underneath it.
+    pub is_directory: bool,
+}
+
+/// The archive formats that can be peeked into.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+enum Format {
+    Zip,
+    Tar,
+}
+
+impl Format {
+    /// Picks the archive format based on a file’s extension.
+    fn from_extension(ext: &str) -> Option<Self> {
+        match ext.to_ascii_lowercase().as_str() {
+            "zip" | "jar" => Some(Self::Zip),
+            "tar" => Some(Self::Tar),
+            _ => None,
+        }
+    }
+}
+
+/// Whether a file with the given extension is an archive that can be peeked
+/// into.
+pub fn is_archive(ext: Option<&str>) -> bool {
+    ext.and_then(Format::from_extension).is_some()
+}
+
+/// Reads the top-level entries of the archive at the given path, or returns
+/// `None` if it isn’t an archive that can be read. A corrupt archive results
+/// in an error rather than a panic.
+pub fn top_level_entries(path: &Path, ext: Option<&str>) -> Option<io::Result<Vec<ArchiveEntry>>> {
+    let format = ext.and_then(Format::from_extension)?;
+
+    Some(fs::File::open(path).and_then(|mut file| {
+        match format {
+            Format::Zip => zip_names(&mut file),
+            Format::Tar => tar_names(&mut file),
+        }
+        .map(|names| top_level(names.iter().map(String::as_str)))
+    }))
+}
+
+/// Collapses a list of paths inside an archive into the distinct entries at
+/// its top level, keeping the order in which they first appear.
+fn top_level<'a>(names: impl Iterator<Item = &'a str>) -> Vec<ArchiveEntry> {
+    let mut entries: Vec<ArchiveEntry> = Vec::new();
+
+    for name in names {
+        let name = name.trim_start_matches("./").trim_start_matches('/');
+        let (first, rest) = match name.split_once('/') {
+            Some((first, rest)) => (first, Some(rest)),
+            None => (name, None),
+        };
+
+        if first.is_empty() || first == "." {
+            continue;
+        }
+
+        // An entry with a trailing slash, or one with anything below it, has
+        // to be a directory.
+        let is_directory = rest.is_some();
+
+        if let Some(entry) = entries.iter_mut().find(|e| e.name == first) {
+            entry.is_directory |= is_directory;
+        } else {
+            entries.push(ArchiveEntry {
+                name: first.to_string(),
+                is_directory,
+            });
+        }
+    }
+
+    entries
+}
+
+fn corrupt(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+fn read_u16(bytes: &[u8], at: usize) -> usize {
+    u16::from_le_bytes([bytes[at], bytes[at + 1]]).into()
+}
+
+fn read_u32(bytes: &[u8], at: usize) -> u64 {
+    u32::from_le_bytes([bytes[at], bytes[at + 1], bytes[at + 2], bytes[at + 3]]).into()
+}
+
+/// Reads the names of every entry in a zip file from its central directory.
+fn zip_names<R: Read + Seek>(reader: &mut R) -> io::Result<Vec<String>> {
+    const END_SIGNATURE: &[u8] = b"PK\x05\x06";
+    const ENTRY_SIGNATURE: &[u8] = b"PK\x01\x02";
+    const END_LENGTH: u64 = 22;
+
+    // The end-of-central-directory record sits at the very end of the file,
+    // followed only by a comment of at most 64 KiB.
+    let length = reader.seek(SeekFrom::End(0))?;
+    let tail_length = length.min(END_LENGTH + u64::from(u16::MAX));
+    reader.seek(SeekFrom::Start(length - tail_length))?;
+    let mut tail = Vec::new();
+    reader.take(tail_length).read_to_end(&mut tail)?;
+
+    let end = tail
+        .windows(END_SIGNATURE.len())
+        .rposition(|w| w == END_SIGNATURE)
+        .filter(|&pos| tail.len() - pos >= END_LENGTH as usize)
+        .ok_or_else(|| corrupt("missing zip end of central directory"))?;
+
+    let count = read_u16(&tail, end + 10);
+    let directory_size = read_u32(&tail, end + 12);
+    let directory_offset = read_u32(&tail, end + 16);
+
+    if directory_offset + directory_size > length {
+        return Err(corrupt("zip central directory is out of bounds"));
+    }
+
+    reader.seek(SeekFrom::Start(directory_offset))?;
+    let mut directory = Vec::new();
+    reader.take(directory_size).read_to_end(&mut directory)?;
+
+    let mut names = Vec::with_capacity(count);
+    let mut pos = 0;
+    for _ in 0..count {
+        if directory.len() < pos + 46 || &directory[pos..pos + 4] != ENTRY_SIGNATURE {
+            return Err(corrupt("invalid zip central directory entry"));
+        }
+
+        let name_length = read_u16(&directory, pos + 28);
+        let extra_length = read_u16(&directory, pos + 30);
+        let comment_length = read_u16(&directory, pos + 32);
+
+        let name = directory
+            .get(pos + 46..pos + 46 + name_length)
+            .ok_or_else(|| corrupt("truncated zip entry name"))?;
+        names.push(String::from_utf8_lossy(name).into_owned());
+
+        pos += 46 + name_length + extra_length + comment_length;
+    }
+
+    Ok(names)
+}
+
+/// Reads the names of every entry in a tar file from their headers.
+fn tar_names<R: Read + Seek>(reader: &mut R) -> io::Result<Vec<String>> {
+    const BLOCK: u64 = 512;
+
+    let mut names = Vec::new();
+    let mut long_name = None;
+    let mut header = [0_u8; BLOCK as usize];
+
+    loop {
+        match reader.read_exact(&mut header) {
+            Ok(()) => {}
+            // Some writers leave out the trailing empty blocks.
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof && !names.is_empty() => break,
+            Err(e) => return Err(e),
+        }
+
+        if header.iter().all(|&b| b == 0) {
+            break;
+        }
+
+        let checksum = octal(&header[148..156])?;
+        let actual: u64 = header
+            .iter()
+            .enumerate()
+            .map(|(i, &b)| {
+                if (148..156).contains(&i) {
+                    u64::from(b' ')
+                } else {
+                    u64::from(b)
+                }
+            })
+            .sum();
+        if checksum != actual {
+            return Err(corrupt("invalid tar header checksum"));
+        }
+
+        let size = octal(&header[124..136])?;
+        let padded_size = (size + BLOCK - 1) / BLOCK * BLOCK;
+
+        match header[156] {
+            // A GNU long name, which holds the name of the next entry.
+            b'L' => {
+                let mut name = Vec::new();
+                reader.by_ref().take(size).read_to_end(&mut name)?;
+                reader.seek(SeekFrom::Current((padded_size - size) as i64))?;
+                long_name = Some(String::from_utf8_lossy(until_nul(&name)).into_owned());
+                continue;
+            }
+
+            // Extended headers that describe other entries.
+            b'x' | b'g' | b'K' => {}
+
+            typeflag => {
+                let mut name = long_name.take().unwrap_or_else(|| {
+                    let name = String::from_utf8_lossy(until_nul(&header[0..100]));
+                    let prefix = String::from_utf8_lossy(until_nul(&header[345..500]));
+                    if &header[257..262] == b"ustar" && !prefix.is_empty() {
+                        format!("{prefix}/{name}")
+                    } else {
+                        name.into_owned()
+                    }
+                });
+
+                if typeflag == b'5' && !name.ends_with('/') {
+                    name.push('/');
+                }
+
+                names.push(name);
+            }
+        }
+
+        reader.seek(SeekFrom::Current(padded_size as i64))?;
+    }
+
+    Ok(names)
+}
+
+fn until_nul(bytes: &[u8]) -> &[u8] {
+    let end = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+    &bytes[..end]
+}
+
+/// Parses an octal number from a tar header field, which is padded with
+/// spaces or NUL bytes.
+fn octal(bytes: &[u8]) -> io::Result<u64> {
+    let digits = String::from_utf8_lossy(until_nul(bytes));
+    let digits = digits.trim();
+
+    if digits.is_empty() {
+        return Ok(0);
+    }
+
+    u64::from_str_radix(digits, 8).map_err(|_| corrupt("invalid number in tar header"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::io::Cursor;
+
+    fn entry(name: &str, is_directory: bool) -> ArchiveEntry {
+        ArchiveEntry {
+            name: name.into(),
+            is_directory,
+        }
+    }
+
+    /// Builds a zip file containing just a central directory, which is all
+    /// that gets read.
+    fn zip(names: &[&str]) -> Vec<u8> {
+        let mut directory = Vec::new();
+        for name in names {
+            directory.extend_from_slice(b"PK\x01\x02");
+            directory.extend_from_slice(&[0; 24]);
+            directory.extend_from_slice(&(name.len() as u16).to_le_bytes());
+            directory.extend_from_slice(&[0; 16]);
+            directory.extend_from_slice(name.as_bytes());
+        }
+
+        let mut bytes = b"local file data".to_vec();
+        let offset = bytes.len() as u32;
+        bytes.extend_from_slice(&directory);
+        bytes.extend_from_slice(b"PK\x05\x06");
+        bytes.extend_from_slice(&[0; 4]);
+        bytes.extend_from_slice(&(names.len() as u16).to_le_bytes());
+        bytes.extend_from_slice(&(names.len() as u16).to_le_bytes());
+        bytes.extend_from_slice(&(directory.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(&offset.to_le_bytes());
+        bytes.extend_from_slice(&[0; 2]);
+        bytes
+    }
+
+    /// Builds a tar file with a header and contents for each entry.
+    fn tar(entries: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        for (name, contents) in entries {
+            let mut header = [0_u8; 512];
+            header[..name.len()].copy_from_slice(name.as_bytes());
+            header[124..135].copy_from_slice(format!("{:011o}", contents.len()).as_bytes());
+            header[156] = if name.ends_with('/') { b'5' } else { b'0' };
+            header[257..262].copy_from_slice(b"ustar");
+            header[148..156].copy_from_slice(b"        ");
+            let checksum: u32 = header.iter().map(|&b| u32::from(b)).sum();
+            header[148..155].copy_from_slice(format!("{checksum:06o}\0").as_bytes());
+
+            bytes.extend_from_slice(&header);
+            bytes.extend_from_slice(contents);
+            bytes.resize((bytes.len() + 511) / 512 * 512, 0);
+        }
+        bytes.extend_from_slice(&[0; 1024]);
+        bytes
+    }
+
+    #[test]
+    fn zip_top_level() {
+        let bytes = zip(&[
+            "README.md",
+            "src/",
+            "src/main.rs",
+            "src/lib.rs",
+            "docs/a.md",
+        ]);
+        let names = zip_names(&mut Cursor::new(bytes)).unwrap();
+        assert_eq!(
+            top_level(names.iter().map(String::as_str)),
+            vec![
+                entry("README.md", false),
+                entry("src", true),
+                entry("docs", true)
+            ]
+        );
+    }
+
+    #[test]
+    fn zip_empty() {
+        let names = zip_names(&mut Cursor::new(zip(&[]))).unwrap();
+        assert!(names.is_empty());
+    }
+
+    #[test]
+    fn zip_corrupt() {
+        let mut bytes = zip(&["README.md"]);
+        bytes.truncate(bytes.len() - 10);
+        assert!(zip_names(&mut Cursor::new(bytes)).is_err());
+        assert!(zip_names(&mut Cursor::new(b"not a zip".to_vec())).is_err());
+    }
+
+    #[test]
+    fn tar_top_level() {
+        let bytes = tar(&[
+            ("./", b""),
+            ("./notes.txt", b"some notes"),
+            ("./src/", b""),
+            ("./src/main.rs", &[b'x'; 600]),
+            ("./Cargo.toml", b"[package]"),
+        ]);
+        let names = tar_names(&mut Cursor::new(bytes)).unwrap();
+        assert_eq!(
+            top_level(names.iter().map(String::as_str)),
+            vec![
+                entry("notes.txt", false),
+                entry("src", true),
+                entry("Cargo.toml", false)
+            ]
+        );
+    }
+
+    #[test]
+    fn tar_corrupt() {
+        let mut bytes = tar(&[("notes.txt", b"some notes")]);
+        bytes[0] = b'N';
+        assert!(tar_names(&mut Cursor::new(bytes)).is_err());
+        assert!(tar_names(&mut Cursor::new(b"not a tar".to_vec())).is_err());
+    }
+
+    #[test]
+    fn extensions() {
+        assert!(is_archive(Some("zip")));
+        assert!(is_archive(Some("TAR")));
+        assert!(!is_archive(Some("gz")));
+        assert!(!is_archive(None));
+    }
+}
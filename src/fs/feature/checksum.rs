@@ -0,0 +1,157 @@
+//! Digests of files’ contents, for the checksum column.
+//!
+//! Files are read a buffer at a time, so even large ones never have to fit
+//! in memory all at once.
+
+use std::fmt::Write as _;
+use std::io::{self, Read};
+
+use md5::Md5;
+use sha2::{Digest, Sha256};
+
+/// How much of a file to read at a time.
+const BUFFER_SIZE: usize = 64 * 1024;
+
+/// Which digest to compute, from `--checksum`.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum Algorithm {
+    Crc32,
+    Md5,
+    Sha256,
+}
+
+impl Algorithm {
+    /// The text to print at the top of the column.
+    pub fn header(self) -> &'static str {
+        match self {
+            Self::Crc32 => "CRC32",
+            Self::Md5 => "MD5",
+            Self::Sha256 => "SHA256",
+        }
+    }
+
+    /// Reads everything from the reader and returns its digest, in
+    /// lowercase hexadecimal.
+    pub fn digest(self, mut reader: impl Read) -> io::Result<String> {
+        let mut hasher = match self {
+            Self::Crc32 => Hasher::Crc32(crc32fast::Hasher::new()),
+            Self::Md5 => Hasher::Md5(Md5::new()),
+            Self::Sha256 => Hasher::Sha256(Sha256::new()),
+        };
+
+        let mut buffer = vec![0; BUFFER_SIZE];
+        loop {
+            match reader.read(&mut buffer) {
+                Ok(0) => break,
+                Ok(count) => hasher.update(&buffer[..count]),
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+
+        let mut hex = String::new();
+        for byte in hasher.finish() {
+            let _ = write!(hex, "{byte:02x}");
+        }
+        Ok(hex)
+    }
+}
+
+enum Hasher {
+    Crc32(crc32fast::Hasher),
+    Md5(Md5),
+    Sha256(Sha256),
+}
+
+impl Hasher {
+    fn update(&mut self, data: &[u8]) {
+        match self {
+            Self::Crc32(crc) => crc.update(data),
+            Self::Md5(md5) => md5.update(data),
+            Self::Sha256(sha) => sha.update(data),
+        }
+    }
+
+    fn finish(self) -> Vec<u8> {
+        match self {
+            Self::Crc32(crc) => crc.finalize().to_be_bytes().to_vec(),
+            Self::Md5(md5) => md5.finalize().to_vec(),
+            Self::Sha256(sha) => sha.finalize().to_vec(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn digest(algorithm: Algorithm, data: &[u8]) -> String {
+        algorithm.digest(data).unwrap()
+    }
+
+    #[test]
+    fn crc32() {
+        assert_eq!(digest(Algorithm::Crc32, b""), "00000000");
+        assert_eq!(digest(Algorithm::Crc32, b"123456789"), "cbf43926");
+        assert_eq!(
+            digest(
+                Algorithm::Crc32,
+                b"The quick brown fox jumps over the lazy dog"
+            ),
+            "414fa339"
+        );
+    }
+
+    #[test]
+    fn md5() {
+        assert_eq!(
+            digest(Algorithm::Md5, b""),
+            "d41d8cd98f00b204e9800998ecf8427e"
+        );
+        assert_eq!(
+            digest(
+                Algorithm::Md5,
+                b"The quick brown fox jumps over the lazy dog"
+            ),
+            "9e107d9d372bb6826bd81d3542a419d6"
+        );
+    }
+
+    #[test]
+    fn sha256() {
+        assert_eq!(
+            digest(Algorithm::Sha256, b""),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        assert_eq!(
+            digest(Algorithm::Sha256, b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    /// A reader that hands out its data a few bytes at a time, so blocks
+    /// get split up between reads.
+    struct Trickle<'a>(&'a [u8]);
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let count = self.0.len().min(buf.len()).min(7);
+            buf[..count].copy_from_slice(&self.0[..count]);
+            self.0 = &self.0[count..];
+            Ok(count)
+        }
+    }
+
+    #[test]
+    fn streamed() {
+        let data = vec![b'a'; 1_000_000];
+        assert_eq!(
+            Algorithm::Md5.digest(Trickle(&data)).unwrap(),
+            "7707d6ae4e027c70eea2a935c2296f21"
+        );
+        assert_eq!(
+            Algorithm::Sha256.digest(Trickle(&data)).unwrap(),
+            "cdc76e5c9914fb9281a1c7e284d73e67f1809a48a497200e046d39ccc7112cd0"
+        );
+    }
+}
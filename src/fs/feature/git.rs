@@ -21,6 +21,10 @@ pub struct GitCache {
 
     /// Paths that we’ve confirmed do not have Git repositories underneath them.
     misses: Vec<PathBuf>,
+
+    /// The number of tracked files above which a repository is considered
+    /// too large to query for statuses, if a limit has been set.
+    max_files: Option<usize>,
 }
 
 impl GitCache {
@@ -32,9 +36,17 @@ impl GitCache {
         self.repos
             .iter()
             .find(|repo| repo.has_path(index))
-            .map(|repo| repo.search(index, prefix_lookup))
+            .map(|repo| repo.search(index, prefix_lookup, self.max_files))
             .unwrap_or_default()
     }
+
+    /// Skips querying any repository that tracks more than the given number
+    /// of files, as that can take several seconds.
+    #[must_use]
+    pub fn with_max_files(mut self, max_files: Option<usize>) -> Self {
+        self.max_files = max_files;
+        self
+    }
 }
 
 use std::iter::FromIterator;
@@ -47,6 +59,7 @@ impl FromIterator<PathBuf> for GitCache {
         let mut git = Self {
             repos: Vec::with_capacity(iter.size_hint().0),
             misses: Vec::new(),
+            max_files: None,
         };
 
         if let Ok(path) = env::var("GIT_DIR") {
@@ -140,7 +153,10 @@ impl GitRepo {
     /// The temporary `Processing` enum variant is used after the `git2`
     /// repository is moved out, but before the results have been moved in!
     /// See <https://stackoverflow.com/q/45985827/3484614>
-    fn search(&self, index: &Path, prefix_lookup: bool) -> f::Git {
+    ///
+    /// If the repository tracks more files than the given limit, it isn’t
+    /// queried at all, and every file in it shows up as unmodified.
+    fn search(&self, index: &Path, prefix_lookup: bool, max_files: Option<usize>) -> f::Git {
         use std::mem::replace;
 
         let mut contents = self.contents.lock().unwrap();
@@ -151,7 +167,16 @@ impl GitRepo {
 
         debug!("Querying Git repo {:?} for the first time", &self.workdir);
         let repo = replace(&mut *contents, GitContents::Processing).inner_repo();
-        let statuses = repo_to_statuses(&repo, &self.workdir);
+        let statuses = if too_many_files(max_files, || repo.index().ok().map(|i| i.len())) {
+            eprintln!(
+                "eza: not showing Git statuses for {}: it tracks more than {} files",
+                self.workdir.display(),
+                max_files.unwrap_or_default(),
+            );
+            Git::without_statuses(&self.workdir)
+        } else {
+            repo_to_statuses(&repo, &self.workdir)
+        };
         let result = statuses.status(index, prefix_lookup);
         let _processing = replace(&mut *contents, GitContents::After { statuses });
         result
@@ -211,6 +236,16 @@ impl GitContents {
     }
 }
 
+/// Whether a repository tracks more files than the limit, if there is one.
+/// The number of entries in the index is used as a cheap estimate, and is
+/// only counted when there’s a limit to compare it against.
+fn too_many_files(max_files: Option<usize>, tracked: impl FnOnce() -> Option<usize>) -> bool {
+    match max_files {
+        Some(max_files) => tracked().is_some_and(|count| count > max_files),
+        None => false,
+    }
+}
+
 /// Iterates through a repository’s statuses, consuming it and returning the
 /// mapping of files to their Git status.
 /// We will have already used the working directory at this point, so it gets
@@ -257,6 +292,13 @@ struct Git {
 }
 
 impl Git {
+    /// The statuses for a repository that wasn’t queried. The `.git`
+    /// directory is still ignored, just as it is by `repo_to_statuses`.
+    fn without_statuses(workdir: &Path) -> Self {
+        let statuses = vec![(workdir.join(".git"), git2::Status::IGNORED)];
+        Self { statuses }
+    }
+
     /// Get either the file or directory status for the given path.
     /// “Prefix lookup” means that it should report an aggregate status of all
     /// paths starting with the given prefix (in other words, a directory).
@@ -296,6 +338,10 @@ impl Git {
     /// modified if any file under it has the status modified), except for
     /// ignored status which applies to files under (for example, a directory
     /// is considered ignored if one of its parent directories is ignored).
+    ///
+    /// When the files under a directory have different statuses, the most
+    /// significant one wins: a conflict, then changes to tracked files, and
+    /// only then new files.
     fn dir_status(&self, dir: &Path) -> f::Git {
         let path = reorient(dir);
 
@@ -347,30 +393,35 @@ fn reorient(path: &Path) -> PathBuf {
 }
 
 /// The character to display if the file has been modified, but not staged.
+/// A merge conflict takes precedence over everything else, as Git can report
+/// a conflicted file as modified too. Changes to tracked files come before
+/// untracked ones, so a directory’s rolled-up status shows the former.
 fn working_tree_status(status: git2::Status) -> f::GitStatus {
     #[rustfmt::skip]
     return match status {
-        s if s.contains(git2::Status::WT_NEW)         => f::GitStatus::New,
+        s if s.contains(git2::Status::CONFLICTED)     => f::GitStatus::Conflicted,
         s if s.contains(git2::Status::WT_MODIFIED)    => f::GitStatus::Modified,
         s if s.contains(git2::Status::WT_DELETED)     => f::GitStatus::Deleted,
         s if s.contains(git2::Status::WT_RENAMED)     => f::GitStatus::Renamed,
         s if s.contains(git2::Status::WT_TYPECHANGE)  => f::GitStatus::TypeChange,
+        s if s.contains(git2::Status::WT_NEW)         => f::GitStatus::New,
         s if s.contains(git2::Status::IGNORED)        => f::GitStatus::Ignored,
-        s if s.contains(git2::Status::CONFLICTED)     => f::GitStatus::Conflicted,
         _                                             => f::GitStatus::NotModified,
     };
 }
 
 /// The character to display if the file has been modified and the change
-/// has been staged.
+/// has been staged. An unmerged file can’t be staged, so it shows up as
+/// conflicted here as well.
 fn index_status(status: git2::Status) -> f::GitStatus {
     #[rustfmt::skip]
     return match status {
-        s if s.contains(git2::Status::INDEX_NEW)         => f::GitStatus::New,
+        s if s.contains(git2::Status::CONFLICTED)        => f::GitStatus::Conflicted,
         s if s.contains(git2::Status::INDEX_MODIFIED)    => f::GitStatus::Modified,
         s if s.contains(git2::Status::INDEX_DELETED)     => f::GitStatus::Deleted,
         s if s.contains(git2::Status::INDEX_RENAMED)     => f::GitStatus::Renamed,
         s if s.contains(git2::Status::INDEX_TYPECHANGE)  => f::GitStatus::TypeChange,
+        s if s.contains(git2::Status::INDEX_NEW)         => f::GitStatus::New,
         _                                                => f::GitStatus::NotModified,
     };
 }
@@ -442,3 +493,103 @@ impl f::SubdirGitRepo {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{index_status, too_many_files, working_tree_status, Git};
+    use crate::fs::fields as f;
+    use git2::Status;
+    use std::path::PathBuf;
+
+    #[test]
+    fn conflicted() {
+        let s = Status::CONFLICTED;
+        assert!(working_tree_status(s) == f::GitStatus::Conflicted);
+        assert!(index_status(s) == f::GitStatus::Conflicted);
+    }
+
+    #[test]
+    fn conflicted_and_modified() {
+        let s = Status::CONFLICTED | Status::WT_MODIFIED | Status::INDEX_MODIFIED;
+        assert!(working_tree_status(s) == f::GitStatus::Conflicted);
+        assert!(index_status(s) == f::GitStatus::Conflicted);
+    }
+
+    #[test]
+    fn modified() {
+        let s = Status::WT_MODIFIED;
+        assert!(working_tree_status(s) == f::GitStatus::Modified);
+        assert!(index_status(s) == f::GitStatus::NotModified);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn modified_in_directory() {
+        let git = Git {
+            statuses: vec![
+                (PathBuf::from("/repo/dir/file"), Status::WT_MODIFIED),
+                (PathBuf::from("/repo/other"), Status::WT_DELETED),
+            ],
+        };
+
+        let status = git.dir_status(&PathBuf::from("/repo/dir"));
+        assert!(status.unstaged == f::GitStatus::Modified);
+        assert!(status.staged == f::GitStatus::NotModified);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn tracked_changes_before_new_files() {
+        let git = Git {
+            statuses: vec![
+                (
+                    PathBuf::from("/repo/dir/new"),
+                    Status::WT_NEW | Status::INDEX_NEW,
+                ),
+                (PathBuf::from("/repo/dir/sub/file"), Status::WT_MODIFIED),
+                (PathBuf::from("/repo/dir/staged"), Status::INDEX_MODIFIED),
+            ],
+        };
+
+        let status = git.dir_status(&PathBuf::from("/repo/dir"));
+        assert!(status.unstaged == f::GitStatus::Modified);
+        assert!(status.staged == f::GitStatus::Modified);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn conflict_in_directory() {
+        let git = Git {
+            statuses: vec![
+                (PathBuf::from("/repo/dir/new"), Status::WT_NEW),
+                (PathBuf::from("/repo/dir/both"), Status::CONFLICTED),
+            ],
+        };
+
+        let status = git.dir_status(&PathBuf::from("/repo/dir"));
+        assert!(status.unstaged == f::GitStatus::Conflicted);
+        assert!(status.staged == f::GitStatus::Conflicted);
+    }
+
+    #[test]
+    fn no_limit() {
+        assert!(!too_many_files(None, || panic!(
+            "Counted files without a limit"
+        )));
+    }
+
+    #[test]
+    fn under_limit() {
+        assert!(!too_many_files(Some(1000), || Some(1000)));
+    }
+
+    #[test]
+    fn large_repo_skipped() {
+        assert!(too_many_files(Some(1000), || Some(250_000)));
+    }
+
+    #[test]
+    fn unreadable_index() {
+        assert!(!too_many_files(Some(1000), || None));
+    }
+}
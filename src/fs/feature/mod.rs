@@ -1,3 +1,6 @@
+pub mod archive;
+pub mod checksum;
+pub mod mime;
 pub mod xattr;
 
 #[cfg(feature = "git")]
@@ -29,6 +32,11 @@ pub mod git {
         pub fn get(&self, _index: &Path, _prefix_lookup: bool) -> f::Git {
             unreachable!();
         }
+
+        #[must_use]
+        pub fn with_max_files(self, _max_files: Option<usize>) -> Self {
+            self
+        }
     }
 
     impl f::SubdirGitRepo {
@@ -7,6 +7,9 @@ use std::io;
 use std::path::Path;
 use std::str;
 
+#[cfg(target_os = "macos")]
+use crate::fs::fields::{Tag, TagColour};
+
 pub const ENABLED: bool = cfg!(any(
     target_os = "macos",
     target_os = "linux",
@@ -23,6 +26,11 @@ pub struct Attribute {
 pub trait FileAttributes {
     fn attributes(&self) -> io::Result<Vec<Attribute>>;
     fn symlink_attributes(&self) -> io::Result<Vec<Attribute>>;
+
+    /// Whether there are any attributes, going by just their names, which
+    /// is cheaper than reading all of their values.
+    fn has_attributes(&self) -> io::Result<bool>;
+    fn symlink_has_attributes(&self) -> io::Result<bool>;
 }
 
 #[cfg(any(
@@ -39,6 +47,14 @@ impl FileAttributes for Path {
     fn symlink_attributes(&self) -> io::Result<Vec<Attribute>> {
         extended_attrs::attributes(self, false)
     }
+
+    fn has_attributes(&self) -> io::Result<bool> {
+        extended_attrs::has_attributes(self, true)
+    }
+
+    fn symlink_has_attributes(&self) -> io::Result<bool> {
+        extended_attrs::has_attributes(self, false)
+    }
 }
 
 #[cfg(not(any(
@@ -55,6 +71,14 @@ impl FileAttributes for Path {
     fn symlink_attributes(&self) -> io::Result<Vec<Attribute>> {
         Ok(Vec::new())
     }
+
+    fn has_attributes(&self) -> io::Result<bool> {
+        Ok(false)
+    }
+
+    fn symlink_has_attributes(&self) -> io::Result<bool> {
+        Ok(false)
+    }
 }
 
 #[cfg(any(
@@ -454,6 +478,17 @@ mod extended_attrs {
         Ok(attrs)
     }
 
+    // Whether `path` has any attributes, without reading their values. The
+    // SELinux context doesn’t count, as every file has one on systems that
+    // use it.
+    #[cfg(any(target_os = "macos", target_os = "linux"))]
+    pub fn has_attributes(path: &Path, follow_symlinks: bool) -> io::Result<bool> {
+        let path = CString::new(path.as_os_str().as_bytes())
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+        let attr_names = list_attributes(&path, follow_symlinks, os::list_xattr)?;
+        Ok(attr_names.iter().any(|name| name != "security.selinux"))
+    }
+
     #[cfg(any(target_os = "netbsd", target_os = "freebsd"))]
     fn get_namespace_attributes(
         path: &CStr,
@@ -521,6 +556,16 @@ mod extended_attrs {
 
         Ok(attrs)
     }
+
+    // Whether `path` has any user attributes, without reading their values.
+    // Only root can list the system ones.
+    #[cfg(any(target_os = "netbsd", target_os = "freebsd"))]
+    pub fn has_attributes(path: &Path, follow_symlinks: bool) -> io::Result<bool> {
+        let path = CString::new(path.as_os_str().as_bytes())
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+        let attr_names = list_attributes(&path, follow_symlinks, os::list_user_xattr)?;
+        Ok(!attr_names.is_empty())
+    }
 }
 
 const ATTRIBUTE_VALUE_MAX_HEX_LENGTH: usize = 16;
@@ -659,6 +704,39 @@ fn display_macl(attribute: &Attribute) -> Option<String> {
         })
 }
 
+/// The attribute Finder keeps a file’s tags in.
+#[cfg(target_os = "macos")]
+pub const FINDER_TAGS: &str = "com.apple.metadata:_kMDItemUserTags";
+
+/// Parse the value of the Finder tags attribute: a binary plist holding an
+/// array of strings, each a tag’s name followed by a newline and the number
+/// of its colour. Tags without a colour have no number.
+#[cfg(target_os = "macos")]
+pub fn finder_tags(value: &[u8]) -> Option<Vec<Tag>> {
+    let plist::Value::Array(items) = plist::Value::from_reader(io::Cursor::new(value)).ok()? else {
+        return None;
+    };
+
+    let tags = items
+        .iter()
+        .filter_map(plist::Value::as_string)
+        .map(|item| match item.rsplit_once('\n') {
+            Some((name, number)) => Tag {
+                name: name.into(),
+                colour: number
+                    .parse()
+                    .map_or(TagColour::None, TagColour::from_number),
+            },
+            None => Tag {
+                name: item.into(),
+                colour: TagColour::None,
+            },
+        })
+        .collect();
+
+    Some(tags)
+}
+
 // plist::XmlWriter takes the writer instead of borrowing it.  This is a
 // wrapper around a borrowed vector that just forwards the Write trait
 // calls to the borrowed vector.
@@ -706,3 +784,85 @@ fn plist_value_display(value: &[u8]) -> Option<String> {
         .map(|s| format!("<plist version=\"1.0\">{}</plist>", s.replace('\n', "")))
     })
 }
+
+#[cfg(all(test, target_os = "macos"))]
+mod finder_tags_test {
+    use super::*;
+    use crate::fs::fields::{Tag, TagColour};
+    use crate::fs::test_dir::TestDir;
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    fn tag(name: &str, colour: TagColour) -> Tag {
+        Tag {
+            name: name.into(),
+            colour,
+        }
+    }
+
+    fn bplist(items: &[&str]) -> Vec<u8> {
+        let value = plist::Value::Array(items.iter().map(|i| (*i).into()).collect());
+        let mut buffer = Vec::new();
+        value.to_writer_binary(&mut buffer).unwrap();
+        buffer
+    }
+
+    #[test]
+    fn coloured() {
+        assert_eq!(
+            finder_tags(&bplist(&["Red\n6", "Work\n4"])),
+            Some(vec![
+                tag("Red", TagColour::Red),
+                tag("Work", TagColour::Blue)
+            ])
+        );
+    }
+
+    #[test]
+    fn uncoloured() {
+        assert_eq!(
+            finder_tags(&bplist(&["Important", "Odd\n99"])),
+            Some(vec![
+                tag("Important", TagColour::None),
+                tag("Odd", TagColour::None)
+            ])
+        );
+    }
+
+    #[test]
+    fn not_a_plist() {
+        assert_eq!(finder_tags(b"Red\n6"), None);
+    }
+
+    #[test]
+    fn tagged_file() {
+        let dir = TestDir::new("finder-tags");
+        let file = dir.join("tagged");
+        std::fs::write(&file, "").unwrap();
+
+        let path = CString::new(file.as_os_str().as_bytes()).unwrap();
+        let name = CString::new(FINDER_TAGS).unwrap();
+        let value = bplist(&["Green\n2"]);
+        // SAFETY: Calling C function with valid strings and buffer
+        let result = unsafe {
+            libc::setxattr(
+                path.as_ptr(),
+                name.as_ptr(),
+                value.as_ptr().cast(),
+                value.len(),
+                0,
+                0,
+            )
+        };
+        assert_eq!(result, 0);
+
+        let attributes = file.as_path().attributes().unwrap();
+        let tags = attributes
+            .iter()
+            .find(|a| a.name == FINDER_TAGS)
+            .and_then(|a| a.value.as_deref())
+            .and_then(finder_tags);
+
+        assert_eq!(tags, Some(vec![tag("Green", TagColour::Green)]));
+    }
+}
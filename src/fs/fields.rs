@@ -38,8 +38,6 @@ pub type flag_t = u32;
 /// This type is set entirely by the filesystem, rather than relying on a
 /// file’s contents. So “link” is a type, but “image” is just a type of
 /// regular file. (See the `filetype` module for those checks.)
-///
-/// Its ordering is used when sorting by type.
 #[derive(PartialEq, Eq, PartialOrd, Ord, Copy, Clone)]
 pub enum Type {
     Directory,
@@ -142,6 +140,18 @@ pub enum Blocksize {
     None,
 }
 
+/// The block size a file’s filesystem prefers it to be read and written
+/// in, from `st_blksize`.
+#[derive(Copy, Clone)]
+#[cfg(unix)]
+pub enum IoBlocksize {
+    /// This file is read and written in blocks of the given size.
+    Some(u64),
+
+    /// This file isn’t a regular file, so it isn’t read in blocks.
+    None,
+}
+
 /// The ID of the user that owns a file. This will only ever be a number;
 /// looking up the username is done in the `display` module.
 #[derive(Copy, Clone)]
@@ -255,6 +265,50 @@ pub struct SecurityContext<'a> {
     pub context: SecurityContextType<'a>,
 }
 
+/// The colour of a macOS Finder tag, numbered the way Finder stores it.
+#[cfg(target_os = "macos")]
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum TagColour {
+    None,
+    Grey,
+    Green,
+    Purple,
+    Blue,
+    Yellow,
+    Red,
+    Orange,
+}
+
+#[cfg(target_os = "macos")]
+impl TagColour {
+    /// The colour with the given number, with anything unknown counting as
+    /// no colour at all.
+    pub fn from_number(number: u8) -> Self {
+        match number {
+            1 => Self::Grey,
+            2 => Self::Green,
+            3 => Self::Purple,
+            4 => Self::Blue,
+            5 => Self::Yellow,
+            6 => Self::Red,
+            7 => Self::Orange,
+            _ => Self::None,
+        }
+    }
+}
+
+/// One of the tags a file has been given in Finder.
+#[cfg(target_os = "macos")]
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Tag {
+    pub name: String,
+    pub colour: TagColour,
+}
+
+/// All of a file’s Finder tags, in the order Finder lists them.
+#[cfg(target_os = "macos")]
+pub struct FinderTags(pub Vec<Tag>);
+
 #[allow(dead_code)]
 #[derive(PartialEq, Copy, Clone)]
 pub enum SubdirGitRepoStatus {
@@ -278,6 +332,38 @@ impl Default for SubdirGitRepo {
     }
 }
 
+/// A digest of a file’s contents, from `--checksum`.
+pub enum Checksum {
+    /// The digest, in lowercase hexadecimal.
+    Some(String),
+
+    /// The file isn’t a regular file, so it has no contents to digest, or
+    /// it couldn’t be read.
+    None,
+}
+
+/// A file’s type, guessed from the magic number at the start of its
+/// contents, from `--mime`.
+pub enum Mime {
+    /// The MIME type, such as `image/png`.
+    Some(&'static str),
+
+    /// The file isn’t a regular file, it couldn’t be read, or its contents
+    /// didn’t start with anything recognisable.
+    None,
+}
+
 /// The user file flags on the file. This will only ever be a number;
 /// looking up the flags is done in the `display` module.
 pub struct Flags(pub flag_t);
+
+/// A flag on a file that stops it from being changed, which gets its name
+/// painted distinctly.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum Lock {
+    /// The file can’t be changed, renamed, or deleted at all.
+    Immutable,
+
+    /// The file can only be written to by adding to the end of it.
+    AppendOnly,
+}
@@ -2,6 +2,7 @@
 
 #[cfg(unix)]
 use std::collections::HashMap;
+use std::ffi::OsStr;
 use std::io;
 #[cfg(unix)]
 use std::os::unix::fs::{FileTypeExt, MetadataExt, PermissionsExt};
@@ -21,6 +22,8 @@ use log::*;
 use once_cell::sync::Lazy;
 
 use crate::fs::dir::Dir;
+use crate::fs::feature::checksum::Algorithm;
+use crate::fs::feature::mime;
 use crate::fs::feature::xattr;
 use crate::fs::feature::xattr::{Attribute, FileAttributes};
 use crate::fs::fields as f;
@@ -222,6 +225,20 @@ impl<'dir> File<'dir> {
         }
     }
 
+    /// The file’s name as it is on the filesystem. Unlike `name`, which has
+    /// to be valid UTF-8, this keeps any bytes that aren’t, so it can be
+    /// displayed or joined onto a path without being mangled.
+    pub fn os_name(&self) -> &OsStr {
+        if self.is_all_all {
+            return OsStr::new(&self.name);
+        }
+
+        self.path
+            .components()
+            .next_back()
+            .map_or(self.path.as_os_str(), |back| back.as_os_str())
+    }
+
     /// Extract an extension from a file path, if one is present, in lowercase.
     ///
     /// The extension is the series of characters after the last dot. This
@@ -266,6 +283,28 @@ impl<'dir> File<'dir> {
             .get_or_init(|| self.gather_extended_attributes())
     }
 
+    /// Whether this file has any extended attributes, going by just their
+    /// names, for the `@` indicator of `--classify-xattrs`.
+    pub fn has_extended_attributes(&self) -> bool {
+        if !xattr::ENABLED {
+            return false;
+        }
+
+        let has_attributes = if self.deref_links {
+            self.path.has_attributes()
+        } else {
+            self.path.symlink_has_attributes()
+        };
+        has_attributes.unwrap_or_else(|e| {
+            error!(
+                "Error looking up extended attributes for {}: {}",
+                self.path.display(),
+                e
+            );
+            false
+        })
+    }
+
     /// Whether this file is a directory on the filesystem.
     pub fn is_directory(&self) -> bool {
         self.metadata.is_dir()
@@ -313,6 +352,28 @@ impl<'dir> File<'dir> {
         self.is_file() && (self.metadata.permissions().mode() & bit) == bit
     }
 
+    /// Whether this file’s permissions are a security risk: anyone can
+    /// write to it, it’s a setuid or setgid file, or it’s a directory anyone
+    /// can write to without the sticky bit that stops them deleting other
+    /// people’s files. A symlink’s own permissions don’t mean anything, so
+    /// symlinks are never risky.
+    #[cfg(unix)]
+    pub fn has_risky_permissions(&self) -> bool {
+        if self.is_link() {
+            return false;
+        }
+
+        let Some(p) = self.permissions() else {
+            return false;
+        };
+
+        if self.is_directory() {
+            p.other_write && !p.sticky
+        } else {
+            p.other_write || (self.is_file() && (p.setuid || p.setgid))
+        }
+    }
+
     /// Whether this file is a symlink on the filesystem.
     pub fn is_link(&self) -> bool {
         self.metadata.file_type().is_symlink()
@@ -353,7 +414,7 @@ impl<'dir> File<'dir> {
                     self.path
                         .parent()
                         .and_then(|parent| std::fs::canonicalize(parent).ok())
-                        .map(|p| p.join(self.name.clone()))
+                        .map(|p| p.join(self.os_name()))
                 } else {
                     std::fs::canonicalize(&self.path).ok()
                 }
@@ -512,6 +573,22 @@ impl<'dir> File<'dir> {
         }
     }
 
+    /// The block size the filesystem prefers for reading and writing this
+    /// file, in bytes. Only regular files have one worth showing.
+    #[cfg(unix)]
+    pub fn io_blocksize(&self) -> f::IoBlocksize {
+        if self.deref_links && self.is_link() {
+            match self.link_target() {
+                FileTarget::Ok(f) => f.io_blocksize(),
+                _ => f::IoBlocksize::None,
+            }
+        } else if self.is_file() {
+            f::IoBlocksize::Some(self.metadata.blksize())
+        } else {
+            f::IoBlocksize::None
+        }
+    }
+
     /// The ID of the user that own this file. If dereferencing links, the links
     /// may be broken, in which case `None` will be returned.
     #[cfg(unix)]
@@ -604,21 +681,24 @@ impl<'dir> File<'dir> {
                 return RecursiveSize::Some(size.0, size.1);
             }
             Dir::read_dir(self.path.clone()).map_or(RecursiveSize::Unknown, |dir| {
-                let mut size = 0;
-                let mut blocks = 0;
+                let mut size: u64 = 0;
+                let mut blocks: u64 = 0;
                 for file in dir
                     .files(super::DotFilter::Dotfiles, None, false, false, true)
                     .flatten()
                 {
                     match file.recursive_directory_size() {
+                        // Corrupt filesystems can report absurd sizes, so
+                        // the totals stop at the largest value rather than
+                        // overflowing.
                         RecursiveSize::Some(bytes, blks) => {
-                            size += bytes;
-                            blocks += blks;
+                            size = size.saturating_add(bytes);
+                            blocks = blocks.saturating_add(blks);
                         }
                         RecursiveSize::Unknown => {}
                         RecursiveSize::None => {
-                            size += file.metadata.size();
-                            blocks += file.metadata.blocks();
+                            size = size.saturating_add(file.metadata.size());
+                            blocks = blocks.saturating_add(file.metadata.blocks());
                         }
                     }
                 }
@@ -780,8 +860,20 @@ impl<'dir> File<'dir> {
     /// This is used a the leftmost character of the permissions column.
     /// The file type can usually be guessed from the colour of the file, but
     /// ls puts this character there.
+    ///
+    /// When dereferencing, this is the type of the link’s target. The target
+    /// is only ever `stat`ed, never opened, so a link to a FIFO or a device
+    /// can’t block.
     #[cfg(unix)]
     pub fn type_char(&self) -> f::Type {
+        if self.is_link() && self.deref_links {
+            // A broken chain of links is still shown as a link.
+            return match self.link_target_recurse() {
+                FileTarget::Ok(f) => f.type_char(),
+                _ => f::Type::Link,
+            };
+        }
+
         if self.is_file() {
             f::Type::File
         } else if self.is_directory() {
@@ -890,6 +982,21 @@ impl<'dir> File<'dir> {
         }
     }
 
+    /// The tags this file has been given in Finder, ignoring the
+    /// persistence flags macOS can add to the end of the attribute’s name.
+    #[cfg(target_os = "macos")]
+    pub fn finder_tags(&self) -> f::FinderTags {
+        let tags = self
+            .extended_attributes()
+            .iter()
+            .find(|a| a.name.split('#').next() == Some(xattr::FINDER_TAGS))
+            .and_then(|a| a.value.as_deref())
+            .and_then(xattr::finder_tags)
+            .unwrap_or_default();
+
+        f::FinderTags(tags)
+    }
+
     /// User file flags.
     #[cfg(any(
         target_os = "macos",
@@ -912,6 +1019,123 @@ impl<'dir> File<'dir> {
         f::Flags(self.metadata.st_flags())
     }
 
+    /// Whether this file’s flags stop it from being changed, for either the
+    /// owner or the system.
+    #[cfg(any(
+        target_os = "macos",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd",
+        target_os = "dragonfly"
+    ))]
+    pub fn lock(&self) -> Option<f::Lock> {
+        const UF_IMMUTABLE: f::flag_t = 0x0000_0002;
+        const UF_APPEND: f::flag_t = 0x0000_0004;
+        const SF_IMMUTABLE: f::flag_t = 0x0002_0000;
+        const SF_APPEND: f::flag_t = 0x0004_0000;
+
+        let f::Flags(flags) = self.flags();
+        if flags & (UF_IMMUTABLE | SF_IMMUTABLE) != 0 {
+            Some(f::Lock::Immutable)
+        } else if flags & (UF_APPEND | SF_APPEND) != 0 {
+            Some(f::Lock::AppendOnly)
+        } else {
+            None
+        }
+    }
+
+    /// Whether this file’s inode flags stop it from being changed. Reading
+    /// them means opening the file, so only regular files and directories
+    /// get looked at, as opening a device or a pipe could block or have
+    /// side effects.
+    #[cfg(target_os = "linux")]
+    pub fn lock(&self) -> Option<f::Lock> {
+        use std::os::unix::fs::OpenOptionsExt;
+        use std::os::unix::io::AsRawFd;
+
+        const FS_IMMUTABLE_FL: libc::c_int = 0x0000_0010;
+        const FS_APPEND_FL: libc::c_int = 0x0000_0020;
+
+        if !self.is_file() && !self.is_directory() {
+            return None;
+        }
+
+        let file = std::fs::OpenOptions::new()
+            .read(true)
+            .custom_flags(libc::O_NONBLOCK)
+            .open(&self.path)
+            .ok()?;
+
+        let mut flags: libc::c_int = 0;
+        // SAFETY: FS_IOC_GETFLAGS writes a single int through the pointer,
+        // and the file stays open for the length of the call.
+        let result = unsafe {
+            libc::ioctl(
+                file.as_raw_fd(),
+                libc::FS_IOC_GETFLAGS,
+                std::ptr::addr_of_mut!(flags),
+            )
+        };
+        if result != 0 {
+            return None;
+        }
+
+        if flags & FS_IMMUTABLE_FL != 0 {
+            Some(f::Lock::Immutable)
+        } else if flags & FS_APPEND_FL != 0 {
+            Some(f::Lock::AppendOnly)
+        } else {
+            None
+        }
+    }
+
+    /// Files have no flags to lock them on this platform.
+    #[cfg(not(any(
+        target_os = "macos",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd",
+        target_os = "dragonfly",
+        target_os = "linux"
+    )))]
+    pub fn lock(&self) -> Option<f::Lock> {
+        None
+    }
+
+    /// A digest of this file’s contents. Only regular files are read, as
+    /// directories and special files have no contents to digest, and reading
+    /// from a pipe or a device could block or never end.
+    pub fn checksum(&self, algorithm: Algorithm) -> f::Checksum {
+        if !self.is_file() {
+            return f::Checksum::None;
+        }
+
+        match std::fs::File::open(&self.path).and_then(|file| algorithm.digest(file)) {
+            Ok(digest) => f::Checksum::Some(digest),
+            Err(e) => {
+                error!("Error reading {:?} for its checksum: {}", self.path, e);
+                f::Checksum::None
+            }
+        }
+    }
+
+    /// This file’s MIME type, guessed from the first few bytes of its
+    /// contents. As with checksums, only regular files get read.
+    pub fn mime(&self) -> f::Mime {
+        if !self.is_file() {
+            return f::Mime::None;
+        }
+
+        match std::fs::File::open(&self.path).and_then(mime::detect) {
+            Ok(Some(mime)) => f::Mime::Some(mime),
+            Ok(None) => f::Mime::None,
+            Err(e) => {
+                error!("Error reading {:?} for its MIME type: {}", self.path, e);
+                f::Mime::None
+            }
+        }
+    }
+
     #[cfg(windows)]
     pub fn flags(&self) -> f::Flags {
         f::Flags(self.metadata.file_attributes())
@@ -1045,3 +1269,98 @@ mod filename_test {
         assert_eq!("/", File::filename(Path::new("/")));
     }
 }
+
+#[cfg(test)]
+#[cfg(unix)]
+mod deref_test {
+    use super::File;
+    use crate::fs::fields as f;
+    use crate::fs::test_dir::TestDir;
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+    use std::path::PathBuf;
+
+    /// Creates a FIFO and a symlink to it in a fresh temporary directory.
+    fn fifo_and_link(name: &str) -> (TestDir, PathBuf) {
+        let dir = TestDir::new(name);
+
+        let fifo = dir.join("fifo");
+        let c_path = CString::new(fifo.as_os_str().as_bytes()).unwrap();
+        // SAFETY: Calling libc function with a valid, NUL-terminated path
+        assert_eq!(unsafe { libc::mkfifo(c_path.as_ptr(), 0o644) }, 0);
+
+        let link = dir.join("link");
+        std::os::unix::fs::symlink(&fifo, &link).unwrap();
+        (dir, link)
+    }
+
+    #[test]
+    fn link_to_fifo_dereferenced() {
+        let (_dir, link) = fifo_and_link("deref-fifo");
+
+        // Nothing here may open the FIFO, or the test would hang waiting for
+        // a writer.
+        let file = File::from_args(link, None, None, true, false).unwrap();
+        assert!(matches!(file.type_char(), f::Type::Pipe));
+        assert!(file.permissions().is_some());
+        assert!(matches!(file.size(), f::Size::None));
+    }
+
+    #[test]
+    fn link_to_fifo_not_dereferenced() {
+        let (_dir, link) = fifo_and_link("no-deref-fifo");
+
+        let file = File::from_args(link, None, None, false, false).unwrap();
+        assert!(matches!(file.type_char(), f::Type::Link));
+    }
+}
+
+#[cfg(test)]
+mod checksum_test {
+    use super::File;
+    use crate::fs::feature::checksum::Algorithm;
+    use crate::fs::test_dir::TestDir;
+    use crate::output::TextCell;
+
+    use nu_ansi_term::Style;
+
+    fn rendered(file: &File<'_>, algorithm: Algorithm) -> TextCell {
+        file.checksum(algorithm)
+            .render(Style::default(), Style::default())
+    }
+
+    #[test]
+    fn regular_file() {
+        let dir = TestDir::new("checksum-file");
+        let path = dir.join("digits");
+        std::fs::write(&path, "123456789").unwrap();
+
+        let file = File::from_args(path, None, None, false, false).unwrap();
+        assert_eq!(
+            rendered(&file, Algorithm::Crc32),
+            TextCell::paint_str(Style::default(), "cbf43926")
+        );
+        assert_eq!(
+            rendered(&file, Algorithm::Md5),
+            TextCell::paint_str(Style::default(), "25f9e794323b453885f5181f1b624d0b")
+        );
+        assert_eq!(
+            rendered(&file, Algorithm::Sha256),
+            TextCell::paint_str(
+                Style::default(),
+                "15e2b0d3c33891ebb0f1ef609ec419420c20e320ce94c65fbc8c3312448eb225"
+            )
+        );
+    }
+
+    #[test]
+    fn directory() {
+        let dir = TestDir::new("checksum-dir");
+
+        let file = File::from_args(dir.to_path_buf(), None, None, false, false).unwrap();
+        assert_eq!(
+            rendered(&file, Algorithm::Sha256),
+            TextCell::blank(Style::default())
+        );
+    }
+}
@@ -1,10 +1,21 @@
 //! Filtering and sorting the list of files before displaying them.
 
+use std::cell::RefCell;
 use std::cmp::Ordering;
+use std::collections::HashMap;
+#[cfg(unix)]
+use std::ffi::OsString;
+use std::fmt;
 use std::iter::FromIterator;
 #[cfg(unix)]
 use std::os::unix::fs::MetadataExt;
+use std::path::PathBuf;
+
+#[cfg(unix)]
+use uzers::{Groups, Users, UsersCache};
 
+use crate::fs::feature::git::GitCache;
+use crate::fs::fields as f;
 use crate::fs::DotFilter;
 use crate::fs::File;
 
@@ -39,12 +50,12 @@ pub enum FileFilterFlags {
 /// performing the comparison.
 #[derive(PartialEq, Eq, Debug, Clone)]
 pub struct FileFilter {
-    /// Whether directories should be listed first, and other types of file
-    /// second. Some users prefer it like this.
-    pub list_dirs_first: bool,
+    /// Whether directories should be listed before or after other types of
+    /// file. Some users prefer it like this.
+    pub group_dirs: GroupDirs,
 
-    /// The metadata field to sort by.
-    pub sort_field: SortField,
+    /// The metadata fields to sort by, in order of precedence.
+    pub sort_keys: SortKeys,
 
     // Flags that the file filtering process follow
     pub flags: Vec<FileFilterFlags>,
@@ -68,6 +79,15 @@ pub struct FileFilter {
 
     /// Whether to ignore Git-ignored patterns.
     pub git_ignore: GitIgnore,
+
+    /// Glob patterns of directories not to recurse into. Any directory
+    /// whose name matches is still displayed, but its contents aren’t.
+    pub prune_patterns: PrunePatterns,
+
+    /// The fewest hard links a file needs to be listed, if any. Directories
+    /// are always listed, as their link counts come from how many
+    /// subdirectories they have rather than from being linked to.
+    pub min_links: Option<f::nlink_t>,
 }
 
 impl FileFilter {
@@ -92,6 +112,11 @@ impl FileFilter {
             }
             _ => {}
         }
+
+        #[cfg(unix)]
+        if let Some(min_links) = self.min_links {
+            files.retain(|f| f.is_directory() || f.links().count >= min_links);
+        }
     }
 
     /// Remove every file in the given vector that does *not* pass the
@@ -108,24 +133,107 @@ impl FileFilter {
     }
 
     /// Sort the files in the given vector based on the sort field option.
-    pub fn sort_files<'a, F>(&self, files: &mut [F])
+    /// The Git cache is only needed to sort by Git status.
+    pub fn sort_files<'a, F>(&self, files: &mut [F], git: Option<&GitCache>)
     where
         F: AsRef<File<'a>>,
     {
-        files.sort_by(|a, b| self.sort_field.compare_files(a.as_ref(), b.as_ref()));
+        let context = SortContext::new(git);
 
+        // Shuffling from the order the files were read in would make the
+        // same seed give a different order on another filesystem, so they
+        // get put in name order first.
+        if let Some(seed) = self.sort_keys.shuffle_seed() {
+            files.sort_by(|a, b| natord::compare(&a.as_ref().name, &b.as_ref().name));
+            shuffle(files, seed);
+        }
+
+        files.sort_by(|a, b| {
+            self.sort_keys
+                .compare_files(a.as_ref(), b.as_ref(), &context)
+        });
+
+        // Reversing afterwards flips the direction of every key at once.
         if self.flags.contains(&FileFilterFlags::Reverse) {
             files.reverse();
         }
 
-        if self.list_dirs_first {
-            // This relies on the fact that `sort_by` is *stable*: it will keep
-            // adjacent elements next to each other.
-            files.sort_by(|a, b| {
-                b.as_ref()
-                    .points_to_directory()
-                    .cmp(&a.as_ref().points_to_directory())
-            });
+        // This relies on the fact that `sort_by` is *stable*: it will keep
+        // adjacent elements next to each other.
+        match self.group_dirs {
+            GroupDirs::First { with_links } => files.sort_by(|a, b| {
+                GroupDirs::is_grouped(b.as_ref(), with_links)
+                    .cmp(&GroupDirs::is_grouped(a.as_ref(), with_links))
+            }),
+            GroupDirs::Last { with_links } => files.sort_by(|a, b| {
+                GroupDirs::is_grouped(a.as_ref(), with_links)
+                    .cmp(&GroupDirs::is_grouped(b.as_ref(), with_links))
+            }),
+            GroupDirs::None => {}
+        }
+    }
+}
+
+/// Shuffles the items with a Fisher–Yates shuffle, which puts them in the
+/// same order for the same seed on every run and every platform.
+fn shuffle<T>(items: &mut [T], seed: u64) {
+    let mut rng = SplitMix64(seed);
+    for i in (1..items.len()).rev() {
+        items.swap(i, rng.below(i + 1));
+    }
+}
+
+/// A seed for shuffling when none was given, which is different each time.
+fn random_seed() -> u64 {
+    use std::collections::hash_map::RandomState;
+    use std::hash::{BuildHasher, Hasher};
+
+    RandomState::new().build_hasher().finish()
+}
+
+/// The SplitMix64 generator. It isn’t anywhere near good enough for
+/// anything secret, but it’s small, fast, and plenty for shuffling files.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number from zero up to, but not including, the bound.
+    fn below(&mut self, bound: usize) -> usize {
+        ((u128::from(self.next_u64()) * bound as u128) >> 64) as usize
+    }
+}
+
+/// Where to put directories in relation to the other files.
+#[derive(PartialEq, Eq, Debug, Default, Copy, Clone)]
+pub enum GroupDirs {
+    /// Mix directories in with the other files, in sort order.
+    #[default]
+    None,
+
+    /// List directories before the other files, along with symlinks to
+    /// directories if `with_links` is set.
+    First { with_links: bool },
+
+    /// List directories after the other files, along with symlinks to
+    /// directories if `with_links` is set.
+    Last { with_links: bool },
+}
+
+impl GroupDirs {
+    /// Whether the file goes in the group of directories. Symlinks only
+    /// count if they point to a directory and links are being grouped.
+    fn is_grouped(file: &File<'_>, with_links: bool) -> bool {
+        if with_links {
+            file.points_to_directory()
+        } else {
+            file.is_directory()
         }
     }
 }
@@ -180,10 +288,10 @@ pub enum SortField {
     /// The time the file was created (the “btime” or “birthtime”).
     CreatedDate,
 
-    /// The type of the file: directories, links, pipes, regular, files, etc.
-    ///
-    /// Files are ordered according to the `PartialOrd` implementation of
-    /// `fs::fields::Type`, so changing that will change this.
+    /// The type of the file, grouped into categories: directories, then
+    /// symlinks, then regular files, then every kind of special file, such
+    /// as pipes, sockets, and devices. Files in the same category are
+    /// sorted by name.
     FileType,
 
     /// The “age” of the file, which is the time it was modified sorted
@@ -200,6 +308,208 @@ pub enum SortField {
     /// The file's name, however if the name of the file begins with `.`
     /// ignore the leading `.` and then sort as Name
     NameMixHidden(SortCase),
+
+    /// The file’s Git status, with the files most in need of attention
+    /// first: conflicts, then modifications, then new files, and then files
+    /// with nothing to commit. Files outside a repository count as clean.
+    GitStatus,
+
+    /// The number of entries directly inside a directory. Other files count
+    /// as having none, and directories that can’t be read are put after the
+    /// empty ones.
+    Children,
+
+    /// A shuffled order, which is the same every time for the same seed.
+    /// Without a seed, a different one is picked each time. The shuffling
+    /// happens before the comparisons, so as a key this orders everything
+    /// equally, and files that are equal by every other key are left in
+    /// their shuffled order.
+    Random(Option<u64>),
+
+    /// The name of the user that owns the file, or its user ID.
+    #[cfg(unix)]
+    User(OwnerOrder),
+
+    /// The name of the group that owns the file, or its group ID.
+    #[cfg(unix)]
+    Group(OwnerOrder),
+}
+
+/// Writes the word for the field that `--sort` accepts. Sorting owners by
+/// ID comes from `--numeric`, so it’s written the same as by name.
+impl fmt::Display for SortField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        #[rustfmt::skip]
+        let word = match self {
+            Self::Unsorted                        => "none",
+            Self::Name(SortCase::AaBbCc)          => "name",
+            Self::Name(SortCase::ABCabc)          => "Name",
+            Self::NameMixHidden(SortCase::AaBbCc) => ".name",
+            Self::NameMixHidden(SortCase::ABCabc) => ".Name",
+            Self::Extension(SortCase::AaBbCc)     => "extension",
+            Self::Extension(SortCase::ABCabc)     => "Extension",
+            Self::Size                            => "size",
+            #[cfg(unix)]
+            Self::FileInode                       => "inode",
+            Self::ModifiedDate                    => "modified",
+            Self::AccessedDate                    => "accessed",
+            Self::ChangedDate                     => "changed",
+            Self::CreatedDate                     => "created",
+            Self::FileType                        => "type",
+            Self::ModifiedAge                     => "age",
+            Self::GitStatus                       => "git",
+            Self::Children                        => "children",
+            Self::Random(_)                       => "random",
+            #[cfg(unix)]
+            Self::User(_)                         => "owner",
+            #[cfg(unix)]
+            Self::Group(_)                        => "group",
+        };
+
+        write!(f, "{word}")
+    }
+}
+
+/// Whether owners should be sorted by their names or by their IDs.
+///
+/// Sorting by name looks each user and group up, with the ones that have no
+/// name, such as the owners of files from another system, listed after the
+/// named ones in order of their IDs. `--numeric` sorts by ID throughout, to
+/// match the numbers in the table.
+#[cfg(unix)]
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum OwnerOrder {
+    Name,
+    Numeric,
+}
+
+/// What the sort fields can look up about the files being compared, beyond
+/// the files themselves.
+pub struct SortContext<'a> {
+    /// The Git statuses, for sorting by Git status.
+    git: Option<&'a GitCache>,
+
+    /// The names of users and groups, for sorting by owner. Nothing is
+    /// looked up until a comparison needs it, and each ID only once.
+    #[cfg(unix)]
+    users: UsersCache,
+
+    /// The number of entries in each directory, for sorting by children,
+    /// or `None` if it couldn’t be read. Each directory is only read the
+    /// first time it’s compared.
+    children: RefCell<HashMap<PathBuf, Option<usize>>>,
+}
+
+impl<'a> SortContext<'a> {
+    pub fn new(git: Option<&'a GitCache>) -> Self {
+        Self {
+            git,
+            #[cfg(unix)]
+            users: UsersCache::new(),
+            children: RefCell::default(),
+        }
+    }
+
+    /// The number of entries directly inside a file, along with whether it
+    /// was a directory that couldn’t be read, which counts as empty.
+    fn children(&self, file: &File<'_>) -> (usize, bool) {
+        if !file.is_directory() {
+            return (0, false);
+        }
+
+        let mut children = self.children.borrow_mut();
+        let count = children
+            .entry(file.path.clone())
+            .or_insert_with(|| std::fs::read_dir(&file.path).ok().map(Iterator::count));
+
+        match *count {
+            Some(count) => (count, false),
+            None => (0, true),
+        }
+    }
+}
+
+/// One field to sort by, along with which way round to sort it.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub struct SortKey {
+    /// The field to compare files by.
+    pub field: SortField,
+
+    /// Whether to put the files this field considers greatest first.
+    pub descending: bool,
+}
+
+/// The fields to sort by, in order: files that compare equal on one key are
+/// compared on the next, and so on.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct SortKeys(pub Vec<SortKey>);
+
+impl From<SortField> for SortKeys {
+    fn from(field: SortField) -> Self {
+        Self(vec![SortKey {
+            field,
+            descending: false,
+        }])
+    }
+}
+
+impl SortKeys {
+    /// Compares two files by each of the keys in turn.
+    pub fn compare_files(&self, a: &File<'_>, b: &File<'_>, context: &SortContext<'_>) -> Ordering {
+        self.compare_with(|field| field.compare_files(a, b, context))
+    }
+
+    /// Whether any of the keys sorts by the given field.
+    pub fn contains(&self, field: SortField) -> bool {
+        self.0.iter().any(|key| key.field == field)
+    }
+
+    /// The seed to shuffle the files with, if any of the keys is random.
+    /// Keys that weren’t given a seed get a new random one.
+    fn shuffle_seed(&self) -> Option<u64> {
+        self.0.iter().find_map(|key| match key.field {
+            SortField::Random(seed) => Some(seed.unwrap_or_else(random_seed)),
+            _ => None,
+        })
+    }
+
+    /// Compares two things by each of the keys in turn, using the given
+    /// function to compare them by a single field.
+    fn compare_with(&self, compare: impl Fn(SortField) -> Ordering) -> Ordering {
+        for key in &self.0 {
+            let order = compare(key.field);
+            let order = if key.descending {
+                order.reverse()
+            } else {
+                order
+            };
+
+            if order != Ordering::Equal {
+                return order;
+            }
+        }
+
+        Ordering::Equal
+    }
+}
+
+/// Writes the keys the way they would be given to `--sort`, such as
+/// `size:desc,name`.
+impl fmt::Display for SortKeys {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, key) in self.0.iter().enumerate() {
+            if index > 0 {
+                write!(f, ",")?;
+            }
+
+            write!(f, "{}", key.field)?;
+            if key.descending {
+                write!(f, ":desc")?;
+            }
+        }
+
+        Ok(())
+    }
 }
 
 /// Whether a field should be sorted case-sensitively or case-insensitively.
@@ -230,8 +540,9 @@ impl SortField {
     /// into groups between letters and numbers, and then sorts those blocks
     /// together, so `file10` will sort after `file9`, instead of before it
     /// because of the `1`.
-    pub fn compare_files(self, a: &File<'_>, b: &File<'_>) -> Ordering {
+    pub fn compare_files(self, a: &File<'_>, b: &File<'_>, context: &SortContext<'_>) -> Ordering {
         use self::SortCase::{ABCabc, AaBbCc};
+        let git = context.git;
 
         #[rustfmt::skip]
         return match self {
@@ -250,7 +561,7 @@ impl SortField {
             Self::CreatedDate   => a.created_time().cmp(&b.created_time()),
             Self::ModifiedAge   => b.modified_time().cmp(&a.modified_time()),  // flip b and a
 
-            Self::FileType => match a.type_char().cmp(&b.type_char()) { // todo: this recomputes
+            Self::FileType => match Self::type_rank(a.type_char()).cmp(&Self::type_rank(b.type_char())) { // todo: this recomputes
                 Ordering::Equal  => natord::compare(&a.name, &b.name),
                 order            => order,
             },
@@ -273,7 +584,81 @@ impl SortField {
                 Self::strip_dot(&a.name),
                 Self::strip_dot(&b.name)
             ),
+
+            Self::GitStatus => Self::git_rank(a, git).cmp(&Self::git_rank(b, git)),
+
+            Self::Children  => context.children(a).cmp(&context.children(b)),
+
+            Self::Random(_) => Ordering::Equal,
+
+            #[cfg(unix)]
+            Self::User(order) => Self::compare_owners(
+                a.user().map(|u| u.0),
+                b.user().map(|u| u.0),
+                |uid| context.users.get_user_by_uid(uid).map(|u| u.name().to_owned()),
+                order,
+            ),
+            #[cfg(unix)]
+            Self::Group(order) => Self::compare_owners(
+                a.group().map(|g| g.0),
+                b.group().map(|g| g.0),
+                |gid| context.users.get_group_by_gid(gid).map(|g| g.name().to_owned()),
+                order,
+            ),
+        };
+    }
+
+    /// Compares two owner IDs, either by the names the given function looks
+    /// up for them, with unnamed owners after named ones, or just by ID.
+    /// Files without an owner at all go last.
+    #[cfg(unix)]
+    fn compare_owners(
+        a: Option<u32>,
+        b: Option<u32>,
+        name: impl Fn(u32) -> Option<OsString>,
+        order: OwnerOrder,
+    ) -> Ordering {
+        let key = |id: Option<u32>| {
+            let name = match order {
+                OwnerOrder::Name => id.and_then(&name),
+                OwnerOrder::Numeric => None,
+            };
+            (id.is_none(), name.is_none(), name, id)
+        };
+
+        key(a).cmp(&key(b))
+    }
+
+    /// Ranks a file by how much its Git status needs attention, with the
+    /// lowest rank first. A file with both staged and unstaged changes is
+    /// ranked by whichever needs the most.
+    fn git_rank(file: &File<'_>, git: Option<&GitCache>) -> u8 {
+        let Some(git) = git else {
+            return Self::status_rank(f::GitStatus::NotModified);
         };
+
+        let status = git.get(&file.path, file.is_directory());
+        Self::status_rank(status.staged).min(Self::status_rank(status.unstaged))
+    }
+
+    fn status_rank(status: f::GitStatus) -> u8 {
+        use f::GitStatus::*;
+
+        match status {
+            Conflicted => 0,
+            Modified | Deleted | Renamed | TypeChange => 1,
+            New => 2,
+            NotModified | Ignored => 3,
+        }
+    }
+
+    fn type_rank(file_type: f::Type) -> u8 {
+        match file_type {
+            f::Type::Directory => 0,
+            f::Type::Link => 1,
+            f::Type::File => 2,
+            _ => 3,
+        }
     }
 
     fn strip_dot(n: &str) -> &str {
@@ -344,6 +729,20 @@ impl IgnorePatterns {
     }
 }
 
+/// The **prune patterns** are a list of globs that are tested against the
+/// name of each directory before recursing into it. This lets a user skip,
+/// say, `node_modules` or `.git` while still seeing that they exist.
+#[derive(PartialEq, Eq, Default, Debug, Clone)]
+pub struct PrunePatterns(pub IgnorePatterns);
+
+impl PrunePatterns {
+    /// Test whether the given file is a directory that shouldn’t be
+    /// descended into.
+    pub fn is_pruned(&self, file: &File<'_>) -> bool {
+        file.is_directory() && self.0.is_ignored(&file.name)
+    }
+}
+
 /// Whether to ignore or display files that Git would ignore.
 #[derive(PartialEq, Eq, Debug, Copy, Clone)]
 pub enum GitIgnore {
@@ -389,3 +788,394 @@ mod test_ignores {
         assert!(pats.is_ignored("test.mp3"));
     }
 }
+
+#[cfg(test)]
+mod test_prunes {
+    use super::*;
+    use crate::fs::test_dir::TestDir;
+
+    #[test]
+    fn prunes_matching_directories_only() {
+        let dir = TestDir::new("prune");
+        std::fs::create_dir_all(dir.join("node_modules").join("left-pad")).unwrap();
+        std::fs::create_dir(dir.join("src")).unwrap();
+        std::fs::write(dir.join("node_modules.txt"), "").unwrap();
+
+        let (pats, fails) = IgnorePatterns::parse_from_iter(vec!["node_modules*"]);
+        assert!(fails.is_empty());
+        let prunes = PrunePatterns(pats);
+
+        let file = |name: &str| File::from_args(dir.join(name), None, None, false, false).unwrap();
+        assert!(prunes.is_pruned(&file("node_modules")));
+        assert!(!prunes.is_pruned(&file("src")));
+        assert!(!prunes.is_pruned(&file("node_modules.txt")));
+    }
+}
+
+#[cfg(test)]
+mod test_sort_keys {
+    use super::*;
+    use crate::fs::test_dir::TestDir;
+
+    fn key(field: SortField, descending: bool) -> SortKey {
+        SortKey { field, descending }
+    }
+
+    #[test]
+    fn later_keys_break_ties() {
+        let keys = SortKeys(vec![
+            key(SortField::Size, false),
+            key(SortField::FileType, false),
+        ]);
+        let order = keys.compare_with(|field| match field {
+            SortField::Size => Ordering::Equal,
+            _ => Ordering::Less,
+        });
+        assert_eq!(order, Ordering::Less);
+    }
+
+    #[test]
+    fn descending_keys() {
+        let keys = SortKeys(vec![
+            key(SortField::Size, true),
+            key(SortField::FileType, false),
+        ]);
+        assert_eq!(keys.compare_with(|_| Ordering::Less), Ordering::Greater);
+    }
+
+    #[test]
+    fn display() {
+        let keys = SortKeys(vec![
+            key(SortField::Size, true),
+            key(SortField::Name(SortCase::AaBbCc), false),
+        ]);
+        assert_eq!(keys.to_string(), "Size:desc, Name(AaBbCc)");
+    }
+
+    fn sorted_names(test: &str, keys: SortKeys, flags: Vec<FileFilterFlags>) -> Vec<String> {
+        let dir = TestDir::new(test);
+        std::fs::write(dir.join("a"), "aaa").unwrap();
+        std::fs::write(dir.join("b"), "b").unwrap();
+        std::fs::write(dir.join("c"), "ccc").unwrap();
+
+        let filter = FileFilter {
+            group_dirs: GroupDirs::None,
+            sort_keys: keys,
+            flags,
+            dot_filter: DotFilter::default(),
+            ignore_patterns: IgnorePatterns::empty(),
+            git_ignore: GitIgnore::Off,
+            prune_patterns: PrunePatterns::default(),
+            min_links: None,
+        };
+
+        let mut files: Vec<_> = ["a", "b", "c"]
+            .iter()
+            .map(|name| File::from_args(dir.join(name), None, None, false, false).unwrap())
+            .collect();
+        filter.sort_files(&mut files, None);
+        files.iter().map(|f| f.name.clone()).collect()
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn min_links() {
+        let dir = TestDir::new("min-links");
+        std::fs::write(dir.join("linked"), "data").unwrap();
+        std::fs::hard_link(dir.join("linked"), dir.join("link")).unwrap();
+        std::fs::write(dir.join("single"), "data").unwrap();
+        std::fs::create_dir(dir.join("subdir")).unwrap();
+
+        let filter = FileFilter {
+            group_dirs: GroupDirs::None,
+            sort_keys: SortKeys(vec![]),
+            flags: vec![],
+            dot_filter: DotFilter::default(),
+            ignore_patterns: IgnorePatterns::empty(),
+            git_ignore: GitIgnore::Off,
+            prune_patterns: PrunePatterns::default(),
+            min_links: Some(2),
+        };
+
+        let mut files: Vec<_> = ["link", "linked", "single", "subdir"]
+            .iter()
+            .map(|name| File::from_args(dir.join(name), None, None, false, false).unwrap())
+            .collect();
+        filter.filter_child_files(&mut files);
+        let names: Vec<_> = files.iter().map(|f| f.name.clone()).collect();
+
+        assert_eq!(names, vec!["link", "linked", "subdir"]);
+    }
+
+    #[test]
+    fn mixed_directions() {
+        let keys = SortKeys(vec![
+            key(SortField::Size, true),
+            key(SortField::Name(SortCase::AaBbCc), false),
+        ]);
+        assert_eq!(sorted_names("mixed", keys, vec![]), vec!["a", "c", "b"]);
+    }
+
+    #[test]
+    fn git_status_ranks() {
+        use f::GitStatus::*;
+
+        let ranks = [Conflicted, Modified, New, NotModified].map(SortField::status_rank);
+        assert!(ranks.windows(2).all(|pair| pair[0] < pair[1]), "{ranks:?}");
+        assert_eq!(
+            SortField::status_rank(Ignored),
+            SortField::status_rank(NotModified)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "git")]
+    fn git_status() {
+        let dir = TestDir::new("sort-git");
+
+        // Commit two files, then change one of them and add a third.
+        let repo = git2::Repository::init(&*dir).unwrap();
+        std::fs::write(dir.join("clean"), "clean").unwrap();
+        std::fs::write(dir.join("modified"), "before").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(std::path::Path::new("clean")).unwrap();
+        index.add_path(std::path::Path::new("modified")).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = git2::Signature::now("eza", "eza@example.com").unwrap();
+        repo.commit(Some("HEAD"), &signature, &signature, "Initial", &tree, &[])
+            .unwrap();
+        std::fs::write(dir.join("modified"), "after").unwrap();
+        std::fs::write(dir.join("untracked"), "new").unwrap();
+
+        let git: GitCache = [dir.to_path_buf()].into_iter().collect();
+        let filter = FileFilter {
+            group_dirs: GroupDirs::None,
+            sort_keys: SortKeys(vec![
+                key(SortField::GitStatus, false),
+                key(SortField::Name(SortCase::AaBbCc), false),
+            ]),
+            flags: vec![],
+            dot_filter: DotFilter::default(),
+            ignore_patterns: IgnorePatterns::empty(),
+            git_ignore: GitIgnore::Off,
+            prune_patterns: PrunePatterns::default(),
+            min_links: None,
+        };
+
+        let mut files: Vec<_> = ["clean", "modified", "untracked"]
+            .iter()
+            .map(|name| File::from_args(dir.join(name), None, None, false, false).unwrap())
+            .collect();
+        filter.sort_files(&mut files, Some(&git));
+        let names = files.iter().map(|f| f.name.clone()).collect::<Vec<_>>();
+
+        assert_eq!(names, vec!["modified", "untracked", "clean"]);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn owners_by_name() {
+        use uzers::mock::MockUsers;
+        use uzers::User;
+
+        let mut users = MockUsers::with_current_uid(1000);
+        users.add_user(User::new(1000, "zoe", 100));
+        users.add_user(User::new(1001, "amy", 100));
+        let name = |uid| users.get_user_by_uid(uid).map(|u| u.name().to_owned());
+
+        let mut uids = vec![Some(2000), None, Some(1000), Some(1500), Some(1001)];
+        uids.sort_by(|a, b| SortField::compare_owners(*a, *b, name, OwnerOrder::Name));
+        assert_eq!(
+            uids,
+            vec![Some(1001), Some(1000), Some(1500), Some(2000), None]
+        );
+
+        uids.sort_by(|a, b| SortField::compare_owners(*a, *b, name, OwnerOrder::Numeric));
+        assert_eq!(
+            uids,
+            vec![Some(1000), Some(1001), Some(1500), Some(2000), None]
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn same_owner_then_size() {
+        let keys = SortKeys(vec![
+            key(SortField::User(OwnerOrder::Name), false),
+            key(SortField::Group(OwnerOrder::Name), false),
+            key(SortField::Size, false),
+        ]);
+        assert_eq!(sorted_names("owner", keys, vec![]), vec!["b", "a", "c"]);
+    }
+
+    #[test]
+    fn same_seed_same_order() {
+        let shuffled = |seed| {
+            let mut numbers = (0..20).collect::<Vec<_>>();
+            shuffle(&mut numbers, seed);
+            numbers
+        };
+
+        assert_eq!(shuffled(42), shuffled(42));
+        assert_ne!(shuffled(42), shuffled(43));
+
+        let mut sorted = shuffled(42);
+        sorted.sort_unstable();
+        assert_eq!(sorted, (0..20).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn random_seeded() {
+        let keys = || SortKeys::from(SortField::Random(Some(7)));
+        let first = sorted_names("random-1", keys(), vec![]);
+        let second = sorted_names("random-2", keys(), vec![]);
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn children() {
+        let dir = TestDir::new("children");
+        for (name, count) in [("few", 1), ("many", 3), ("none", 0)] {
+            std::fs::create_dir(dir.join(name)).unwrap();
+            for child in 0..count {
+                std::fs::write(dir.join(name).join(child.to_string()), "").unwrap();
+            }
+        }
+        std::fs::write(dir.join("file"), "").unwrap();
+
+        let filter = FileFilter {
+            group_dirs: GroupDirs::None,
+            sort_keys: SortKeys(vec![
+                key(SortField::Children, true),
+                key(SortField::Name(SortCase::AaBbCc), false),
+            ]),
+            flags: vec![],
+            dot_filter: DotFilter::default(),
+            ignore_patterns: IgnorePatterns::empty(),
+            git_ignore: GitIgnore::Off,
+            prune_patterns: PrunePatterns::default(),
+            min_links: None,
+        };
+
+        let mut files: Vec<_> = ["file", "few", "none", "many"]
+            .iter()
+            .map(|name| File::from_args(dir.join(name), None, None, false, false).unwrap())
+            .collect();
+        filter.sort_files(&mut files, None);
+        let names = files.iter().map(|f| f.name.clone()).collect::<Vec<_>>();
+
+        assert_eq!(names, vec!["many", "few", "file", "none"]);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn file_types() {
+        use std::ffi::CString;
+        use std::os::unix::ffi::OsStrExt;
+
+        let dir = TestDir::new("sort-type");
+        std::fs::create_dir(dir.join("b_dir")).unwrap();
+        std::fs::create_dir(dir.join("d_dir")).unwrap();
+        std::fs::write(dir.join("a_file"), "").unwrap();
+        std::fs::write(dir.join("c_file"), "").unwrap();
+        std::os::unix::fs::symlink("a_file", dir.join("e_link")).unwrap();
+        std::os::unix::fs::symlink("b_dir", dir.join("a_link")).unwrap();
+
+        let fifo = CString::new(dir.join("0_fifo").as_os_str().as_bytes()).unwrap();
+        // SAFETY: Calling libc function with a valid, NUL-terminated path
+        assert_eq!(unsafe { libc::mkfifo(fifo.as_ptr(), 0o644) }, 0);
+
+        let filter = FileFilter {
+            group_dirs: GroupDirs::None,
+            sort_keys: SortKeys::from(SortField::FileType),
+            flags: vec![],
+            dot_filter: DotFilter::default(),
+            ignore_patterns: IgnorePatterns::empty(),
+            git_ignore: GitIgnore::Off,
+            prune_patterns: PrunePatterns::default(),
+            min_links: None,
+        };
+
+        let mut files: Vec<_> = [
+            "c_file", "e_link", "0_fifo", "d_dir", "a_file", "a_link", "b_dir",
+        ]
+        .iter()
+        .map(|name| File::from_args(dir.join(name), None, None, false, false).unwrap())
+        .collect();
+        filter.sort_files(&mut files, None);
+        let names = files.iter().map(|f| f.name.clone()).collect::<Vec<_>>();
+
+        assert_eq!(
+            names,
+            vec!["b_dir", "d_dir", "a_link", "e_link", "a_file", "c_file", "0_fifo"]
+        );
+    }
+
+    /// Sorts a directory, a file, and a symlink to each of them by name,
+    /// grouping directories as given.
+    #[cfg(unix)]
+    fn grouped_names(group_dirs: GroupDirs) -> Vec<String> {
+        let dir = TestDir::new(&format!("group-dirs-{group_dirs:?}"));
+        std::fs::create_dir(dir.join("d_dir")).unwrap();
+        std::fs::write(dir.join("c_file"), "").unwrap();
+        std::os::unix::fs::symlink("d_dir", dir.join("a_dir_link")).unwrap();
+        std::os::unix::fs::symlink("c_file", dir.join("b_file_link")).unwrap();
+
+        let filter = FileFilter {
+            group_dirs,
+            sort_keys: SortKeys::from(SortField::Name(SortCase::AaBbCc)),
+            flags: vec![],
+            dot_filter: DotFilter::default(),
+            ignore_patterns: IgnorePatterns::empty(),
+            git_ignore: GitIgnore::Off,
+            prune_patterns: PrunePatterns::default(),
+            min_links: None,
+        };
+
+        let mut files: Vec<_> = ["c_file", "d_dir", "b_file_link", "a_dir_link"]
+            .iter()
+            .map(|name| File::from_args(dir.join(name), None, None, false, false).unwrap())
+            .collect();
+        filter.sort_files(&mut files, None);
+        files.iter().map(|f| f.name.clone()).collect()
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn group_dirs_first() {
+        assert_eq!(
+            grouped_names(GroupDirs::First { with_links: false }),
+            vec!["d_dir", "a_dir_link", "b_file_link", "c_file"]
+        );
+        assert_eq!(
+            grouped_names(GroupDirs::First { with_links: true }),
+            vec!["a_dir_link", "d_dir", "b_file_link", "c_file"]
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn group_dirs_last() {
+        assert_eq!(
+            grouped_names(GroupDirs::Last { with_links: false }),
+            vec!["a_dir_link", "b_file_link", "c_file", "d_dir"]
+        );
+        assert_eq!(
+            grouped_names(GroupDirs::Last { with_links: true }),
+            vec!["b_file_link", "c_file", "a_dir_link", "d_dir"]
+        );
+    }
+
+    #[test]
+    fn mixed_directions_reversed() {
+        let keys = SortKeys(vec![
+            key(SortField::Size, true),
+            key(SortField::Name(SortCase::AaBbCc), false),
+        ]);
+        assert_eq!(
+            sorted_names("reversed", keys, vec![FileFilterFlags::Reverse]),
+            vec!["b", "c", "a"]
+        );
+    }
+}
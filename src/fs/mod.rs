@@ -1,5 +1,5 @@
 mod dir;
-pub use self::dir::{Dir, DotFilter};
+pub use self::dir::{Ancestors, Dir, DirId, DotFilter, Listed};
 
 mod file;
 pub use self::file::{File, FileTarget};
@@ -10,3 +10,6 @@ pub mod fields;
 pub mod filter;
 pub mod mounts;
 pub mod recursive_size;
+
+#[cfg(test)]
+pub mod test_dir;
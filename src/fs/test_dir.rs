@@ -0,0 +1,50 @@
+//! A scratch directory for tests that need real files to look at.
+
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+
+/// An empty directory under the system’s temporary directory, named after
+/// the test that uses it, which gets removed along with everything in it
+/// when it’s dropped, even if the test panics.
+#[derive(Debug)]
+pub struct TestDir {
+    path: PathBuf,
+}
+
+impl TestDir {
+    /// Makes a new, empty directory for the test with the given name. Tests
+    /// running at the same time need different names.
+    pub fn new(name: &str) -> Self {
+        let path = std::env::temp_dir().join(format!("eza-{name}-{}", std::process::id()));
+
+        // A directory left over from a test that was killed part of the way
+        // through would otherwise have its files in this one’s listings.
+        let _ = std::fs::remove_dir_all(&path);
+        std::fs::create_dir_all(&path).unwrap();
+
+        // Git wants the real path, which the temporary directory isn’t on
+        // every system.
+        let path = path.canonicalize().unwrap();
+        Self { path }
+    }
+}
+
+impl Deref for TestDir {
+    type Target = Path;
+
+    fn deref(&self) -> &Self::Target {
+        &self.path
+    }
+}
+
+impl AsRef<Path> for TestDir {
+    fn as_ref(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for TestDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.path);
+    }
+}
@@ -24,19 +24,28 @@
 use std::env;
 use std::ffi::{OsStr, OsString};
 use std::io::{self, stdin, ErrorKind, IsTerminal, Read, Write};
-use std::path::{Component, PathBuf};
+use std::path::{Component, Path, PathBuf};
 use std::process::exit;
 
 use nu_ansi_term::{AnsiStrings as ANSIStrings, Style};
 
+use crate::fs::dir_action::RecurseOptions;
 use crate::fs::feature::git::GitCache;
-use crate::fs::filter::GitIgnore;
-use crate::fs::{Dir, File};
+use crate::fs::filter::{FileFilterFlags, GitIgnore};
+use crate::fs::{Ancestors, Dir, DirId, File, Listed};
 use crate::options::stdin::FilesInput;
 use crate::options::{vars, Options, OptionsResult, Vars};
-use crate::output::{details, escape, file_name, grid, grid_details, lines, Mode, View};
-use crate::theme::Theme;
+use crate::output::limit::{self, LimitedWriter};
+use crate::output::markup::{Markup, MarkupWriter};
+use crate::output::progress::Progress;
+use crate::output::summary::DirSummary;
+use crate::output::{
+    csv, details, escape_os, file_name, grid, grid_details, lines, template, tree, Mode,
+    RawBytesWriter, View, ELLIPSIS,
+};
+use crate::theme::{Theme, UseColours};
 use log::*;
+use typed_arena::Arena;
 
 mod fs;
 mod info;
@@ -56,8 +65,13 @@ fn main() {
     let stdout_istty = io::stdout().is_terminal();
 
     let mut input = String::new();
+    let defaults = options::default_args::from_vars(&LiveVars);
     let args: Vec<_> = env::args_os().skip(1).collect();
-    match Options::parse(args.iter().map(std::convert::AsRef::as_ref), &LiveVars) {
+    match Options::parse_with_defaults(
+        &defaults,
+        args.iter().map(std::convert::AsRef::as_ref),
+        &LiveVars,
+    ) {
         OptionsResult::Ok(options, mut input_paths) => {
             // List the current directory by default.
             // (This has to be done here, otherwise git_options won’t see it.)
@@ -66,6 +80,7 @@ fn main() {
                     FilesInput::Args => {
                         input_paths = vec![OsStr::new(".")];
                     }
+                    FilesInput::Tree(_) => {}
                     FilesInput::Stdin(separator) => {
                         stdin()
                             .read_to_string(&mut input)
@@ -82,11 +97,27 @@ fn main() {
             }
 
             let git = git_options(&options, &input_paths);
-            let writer = io::stdout();
-            let git_repos = git_repos(&options, &input_paths);
-
-            let console_width = options.view.width.actual_terminal_width();
+            let max_output = options.view.max_output;
+            let markup = match options.theme.use_colours {
+                UseColours::Html => Some(Markup::Html),
+                UseColours::Pango => Some(Markup::Pango),
+                _ => None,
+            };
+            let writer = LimitedWriter::new(
+                MarkupWriter::new(RawBytesWriter::new(io::stdout()), markup),
+                max_output,
+            );
+            let git_repos = options.git_repos(&input_paths);
+
+            let console_width = options
+                .view
+                .width
+                .actual_terminal_width(options.view.min_width, options.view.max_width);
             let theme = options.theme.to_theme(stdout_istty);
+            let progress = match options.dir_action.recurse_options() {
+                Some(r) if r.progress => Progress::stderr(),
+                _ => None,
+            };
             let exa = Exa {
                 options,
                 writer,
@@ -95,6 +126,8 @@ fn main() {
                 console_width,
                 git,
                 git_repos,
+                progress,
+                listed: Listed::default(),
             };
 
             info!("matching on exa.run");
@@ -109,6 +142,13 @@ fn main() {
                     exit(exits::SUCCESS);
                 }
 
+                Err(e) if limit::is_limit_reached(&e) => {
+                    if let Some(bytes) = max_output {
+                        eprintln!("eza: output truncated after {bytes} bytes (--max-output)");
+                    }
+                    exit(exits::SUCCESS);
+                }
+
                 Err(e) => {
                     eprintln!("{e}");
                     trace!("exa.run: exit RUNTIME_ERROR");
@@ -125,6 +165,10 @@ fn main() {
             print!("{version_str}");
         }
 
+        OptionsResult::Explain(explanation) => {
+            print!("{explanation}");
+        }
+
         OptionsResult::InvalidOptions(error) => {
             eprintln!("eza: {error}");
 
@@ -143,7 +187,7 @@ pub struct Exa<'args> {
     pub options: Options,
 
     /// The output handle that we write to.
-    pub writer: io::Stdout,
+    pub writer: LimitedWriter<MarkupWriter<RawBytesWriter<io::Stdout>>>,
 
     /// List of the free command-line arguments that should correspond to file
     /// names (anything that isn’t an option).
@@ -164,6 +208,14 @@ pub struct Exa<'args> {
     pub git: Option<GitCache>,
 
     pub git_repos: bool,
+
+    /// The counter of directories scanned so far, if `--progress` was
+    /// passed and stderr is a terminal.
+    pub progress: Option<Progress>,
+
+    /// The directories listed so far when recursing, so overlapping path
+    /// arguments don’t get the same directory listed twice.
+    pub listed: Listed,
 }
 
 /// The “real” environment variables type.
@@ -180,75 +232,33 @@ impl Vars for LiveVars {
 /// listed before they’re actually listed, if the options demand it.
 fn git_options(options: &Options, args: &[&OsStr]) -> Option<GitCache> {
     if options.should_scan_for_git() {
-        Some(args.iter().map(PathBuf::from).collect())
+        let git: GitCache = args.iter().map(PathBuf::from).collect();
+        Some(git.with_max_files(options.git_max_files))
     } else {
         None
     }
 }
 
-#[cfg(not(feature = "git"))]
-fn git_repos(_options: &Options, _args: &[&OsStr]) -> bool {
-    return false;
-}
-
-#[cfg(feature = "git")]
-fn get_files_in_dir(paths: &mut Vec<PathBuf>, path: PathBuf) {
-    let temp_paths = if path.is_dir() {
-        match path.read_dir() {
-            Err(_) => {
-                vec![path]
-            }
-            Ok(d) => d
-                .filter_map(|entry| entry.ok().map(|e| e.path()))
-                .collect::<Vec<PathBuf>>(),
-        }
-    } else {
-        vec![path]
-    };
-    paths.extend(temp_paths);
-}
-
-#[cfg(feature = "git")]
-fn git_repos(options: &Options, args: &[&OsStr]) -> bool {
-    let option_enabled = match options.view.mode {
-        Mode::Details(details::Options {
-            table: Some(ref table),
-            ..
-        })
-        | Mode::GridDetails(grid_details::Options {
-            details:
-                details::Options {
-                    table: Some(ref table),
-                    ..
-                },
-            ..
-        }) => table.columns.subdir_git_repos || table.columns.subdir_git_repos_no_stat,
-        _ => false,
-    };
-    if option_enabled {
-        let paths: Vec<PathBuf> = args.iter().map(PathBuf::from).collect::<Vec<PathBuf>>();
-        let mut files: Vec<PathBuf> = Vec::new();
-        for path in paths {
-            get_files_in_dir(&mut files, path);
-        }
-        let repos: Vec<bool> = files
-            .iter()
-            .map(git2::Repository::open)
-            .map(|repo| repo.is_ok())
-            .collect();
-        repos.contains(&true)
-    } else {
-        false
-    }
-}
-
 impl<'args> Exa<'args> {
     /// # Errors
     ///
     /// Will return `Err` if printing to stderr fails.
     pub fn run(mut self) -> io::Result<i32> {
+        let result = self.list();
+
+        // Close the markup even if the output was cut short.
+        self.writer.get_mut().finish()?;
+        result
+    }
+
+    fn list(&mut self) -> io::Result<i32> {
         debug!("Running with options: {:#?}", self.options);
 
+        if let FilesInput::Tree(path) = &self.options.stdin {
+            let path = PathBuf::from(path);
+            return self.print_tree_file(&path);
+        }
+
         let mut files = Vec::new();
         let mut dirs = Vec::new();
         let mut exit_status = 0;
@@ -292,23 +302,133 @@ impl<'args> Exa<'args> {
         let is_only_dir = dirs.len() == 1 && no_files;
 
         self.options.filter.filter_argument_files(&mut files);
-        self.print_files(None, files)?;
+        self.print_files(None, files, 0)?;
+
+        let summary_paths = match self.options.dir_action.recurse_options() {
+            Some(r) if r.tree_summary => dirs.iter().map(|d| d.path.clone()).collect(),
+            _ => Vec::new(),
+        };
+
+        let exit_status = match self.options.dir_action.recurse_options() {
+            Some(r) if r.global_sort => self.print_dirs_globally(dirs, no_files, exit_status, r)?,
+            _ => self.print_dirs(
+                dirs,
+                no_files,
+                is_only_dir,
+                exit_status,
+                &Ancestors::default(),
+                0,
+            )?,
+        };
+        self.clear_progress();
+
+        for path in summary_paths {
+            writeln!(&mut self.writer)?;
+            self.print_tree_summary(path)?;
+        }
 
-        self.print_dirs(dirs, no_files, is_only_dir, exit_status)
+        Ok(exit_status)
     }
 
+    /// Draws a tree of just the directories under one of the arguments,
+    /// after the listing, for `--tree-summary`.
+    fn print_tree_summary(&mut self, path: PathBuf) -> io::Result<()> {
+        let Some(recurse) = self.options.dir_action.recurse_options() else {
+            return Ok(());
+        };
+
+        let file = File::from_args(path, None, None, false, false)?;
+
+        let mut filter = self.options.filter.clone();
+        filter.flags.retain(|f| *f != FileFilterFlags::OnlyFiles);
+        filter.flags.push(FileFilterFlags::OnlyDirs);
+
+        let opts = details::Options {
+            table: None,
+            header: false,
+            xattr: false,
+            secattr: false,
+            mounts: false,
+            archives: false,
+            indented: false,
+            name_width: None,
+            ellipsis: String::from(ELLIPSIS),
+            git_summary: false,
+            color_scale: self.options.theme.colour_scale,
+        };
+
+        let recurse = RecurseOptions {
+            tree: true,
+            summarize: false,
+            collapse: false,
+            tree_summary: false,
+            ..recurse
+        };
+
+        let r = details::Render {
+            dir: None,
+            files: vec![file],
+            theme: &self.theme,
+            file_style: &self.options.view.file_style,
+            opts: &opts,
+            recurse: Some(recurse),
+            filter: &filter,
+            git_ignoring: self.options.filter.git_ignore == GitIgnore::CheckAndIgnore,
+            git: self.git.as_ref(),
+            git_repos: false,
+            depth: 0,
+            progress: None,
+        };
+        r.render(&mut self.writer)
+    }
+
+    /// Draws a tree read back in from a file written with
+    /// `--tree-format=indent`, without looking at the filesystem.
+    fn print_tree_file(&mut self, path: &Path) -> io::Result<i32> {
+        let entries =
+            match std::fs::read_to_string(path).and_then(|text| tree::read_indented(&text)) {
+                Ok(entries) => entries,
+                Err(e) => {
+                    writeln!(io::stderr(), "{path:?}: {e}")?;
+                    return Ok(2);
+                }
+            };
+
+        let quote_style = self.options.view.file_style.quote_style;
+        details::render_indented(entries, &self.theme, quote_style, &mut self.writer)?;
+        Ok(0)
+    }
+
+    /// Lists the contents of each of the given directories, which are
+    /// `depth` levels below the arguments, recursing into them if asked.
     fn print_dirs(
         &mut self,
         dir_files: Vec<Dir>,
         mut first: bool,
         is_only_dir: bool,
         exit_status: i32,
+        ancestors: &Ancestors,
+        depth: usize,
     ) -> io::Result<i32> {
         let View {
             file_style: file_name::Options { quote_style, .. },
             ..
         } = self.options.view;
         for dir in dir_files {
+            // A directory that’s already been listed while recursing, as
+            // part of another argument, doesn’t get listed again.
+            let recursing = self
+                .options
+                .dir_action
+                .recurse_options()
+                .is_some_and(|r| !r.tree);
+            if recursing && !self.listed.insert(DirId::from_path(&dir.path)) {
+                debug!("Skipping already-listed directory {:?}", dir.path);
+                continue;
+            }
+
+            self.clear_progress();
+
             // Put a gap between directories, or between the list of files and
             // the first directory.
             if first {
@@ -319,8 +439,8 @@ impl<'args> Exa<'args> {
 
             if !is_only_dir {
                 let mut bits = Vec::new();
-                escape(
-                    dir.path.display().to_string(),
+                escape_os(
+                    dir.path.as_os_str(),
                     &mut bits,
                     Style::default(),
                     Style::default(),
@@ -329,47 +449,69 @@ impl<'args> Exa<'args> {
                 writeln!(&mut self.writer, "{}:", ANSIStrings(&bits))?;
             }
 
-            let mut children = Vec::new();
-            let git_ignore = self.options.filter.git_ignore == GitIgnore::CheckAndIgnore;
-            for file in dir.files(
-                self.options.filter.dot_filter,
-                self.git.as_ref(),
-                git_ignore,
-                self.options.view.deref_links,
-                self.options.view.total_size,
-            ) {
-                match file {
-                    Ok(file) => children.push(file),
-                    Err((path, e)) => writeln!(io::stderr(), "[{}: {}]", path.display(), e)?,
-                }
+            let mut children = self.read_children(&dir, true)?;
+            if let Some(progress) = &self.progress {
+                progress.add_dir(children.len());
             }
 
-            self.options.filter.filter_child_files(&mut children);
-            self.options.filter.sort_files(&mut children);
-
-            if let Some(recurse_opts) = self.options.dir_action.recurse_options() {
-                let depth = dir
+            // The summary counts every entry, even the ones left out by
+            // --per-dir-limit. Trees leave out their own entries, so the
+            // note goes in the right place.
+            let recurse = self.options.dir_action.recurse_options();
+            let summary = recurse
+                .filter(|r| r.summarize)
+                .map(|_| DirSummary::of(&children));
+            let hidden = recurse
+                .filter(|r| !r.tree)
+                .map_or(0, |r| r.limit_entries(&mut children));
+
+            if let Some(recurse_opts) = recurse {
+                let level = dir
                     .path
                     .components()
                     .filter(|&c| c != Component::CurDir)
                     .count()
                     + 1;
-                if !recurse_opts.tree && !recurse_opts.is_too_deep(depth) {
+                if !recurse_opts.tree && !recurse_opts.is_too_deep(level) {
+                    let ancestors = ancestors.with(DirId::from_path(&dir.path));
                     let mut child_dirs = Vec::new();
                     for child_dir in children
                         .iter()
                         .filter(|f| f.is_directory() && !f.is_all_all)
+                        .filter(|f| !self.options.filter.prune_patterns.is_pruned(f))
                     {
+                        if ancestors.contains(DirId::from_metadata(&child_dir.metadata)) {
+                            self.clear_progress();
+                            let e = Ancestors::loop_error();
+                            writeln!(io::stderr(), "{}: {}", child_dir.path.display(), e)?;
+                            continue;
+                        }
+
                         match child_dir.to_dir() {
                             Ok(d) => child_dirs.push(d),
+                            Err(e) if recurse_opts.skips_error(&e) => {}
                             Err(e) => {
+                                self.clear_progress();
                                 writeln!(io::stderr(), "{}: {}", child_dir.path.display(), e)?;
                             }
                         }
                     }
 
-                    self.print_files(Some(&dir), children)?;
-                    match self.print_dirs(child_dirs, false, false, exit_status) {
+                    self.clear_progress();
+                    self.print_files(Some(&dir), children, depth + 1)?;
+                    self.print_hidden_note(hidden)?;
+                    if let Some(summary) = summary {
+                        writeln!(&mut self.writer, "{summary}")?;
+                    }
+
+                    match self.print_dirs(
+                        child_dirs,
+                        false,
+                        false,
+                        exit_status,
+                        &ancestors,
+                        depth + 1,
+                    ) {
                         Ok(_) => (),
                         Err(e) => return Err(e),
                     }
@@ -377,14 +519,177 @@ impl<'args> Exa<'args> {
                 }
             }
 
-            self.print_files(Some(&dir), children)?;
+            self.clear_progress();
+            self.print_files(Some(&dir), children, depth + 1)?;
+            self.print_hidden_note(hidden)?;
         }
 
         Ok(exit_status)
     }
 
-    /// Prints the list of files using whichever view is selected.
-    fn print_files(&mut self, dir: Option<&Dir>, files: Vec<File<'_>>) -> io::Result<()> {
+    /// Reads the entries of a directory that get listed, filtered and
+    /// sorted. Entries that can’t be read are reported if `report` is set.
+    fn read_children<'dir>(&self, dir: &'dir Dir, report: bool) -> io::Result<Vec<File<'dir>>> {
+        let mut children = Vec::new();
+        let git_ignore = self.options.filter.git_ignore == GitIgnore::CheckAndIgnore;
+        for file in dir.files(
+            self.options.filter.dot_filter,
+            self.git.as_ref(),
+            git_ignore,
+            self.options.view.deref_links,
+            self.options.view.total_size,
+        ) {
+            match file {
+                Ok(file) => children.push(file),
+                Err((path, e)) if report => {
+                    self.clear_progress();
+                    writeln!(io::stderr(), "[{}: {}]", path.display(), e)?;
+                }
+                Err(_) => {}
+            }
+        }
+
+        self.options.filter.filter_child_files(&mut children);
+        self.options
+            .filter
+            .sort_files(&mut children, self.git.as_ref());
+        Ok(children)
+    }
+
+    /// Lists every entry under the given directories as one list, sorted
+    /// together instead of directory by directory, for `--global-sort`.
+    fn print_dirs_globally(
+        &mut self,
+        dir_files: Vec<Dir>,
+        first: bool,
+        exit_status: i32,
+        recurse: RecurseOptions,
+    ) -> io::Result<i32> {
+        let dirs = Arena::new();
+        let files = self.globally_sorted(dir_files, recurse, &dirs)?;
+        self.clear_progress();
+        if !first && !files.is_empty() {
+            writeln!(&mut self.writer)?;
+        }
+        self.print_files(None, files, 1)?;
+
+        Ok(exit_status)
+    }
+
+    /// Gathers up the entries of every directory under the given ones, and
+    /// sorts them all together. The entries borrow the directories they were
+    /// read from, so the directories get kept in `dirs` until they’re done
+    /// with.
+    fn globally_sorted<'dir>(
+        &mut self,
+        dir_files: Vec<Dir>,
+        recurse: RecurseOptions,
+        dirs: &'dir Arena<Dir>,
+    ) -> io::Result<Vec<File<'dir>>> {
+        let mut files = Vec::new();
+        for dir in dir_files {
+            self.read_files_under(dir, recurse, &Ancestors::default(), dirs, &mut files)?;
+        }
+
+        self.options
+            .filter
+            .sort_files(&mut files, self.git.as_ref());
+        Ok(files)
+    }
+
+    /// Adds the entries of the given directory, and of every directory under
+    /// it that gets recursed into, to `files`, for `--global-sort`. Each
+    /// directory’s entries are still cut down to the per-directory limit
+    /// first, the same as without sorting them together. The `.` and `..`
+    /// entries are left out, as there would be one of each for every
+    /// directory.
+    fn read_files_under<'dir>(
+        &mut self,
+        dir: Dir,
+        recurse: RecurseOptions,
+        ancestors: &Ancestors,
+        dirs: &'dir Arena<Dir>,
+        files: &mut Vec<File<'dir>>,
+    ) -> io::Result<()> {
+        if !self.listed.insert(DirId::from_path(&dir.path)) {
+            debug!("Skipping already-listed directory {:?}", dir.path);
+            return Ok(());
+        }
+
+        let depth = dir
+            .path
+            .components()
+            .filter(|&c| c != Component::CurDir)
+            .count()
+            + 1;
+        let ancestors = ancestors.with(DirId::from_path(&dir.path));
+        let dir: &'dir Dir = dirs.alloc(dir);
+
+        let mut children = self.read_children(dir, true)?;
+        if let Some(progress) = &self.progress {
+            progress.add_dir(children.len());
+        }
+        recurse.limit_entries(&mut children);
+
+        let mut child_dirs = Vec::new();
+        if !recurse.is_too_deep(depth) {
+            for child_dir in children
+                .iter()
+                .filter(|f| f.is_directory() && !f.is_all_all)
+                .filter(|f| !self.options.filter.prune_patterns.is_pruned(f))
+            {
+                if ancestors.contains(DirId::from_metadata(&child_dir.metadata)) {
+                    self.clear_progress();
+                    let e = Ancestors::loop_error();
+                    writeln!(io::stderr(), "{}: {}", child_dir.path.display(), e)?;
+                    continue;
+                }
+
+                match child_dir.to_dir() {
+                    Ok(d) => child_dirs.push(d),
+                    Err(e) if recurse.skips_error(&e) => {}
+                    Err(e) => {
+                        self.clear_progress();
+                        writeln!(io::stderr(), "{}: {}", child_dir.path.display(), e)?;
+                    }
+                }
+            }
+        }
+
+        files.extend(children.into_iter().filter(|f| !f.is_all_all));
+        for child_dir in child_dirs {
+            self.read_files_under(child_dir, recurse, &ancestors, dirs, files)?;
+        }
+
+        Ok(())
+    }
+
+    /// Wipes the `--progress` counter from the terminal, so it doesn’t get
+    /// mixed up with whatever’s written next.
+    fn clear_progress(&self) {
+        if let Some(progress) = &self.progress {
+            progress.clear();
+        }
+    }
+
+    /// Says how many of a directory’s entries `--per-dir-limit` left out,
+    /// if any.
+    fn print_hidden_note(&mut self, hidden: usize) -> io::Result<()> {
+        if hidden > 0 {
+            writeln!(&mut self.writer, "{}", details::hidden_note(hidden))?;
+        }
+
+        Ok(())
+    }
+
+    /// Prints the list of files using whichever view is selected. The files
+    /// are `depth` levels below the arguments.
+    fn print_files(
+        &mut self,
+        dir: Option<&Dir>,
+        files: Vec<File<'_>>,
+        depth: usize,
+    ) -> io::Result<()> {
         if files.is_empty() {
             return Ok(());
         }
@@ -406,6 +711,7 @@ impl<'args> Exa<'args> {
                     opts,
                     console_width,
                     filter,
+                    git: self.git.as_ref(),
                 };
                 r.render(&mut self.writer)
             }
@@ -417,6 +723,33 @@ impl<'args> Exa<'args> {
                     theme,
                     file_style,
                     filter,
+                    git: self.git.as_ref(),
+                };
+                r.render(&mut self.writer)
+            }
+
+            (Mode::Template(ref opts), _) => {
+                let filter = &self.options.filter;
+                let r = template::Render {
+                    files,
+                    theme,
+                    file_style,
+                    opts,
+                    filter,
+                    git: self.git.as_ref(),
+                };
+                r.render(&mut self.writer)
+            }
+
+            (Mode::Csv(ref opts), _) => {
+                let filter = &self.options.filter;
+                let r = csv::Render {
+                    files,
+                    theme,
+                    opts,
+                    filter,
+                    git: self.git.as_ref(),
+                    git_repos: self.git_repos,
                 };
                 r.render(&mut self.writer)
             }
@@ -439,6 +772,8 @@ impl<'args> Exa<'args> {
                     git_ignoring,
                     git,
                     git_repos,
+                    depth,
+                    progress: self.progress.as_ref(),
                 };
                 r.render(&mut self.writer)
             }
@@ -464,6 +799,7 @@ impl<'args> Exa<'args> {
                     git,
                     console_width,
                     git_repos,
+                    depth,
                 };
                 r.render(&mut self.writer)
             }
@@ -487,6 +823,8 @@ impl<'args> Exa<'args> {
                     git_ignoring,
                     git,
                     git_repos,
+                    depth,
+                    progress: self.progress.as_ref(),
                 };
                 r.render(&mut self.writer)
             }
@@ -508,3 +846,153 @@ mod exits {
     /// Exit code for missing file permissions
     pub const PERMISSION_DENIED: i32 = 13;
 }
+
+#[cfg(test)]
+#[cfg(unix)]
+mod test {
+    use super::*;
+    use crate::fs::test_dir::TestDir;
+    use filetime::FileTime;
+
+    /// Sets the modification time of a file to the start of the given
+    /// month.
+    fn touch(path: &Path, year: i32, month: u32) {
+        let secs = chrono::NaiveDate::from_ymd_opt(year, month, 1)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+            .and_utc()
+            .timestamp();
+        filetime::set_file_mtime(path, FileTime::from_unix_time(secs, 0)).unwrap();
+    }
+
+    /// Sets up to list files with the given arguments, writing to stdout.
+    fn exa(args: &[&str]) -> Exa<'static> {
+        let OptionsResult::Ok(options, _) = Options::parse(args.iter().map(OsStr::new), &None)
+        else {
+            panic!("Failed to parse {args:?}");
+        };
+
+        let theme = options.theme.to_theme(false);
+        Exa {
+            options,
+            writer: LimitedWriter::new(
+                MarkupWriter::new(RawBytesWriter::new(io::stdout()), None),
+                None,
+            ),
+            input_paths: Vec::new(),
+            theme,
+            console_width: None,
+            git: None,
+            git_repos: false,
+            progress: None,
+            listed: Listed::default(),
+        }
+    }
+
+    /// Reads every directory under the given one, and returns the names of
+    /// all their entries in the order `--global-sort` lists them.
+    fn globally_sorted_names(root: &Path, args: &[&str]) -> Vec<String> {
+        let mut exa = exa(args);
+        let recurse = exa.options.dir_action.recurse_options().unwrap();
+
+        let dirs = Arena::new();
+        let root = Dir::read_dir(root.to_path_buf()).unwrap();
+        let files = exa.globally_sorted(vec![root], recurse, &dirs).unwrap();
+        files.iter().map(|f| f.name.clone()).collect()
+    }
+
+    /// Makes a directory with another inside it, and one inside that.
+    fn nested(name: &str) -> TestDir {
+        let root = TestDir::new(name);
+        std::fs::create_dir_all(root.join("sub/deeper")).unwrap();
+        root
+    }
+
+    // A real loop needs hard links to directories or a bind mount, which
+    // can’t be made in a test, so these pretend that `sub` was entered
+    // further up, which is what the recursion sees when it loops back.
+
+    #[test]
+    fn recursion_stops_at_loop() {
+        let root = nested("loop-recurse");
+        let sub = DirId::from_path(&root.join("sub"));
+        let ancestors = Ancestors::default().with(sub);
+
+        let mut exa = exa(&["--recurse", "--oneline"]);
+        let dir = Dir::read_dir(root.to_path_buf()).unwrap();
+        exa.print_dirs(vec![dir], true, false, 0, &ancestors, 0)
+            .unwrap();
+
+        assert!(!exa.listed.insert(DirId::from_path(&root)));
+        assert!(exa.listed.insert(sub));
+        assert!(exa
+            .listed
+            .insert(DirId::from_path(&root.join("sub/deeper"))));
+    }
+
+    #[test]
+    fn recursion_without_loop() {
+        let root = nested("no-loop-recurse");
+
+        let mut exa = exa(&["--recurse", "--oneline"]);
+        let dir = Dir::read_dir(root.to_path_buf()).unwrap();
+        exa.print_dirs(vec![dir], true, false, 0, &Ancestors::default(), 0)
+            .unwrap();
+
+        assert!(!exa.listed.insert(DirId::from_path(&root.join("sub"))));
+        assert!(!exa
+            .listed
+            .insert(DirId::from_path(&root.join("sub/deeper"))));
+    }
+
+    #[test]
+    fn global_sort_stops_at_loop() {
+        let root = nested("loop-global-sort");
+        let ancestors = Ancestors::default().with(DirId::from_path(&root.join("sub")));
+
+        let mut exa = exa(&["--recurse", "--global-sort"]);
+        let recurse = exa.options.dir_action.recurse_options().unwrap();
+        let dirs = Arena::new();
+        let mut files = Vec::new();
+        let dir = Dir::read_dir(root.to_path_buf()).unwrap();
+        exa.read_files_under(dir, recurse, &ancestors, &dirs, &mut files)
+            .unwrap();
+
+        let names = files.iter().map(|f| f.name.as_str()).collect::<Vec<_>>();
+        assert_eq!(names, ["sub"]);
+    }
+
+    #[test]
+    fn global_sort_by_modified() {
+        let root = TestDir::new("global-sort");
+        std::fs::create_dir_all(root.join("a/deeper")).unwrap();
+        std::fs::create_dir_all(root.join("b")).unwrap();
+
+        let files = [
+            ("a/deeper/old", 2020),
+            ("b/newest", 2023),
+            ("a/middle", 2022),
+            ("oldest", 2019),
+            ("b/older", 2021),
+        ];
+        for (name, year) in files {
+            std::fs::write(root.join(name), "").unwrap();
+            touch(&root.join(name), year, 1);
+        }
+
+        // Directories change when files are added to them, so these have
+        // to come last.
+        touch(&root.join("a"), 2018, 1);
+        touch(&root.join("b"), 2018, 2);
+        touch(&root.join("a/deeper"), 2018, 3);
+
+        let args = ["--recurse", "--global-sort", "--sort=modified"];
+        let names = globally_sorted_names(&root, &args);
+
+        assert_eq!(
+            names,
+            ["a", "b", "deeper", "oldest", "old", "older", "middle", "newest"]
+        );
+    }
+}
@@ -0,0 +1,217 @@
+//! Reading a persistent set of default arguments from the environment.
+//!
+//! The contents of `EZA_DEFAULT_ARGS` are split into arguments and put in
+//! front of the ones given on the command-line. Because options nearer the
+//! end of the arguments take precedence, anything given explicitly overrides
+//! these defaults, just as it would with a shell alias.
+
+use std::ffi::OsString;
+
+use crate::options::{vars, Vars};
+
+/// Returns the arguments from the `EZA_DEFAULT_ARGS` environment variable,
+/// which should be prepended to the actual command-line arguments.
+pub fn from_vars<V: Vars>(vars: &V) -> Vec<OsString> {
+    match vars.get(vars::EZA_DEFAULT_ARGS) {
+        Some(value) => split(&value.to_string_lossy())
+            .into_iter()
+            .map(OsString::from)
+            .collect(),
+        None => Vec::new(),
+    }
+}
+
+/// Splits a string into arguments on whitespace, with some simple quoting:
+/// text inside single quotes is taken literally, text inside double quotes
+/// can contain escaped quotes and backslashes, and a backslash outside
+/// quotes escapes the next character.
+fn split(input: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_arg = false;
+    let mut chars = input.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {
+                if in_arg {
+                    args.push(std::mem::take(&mut current));
+                    in_arg = false;
+                }
+            }
+            '\'' => {
+                in_arg = true;
+                current.extend(chars.by_ref().take_while(|&c| c != '\''));
+            }
+            '"' => {
+                in_arg = true;
+                while let Some(c) = chars.next() {
+                    match c {
+                        '"' => break,
+                        '\\' => match chars.next() {
+                            Some(e @ ('"' | '\\')) => current.push(e),
+                            Some(e) => {
+                                current.push('\\');
+                                current.push(e);
+                            }
+                            None => current.push('\\'),
+                        },
+                        c => current.push(c),
+                    }
+                }
+            }
+            '\\' => {
+                in_arg = true;
+                if let Some(e) = chars.next() {
+                    current.push(e);
+                }
+            }
+            c => {
+                in_arg = true;
+                current.push(c);
+            }
+        }
+    }
+
+    if in_arg {
+        args.push(current);
+    }
+
+    args
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fs::filter::{SortCase, SortField, SortKeys};
+    use crate::options::parser::Flag;
+    use crate::options::{Options, OptionsError, OptionsResult};
+    use std::ffi::OsStr;
+
+    /// Only sets `EZA_DEFAULT_ARGS`, so strict mode and the like stay off.
+    struct DefaultArgs(&'static str);
+
+    impl Vars for DefaultArgs {
+        fn get(&self, name: &'static str) -> Option<OsString> {
+            (name == vars::EZA_DEFAULT_ARGS).then(|| self.0.into())
+        }
+    }
+
+    /// Sets `EZA_DEFAULT_ARGS` as above, with strict mode on.
+    struct StrictDefaultArgs(&'static str);
+
+    impl Vars for StrictDefaultArgs {
+        fn get(&self, name: &'static str) -> Option<OsString> {
+            if name == vars::EZA_DEFAULT_ARGS {
+                Some(self.0.into())
+            } else if name == vars::EZA_STRICT {
+                Some("1".into())
+            } else {
+                None
+            }
+        }
+    }
+
+    #[test]
+    fn empty() {
+        assert!(split("").is_empty());
+        assert!(split("   \t ").is_empty());
+    }
+
+    #[test]
+    fn whitespace() {
+        assert_eq!(
+            split("  -l\t--git  --icons "),
+            vec!["-l", "--git", "--icons"]
+        );
+    }
+
+    #[test]
+    fn single_quotes() {
+        assert_eq!(split(r"-I 'a b|c\d'"), vec!["-I", r"a b|c\d"]);
+    }
+
+    #[test]
+    fn double_quotes() {
+        assert_eq!(
+            split(r#"--time-style="+%Y %m" "say \"hi\"""#),
+            vec!["--time-style=+%Y %m", r#"say "hi""#]
+        );
+    }
+
+    #[test]
+    fn backslash() {
+        assert_eq!(split(r"a\ b c"), vec!["a b", "c"]);
+    }
+
+    #[test]
+    fn empty_quotes() {
+        assert_eq!(split("'' x"), vec!["", "x"]);
+    }
+
+    #[test]
+    fn no_variable() {
+        assert!(from_vars(&None::<OsString>).is_empty());
+    }
+
+    fn sort_keys(default_args: &'static str, cli: &[&'static str]) -> SortKeys {
+        sort_keys_with(&DefaultArgs(default_args), cli)
+    }
+
+    fn sort_keys_with<V: Vars>(vars: &V, cli: &[&'static str]) -> SortKeys {
+        let defaults = from_vars(vars);
+        let args = cli.iter().map(OsStr::new);
+
+        match Options::parse_with_defaults(&defaults, args, vars) {
+            OptionsResult::Ok(options, _) => options.filter.sort_keys,
+            _ => panic!("Failed to parse {defaults:?} {cli:?}"),
+        }
+    }
+
+    #[test]
+    fn applied() {
+        assert_eq!(sort_keys("--sort=size", &[]), SortField::Size.into());
+    }
+
+    #[test]
+    fn overridden_by_cli() {
+        assert_eq!(
+            sort_keys("--sort size", &["--sort=name"]),
+            SortField::Name(SortCase::AaBbCc).into()
+        );
+    }
+
+    #[test]
+    fn overridden_strictly() {
+        assert_eq!(
+            sort_keys_with(&StrictDefaultArgs("--sort size"), &["--sort=name"]),
+            SortField::Name(SortCase::AaBbCc).into()
+        );
+    }
+
+    #[test]
+    fn repeated_strictly() {
+        // Flags repeated on the command-line still clash.
+        let defaults = from_vars(&StrictDefaultArgs("-l"));
+        let cli = ["--long", "-l"].map(OsStr::new);
+
+        match Options::parse_with_defaults(&defaults, cli, &StrictDefaultArgs("-l")) {
+            OptionsResult::InvalidOptions(e) => assert_eq!(
+                e,
+                OptionsError::Duplicate(Flag::Long("long"), Flag::Short(b'l'))
+            ),
+            _ => panic!("Expected a duplicate"),
+        }
+    }
+
+    #[test]
+    fn frees_kept() {
+        let args = from_vars(&DefaultArgs("-l"));
+        let cli = [OsStr::new("src")];
+
+        match Options::parse_with_defaults(&args, cli, &DefaultArgs("-l")) {
+            OptionsResult::Ok(_, frees) => assert_eq!(frees, vec![OsStr::new("src")]),
+            _ => panic!("Failed to parse"),
+        }
+    }
+}
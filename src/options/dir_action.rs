@@ -10,10 +10,16 @@ impl DirAction {
     /// There are three possible actions, and they overlap somewhat: the
     /// `--tree` flag is another form of recursion, so those two are allowed
     /// to both be present, but the `--list-dirs` flag is used separately.
-    pub fn deduce(matches: &MatchedFlags<'_>, can_tree: bool) -> Result<Self, OptionsError> {
+    /// A tree can also be picked with `EZA_DEFAULT_VIEW`, which is passed in
+    /// as `default_tree`.
+    pub fn deduce(
+        matches: &MatchedFlags<'_>,
+        can_tree: bool,
+        default_tree: bool,
+    ) -> Result<Self, OptionsError> {
         let recurse = matches.has(&flags::RECURSE)?;
         let as_file = matches.has(&flags::LIST_DIRS)?;
-        let tree = matches.has(&flags::TREE)?;
+        let tree = matches.has(&flags::TREE)? || (default_tree && !as_file);
 
         if matches.is_strict() {
             // Early check for --level when it wouldn’t do anything
@@ -27,6 +33,56 @@ impl DirAction {
                 return Err(OptionsError::Conflict(&flags::RECURSE, &flags::LIST_DIRS));
             } else if tree && as_file {
                 return Err(OptionsError::Conflict(&flags::TREE, &flags::LIST_DIRS));
+            } else if !recurse && !tree && matches.count(&flags::SKIP_UNREADABLE) > 0 {
+                return Err(OptionsError::Useless2(
+                    &flags::SKIP_UNREADABLE,
+                    &flags::RECURSE,
+                    &flags::TREE,
+                ));
+            } else if !tree && matches.count(&flags::COLLAPSE) > 0 {
+                return Err(OptionsError::Useless(&flags::COLLAPSE, false, &flags::TREE));
+            } else if !recurse && matches.count(&flags::SUMMARIZE_DIRS) > 0 {
+                return Err(OptionsError::Useless(
+                    &flags::SUMMARIZE_DIRS,
+                    false,
+                    &flags::RECURSE,
+                ));
+            } else if !recurse && matches.count(&flags::TREE_SUMMARY) > 0 {
+                return Err(OptionsError::Useless(
+                    &flags::TREE_SUMMARY,
+                    false,
+                    &flags::RECURSE,
+                ));
+            } else if tree && matches.count(&flags::TREE_SUMMARY) > 0 {
+                return Err(OptionsError::Useless(
+                    &flags::TREE_SUMMARY,
+                    true,
+                    &flags::TREE,
+                ));
+            } else if !recurse && !tree && matches.count(&flags::PER_DIR_LIMIT) > 0 {
+                return Err(OptionsError::Useless2(
+                    &flags::PER_DIR_LIMIT,
+                    &flags::RECURSE,
+                    &flags::TREE,
+                ));
+            } else if !recurse && !tree && matches.count(&flags::PROGRESS) > 0 {
+                return Err(OptionsError::Useless2(
+                    &flags::PROGRESS,
+                    &flags::RECURSE,
+                    &flags::TREE,
+                ));
+            } else if !recurse && matches.count(&flags::GLOBAL_SORT) > 0 {
+                return Err(OptionsError::Useless(
+                    &flags::GLOBAL_SORT,
+                    false,
+                    &flags::RECURSE,
+                ));
+            } else if tree && matches.count(&flags::GLOBAL_SORT) > 0 {
+                return Err(OptionsError::Useless(
+                    &flags::GLOBAL_SORT,
+                    true,
+                    &flags::TREE,
+                ));
             }
         }
 
@@ -50,12 +106,27 @@ impl RecurseOptions {
     /// determined earlier. The maximum level should be a number, and this
     /// will fail with an `Err` if it isn’t.
     pub fn deduce(matches: &MatchedFlags<'_>, tree: bool) -> Result<Self, OptionsError> {
+        let summarize = matches.has(&flags::SUMMARIZE_DIRS)?;
+        let skip_unreadable = matches.has(&flags::SKIP_UNREADABLE)?;
+        let collapse = tree && matches.has(&flags::COLLAPSE)?;
+        let per_dir_limit = Self::deduce_per_dir_limit(matches)?;
+        let tree_summary = !tree && matches.has(&flags::TREE_SUMMARY)?;
+        let progress = matches.has(&flags::PROGRESS)?;
+        let global_sort = !tree && matches.has(&flags::GLOBAL_SORT)?;
+
         if let Some(level) = matches.get(&flags::LEVEL)? {
             let arg_str = level.to_string_lossy();
             match arg_str.parse() {
                 Ok(l) => Ok(Self {
                     tree,
                     max_depth: Some(l),
+                    summarize,
+                    skip_unreadable,
+                    collapse,
+                    per_dir_limit,
+                    tree_summary,
+                    progress,
+                    global_sort,
                 }),
                 Err(e) => {
                     let source = NumberSource::Arg(&flags::LEVEL);
@@ -66,9 +137,38 @@ impl RecurseOptions {
             Ok(Self {
                 tree,
                 max_depth: None,
+                summarize,
+                skip_unreadable,
+                collapse,
+                per_dir_limit,
+                tree_summary,
+                progress,
+                global_sort,
             })
         }
     }
+
+    /// Determines how many entries to show for each directory, if there’s a
+    /// limit. The limit has to be a positive number, as a directory with all
+    /// of its entries left out would only show the note.
+    fn deduce_per_dir_limit(matches: &MatchedFlags<'_>) -> Result<Option<usize>, OptionsError> {
+        let Some(limit) = matches.get(&flags::PER_DIR_LIMIT)? else {
+            return Ok(None);
+        };
+
+        let arg_str = limit.to_string_lossy();
+        match arg_str.parse() {
+            Ok(0) => Err(OptionsError::BadArgument(
+                &flags::PER_DIR_LIMIT,
+                limit.into(),
+            )),
+            Ok(l) => Ok(Some(l)),
+            Err(e) => {
+                let source = NumberSource::Arg(&flags::PER_DIR_LIMIT);
+                Err(OptionsError::FailedParse(arg_str.to_string(), source, e))
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -76,6 +176,7 @@ mod test {
     use super::*;
     use crate::options::flags;
     use crate::options::parser::Flag;
+    use std::ffi::OsString;
 
     macro_rules! test {
         ($name:ident: $type:ident <- $inputs:expr; $stricts:expr => $result:expr) => {
@@ -90,9 +191,16 @@ mod test {
                     &flags::LIST_DIRS,
                     &flags::TREE,
                     &flags::LEVEL,
+                    &flags::SUMMARIZE_DIRS,
+                    &flags::SKIP_UNREADABLE,
+                    &flags::COLLAPSE,
+                    &flags::PER_DIR_LIMIT,
+                    &flags::TREE_SUMMARY,
+                    &flags::PROGRESS,
+                    &flags::GLOBAL_SORT,
                 ];
                 for result in parse_for_test($inputs.as_ref(), TEST_ARGS, $stricts, |mf| {
-                    $type::deduce(mf, true)
+                    $type::deduce(mf, true, false)
                 }) {
                     assert_eq!(result, $result);
                 }
@@ -109,26 +217,71 @@ mod test {
 
     // Recursing
     use self::DirAction::Recurse;
-    test!(rec_short:       DirAction <- ["-R"];                           Both => Ok(Recurse(RecurseOptions { tree: false, max_depth: None })));
-    test!(rec_long:        DirAction <- ["--recurse"];                    Both => Ok(Recurse(RecurseOptions { tree: false, max_depth: None })));
-    test!(rec_lim_short:   DirAction <- ["-RL4"];                         Both => Ok(Recurse(RecurseOptions { tree: false, max_depth: Some(4) })));
-    test!(rec_lim_short_2: DirAction <- ["-RL=5"];                        Both => Ok(Recurse(RecurseOptions { tree: false, max_depth: Some(5) })));
-    test!(rec_lim_long:    DirAction <- ["--recurse", "--level", "666"];  Both => Ok(Recurse(RecurseOptions { tree: false, max_depth: Some(666) })));
-    test!(rec_lim_long_2:  DirAction <- ["--recurse", "--level=0118"];    Both => Ok(Recurse(RecurseOptions { tree: false, max_depth: Some(118) })));
-    test!(tree:            DirAction <- ["--tree"];                       Both => Ok(Recurse(RecurseOptions { tree: true,  max_depth: None })));
-    test!(rec_tree:        DirAction <- ["--recurse", "--tree"];          Both => Ok(Recurse(RecurseOptions { tree: true,  max_depth: None })));
-    test!(rec_short_tree:  DirAction <- ["-TR"];                          Both => Ok(Recurse(RecurseOptions { tree: true,  max_depth: None })));
+    test!(rec_short:       DirAction <- ["-R"];                           Both => Ok(Recurse(RecurseOptions { tree: false, ..Default::default() })));
+    test!(rec_long:        DirAction <- ["--recurse"];                    Both => Ok(Recurse(RecurseOptions { tree: false, ..Default::default() })));
+    test!(rec_lim_short:   DirAction <- ["-RL4"];                         Both => Ok(Recurse(RecurseOptions { tree: false, max_depth: Some(4), ..Default::default() })));
+    test!(rec_lim_short_2: DirAction <- ["-RL=5"];                        Both => Ok(Recurse(RecurseOptions { tree: false, max_depth: Some(5), ..Default::default() })));
+    test!(rec_lim_long:    DirAction <- ["--recurse", "--level", "666"];  Both => Ok(Recurse(RecurseOptions { tree: false, max_depth: Some(666), ..Default::default() })));
+    test!(rec_lim_long_2:  DirAction <- ["--recurse", "--level=0118"];    Both => Ok(Recurse(RecurseOptions { tree: false, max_depth: Some(118), ..Default::default() })));
+    test!(tree:            DirAction <- ["--tree"];                       Both => Ok(Recurse(RecurseOptions { tree: true,  ..Default::default() })));
+    test!(rec_tree:        DirAction <- ["--recurse", "--tree"];          Both => Ok(Recurse(RecurseOptions { tree: true,  ..Default::default() })));
+    test!(rec_short_tree:  DirAction <- ["-TR"];                          Both => Ok(Recurse(RecurseOptions { tree: true,  ..Default::default() })));
 
     // Overriding --list-dirs, --recurse, and --tree
-    test!(dirs_recurse:    DirAction <- ["--list-dirs", "--recurse"];     Last => Ok(Recurse(RecurseOptions { tree: false, max_depth: None })));
-    test!(dirs_tree:       DirAction <- ["--list-dirs", "--tree"];        Last => Ok(Recurse(RecurseOptions { tree: true,  max_depth: None })));
+    test!(dirs_recurse:    DirAction <- ["--list-dirs", "--recurse"];     Last => Ok(Recurse(RecurseOptions { tree: false, ..Default::default() })));
+    test!(dirs_tree:       DirAction <- ["--list-dirs", "--tree"];        Last => Ok(Recurse(RecurseOptions { tree: true,  ..Default::default() })));
     test!(just_level:      DirAction <- ["--level=4"];                    Last => Ok(DirAction::List));
 
     test!(dirs_recurse_2:  DirAction <- ["--list-dirs", "--recurse"]; Complain => Err(OptionsError::Conflict(&flags::RECURSE, &flags::LIST_DIRS)));
     test!(dirs_tree_2:     DirAction <- ["--list-dirs", "--tree"];    Complain => Err(OptionsError::Conflict(&flags::TREE,    &flags::LIST_DIRS)));
     test!(just_level_2:    DirAction <- ["--level=4"];                Complain => Err(OptionsError::Useless2(&flags::LEVEL, &flags::RECURSE, &flags::TREE)));
 
+    // Summarising each directory
+    test!(summarize:       DirAction <- ["-R", "--summarize-dirs"];       Both => Ok(Recurse(RecurseOptions { tree: false, summarize: true, ..Default::default() })));
+    test!(summarize_lim:   DirAction <- ["-RL2", "--summarize-dirs"];     Both => Ok(Recurse(RecurseOptions { tree: false, max_depth: Some(2), summarize: true, ..Default::default() })));
+    test!(just_summarize:  DirAction <- ["--summarize-dirs"];             Last => Ok(DirAction::List));
+    test!(just_summarize_2: DirAction <- ["--summarize-dirs"];        Complain => Err(OptionsError::Useless(&flags::SUMMARIZE_DIRS, false, &flags::RECURSE)));
+
+    // Skipping unreadable directories
+    test!(skip_recurse:    DirAction <- ["-R", "--skip-unreadable"];      Both => Ok(Recurse(RecurseOptions { tree: false, skip_unreadable: true, ..Default::default() })));
+    test!(skip_tree:       DirAction <- ["-T", "--skip-unreadable"];      Both => Ok(Recurse(RecurseOptions { tree: true,  skip_unreadable: true, ..Default::default() })));
+    test!(just_skip:       DirAction <- ["--skip-unreadable"];            Last => Ok(DirAction::List));
+    test!(just_skip_2:     DirAction <- ["--skip-unreadable"];        Complain => Err(OptionsError::Useless2(&flags::SKIP_UNREADABLE, &flags::RECURSE, &flags::TREE)));
+
+    // Collapsing single-child directories
+    test!(collapse:        DirAction <- ["-T", "--collapse"];             Both => Ok(Recurse(RecurseOptions { tree: true,  collapse: true, ..Default::default() })));
+    test!(collapse_rec:    DirAction <- ["-R", "--collapse"];             Last => Ok(Recurse(RecurseOptions { tree: false, ..Default::default() })));
+    test!(collapse_rec_2:  DirAction <- ["-R", "--collapse"];         Complain => Err(OptionsError::Useless(&flags::COLLAPSE, false, &flags::TREE)));
+
+    // Limiting the entries per directory
+    test!(limit_recurse:   DirAction <- ["-R", "--per-dir-limit=5"];      Both => Ok(Recurse(RecurseOptions { tree: false, per_dir_limit: Some(5), ..Default::default() })));
+    test!(limit_tree:      DirAction <- ["-T", "--per-dir-limit", "2"];   Both => Ok(Recurse(RecurseOptions { tree: true,  per_dir_limit: Some(2), ..Default::default() })));
+    test!(limit_zero:      DirAction <- ["-R", "--per-dir-limit=0"];      Both => Err(OptionsError::BadArgument(&flags::PER_DIR_LIMIT, OsString::from("0"))));
+    test!(limit_bad:       DirAction <- ["-R", "--per-dir-limit=lots"];   Both => Err(OptionsError::FailedParse(String::from("lots"), NumberSource::Arg(&flags::PER_DIR_LIMIT), "lots".parse::<usize>().unwrap_err())));
+    test!(just_limit:      DirAction <- ["--per-dir-limit=5"];            Last => Ok(DirAction::List));
+    test!(just_limit_2:    DirAction <- ["--per-dir-limit=5"];        Complain => Err(OptionsError::Useless2(&flags::PER_DIR_LIMIT, &flags::RECURSE, &flags::TREE)));
+
+    // Following the listing with a tree of directories
+    test!(tree_summary:    DirAction <- ["-R", "--tree-summary"];         Both => Ok(Recurse(RecurseOptions { tree: false, tree_summary: true, ..Default::default() })));
+    test!(tree_summary_t:  DirAction <- ["-RT", "--tree-summary"];        Last => Ok(Recurse(RecurseOptions { tree: true,  ..Default::default() })));
+    test!(tree_summary_t2: DirAction <- ["-RT", "--tree-summary"];    Complain => Err(OptionsError::Useless(&flags::TREE_SUMMARY, true, &flags::TREE)));
+    test!(just_tree_sum:   DirAction <- ["--tree-summary"];               Last => Ok(DirAction::List));
+    test!(just_tree_sum_2: DirAction <- ["--tree-summary"];           Complain => Err(OptionsError::Useless(&flags::TREE_SUMMARY, false, &flags::RECURSE)));
+
+    // Counting the directories scanned
+    test!(progress:        DirAction <- ["-R", "--progress"];             Both => Ok(Recurse(RecurseOptions { tree: false, progress: true, ..Default::default() })));
+    test!(progress_tree:   DirAction <- ["-T", "--progress"];             Both => Ok(Recurse(RecurseOptions { tree: true,  progress: true, ..Default::default() })));
+    test!(just_progress:   DirAction <- ["--progress"];                   Last => Ok(DirAction::List));
+    test!(just_progress_2: DirAction <- ["--progress"];               Complain => Err(OptionsError::Useless2(&flags::PROGRESS, &flags::RECURSE, &flags::TREE)));
+
+    // Sorting every entry together
+    test!(global_sort:     DirAction <- ["-R", "--global-sort"];          Both => Ok(Recurse(RecurseOptions { tree: false, global_sort: true, ..Default::default() })));
+    test!(global_sort_t:   DirAction <- ["-RT", "--global-sort"];         Last => Ok(Recurse(RecurseOptions { tree: true,  ..Default::default() })));
+    test!(global_sort_t2:  DirAction <- ["-RT", "--global-sort"];     Complain => Err(OptionsError::Useless(&flags::GLOBAL_SORT, true, &flags::TREE)));
+    test!(just_global:     DirAction <- ["--global-sort"];                Last => Ok(DirAction::List));
+    test!(just_global_2:   DirAction <- ["--global-sort"];            Complain => Err(OptionsError::Useless(&flags::GLOBAL_SORT, false, &flags::RECURSE)));
+
     // Overriding levels
-    test!(overriding_1:    DirAction <- ["-RL=6", "-L=7"];                Last => Ok(Recurse(RecurseOptions { tree: false, max_depth: Some(7) })));
+    test!(overriding_1:    DirAction <- ["-RL=6", "-L=7"];                Last => Ok(Recurse(RecurseOptions { tree: false, max_depth: Some(7), ..Default::default() })));
     test!(overriding_2:    DirAction <- ["-RL=6", "-L=7"];            Complain => Err(OptionsError::Duplicate(Flag::Short(b'L'), Flag::Short(b'L'))));
 }
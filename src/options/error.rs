@@ -1,9 +1,11 @@
 use std::ffi::OsString;
 use std::fmt;
 use std::num::ParseIntError;
+use std::ops::RangeInclusive;
 
-use crate::options::flags;
 use crate::options::parser::{Arg, Flag, ParseError};
+use crate::options::{flags, vars};
+use crate::output::template::TemplateError;
 
 /// Something wrong with the combination of options the user has picked.
 #[derive(PartialEq, Eq, Debug)]
@@ -14,6 +16,10 @@ pub enum OptionsError {
     /// The user supplied an illegal choice to an Argument.
     BadArgument(&'static Arg, OsString),
 
+    /// The user supplied an illegal choice to an Argument that’s close
+    /// enough to one of the legal ones to be a typo of it.
+    BadArgumentWithSuggestion(&'static Arg, OsString, String),
+
     /// The user supplied a set of options that are unsupported
     Unsupported(String),
 
@@ -31,12 +37,46 @@ pub enum OptionsError {
     /// are not present.
     Useless2(&'static Arg, &'static Arg, &'static Arg),
 
+    /// Several options were given that do nothing, each of them for one of
+    /// the reasons above, so they can all be reported at once.
+    MultipleUseless(Vec<OptionsError>),
+
     /// A very specific edge case where --tree can’t be used with --all twice.
     TreeAllAll,
 
     /// A numeric option was given that failed to be parsed as a number.
     FailedParse(String, NumberSource, ParseIntError),
 
+    /// A numeric option was given a number outside the range it allows.
+    OutOfRange(String, NumberSource, RangeInclusive<isize>),
+
+    /// The `--width` option was given something that’s neither a number of
+    /// columns nor one of its keywords.
+    BadWidth(String),
+
+    /// The colour scale gradient variable was set to something that isn’t a
+    /// list of colours.
+    BadColorScaleGradient(String),
+
+    /// A custom time style had nothing after its `+`, or, if `recent` is
+    /// set, an empty second line for recent files.
+    EmptyTimeFormat { recent: bool },
+
+    /// The named recent-duration environment variable was set to something
+    /// that isn’t a duration such as `7d`.
+    BadRecentDuration(&'static str, String),
+
+    /// A setting in the `EZA_ICONS` environment variable wasn’t recognised.
+    BadIconsSetting(String),
+
+    /// The `EZA_DEFAULT_VIEW` environment variable named a view that
+    /// doesn’t exist.
+    BadDefaultView(String),
+
+    /// The `--format-template` option was given a template that couldn’t be
+    /// parsed.
+    BadTemplate(TemplateError),
+
     /// A glob ignore was given that failed to be parsed as a pattern.
     FailedGlobPattern(String),
 }
@@ -85,6 +125,9 @@ impl fmt::Display for OptionsError {
                     write!(f, "Option {arg} has no {attempt:?} setting")
                 }
             }
+            Self::BadArgumentWithSuggestion(arg, attempt, choice) => {
+                write!(f, "Option {arg} has no {attempt:?} setting (did you mean {choice:?}?)")
+            }
             Self::Parse(e)                   => write!(f, "{e}"),
             Self::Unsupported(e)             => write!(f, "{e}"),
             Self::Conflict(a, b)             => write!(f, "Option {a} conflicts with option {b}"),
@@ -93,14 +136,44 @@ impl fmt::Display for OptionsError {
             Self::Useless(a, false, b)       => write!(f, "Option {a} is useless without option {b}"),
             Self::Useless(a, true, b)        => write!(f, "Option {a} is useless given option {b}"),
             Self::Useless2(a, b1, b2)        => write!(f, "Option {a} is useless without options {b1} or {b2}"),
+            Self::MultipleUseless(errors)    => {
+                let reasons = errors.iter().map(ToString::to_string).collect::<Vec<_>>();
+                write!(f, "{}", reasons.join("; "))
+            }
             Self::TreeAllAll                 => write!(f, "Option --tree is useless given --all --all"),
             Self::FailedParse(s, n, e)       => write!(f, "Value {s:?} not valid for {n}: {e}"),
+            Self::OutOfRange(s, n, r)        => write!(f, "Value {s:?} not valid for {n}: expected a number from {} to {}", r.start(), r.end()),
+            Self::BadWidth(s)                => write!(f, "Value {s:?} not valid for option {}: expected a number of columns, \"auto\", or \"max\"", flags::WIDTH),
+            Self::BadColorScaleGradient(s)   => write!(f, "Value {s:?} not valid for {}: expected two to eight hex colours, such as \"#1e90ff,#ff4500\"", vars::EZA_COLOR_SCALE_GRADIENT),
+            Self::EmptyTimeFormat { recent: false } => write!(f, "Custom timestamp format for option {} is empty: supply a chrono format string after the plus sign", flags::TIME_STYLE),
+            Self::EmptyTimeFormat { recent: true }  => write!(f, "Custom timestamp format for recent files is empty: supply a chrono format string on the second line of option {}", flags::TIME_STYLE),
+            Self::BadRecentDuration(v, s)    => write!(f, "Value {s:?} not valid for {v}: expected a number followed by s, m, h, d, or w, such as \"7d\""),
+            Self::BadIconsSetting(s)         => write!(f, "Setting {s:?} not valid in {}: expected always, auto, never, ascii, nerd, or spacing=N", vars::EZA_ICONS),
+            Self::BadDefaultView(s)          => write!(f, "Value {s:?} not valid for {}: expected long, oneline, grid, or tree", vars::EZA_DEFAULT_VIEW),
+            Self::BadTemplate(e)             => write!(f, "Invalid template for option {}: {e}", flags::FORMAT_TEMPLATE),
             Self::FailedGlobPattern(ref e)   => write!(f, "Failed to parse glob pattern: {e}"),
         };
     }
 }
 
 impl OptionsError {
+    /// The error for an illegal choice to an Argument, suggesting the legal
+    /// choice it’s closest to if it looks like a typo of one.
+    pub fn bad_argument(arg: &'static Arg, attempt: impl Into<OsString>) -> Self {
+        use crate::options::parser::TakesValue;
+
+        let attempt = attempt.into();
+        let choices = match arg.takes_value {
+            TakesValue::Necessary(Some(values)) | TakesValue::Optional(Some(values), _) => values,
+            _ => &[],
+        };
+
+        match closest_choice(&attempt.to_string_lossy(), choices) {
+            Some(choice) => Self::BadArgumentWithSuggestion(arg, attempt, choice.to_owned()),
+            None => Self::BadArgument(arg, attempt),
+        }
+    }
+
     /// Try to second-guess what the user was trying to do, depending on what
     /// went wrong.
     pub fn suggestion(&self) -> Option<&'static str> {
@@ -117,6 +190,40 @@ impl OptionsError {
     }
 }
 
+/// The choice that the attempt is the fewest edits away from, as long as
+/// that’s at most two, or one for choices short enough that two edits would
+/// turn almost anything into them.
+fn closest_choice(attempt: &str, choices: &'static [&'static str]) -> Option<&'static str> {
+    let attempt = attempt.trim().to_lowercase();
+
+    choices
+        .iter()
+        .map(|choice| (edit_distance(&attempt, choice), *choice))
+        .filter(|(distance, choice)| *distance <= (choice.len() / 3).min(2))
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, choice)| choice)
+}
+
+/// The Levenshtein distance between two strings: how many characters have
+/// to be inserted, deleted, or replaced to turn one into the other.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut previous = (0..=b.len()).collect::<Vec<_>>();
+
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let replace = previous[j] + usize::from(a_char != *b_char);
+            let delete = previous[j + 1] + 1;
+            let insert = current[j] + 1;
+            current.push(replace.min(delete).min(insert));
+        }
+        previous = current;
+    }
+
+    previous[b.len()]
+}
+
 /// A list of legal choices for an argument-taking option.
 #[derive(PartialEq, Eq, Debug)]
 pub struct Choices(pub &'static [&'static str]);
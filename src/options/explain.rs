@@ -0,0 +1,386 @@
+//! Explaining the options that were deduced, and where they came from.
+//!
+//! When flags, environment variables, and aliases interact, it can be hard
+//! to tell why eza picked the view it did. The `--explain` flag prints the
+//! resolved options instead of listing any files.
+
+use std::ffi::OsStr;
+use std::fmt;
+
+use crate::fs::dir_action::DirAction;
+use crate::options::parser::{Arg, Flag, MatchedFlags};
+use crate::options::stdin::FilesInput;
+use crate::options::{flags, vars, Options, Vars};
+use crate::output::{Mode, TerminalWidth};
+use crate::theme::UseColours;
+
+/// Where the value of a resolved option came from.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum Source {
+    /// It was given as a command-line flag.
+    Flag(Flag),
+
+    /// It was read from an environment variable.
+    Env(&'static str),
+
+    /// Nothing was given, so the default was used.
+    Default,
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Flag(flag) => write!(f, "from {flag}"),
+            Self::Env(env) => write!(f, "from ${env}"),
+            Self::Default => write!(f, "default"),
+        }
+    }
+}
+
+/// One resolved option: its name, its value, and, if a single thing
+/// decided it, where that value came from.
+#[derive(PartialEq, Eq, Debug)]
+struct Line {
+    name: &'static str,
+    value: String,
+    source: Option<Source>,
+}
+
+/// The options that were deduced from the command-line and environment,
+/// in a form that can be displayed to the user.
+#[derive(PartialEq, Eq, Debug)]
+pub struct Explanation {
+    lines: Vec<Line>,
+}
+
+impl Explanation {
+    /// Determines whether to explain the options, based on the user’s
+    /// command-line arguments. Like --help, this doesn’t check for errors.
+    pub fn deduce<V: Vars>(
+        matches: &MatchedFlags<'_>,
+        options: &Options,
+        frees: &[&OsStr],
+        vars: &V,
+    ) -> Option<Self> {
+        if matches.count(&flags::EXPLAIN) == 0 {
+            return None;
+        }
+
+        // Finds the last of the given flags, as that’s the one that counts.
+        // Flags put in front of the arguments by `EZA_DEFAULT_ARGS` come
+        // from that variable, not from the command-line.
+        let last_of = |args: &[&'static Arg]| {
+            matches
+                .last_where_with_origin(|f| args.iter().any(|a| f.matches(a)))
+                .map(|(flag, from_defaults)| {
+                    if from_defaults {
+                        Source::Env(vars::EZA_DEFAULT_ARGS)
+                    } else {
+                        Source::Flag(*flag)
+                    }
+                })
+        };
+
+        let env = |name: &'static str| vars.get(name).map(|_| Source::Env(name));
+
+        let mut lines = Vec::new();
+
+        let view = match options.view.mode {
+            Mode::Lines => "lines",
+            Mode::Grid(_) => "grid",
+            Mode::Details(_) => "details",
+            Mode::GridDetails(_) => "grid-details",
+            Mode::Template(_) => "template",
+            Mode::Csv(_) => "csv",
+        };
+        lines.push(Line {
+            name: "view",
+            value: view.into(),
+            source: Some(
+                last_of(&[&flags::FORMAT_TEMPLATE, &flags::CSV])
+                    .or_else(|| {
+                        last_of(&[&flags::ONE_LINE, &flags::LONG, &flags::GRID, &flags::TREE])
+                    })
+                    .or_else(|| env(vars::EZA_DEFAULT_VIEW))
+                    .unwrap_or(Source::Default),
+            ),
+        });
+
+        let table = match &options.view.mode {
+            Mode::Details(details) => details.table.as_ref(),
+            Mode::GridDetails(grid_details) => grid_details.details.table.as_ref(),
+            _ => None,
+        };
+        if let Some(table) = table {
+            // The Git columns depend on the paths being listed, so they’re
+            // worked out the same way as when the files are listed.
+            let paths = if frees.is_empty() && options.stdin == FilesInput::Args {
+                vec![OsStr::new(".")]
+            } else {
+                frees.to_vec()
+            };
+            let columns = table
+                .columns
+                .collect(options.should_scan_for_git(), options.git_repos(&paths))
+                .into_iter()
+                .map(|c| c.name())
+                .collect::<Vec<_>>();
+            lines.push(Line {
+                name: "columns",
+                value: columns.join(", "),
+                source: None,
+            });
+        }
+
+        let (width, source) = match options.view.width {
+            TerminalWidth::Set(width) => (
+                width.to_string(),
+                last_of(&[&flags::WIDTH]).or_else(|| env(vars::COLUMNS)),
+            ),
+            TerminalWidth::Offset(offset) => (
+                format!("automatic, {offset} columns"),
+                last_of(&[&flags::WIDTH]),
+            ),
+            TerminalWidth::Automatic => ("automatic".into(), last_of(&[&flags::WIDTH])),
+        };
+        lines.push(Line {
+            name: "width",
+            value: width,
+            source: Some(source.unwrap_or(Source::Default)),
+        });
+
+        if let Some(min_width) = options.view.min_width {
+            lines.push(Line {
+                name: "min width",
+                value: min_width.to_string(),
+                source: last_of(&[&flags::MIN_WIDTH]).or_else(|| env(vars::EZA_MIN_WIDTH)),
+            });
+        }
+
+        if let Some(max_width) = options.view.max_width {
+            lines.push(Line {
+                name: "max width",
+                value: max_width.to_string(),
+                source: last_of(&[&flags::MAX_WIDTH]).or_else(|| env(vars::EZA_MAX_WIDTH)),
+            });
+        }
+
+        let colours = match options.theme.use_colours {
+            UseColours::Always => "always",
+            UseColours::Automatic => "automatic",
+            UseColours::Never => "never",
+            UseColours::Html => "html",
+            UseColours::Pango => "pango",
+        };
+        lines.push(Line {
+            name: "colours",
+            value: colours.into(),
+            source: Some(
+                last_of(&[&flags::COLOR, &flags::COLOUR])
+                    .or_else(|| env(vars::NO_COLOR))
+                    .or_else(|| UseColours::dumb_terminal(vars).then_some(Source::Env(vars::TERM)))
+                    .unwrap_or(Source::Default),
+            ),
+        });
+
+        lines.push(Line {
+            name: "sort",
+            value: options.filter.sort_keys.to_string(),
+            source: Some(last_of(&[&flags::SORT]).unwrap_or(Source::Default)),
+        });
+
+        let recursion = match options.dir_action {
+            DirAction::AsFile => "list directories as files".into(),
+            DirAction::List => "list directory contents".into(),
+            DirAction::Recurse(r) => {
+                let kind = if r.tree { "tree" } else { "recurse" };
+                match r.max_depth {
+                    Some(depth) => format!("{kind}, {depth} levels deep"),
+                    None => kind.into(),
+                }
+            }
+        };
+        lines.push(Line {
+            name: "recursion",
+            value: recursion,
+            source: Some(
+                last_of(&[&flags::LIST_DIRS, &flags::RECURSE, &flags::TREE])
+                    .unwrap_or(Source::Default),
+            ),
+        });
+
+        let strict = env(vars::EZA_STRICT).or_else(|| env(vars::EXA_STRICT));
+        lines.push(Line {
+            name: "strict",
+            value: if matches.is_strict() { "on" } else { "off" }.into(),
+            source: Some(strict.unwrap_or(Source::Default)),
+        });
+
+        Some(Self { lines })
+    }
+}
+
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for line in &self.lines {
+            match line.source {
+                Some(source) => writeln!(f, "{:<10} {} ({source})", line.name, line.value)?,
+                None => writeln!(f, "{:<10} {}", line.name, line.value)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::options::{vars, Options, OptionsResult, Vars};
+    use std::ffi::{OsStr, OsString};
+
+    fn explain(args: &[&'static str]) -> String {
+        let args = args.iter().map(OsStr::new);
+        match Options::parse(args, &None) {
+            OptionsResult::Explain(explanation) => explanation.to_string(),
+            _ => panic!("Expected an explanation"),
+        }
+    }
+
+    #[test]
+    fn not_explained() {
+        let args = vec![OsStr::new("--long")];
+        let opts = Options::parse(args, &None);
+        assert!(!matches!(opts, OptionsResult::Explain(_)));
+    }
+
+    #[test]
+    fn long() {
+        let text = explain(&["--explain", "--long", "--sort=size"]);
+        assert!(text.contains("view       details (from --long)"), "{text}");
+        assert!(
+            text.contains("columns    permissions, size, user, modified"),
+            "{text}"
+        );
+        assert!(text.contains("sort       size (from --sort)"), "{text}");
+    }
+
+    #[test]
+    fn defaults() {
+        let text = explain(&["--explain"]);
+        assert!(text.contains("sort       name (default)"), "{text}");
+        assert!(
+            text.contains("recursion  list directory contents (default)"),
+            "{text}"
+        );
+    }
+
+    #[test]
+    fn sort_keys() {
+        let text = explain(&["--explain", "--sort=filesize:desc,Name"]);
+        assert!(
+            text.contains("sort       size:desc,Name (from --sort)"),
+            "{text}"
+        );
+    }
+
+    #[test]
+    fn short_flags() {
+        let text = explain(&["--explain", "-T", "-w", "80"]);
+        assert!(text.contains("view       details (from -T)"), "{text}");
+        assert!(text.contains("recursion  tree (from -T)"), "{text}");
+        assert!(text.contains("width      80 (from -w)"), "{text}");
+    }
+
+    #[test]
+    fn max_width() {
+        let text = explain(&["--explain", "-w", "200", "--max-width=120"]);
+        assert!(text.contains("width      120 (from -w)"), "{text}");
+        assert!(text.contains("max width  120 (from --max-width)"), "{text}");
+    }
+
+    #[test]
+    fn min_width() {
+        let text = explain(&["--explain", "--min-width=60"]);
+        assert!(text.contains("min width  60 (from --min-width)"), "{text}");
+    }
+
+    #[test]
+    fn last_flag_counts() {
+        let text = explain(&["--explain", "--long", "-1"]);
+        assert!(text.contains("view       lines (from -1)"), "{text}");
+        assert!(!text.contains("columns"), "{text}");
+    }
+
+    /// Explains the options with the given default arguments in front of
+    /// the command-line ones.
+    fn explain_with_defaults(defaults: &[&str], args: &[&'static str]) -> String {
+        let defaults = defaults.iter().map(OsString::from).collect::<Vec<_>>();
+        let args = args.iter().map(OsStr::new);
+        match Options::parse_with_defaults(&defaults, args, &None) {
+            OptionsResult::Explain(explanation) => explanation.to_string(),
+            _ => panic!("Expected an explanation"),
+        }
+    }
+
+    #[test]
+    fn from_default_args() {
+        let text = explain_with_defaults(&["--long", "--sort=size"], &["--explain"]);
+        assert!(
+            text.contains("view       details (from $EZA_DEFAULT_ARGS)"),
+            "{text}"
+        );
+        assert!(
+            text.contains("sort       size (from $EZA_DEFAULT_ARGS)"),
+            "{text}"
+        );
+    }
+
+    #[test]
+    fn default_args_overridden() {
+        let text = explain_with_defaults(&["--long"], &["--explain", "-1"]);
+        assert!(text.contains("view       lines (from -1)"), "{text}");
+    }
+
+    /// Only sets `EZA_DEFAULT_VIEW`.
+    struct DefaultView(&'static str);
+
+    impl Vars for DefaultView {
+        fn get(&self, name: &'static str) -> Option<OsString> {
+            (name == vars::EZA_DEFAULT_VIEW).then(|| self.0.into())
+        }
+    }
+
+    #[test]
+    fn default_view() {
+        let args = ["--explain"].map(OsStr::new);
+        let OptionsResult::Explain(explanation) = Options::parse(args, &DefaultView("long")) else {
+            panic!("Expected an explanation");
+        };
+        let text = explanation.to_string();
+        assert!(
+            text.contains("view       details (from $EZA_DEFAULT_VIEW)"),
+            "{text}"
+        );
+    }
+
+    #[test]
+    fn default_view_overridden() {
+        let args = ["--explain", "--grid"].map(OsStr::new);
+        let OptionsResult::Explain(explanation) = Options::parse(args, &DefaultView("long")) else {
+            panic!("Expected an explanation");
+        };
+        let text = explanation.to_string();
+        assert!(text.contains("view       grid (from --grid)"), "{text}");
+    }
+
+    #[test]
+    fn repo_column_needs_repos() {
+        // None of the test files are repositories, so the column that shows
+        // their status isn’t in the listing, or the explanation.
+        let text = explain(&["--explain", "--long", "--git-repos", "tests/itest"]);
+        assert!(
+            text.contains("columns    permissions, size, user, modified\n"),
+            "{text}"
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use crate::fs::filter::GitIgnore;
 use crate::options::parser::MatchedFlags;
 use crate::options::vars::{self, Vars};
 use crate::options::{flags, NumberSource, OptionsError};
@@ -5,6 +6,7 @@ use crate::options::{flags, NumberSource, OptionsError};
 use crate::output::file_name::{
     Absolute, Classify, EmbedHyperlinks, Options, QuoteStyle, ShowIcons,
 };
+use crate::output::icons::IconSet;
 
 impl Options {
     pub fn deduce<V: Vars>(
@@ -12,87 +14,316 @@ impl Options {
         vars: &V,
         is_a_tty: bool,
     ) -> Result<Self, OptionsError> {
-        let classify = Classify::deduce(matches)?;
-        let show_icons = ShowIcons::deduce(matches, vars)?;
+        let plain = Self::deduce_plain(matches)?;
+
+        let classify = Classify::deduce(matches, plain)?;
+        let classify_link_targets = Self::deduce_classify_link_targets(matches)?;
+        let classify_xattrs = Self::deduce_classify_xattrs(matches)?;
+        let show_icons = ShowIcons::deduce(matches, vars, plain)?;
+        let icon_set = IconSet::deduce(matches, vars)?;
 
         let quote_style = QuoteStyle::deduce(matches)?;
-        let embed_hyperlinks = EmbedHyperlinks::deduce(matches)?;
+        let embed_hyperlinks = EmbedHyperlinks::deduce(matches, plain)?;
 
         let absolute = Absolute::deduce(matches)?;
+        let full_paths = Self::deduce_full_paths(matches)?;
+        let resolve_link_targets = matches.has(&flags::RESOLVE_LINK_TARGETS)?;
+        let highlight_risky = matches.has(&flags::HIGHLIGHT_RISKY)?;
+        let mark_ignored = Self::deduce_mark_ignored(matches)?;
 
         Ok(Self {
             classify,
+            classify_link_targets,
+            classify_xattrs,
             show_icons,
+            icon_set,
             quote_style,
             embed_hyperlinks,
             absolute,
+            full_paths,
+            resolve_link_targets,
+            highlight_risky,
+            mark_ignored,
             is_a_tty,
         })
     }
+
+    /// Whether the user asked for plain output with `--color=never`. Plain
+    /// output drops icons, hyperlinks, and file kind indicators too, unless
+    /// they were forced on with `always`.
+    fn deduce_plain(matches: &MatchedFlags<'_>) -> Result<bool, OptionsError> {
+        let word = matches.get_where(|f| f.matches(&flags::COLOR) || f.matches(&flags::COLOUR))?;
+        Ok(word.is_some_and(|w| w == "never"))
+    }
+
+    /// Whether to show the paths of the files in recursed directories with
+    /// `--full-paths`. This does nothing without `--recurse`.
+    fn deduce_full_paths(matches: &MatchedFlags<'_>) -> Result<bool, OptionsError> {
+        let full_paths = matches.has(&flags::FULL_PATHS)?;
+
+        if full_paths && !matches.has(&flags::RECURSE)? {
+            if matches.is_strict() {
+                return Err(OptionsError::Useless(
+                    &flags::FULL_PATHS,
+                    false,
+                    &flags::RECURSE,
+                ));
+            }
+            return Ok(false);
+        }
+
+        Ok(full_paths)
+    }
+
+    /// Whether to dim the names of files that Git ignores with
+    /// `--mark-ignored`. This does nothing when `--git-ignore` is hiding
+    /// them anyway.
+    fn deduce_mark_ignored(matches: &MatchedFlags<'_>) -> Result<bool, OptionsError> {
+        let mark_ignored = matches.has(&flags::MARK_IGNORED)?;
+
+        if mark_ignored && GitIgnore::deduce(matches)? == GitIgnore::CheckAndIgnore {
+            if matches.is_strict() {
+                return Err(OptionsError::Useless(
+                    &flags::MARK_IGNORED,
+                    true,
+                    &flags::GIT_IGNORE,
+                ));
+            }
+            return Ok(false);
+        }
+
+        Ok(mark_ignored)
+    }
+
+    /// Whether to follow the `@` of a symlink with the indicator for the
+    /// type of file it points to. This does nothing without `--classify`.
+    fn deduce_classify_link_targets(matches: &MatchedFlags<'_>) -> Result<bool, OptionsError> {
+        let classify_link_targets = matches.has(&flags::CLASSIFY_LINK_TARGETS)?;
+
+        if classify_link_targets && matches.is_strict() && matches.count(&flags::CLASSIFY) == 0 {
+            return Err(OptionsError::Useless(
+                &flags::CLASSIFY_LINK_TARGETS,
+                false,
+                &flags::CLASSIFY,
+            ));
+        }
+
+        Ok(classify_link_targets)
+    }
+
+    /// Whether to add an `@` to files with extended attributes. This does
+    /// nothing without `--classify`.
+    fn deduce_classify_xattrs(matches: &MatchedFlags<'_>) -> Result<bool, OptionsError> {
+        let classify_xattrs = matches.has(&flags::CLASSIFY_XATTRS)?;
+
+        if classify_xattrs && matches.is_strict() && matches.count(&flags::CLASSIFY) == 0 {
+            return Err(OptionsError::Useless(
+                &flags::CLASSIFY_XATTRS,
+                false,
+                &flags::CLASSIFY,
+            ));
+        }
+
+        Ok(classify_xattrs)
+    }
 }
 
 impl Classify {
-    fn deduce(matches: &MatchedFlags<'_>) -> Result<Self, OptionsError> {
+    fn deduce(matches: &MatchedFlags<'_>, plain: bool) -> Result<Self, OptionsError> {
         let mode_opt = matches.get(&flags::CLASSIFY)?;
+        let style_opt = matches.get(&flags::INDICATOR_STYLE)?;
 
-        match mode_opt {
-            Some(word) => match word.to_str() {
+        if matches.is_strict() && mode_opt.is_some() && style_opt.is_some() {
+            return Err(OptionsError::Conflict(
+                &flags::CLASSIFY,
+                &flags::INDICATOR_STYLE,
+            ));
+        }
+
+        match (mode_opt, style_opt) {
+            (Some(word), _) => match word.to_str() {
                 Some("always") => Ok(Self::AddFileIndicators),
+                Some("auto" | "automatic") if plain => Ok(Self::JustFilenames),
                 Some("auto" | "automatic") => Ok(Self::AutomaticAddFileIndicators),
                 Some("never") => Ok(Self::JustFilenames),
                 _ => Err(OptionsError::BadArgument(&flags::CLASSIFY, word.into())),
             },
+            // `-p` is the lighter version of `-F` from `ls`, where only
+            // directories get a character after them.
+            (None, Some(word)) => match word.to_str() {
+                Some("slash") => Ok(Self::AddDirectorySlashes),
+                Some("classify") => Ok(Self::AddFileIndicators),
+                Some("none") => Ok(Self::JustFilenames),
+                _ => Err(OptionsError::BadArgument(
+                    &flags::INDICATOR_STYLE,
+                    word.into(),
+                )),
+            },
             // No flag given, default to just filenames
-            None => Ok(Self::JustFilenames),
+            (None, None) => Ok(Self::JustFilenames),
         }
     }
 }
 
-impl ShowIcons {
-    pub fn deduce<V: Vars>(matches: &MatchedFlags<'_>, vars: &V) -> Result<Self, OptionsError> {
-        enum AlwaysOrAuto {
-            Always,
-            Automatic,
+/// When to show icons, as given by `--icons=WHEN` or in `EZA_ICONS`.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+enum IconsWhen {
+    Always,
+    Automatic,
+    Never,
+}
+
+/// The icon settings from the `EZA_ICONS` environment variable, a list such
+/// as `auto,ascii,spacing=2`. Each of them is only used when the
+/// command-line doesn’t give its own.
+#[derive(PartialEq, Eq, Debug, Default)]
+struct IconsVar {
+    when: Option<IconsWhen>,
+    set: Option<IconSet>,
+    spacing: Option<u32>,
+}
+
+impl IconsVar {
+    /// Reads the settings from the environment. Unknown or malformed
+    /// settings are skipped, unless being strict, when they’re an error.
+    fn deduce<V: Vars>(matches: &MatchedFlags<'_>, vars: &V) -> Result<Self, OptionsError> {
+        let Some(var) = vars.get(vars::EZA_ICONS) else {
+            return Ok(Self::default());
+        };
+
+        let mut settings = Self::default();
+        for token in var.to_string_lossy().split(',').map(str::trim) {
+            if token.is_empty() {
+                continue;
+            }
+
+            match token {
+                "always" => settings.when = Some(IconsWhen::Always),
+                "auto" | "automatic" => settings.when = Some(IconsWhen::Automatic),
+                "never" => settings.when = Some(IconsWhen::Never),
+                "ascii" => settings.set = Some(IconSet::Ascii),
+                "nerd" | "nerdfont" => settings.set = Some(IconSet::NerdFont),
+                _ => match token.strip_prefix("spacing=").map(str::parse) {
+                    Some(Ok(spacing)) => settings.spacing = Some(spacing),
+                    _ if matches.is_strict() => {
+                        return Err(OptionsError::BadIconsSetting(token.into()))
+                    }
+                    _ => {}
+                },
+            }
         }
 
-        let force_icons = vars.get(vars::EZA_ICONS_AUTO).is_some();
+        Ok(settings)
+    }
+}
+
+impl ShowIcons {
+    pub fn deduce<V: Vars>(
+        matches: &MatchedFlags<'_>,
+        vars: &V,
+        plain: bool,
+    ) -> Result<Self, OptionsError> {
+        let env = IconsVar::deduce(matches, vars)?;
+        let force_icons = vars.get(vars::EZA_ICONS_AUTO).is_some() || env.when.is_some();
         let mode_opt = matches.get(&flags::ICONS)?;
         if !force_icons && !matches.has(&flags::ICONS)? && mode_opt.is_none() {
+            if matches.is_strict() && matches.get(&flags::ICON_SPACING)?.is_some() {
+                return Err(OptionsError::Useless(
+                    &flags::ICON_SPACING,
+                    false,
+                    &flags::ICONS,
+                ));
+            }
+
             return Ok(Self::Never);
         }
 
         let mode = match mode_opt {
             Some(word) => match word.to_str() {
-                Some("always") => AlwaysOrAuto::Always,
-                Some("auto" | "automatic") => AlwaysOrAuto::Automatic,
-                Some("never") => return Ok(Self::Never),
-                None => AlwaysOrAuto::Automatic,
+                Some("always") => IconsWhen::Always,
+                Some("auto" | "automatic" | "ascii") => IconsWhen::Automatic,
+                Some("never") => IconsWhen::Never,
+                None => IconsWhen::Automatic,
                 _ => return Err(OptionsError::BadArgument(&flags::ICONS, word.into())),
             },
-            None => AlwaysOrAuto::Automatic,
+            None => env.when.unwrap_or(IconsWhen::Automatic),
         };
 
-        let width = if let Some(columns) = vars
+        if mode == IconsWhen::Never || (plain && mode == IconsWhen::Automatic) {
+            return Ok(Self::Never);
+        }
+
+        let width = Self::deduce_spacing(matches, vars, &env)?;
+
+        match mode {
+            IconsWhen::Always => Ok(Self::Always(width)),
+            IconsWhen::Automatic | IconsWhen::Never => Ok(Self::Automatic(width)),
+        }
+    }
+
+    /// The number of spaces between an icon and its file name. The
+    /// `--icon-spacing` flag takes precedence over the environment, where
+    /// `EZA_ICONS` comes before `EZA_ICON_SPACING`, and without any of them
+    /// there’s a single space.
+    fn deduce_spacing<V: Vars>(
+        matches: &MatchedFlags<'_>,
+        vars: &V,
+        env: &IconsVar,
+    ) -> Result<u32, OptionsError> {
+        if let Some(spacing) = matches.get(&flags::ICON_SPACING)? {
+            let arg_str = spacing.to_string_lossy();
+            return arg_str.parse().map_err(|e| {
+                let source = NumberSource::Arg(&flags::ICON_SPACING);
+                OptionsError::FailedParse(arg_str.to_string(), source, e)
+            });
+        }
+
+        if let Some(spacing) = env.spacing {
+            return Ok(spacing);
+        }
+
+        if let Some(columns) = vars
             .get_with_fallback(vars::EXA_ICON_SPACING, vars::EZA_ICON_SPACING)
             .and_then(|s| s.into_string().ok())
         {
             match columns.parse() {
-                Ok(width) => width,
+                Ok(width) => Ok(width),
                 Err(e) => {
                     let source = NumberSource::Env(
                         vars.source(vars::EXA_ICON_SPACING, vars::EZA_ICON_SPACING)
                             .unwrap(),
                     );
-                    return Err(OptionsError::FailedParse(columns, source, e));
+                    Err(OptionsError::FailedParse(columns, source, e))
                 }
             }
         } else {
-            1
-        };
+            Ok(1)
+        }
+    }
+}
 
-        match mode {
-            AlwaysOrAuto::Always => Ok(Self::Always(width)),
-            AlwaysOrAuto::Automatic => Ok(Self::Automatic(width)),
+impl IconSet {
+    /// Determines which set of icons to use. `--icons=ascii` picks the basic
+    /// set, as does setting `EZA_ICON_SET` to `ascii` for users whose fonts
+    /// lack the Nerd Font icons. A set named in `EZA_ICONS` comes before
+    /// `EZA_ICON_SET`. Otherwise, the Nerd Font icons are used.
+    pub fn deduce<V: Vars>(matches: &MatchedFlags<'_>, vars: &V) -> Result<Self, OptionsError> {
+        if matches
+            .get(&flags::ICONS)?
+            .is_some_and(|word| word == "ascii")
+        {
+            return Ok(Self::Ascii);
+        }
+
+        if let Some(set) = IconsVar::deduce(matches, vars)?.set {
+            return Ok(set);
+        }
+
+        match vars.get(vars::EZA_ICON_SET) {
+            Some(set) if set.to_string_lossy().trim().eq_ignore_ascii_case("ascii") => {
+                Ok(Self::Ascii)
+            }
+            _ => Ok(Self::NerdFont),
         }
     }
 }
@@ -108,13 +339,16 @@ impl QuoteStyle {
 }
 
 impl EmbedHyperlinks {
-    fn deduce(matches: &MatchedFlags<'_>) -> Result<Self, OptionsError> {
-        let flagged = matches.has(&flags::HYPERLINK)?;
-
-        if flagged {
-            Ok(Self::On)
-        } else {
-            Ok(Self::Off)
+    fn deduce(matches: &MatchedFlags<'_>, plain: bool) -> Result<Self, OptionsError> {
+        match matches.get(&flags::HYPERLINK)? {
+            Some(word) => match word.to_str() {
+                Some("always") => Ok(Self::On),
+                Some("auto" | "automatic") if plain => Ok(Self::Off),
+                Some("auto" | "automatic") => Ok(Self::On),
+                Some("never") => Ok(Self::Off),
+                _ => Err(OptionsError::BadArgument(&flags::HYPERLINK, word.into())),
+            },
+            None => Ok(Self::Off),
         }
     }
 }
@@ -132,3 +366,213 @@ impl Absolute {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::options::flags;
+    use crate::options::parser::Arg;
+    use std::ffi::OsString;
+
+    use crate::options::test::parse_for_test;
+    use crate::options::test::Strictnesses::*;
+
+    /// Environment variables with only the given ones set.
+    struct Env(&'static [(&'static str, &'static str)]);
+
+    impl Vars for Env {
+        fn get(&self, name: &'static str) -> Option<OsString> {
+            self.0
+                .iter()
+                .find(|(n, _)| *n == name)
+                .map(|(_, value)| OsString::from(value))
+        }
+    }
+
+    static TEST_ARGS: &[&Arg] = &[
+        &flags::CLASSIFY,
+        &flags::CLASSIFY_LINK_TARGETS,
+        &flags::CLASSIFY_XATTRS,
+        &flags::INDICATOR_STYLE,
+        &flags::RESOLVE_LINK_TARGETS,
+        &flags::COLOR,
+        &flags::COLOUR,
+        &flags::ICONS,
+        &flags::ICON_SPACING,
+        &flags::HYPERLINK,
+        &flags::RECURSE,
+        &flags::FULL_PATHS,
+        &flags::ALL,
+        &flags::GIT_IGNORE,
+        &flags::MARK_IGNORED,
+    ];
+
+    macro_rules! test {
+        ($name:ident: $field:ident <- $inputs:expr; $stricts:expr => $result:expr) => {
+            #[test]
+            fn $name() {
+                for result in parse_for_test($inputs.as_ref(), TEST_ARGS, $stricts, |mf| {
+                    Options::deduce(mf, &None::<OsString>, true).map(|o| o.$field)
+                }) {
+                    assert_eq!(result, $result);
+                }
+            }
+        };
+    }
+
+    // Classifying link targets
+    test!(link_targets:        classify_link_targets <- ["-F", "--classify-link-targets"];   Both => Ok(true));
+    test!(link_targets_off:    classify_link_targets <- ["-F"];                              Both => Ok(false));
+    test!(link_targets_alone:  classify_link_targets <- ["--classify-link-targets"];         Last => Ok(true));
+    test!(link_targets_alone_2: classify_link_targets <- ["--classify-link-targets"];    Complain => Err(OptionsError::Useless(&flags::CLASSIFY_LINK_TARGETS, false, &flags::CLASSIFY)));
+
+    // Classifying files with extended attributes
+    test!(xattrs:              classify_xattrs <- ["-F", "--classify-xattrs"];   Both => Ok(true));
+    test!(xattrs_off:          classify_xattrs <- ["-F"];                        Both => Ok(false));
+    test!(xattrs_alone:        classify_xattrs <- ["--classify-xattrs"];         Last => Ok(true));
+    test!(xattrs_alone_2:      classify_xattrs <- ["--classify-xattrs"];     Complain => Err(OptionsError::Useless(&flags::CLASSIFY_XATTRS, false, &flags::CLASSIFY)));
+
+    // Resolving link targets
+    test!(resolve_targets:     resolve_link_targets <- ["--resolve-link-targets"];   Both => Ok(true));
+    test!(resolve_targets_off: resolve_link_targets <- [];                           Both => Ok(false));
+
+    // Indicator styles
+    test!(classify:            classify <- ["--classify"];                           Both => Ok(Classify::AutomaticAddFileIndicators));
+    test!(slash:               classify <- ["-p"];                                   Both => Ok(Classify::AddDirectorySlashes));
+    test!(slash_long:          classify <- ["--indicator-style"];                    Both => Ok(Classify::AddDirectorySlashes));
+    test!(slash_named:         classify <- ["--indicator-style=slash"];              Both => Ok(Classify::AddDirectorySlashes));
+    test!(style_classify:      classify <- ["--indicator-style", "classify"];        Both => Ok(Classify::AddFileIndicators));
+    test!(style_none:          classify <- ["--indicator-style=none"];               Both => Ok(Classify::JustFilenames));
+    test!(style_bad:           classify <- ["--indicator-style=type"];               Both => Err(OptionsError::BadArgument(&flags::INDICATOR_STYLE, OsString::from("type"))));
+    test!(style_and_classify:  classify <- ["-p", "-F"];                             Last => Ok(Classify::AutomaticAddFileIndicators));
+    test!(style_and_classify_2: classify <- ["-p", "-F"];                        Complain => Err(OptionsError::Conflict(&flags::CLASSIFY, &flags::INDICATOR_STYLE)));
+    test!(classify_plain:      classify <- ["--classify", "--color=never"];          Both => Ok(Classify::JustFilenames));
+    test!(classify_plain_always: classify <- ["--classify=always", "--colour=never"]; Both => Ok(Classify::AddFileIndicators));
+    test!(classify_colour:     classify <- ["--classify", "--color=always"];         Both => Ok(Classify::AutomaticAddFileIndicators));
+
+    // Full paths
+    test!(full_paths:          full_paths <- ["--recurse", "--full-paths"];          Both => Ok(true));
+    test!(full_paths_short:    full_paths <- ["-R", "--full-paths"];                 Both => Ok(true));
+    test!(full_paths_off:      full_paths <- ["--recurse"];                          Both => Ok(false));
+    test!(full_paths_alone:    full_paths <- ["--full-paths"];                       Last => Ok(false));
+    test!(full_paths_alone_2:  full_paths <- ["--full-paths"];                   Complain => Err(OptionsError::Useless(&flags::FULL_PATHS, false, &flags::RECURSE)));
+
+    // Marking ignored files
+    test!(mark_ignored:        mark_ignored <- ["--mark-ignored"];                   Both => Ok(true));
+    test!(mark_ignored_off:    mark_ignored <- [];                                   Both => Ok(false));
+    test!(mark_ignored_all:    mark_ignored <- ["--git-ignore", "-aaa", "--mark-ignored"]; Both => Ok(true));
+    test!(mark_ignored_hidden: mark_ignored <- ["--git-ignore", "--mark-ignored"];   Last => Ok(false));
+    test!(mark_ignored_hidden_2: mark_ignored <- ["--git-ignore", "--mark-ignored"]; Complain => Err(OptionsError::Useless(&flags::MARK_IGNORED, true, &flags::GIT_IGNORE)));
+
+    // Icons
+    test!(icons:               show_icons <- ["--icons"];                            Both => Ok(ShowIcons::Automatic(1)));
+    test!(icons_never:         show_icons <- ["--icons", "--color=never"];           Both => Ok(ShowIcons::Never));
+    test!(icons_always:        show_icons <- ["--icons=always", "--colour=never"];   Both => Ok(ShowIcons::Always(1)));
+    test!(icons_colour:        show_icons <- ["--icons", "--color=always"];          Both => Ok(ShowIcons::Automatic(1)));
+    test!(icons_ascii:         show_icons <- ["--icons=ascii"];                      Both => Ok(ShowIcons::Automatic(1)));
+
+    // Icon spacing
+    test!(spacing:             show_icons <- ["--icons", "--icon-spacing=3"];        Both => Ok(ShowIcons::Automatic(3)));
+    test!(spacing_zero:        show_icons <- ["--icons=always", "--icon-spacing", "0"]; Both => Ok(ShowIcons::Always(0)));
+    test!(spacing_no_icons:    show_icons <- ["--icon-spacing=2"];                   Last => Ok(ShowIcons::Never));
+    test!(spacing_no_icons_2:  show_icons <- ["--icon-spacing=2"];                   Complain => Err(OptionsError::Useless(&flags::ICON_SPACING, false, &flags::ICONS)));
+
+    #[test]
+    fn spacing_bad() {
+        for result in parse_for_test(&["--icons", "--icon-spacing=wide"], TEST_ARGS, Both, |mf| {
+            ShowIcons::deduce(mf, &None::<OsString>, false)
+        }) {
+            assert!(matches!(
+                result,
+                Err(OptionsError::FailedParse(_, NumberSource::Arg(_), _))
+            ));
+        }
+    }
+
+    #[test]
+    fn spacing_overrides_env() {
+        let env = Env(&[("EZA_ICON_SPACING", "4")]);
+        for result in parse_for_test(&["--icons", "--icon-spacing=2"], TEST_ARGS, Both, |mf| {
+            ShowIcons::deduce(mf, &env, false)
+        }) {
+            assert_eq!(result, Ok(ShowIcons::Automatic(2)));
+        }
+    }
+
+    // Icon sets
+    test!(icon_set:            icon_set <- ["--icons"];                              Both => Ok(IconSet::NerdFont));
+    test!(icon_set_ascii:      icon_set <- ["--icons=ascii"];                        Both => Ok(IconSet::Ascii));
+    test!(icon_set_always:     icon_set <- ["--icons=always"];                       Both => Ok(IconSet::NerdFont));
+
+    #[test]
+    fn icon_set_env() {
+        for result in parse_for_test(&["--icons"], TEST_ARGS, Both, |mf| {
+            IconSet::deduce(mf, &Some(OsString::from("ascii")))
+        }) {
+            assert_eq!(result, Ok(IconSet::Ascii));
+        }
+    }
+
+    // Icon settings from the environment
+    macro_rules! env_test {
+        ($name:ident: $type:ident <- $inputs:expr, $env:expr; $stricts:expr => $result:expr) => {
+            #[test]
+            fn $name() {
+                let env = Env(&[("EZA_ICONS", $env)]);
+                for result in parse_for_test($inputs.as_ref(), TEST_ARGS, $stricts, |mf| {
+                    $type::deduce(mf, &env, false)
+                }) {
+                    assert_eq!(result, $result);
+                }
+            }
+        };
+    }
+
+    env_test!(env_auto:          ShowIcons <- [], "auto";                           Both => Ok(ShowIcons::Automatic(1)));
+    env_test!(env_always:        ShowIcons <- [], "always,spacing=2";               Both => Ok(ShowIcons::Always(2)));
+    env_test!(env_spacing_only:  ShowIcons <- [], "spacing=2";                      Both => Ok(ShowIcons::Never));
+    env_test!(env_spaces:        ShowIcons <- [], " auto , spacing=3 ";             Both => Ok(ShowIcons::Automatic(3)));
+    env_test!(env_flag_when:     ShowIcons <- ["--icons=never"], "always";          Both => Ok(ShowIcons::Never));
+    env_test!(env_flag_on:       ShowIcons <- ["--icons=always"], "never,spacing=2"; Both => Ok(ShowIcons::Always(2)));
+    env_test!(env_flag_spacing:  ShowIcons <- ["--icon-spacing=4"], "auto,spacing=2"; Both => Ok(ShowIcons::Automatic(4)));
+    env_test!(env_skipped:       ShowIcons <- [], "auto,sparkly,spacing=x";         Last => Ok(ShowIcons::Automatic(1)));
+    env_test!(env_skipped_2:     ShowIcons <- [], "auto,sparkly,spacing=x";         Complain => Err(OptionsError::BadIconsSetting(String::from("sparkly"))));
+
+    #[test]
+    fn env_icon_set() {
+        let env = Env(&[("EZA_ICONS", "auto,ascii"), ("EZA_ICON_SET", "nerd")]);
+        for result in parse_for_test(&["--icons"], TEST_ARGS, Both, |mf| {
+            IconSet::deduce(mf, &env)
+        }) {
+            assert_eq!(result, Ok(IconSet::Ascii));
+        }
+    }
+
+    #[test]
+    fn env_icon_set_flag() {
+        let env = Env(&[("EZA_ICONS", "nerd")]);
+        for result in parse_for_test(&["--icons=ascii"], TEST_ARGS, Both, |mf| {
+            IconSet::deduce(mf, &env)
+        }) {
+            assert_eq!(result, Ok(IconSet::Ascii));
+        }
+    }
+
+    #[test]
+    fn env_spacing_over_old_var() {
+        let env = Env(&[("EZA_ICONS", "auto,spacing=2"), ("EZA_ICON_SPACING", "4")]);
+        for result in parse_for_test(&[], TEST_ARGS, Both, |mf| {
+            ShowIcons::deduce(mf, &env, false)
+        }) {
+            assert_eq!(result, Ok(ShowIcons::Automatic(2)));
+        }
+    }
+
+    // Hyperlinks
+    test!(hyperlink:           embed_hyperlinks <- ["--hyperlink"];                           Both => Ok(EmbedHyperlinks::On));
+    test!(hyperlink_off:       embed_hyperlinks <- [];                                        Both => Ok(EmbedHyperlinks::Off));
+    test!(hyperlink_never:     embed_hyperlinks <- ["--hyperlink", "--color=never"];          Both => Ok(EmbedHyperlinks::Off));
+    test!(hyperlink_always:    embed_hyperlinks <- ["--hyperlink=always", "--color=never"];   Both => Ok(EmbedHyperlinks::On));
+    test!(hyperlink_disabled:  embed_hyperlinks <- ["--hyperlink=never"];                     Both => Ok(EmbedHyperlinks::Off));
+    test!(hyperlink_error:     embed_hyperlinks <- ["--hyperlink=sometimes"];                 Both => Err(OptionsError::BadArgument(&flags::HYPERLINK, OsString::from("sometimes"))));
+}
@@ -1,12 +1,16 @@
 //! Parsing the options for `FileFilter`.
 
+use crate::fs::fields::nlink_t;
+#[cfg(unix)]
+use crate::fs::filter::OwnerOrder;
 use crate::fs::filter::{
-    FileFilter, FileFilterFlags, GitIgnore, IgnorePatterns, SortCase, SortField,
+    FileFilter, FileFilterFlags, GitIgnore, GroupDirs, IgnorePatterns, PrunePatterns, SortCase,
+    SortField, SortKey, SortKeys,
 };
 use crate::fs::DotFilter;
 
-use crate::options::parser::MatchedFlags;
-use crate::options::{flags, OptionsError};
+use crate::options::parser::{Flag, MatchedFlags};
+use crate::options::{flags, NumberSource, OptionsError};
 
 impl FileFilter {
     /// Determines which of all the file filter options to use.
@@ -26,24 +30,108 @@ impl FileFilter {
 
         #[rustfmt::skip]
         return Ok(Self {
-            list_dirs_first:  matches.has(&flags::DIRS_FIRST)?,
+            group_dirs:       GroupDirs::deduce(matches)?,
             flags: filter_flags,
-            sort_field:       SortField::deduce(matches)?,
+            sort_keys:        SortKeys::deduce(matches)?,
             dot_filter:       DotFilter::deduce(matches)?,
             ignore_patterns:  IgnorePatterns::deduce(matches)?,
             git_ignore:       GitIgnore::deduce(matches)?,
+            prune_patterns:   PrunePatterns::deduce(matches)?,
+            min_links:        Self::deduce_min_links(matches)?,
         });
     }
+
+    /// Determines the fewest hard links a file needs to be listed, from the
+    /// `--min-links` flag. The links column also uses this to highlight the
+    /// files that pass. Every file has at least one link, so zero is an
+    /// error rather than a way of turning the filter off.
+    pub(super) fn deduce_min_links(
+        matches: &MatchedFlags<'_>,
+    ) -> Result<Option<nlink_t>, OptionsError> {
+        let Some(min) = matches.get(&flags::MIN_LINKS)? else {
+            return Ok(None);
+        };
+
+        let arg_str = min.to_string_lossy();
+        match arg_str.parse() {
+            Ok(0) => Err(OptionsError::BadArgument(&flags::MIN_LINKS, min.into())),
+            Ok(n) => Ok(Some(n)),
+            Err(e) => {
+                let source = NumberSource::Arg(&flags::MIN_LINKS);
+                Err(OptionsError::FailedParse(arg_str.to_string(), source, e))
+            }
+        }
+    }
 }
 
-impl SortField {
-    /// Determines which sort field to use based on the `--sort` argument.
-    /// This argument’s value can be one of several flags, listed above.
-    /// Returns the default sort field if none is given, or `Err` if the
-    /// value doesn’t correspond to a sort field we know about.
+impl GroupDirs {
+    /// Determines where to put directories based on the
+    /// `--group-directories-first` (or `--gdf`) and `--group-directories-last`
+    /// flags. When both are given, the one nearest the end wins, unless
+    /// strict mode is on, in which case they conflict. Symlinks to
+    /// directories are grouped with the directories if
+    /// `--group-directories=with-links` is also given.
     fn deduce(matches: &MatchedFlags<'_>) -> Result<Self, OptionsError> {
+        let is_first = |f: &Flag| f.matches(&flags::DIRS_FIRST) || f.matches(&flags::GDF);
+        let first = matches.has_where(is_first)?;
+        let last = matches.has(&flags::DIRS_LAST)?;
+
+        if matches.is_strict() && first.is_some() && last {
+            return Err(OptionsError::Conflict(
+                &flags::DIRS_FIRST,
+                &flags::DIRS_LAST,
+            ));
+        }
+
+        let links = matches.get(&flags::GROUP_DIRS_WITH)?;
+        let flag = matches.has_where_any(|f| is_first(f) || f.matches(&flags::DIRS_LAST));
+
+        if matches.is_strict() && links.is_some() && flag.is_none() {
+            return Err(OptionsError::Useless(
+                &flags::GROUP_DIRS_WITH,
+                false,
+                &flags::DIRS_FIRST,
+            ));
+        }
+
+        // Whether symlinks to directories are grouped with them.
+        let with_links = match links {
+            None => false,
+            Some(word) if word == "dirs" => false,
+            Some(word) if word == "with-links" => true,
+            Some(word) => {
+                return Err(OptionsError::BadArgument(
+                    &flags::GROUP_DIRS_WITH,
+                    word.into(),
+                ))
+            }
+        };
+
+        Ok(match flag {
+            Some(f) if is_first(f) => Self::First { with_links },
+            Some(_) => Self::Last { with_links },
+            None => Self::None,
+        })
+    }
+}
+
+impl SortKeys {
+    /// Determines which fields to sort by based on the `--sort` argument.
+    /// Its value is a comma-separated list of fields, each of which can
+    /// have `:asc` or `:desc` after it to pick its direction, such as
+    /// `size:desc,name`. Files that are equal by one field are then sorted
+    /// by the next. Returns the default sort field if none is given, or
+    /// `Err` if any of the fields isn’t one we know about. Sorting by Git
+    /// status needs `--git`, as that’s what makes eza look for repositories,
+    /// and `--numeric` sorts owners and groups by ID instead of by name.
+    pub(super) fn deduce(matches: &MatchedFlags<'_>) -> Result<Self, OptionsError> {
+        let seed = Self::deduce_seed(matches)?;
+
         let Some(word) = matches.get(&flags::SORT)? else {
-            return Ok(Self::default());
+            if seed.is_some() && matches.is_strict() {
+                return Err(OptionsError::Useless(&flags::SEED, false, &flags::SORT));
+            }
+            return Ok(SortField::default().into());
         };
 
         // Get String because we can’t match an OsStr
@@ -51,6 +139,96 @@ impl SortField {
             return Err(OptionsError::BadArgument(&flags::SORT, word.into()));
         };
 
+        let mut keys = word
+            .split(',')
+            .map(SortKey::deduce)
+            .collect::<Result<_, _>>()
+            .map(Self)?;
+
+        #[cfg(unix)]
+        if matches.has(&flags::NUMERIC)? {
+            for key in &mut keys.0 {
+                if let SortField::User(order) | SortField::Group(order) = &mut key.field {
+                    *order = OwnerOrder::Numeric;
+                }
+            }
+        }
+
+        if let Some(seed) = seed {
+            let mut shuffled = false;
+            for key in &mut keys.0 {
+                if let SortField::Random(random) = &mut key.field {
+                    *random = Some(seed);
+                    shuffled = true;
+                }
+            }
+
+            if !shuffled && matches.is_strict() {
+                return Err(OptionsError::Useless(&flags::SEED, false, &flags::SORT));
+            }
+        }
+
+        if keys.contains(SortField::GitStatus) && matches.count(&flags::GIT) == 0 {
+            return Err(OptionsError::Unsupported(String::from(
+                "Option --sort=git can't be used without --git",
+            )));
+        }
+
+        Ok(keys)
+    }
+
+    /// Determines the seed to shuffle with for `--sort=random`, so the same
+    /// seed always gives the same order.
+    fn deduce_seed(matches: &MatchedFlags<'_>) -> Result<Option<u64>, OptionsError> {
+        let Some(seed) = matches.get(&flags::SEED)? else {
+            return Ok(None);
+        };
+
+        let arg_str = seed.to_string_lossy();
+        match arg_str.parse() {
+            Ok(n) => Ok(Some(n)),
+            Err(e) => {
+                let source = NumberSource::Arg(&flags::SEED);
+                Err(OptionsError::FailedParse(arg_str.to_string(), source, e))
+            }
+        }
+    }
+
+    /// Whether any of the keys sorts by owner or group, which `--numeric`
+    /// affects even without `--long`.
+    pub(super) fn sorts_by_owner(&self) -> bool {
+        #[cfg(unix)]
+        return self
+            .0
+            .iter()
+            .any(|key| matches!(key.field, SortField::User(_) | SortField::Group(_)));
+
+        #[cfg(not(unix))]
+        return false;
+    }
+}
+
+impl SortKey {
+    /// Determines a single sort key, with its optional direction.
+    fn deduce(word: &str) -> Result<Self, OptionsError> {
+        let (field, descending) = match word.rsplit_once(':') {
+            Some((field, "asc")) => (field, false),
+            Some((field, "desc")) => (field, true),
+            _ => (word, false),
+        };
+
+        Ok(Self {
+            field: SortField::deduce(field)?,
+            descending,
+        })
+    }
+}
+
+impl SortField {
+    /// Determines which sort field a single word of the `--sort` argument
+    /// means. It can be one of several flags, listed above. Returns `Err`
+    /// if the word doesn’t correspond to a sort field we know about.
+    fn deduce(word: &str) -> Result<Self, OptionsError> {
         let field = match word {
             "name" | "filename" => Self::Name(SortCase::AaBbCc),
             "Name" | "Filename" => Self::Name(SortCase::ABCabc),
@@ -77,6 +255,13 @@ impl SortField {
             #[cfg(unix)]
             "inode" => Self::FileInode,
             "type" => Self::FileType,
+            "git" => Self::GitStatus,
+            "children" => Self::Children,
+            "random" => Self::Random(None),
+            #[cfg(unix)]
+            "owner" | "user" => Self::User(OwnerOrder::Name),
+            #[cfg(unix)]
+            "group" => Self::Group(OwnerOrder::Name),
             "none" => Self::Unsorted,
             _ => {
                 return Err(OptionsError::BadArgument(&flags::SORT, word.into()));
@@ -124,9 +309,20 @@ impl Default for SortField {
     }
 }
 
+/// How many times `--all` has to be given to show files that ignore files,
+/// such as `.gitignore` with `--git-ignore`, would otherwise hide.
+///
+/// Each level shows everything the one before it does, and more:
+///
+/// 1. `-a` shows dotfiles;
+/// 2. `-aa` also shows the `.` and `..` directories;
+/// 3. `-aaa` also shows ignored files, overriding the ignore files.
+pub const ALL_SHOWS_IGNORED: usize = 3;
+
 impl DotFilter {
     /// Determines the dot filter based on how many `--all` options were
     /// given: one will show dotfiles, but two will show `.` and `..` too.
+    /// A third shows ignored files as well, which `GitIgnore` deals with.
     /// --almost-all is equivalent to --all, included for compatibility with
     /// `ls -A`.
     ///
@@ -149,7 +345,7 @@ impl DotFilter {
             (c, _) => {
                 if matches.count(&flags::TREE) > 0 {
                     Err(OptionsError::TreeAllAll)
-                } else if matches.is_strict() && c > 2 {
+                } else if matches.is_strict() && c > ALL_SHOWS_IGNORED {
                     Err(OptionsError::Conflict(&flags::ALL, &flags::ALL))
                 } else {
                     Ok(Self::DotfilesAndDots)
@@ -183,9 +379,33 @@ impl IgnorePatterns {
     }
 }
 
+impl PrunePatterns {
+    /// Determines the set of glob patterns for directories not to recurse
+    /// into, based on every `--prune-dirs` argument’s value. Unlike
+    /// `--ignore-glob`, this can be given more than once, and each value
+    /// can hold several patterns separated by pipe (`|`) characters.
+    pub fn deduce(matches: &MatchedFlags<'_>) -> Result<Self, OptionsError> {
+        let inputs = matches
+            .get_all(&flags::PRUNE_DIRS)
+            .into_iter()
+            .map(|input| input.to_string_lossy().into_owned())
+            .collect::<Vec<_>>();
+
+        let (patterns, mut errors) =
+            IgnorePatterns::parse_from_iter(inputs.iter().flat_map(|input| input.split('|')));
+
+        match errors.pop() {
+            Some(e) => Err(e.into()),
+            None => Ok(Self(patterns)),
+        }
+    }
+}
+
 impl GitIgnore {
+    /// Determines whether to hide Git-ignored files, which `--git-ignore`
+    /// does unless `--all` was given enough times to show them anyway.
     pub fn deduce(matches: &MatchedFlags<'_>) -> Result<Self, OptionsError> {
-        if matches.has(&flags::GIT_IGNORE)? {
+        if matches.has(&flags::GIT_IGNORE)? && matches.count(&flags::ALL) < ALL_SHOWS_IGNORED {
             Ok(Self::CheckAndIgnore)
         } else {
             Ok(Self::Off)
@@ -215,6 +435,14 @@ mod test {
                     &flags::TREE,
                     &flags::IGNORE_GLOB,
                     &flags::GIT_IGNORE,
+                    &flags::DIRS_FIRST,
+                    &flags::GDF,
+                    &flags::DIRS_LAST,
+                    &flags::GROUP_DIRS_WITH,
+                    &flags::PRUNE_DIRS,
+                    &flags::GIT,
+                    &flags::NUMERIC,
+                    &flags::SEED,
                 ];
                 for result in parse_for_test($inputs.as_ref(), TEST_ARGS, $stricts, |mf| {
                     $type::deduce(mf)
@@ -228,32 +456,73 @@ mod test {
     mod sort_fields {
         use super::*;
 
+        fn key(field: SortField, descending: bool) -> SortKey {
+            SortKey { field, descending }
+        }
+
         // Default behaviour
-        test!(empty:         SortField <- [];                  Both => Ok(SortField::default()));
+        test!(empty:         SortKeys <- [];                  Both => Ok(SortField::default().into()));
 
         // Sort field arguments
-        test!(one_arg:       SortField <- ["--sort=mod"];      Both => Ok(SortField::ModifiedDate));
-        test!(one_long:      SortField <- ["--sort=size"];     Both => Ok(SortField::Size));
-        test!(one_short:     SortField <- ["-saccessed"];      Both => Ok(SortField::AccessedDate));
-        test!(lowercase:     SortField <- ["--sort", "name"];  Both => Ok(SortField::Name(SortCase::AaBbCc)));
-        test!(uppercase:     SortField <- ["--sort", "Name"];  Both => Ok(SortField::Name(SortCase::ABCabc)));
-        test!(old:           SortField <- ["--sort", "new"];   Both => Ok(SortField::ModifiedDate));
-        test!(oldest:        SortField <- ["--sort=newest"];   Both => Ok(SortField::ModifiedDate));
-        test!(new:           SortField <- ["--sort", "old"];   Both => Ok(SortField::ModifiedAge));
-        test!(newest:        SortField <- ["--sort=oldest"];   Both => Ok(SortField::ModifiedAge));
-        test!(age:           SortField <- ["-sage"];           Both => Ok(SortField::ModifiedAge));
-
-        test!(mix_hidden_lowercase:     SortField <- ["--sort", ".name"];  Both => Ok(SortField::NameMixHidden(SortCase::AaBbCc)));
-        test!(mix_hidden_uppercase:     SortField <- ["--sort", ".Name"];  Both => Ok(SortField::NameMixHidden(SortCase::ABCabc)));
+        test!(one_arg:       SortKeys <- ["--sort=mod"];      Both => Ok(SortField::ModifiedDate.into()));
+        test!(one_long:      SortKeys <- ["--sort=size"];     Both => Ok(SortField::Size.into()));
+        test!(one_short:     SortKeys <- ["-saccessed"];      Both => Ok(SortField::AccessedDate.into()));
+        test!(lowercase:     SortKeys <- ["--sort", "name"];  Both => Ok(SortField::Name(SortCase::AaBbCc).into()));
+        test!(uppercase:     SortKeys <- ["--sort", "Name"];  Both => Ok(SortField::Name(SortCase::ABCabc).into()));
+        test!(old:           SortKeys <- ["--sort", "new"];   Both => Ok(SortField::ModifiedDate.into()));
+        test!(oldest:        SortKeys <- ["--sort=newest"];   Both => Ok(SortField::ModifiedDate.into()));
+        test!(new:           SortKeys <- ["--sort", "old"];   Both => Ok(SortField::ModifiedAge.into()));
+        test!(newest:        SortKeys <- ["--sort=oldest"];   Both => Ok(SortField::ModifiedAge.into()));
+        test!(age:           SortKeys <- ["-sage"];           Both => Ok(SortField::ModifiedAge.into()));
+        test!(children:      SortKeys <- ["--sort=children"]; Both => Ok(SortField::Children.into()));
+
+        test!(mix_hidden_lowercase:     SortKeys <- ["--sort", ".name"];  Both => Ok(SortField::NameMixHidden(SortCase::AaBbCc).into()));
+        test!(mix_hidden_uppercase:     SortKeys <- ["--sort", ".Name"];  Both => Ok(SortField::NameMixHidden(SortCase::ABCabc).into()));
 
         // Errors
-        test!(error:         SortField <- ["--sort=colour"];   Both => Err(OptionsError::BadArgument(&flags::SORT, OsString::from("colour"))));
+        test!(error:         SortKeys <- ["--sort=colour"];   Both => Err(OptionsError::BadArgument(&flags::SORT, OsString::from("colour"))));
 
         // Overriding
-        test!(overridden:    SortField <- ["--sort=cr",       "--sort", "mod"];     Last => Ok(SortField::ModifiedDate));
-        test!(overridden_2:  SortField <- ["--sort", "none",  "--sort=Extension"];  Last => Ok(SortField::Extension(SortCase::ABCabc)));
-        test!(overridden_3:  SortField <- ["--sort=cr",       "--sort", "mod"];     Complain => Err(OptionsError::Duplicate(Flag::Long("sort"), Flag::Long("sort"))));
-        test!(overridden_4:  SortField <- ["--sort", "none",  "--sort=Extension"];  Complain => Err(OptionsError::Duplicate(Flag::Long("sort"), Flag::Long("sort"))));
+        test!(overridden:    SortKeys <- ["--sort=cr",       "--sort", "mod"];     Last => Ok(SortField::ModifiedDate.into()));
+        test!(overridden_2:  SortKeys <- ["--sort", "none",  "--sort=Extension"];  Last => Ok(SortField::Extension(SortCase::ABCabc).into()));
+        test!(overridden_3:  SortKeys <- ["--sort=cr",       "--sort", "mod"];     Complain => Err(OptionsError::Duplicate(Flag::Long("sort"), Flag::Long("sort"))));
+        test!(overridden_4:  SortKeys <- ["--sort", "none",  "--sort=Extension"];  Complain => Err(OptionsError::Duplicate(Flag::Long("sort"), Flag::Long("sort"))));
+
+        // Several keys, with directions
+        test!(two_keys:      SortKeys <- ["--sort=size,name"];         Both => Ok(SortKeys(vec![key(SortField::Size, false), key(SortField::Name(SortCase::AaBbCc), false)])));
+        test!(directions:    SortKeys <- ["--sort=size:desc,name:asc"]; Both => Ok(SortKeys(vec![key(SortField::Size, true), key(SortField::Name(SortCase::AaBbCc), false)])));
+        test!(one_desc:      SortKeys <- ["-smod:desc"];                Both => Ok(SortKeys(vec![key(SortField::ModifiedDate, true)])));
+        test!(created_mod:   SortKeys <- ["--sort", "cr,mod:desc"];     Both => Ok(SortKeys(vec![key(SortField::CreatedDate, false), key(SortField::ModifiedDate, true)])));
+        test!(bad_key:       SortKeys <- ["--sort=size,colour"];        Both => Err(OptionsError::BadArgument(&flags::SORT, OsString::from("colour"))));
+        test!(bad_direction: SortKeys <- ["--sort=size:up"];            Both => Err(OptionsError::BadArgument(&flags::SORT, OsString::from("size:up"))));
+        test!(empty_key:     SortKeys <- ["--sort=size,"];              Both => Err(OptionsError::BadArgument(&flags::SORT, OsString::new())));
+
+        // Git status, which needs --git
+        test!(git:           SortKeys <- ["--sort=git", "--git"];       Both => Ok(SortField::GitStatus.into()));
+        test!(git_name:      SortKeys <- ["--git", "--sort=git,name"];  Both => Ok(SortKeys(vec![key(SortField::GitStatus, false), key(SortField::Name(SortCase::AaBbCc), false)])));
+        test!(git_no_git:    SortKeys <- ["--sort=git"];                Both => Err(OptionsError::Unsupported(String::from("Option --sort=git can't be used without --git"))));
+
+        // Owners and groups, by name unless --numeric is given
+        #[cfg(unix)]
+        test!(owner:         SortKeys <- ["--sort=owner"];              Both => Ok(SortField::User(OwnerOrder::Name).into()));
+        #[cfg(unix)]
+        test!(user:          SortKeys <- ["--sort=user:desc"];          Both => Ok(SortKeys(vec![key(SortField::User(OwnerOrder::Name), true)])));
+        #[cfg(unix)]
+        test!(group_owner:   SortKeys <- ["--sort=group,owner"];        Both => Ok(SortKeys(vec![key(SortField::Group(OwnerOrder::Name), false), key(SortField::User(OwnerOrder::Name), false)])));
+        #[cfg(unix)]
+        test!(owner_numeric: SortKeys <- ["--sort=owner,name", "-n"];   Both => Ok(SortKeys(vec![key(SortField::User(OwnerOrder::Numeric), false), key(SortField::Name(SortCase::AaBbCc), false)])));
+        #[cfg(unix)]
+        test!(group_numeric: SortKeys <- ["--numeric", "-sgroup"];      Both => Ok(SortField::Group(OwnerOrder::Numeric).into()));
+
+        // Shuffling
+        test!(random:        SortKeys <- ["--sort=random"];                 Both => Ok(SortField::Random(None).into()));
+        test!(random_seed:   SortKeys <- ["--sort=random", "--seed=42"];    Both => Ok(SortField::Random(Some(42)).into()));
+        test!(random_desc:   SortKeys <- ["--seed", "7", "-srandom:desc"];  Both => Ok(SortKeys(vec![key(SortField::Random(Some(7)), true)])));
+        test!(bad_seed:      SortKeys <- ["--sort=random", "--seed=-1"];    Both => Err(OptionsError::FailedParse(String::from("-1"), NumberSource::Arg(&flags::SEED), "-1".parse::<u64>().unwrap_err())));
+        test!(seed_no_sort:  SortKeys <- ["--seed=42"];                     Last => Ok(SortField::default().into()));
+        test!(seed_no_sort_2: SortKeys <- ["--seed=42"];                    Complain => Err(OptionsError::Useless(&flags::SEED, false, &flags::SORT)));
+        test!(seed_size:     SortKeys <- ["--sort=size", "--seed=42"];      Last => Ok(SortField::Size.into()));
+        test!(seed_size_2:   SortKeys <- ["--sort=size", "--seed=42"];      Complain => Err(OptionsError::Useless(&flags::SEED, false, &flags::SORT)));
     }
 
     mod dot_filters {
@@ -267,8 +536,9 @@ mod test {
         test!(all_all:          DotFilter <- ["--all", "-a"];  Both => Ok(DotFilter::DotfilesAndDots));
         test!(all_all_2:        DotFilter <- ["-aa"];          Both => Ok(DotFilter::DotfilesAndDots));
 
-        test!(all_all_3:        DotFilter <- ["-aaa"];         Last => Ok(DotFilter::DotfilesAndDots));
-        test!(all_all_4:        DotFilter <- ["-aaa"];         Complain => Err(OptionsError::Conflict(&flags::ALL, &flags::ALL)));
+        test!(all_all_3:        DotFilter <- ["-aaa"];         Both => Ok(DotFilter::DotfilesAndDots));
+        test!(all_all_4:        DotFilter <- ["-aaaa"];        Last => Ok(DotFilter::DotfilesAndDots));
+        test!(all_all_5:        DotFilter <- ["-aaaa"];        Complain => Err(OptionsError::Conflict(&flags::ALL, &flags::ALL)));
 
         // --all and --tree
         test!(tree_a:           DotFilter <- ["-Ta"];          Both => Ok(DotFilter::Dotfiles));
@@ -302,10 +572,180 @@ mod test {
         test!(overridden_4: IgnorePatterns <- ["-I", "*.OGG", "-I*.MP3"];      Complain => Err(OptionsError::Duplicate(Flag::Short(b'I'), Flag::Short(b'I'))));
     }
 
+    mod prune_patterns {
+        use super::*;
+        use std::iter::FromIterator;
+
+        fn pats(strings: &[&'static str]) -> PrunePatterns {
+            PrunePatterns(IgnorePatterns::from_iter(
+                strings.iter().map(|s| glob::Pattern::new(s).unwrap()),
+            ))
+        }
+
+        test!(none:     PrunePatterns <- [];                                                      Both => Ok(pats(&[])));
+        test!(one:      PrunePatterns <- ["--prune-dirs", "node_modules"];                        Both => Ok(pats(&["node_modules"])));
+        test!(piped:    PrunePatterns <- ["--prune-dirs=node_modules|.git"];                      Both => Ok(pats(&["node_modules", ".git"])));
+        test!(repeated: PrunePatterns <- ["--prune-dirs=node_modules", "--prune-dirs", ".git"];   Both => Ok(pats(&["node_modules", ".git"])));
+        test!(mixed:    PrunePatterns <- ["--prune-dirs=target|.git", "--prune-dirs=node_*"];     Both => Ok(pats(&["target", ".git", "node_*"])));
+    }
+
+    mod min_links {
+        use super::*;
+        use crate::options::parser::Arg;
+        use crate::options::test::parse_for_test;
+        use crate::options::test::Strictnesses::*;
+
+        static TEST_ARGS: &[&Arg] = &[&flags::MIN_LINKS];
+
+        fn deduce(inputs: &[&str]) -> Vec<Result<Option<nlink_t>, OptionsError>> {
+            parse_for_test(inputs, TEST_ARGS, Both, FileFilter::deduce_min_links)
+        }
+
+        #[test]
+        fn none() {
+            assert!(deduce(&[]).into_iter().all(|r| r == Ok(None)));
+        }
+
+        #[test]
+        fn two() {
+            assert!(deduce(&["--min-links=2"])
+                .into_iter()
+                .all(|r| r == Ok(Some(2))));
+        }
+
+        #[test]
+        fn zero() {
+            for result in deduce(&["--min-links", "0"]) {
+                let error = OptionsError::BadArgument(&flags::MIN_LINKS, OsString::from("0"));
+                assert_eq!(result, Err(error));
+            }
+        }
+
+        #[test]
+        fn not_a_number() {
+            for result in deduce(&["--min-links=lots"]) {
+                assert!(
+                    matches!(result, Err(OptionsError::FailedParse(..))),
+                    "{result:?}"
+                );
+            }
+        }
+    }
+
+    mod group_dirs {
+        use super::*;
+
+        test!(none:     GroupDirs <- [];                                                      Both => Ok(GroupDirs::None));
+        test!(first:    GroupDirs <- ["--group-directories-first"];                           Both => Ok(GroupDirs::First { with_links: false }));
+        test!(last:     GroupDirs <- ["--group-directories-last"];                            Both => Ok(GroupDirs::Last { with_links: false }));
+        test!(gdf:      GroupDirs <- ["--gdf"];                                               Both => Ok(GroupDirs::First { with_links: false }));
+
+        // Symlinks to directories
+        test!(links:    GroupDirs <- ["--group-directories-first", "--group-directories=with-links"];  Both => Ok(GroupDirs::First { with_links: true }));
+        test!(links_2:  GroupDirs <- ["--group-directories=with-links", "--gdf"];             Both => Ok(GroupDirs::First { with_links: true }));
+        test!(links_3:  GroupDirs <- ["--gdf", "--group-directories", "with-links"];          Both => Ok(GroupDirs::First { with_links: true }));
+        test!(links_4:  GroupDirs <- ["--group-directories-last", "--group-directories=with-links"];   Both => Ok(GroupDirs::Last { with_links: true }));
+        test!(dirs:     GroupDirs <- ["--gdf", "--group-directories=dirs"];                   Both => Ok(GroupDirs::First { with_links: false }));
+        test!(bad:      GroupDirs <- ["--gdf", "--group-directories=symlinks"];               Both => Err(OptionsError::BadArgument(&flags::GROUP_DIRS_WITH, OsString::from("symlinks"))));
+        test!(alone:    GroupDirs <- ["--group-directories=with-links"];                      Last => Ok(GroupDirs::None));
+        test!(alone_2:  GroupDirs <- ["--group-directories=with-links"];                      Complain => Err(OptionsError::Useless(&flags::GROUP_DIRS_WITH, false, &flags::DIRS_FIRST)));
+
+        // The grouping flags don’t take a value, so the next argument is a path
+        test!(path:     GroupDirs <- ["--gdf", "dirs"];                                       Both => Ok(GroupDirs::First { with_links: false }));
+
+        // Overriding
+        test!(both:     GroupDirs <- ["--group-directories-first", "--group-directories-last"];  Last => Ok(GroupDirs::Last { with_links: false }));
+        test!(both_2:   GroupDirs <- ["--group-directories-last", "--group-directories-first"];  Last => Ok(GroupDirs::First { with_links: false }));
+        test!(both_3:   GroupDirs <- ["--group-directories-first", "--group-directories-last"];  Complain => Err(OptionsError::Conflict(&flags::DIRS_FIRST, &flags::DIRS_LAST)));
+        test!(both_4:   GroupDirs <- ["--gdf", "--group-directories-last"];                   Complain => Err(OptionsError::Conflict(&flags::DIRS_FIRST, &flags::DIRS_LAST)));
+        test!(both_5:   GroupDirs <- ["--group-directories=with-links", "--group-directories=dirs", "--gdf"];  Last => Ok(GroupDirs::First { with_links: false }));
+        test!(both_6:   GroupDirs <- ["--gdf", "--group-directories-first"];                  Complain => Err(OptionsError::Duplicate(Flag::Long("gdf"), Flag::Long("group-directories-first"))));
+    }
+
     mod git_ignores {
         use super::*;
 
         test!(off:  GitIgnore <- [];                Both => Ok(GitIgnore::Off));
         test!(on:   GitIgnore <- ["--git-ignore"];  Both => Ok(GitIgnore::CheckAndIgnore));
+
+        // Giving --all three times shows ignored files anyway
+        test!(all:       GitIgnore <- ["--git-ignore", "-a"];    Both => Ok(GitIgnore::CheckAndIgnore));
+        test!(all_all:   GitIgnore <- ["--git-ignore", "-aa"];   Both => Ok(GitIgnore::CheckAndIgnore));
+        test!(all_three: GitIgnore <- ["--git-ignore", "-aaa"];  Both => Ok(GitIgnore::Off));
+        test!(separate:  GitIgnore <- ["-a", "--git-ignore", "-a", "-a"]; Both => Ok(GitIgnore::Off));
+    }
+
+    #[cfg(feature = "git")]
+    mod all_levels {
+        use crate::fs::feature::git::GitCache;
+        use crate::fs::filter::GitIgnore;
+        use crate::fs::test_dir::TestDir;
+        use crate::fs::Dir;
+        use crate::options::{Options, OptionsResult};
+        use std::ffi::OsStr;
+        use std::path::Path;
+
+        /// Makes a Git repository holding a dotfile, a normal file, and a
+        /// file that its `.gitignore` ignores.
+        fn repo(name: &str) -> TestDir {
+            let dir = TestDir::new(name);
+            git2::Repository::init(&*dir).unwrap();
+            std::fs::write(dir.join(".gitignore"), "ignored\n").unwrap();
+            for file in [".hidden", "visible", "ignored"] {
+                std::fs::write(dir.join(file), "").unwrap();
+            }
+            dir
+        }
+
+        /// Lists the directory the way eza would with `--git-ignore` and the
+        /// given `--all` arguments.
+        fn listed(dir: &Path, all: &[&str]) -> Vec<String> {
+            let args = [&["--git-ignore"][..], all].concat();
+            let OptionsResult::Ok(options, _) = Options::parse(args.iter().map(OsStr::new), &None)
+            else {
+                panic!("Failed to parse {args:?}");
+            };
+
+            let git: GitCache = [dir.to_path_buf()].into_iter().collect();
+            let git_ignoring = options.filter.git_ignore == GitIgnore::CheckAndIgnore;
+            let contents = Dir::read_dir(dir.to_path_buf()).unwrap();
+            let mut names = contents
+                .files(
+                    options.filter.dot_filter,
+                    Some(&git),
+                    git_ignoring,
+                    false,
+                    false,
+                )
+                .map(|file| file.unwrap().name)
+                .collect::<Vec<_>>();
+            names.sort();
+            names
+        }
+
+        #[test]
+        fn each_level() {
+            let dir = repo("all-levels");
+            let none = listed(&dir, &[]);
+            let one = listed(&dir, &["-a"]);
+            let two = listed(&dir, &["-aa"]);
+            let three = listed(&dir, &["-aaa"]);
+
+            assert_eq!(none, ["visible"]);
+            assert_eq!(one, [".gitignore", ".hidden", "visible"]);
+            assert_eq!(two, [".", "..", ".gitignore", ".hidden", "visible"]);
+            assert_eq!(
+                three,
+                [
+                    ".",
+                    "..",
+                    ".git",
+                    ".gitignore",
+                    ".hidden",
+                    "ignored",
+                    "visible"
+                ]
+            );
+        }
     }
 }
@@ -3,6 +3,7 @@ use crate::options::parser::{Arg, Args, TakesValue, Values};
 // exa options
 pub static VERSION: Arg = Arg { short: Some(b'v'), long: "version",  takes_value: TakesValue::Forbidden };
 pub static HELP:    Arg = Arg { short: Some(b'?'), long: "help",     takes_value: TakesValue::Forbidden };
+pub static EXPLAIN: Arg = Arg { short: None,       long: "explain",  takes_value: TakesValue::Forbidden };
 
 // display options
 pub static ONE_LINE:    Arg = Arg { short: Some(b'1'), long: "oneline",     takes_value: TakesValue::Forbidden };
@@ -11,94 +12,162 @@ pub static GRID:        Arg = Arg { short: Some(b'G'), long: "grid",        take
 pub static ACROSS:      Arg = Arg { short: Some(b'x'), long: "across",      takes_value: TakesValue::Forbidden };
 pub static RECURSE:     Arg = Arg { short: Some(b'R'), long: "recurse",     takes_value: TakesValue::Forbidden };
 pub static TREE:        Arg = Arg { short: Some(b'T'), long: "tree",        takes_value: TakesValue::Forbidden };
+pub static TREE_FORMAT: Arg = Arg { short: None,       long: "tree-format", takes_value: TakesValue::Necessary(Some(TREE_FORMATS)) };
+pub static FORMAT_TEMPLATE: Arg = Arg { short: None,   long: "format-template", takes_value: TakesValue::Necessary(None) };
+pub static CSV:         Arg = Arg { short: None,       long: "csv",         takes_value: TakesValue::Forbidden };
+pub static CSV_DELIMITER: Arg = Arg { short: None,     long: "csv-delimiter", takes_value: TakesValue::Necessary(None) };
 pub static CLASSIFY:    Arg = Arg { short: Some(b'F'), long: "classify",    takes_value: TakesValue::Optional(Some(WHEN), "auto") };
+pub static CLASSIFY_LINK_TARGETS: Arg = Arg { short: None, long: "classify-link-targets", takes_value: TakesValue::Forbidden };
+pub static CLASSIFY_XATTRS: Arg = Arg { short: None, long: "classify-xattrs", takes_value: TakesValue::Forbidden };
+pub static INDICATOR_STYLE: Arg = Arg { short: Some(b'p'), long: "indicator-style", takes_value: TakesValue::Optional(Some(INDICATOR_STYLES), "slash") };
+pub static RESOLVE_LINK_TARGETS: Arg = Arg { short: None, long: "resolve-link-targets", takes_value: TakesValue::Forbidden };
 pub static DEREF_LINKS: Arg = Arg { short: Some(b'X'), long: "dereference", takes_value: TakesValue::Forbidden };
 pub static WIDTH:       Arg = Arg { short: Some(b'w'), long: "width",       takes_value: TakesValue::Necessary(None) };
+pub static MIN_WIDTH:   Arg = Arg { short: None,       long: "min-width",   takes_value: TakesValue::Necessary(None) };
+pub static MAX_WIDTH:   Arg = Arg { short: None,       long: "max-width",   takes_value: TakesValue::Necessary(None) };
 pub static NO_QUOTES:   Arg = Arg { short: None,       long: "no-quotes",   takes_value: TakesValue::Forbidden };
+pub static FULL_PATHS:  Arg = Arg { short: None,       long: "full-paths",  takes_value: TakesValue::Forbidden };
 pub static ABSOLUTE:    Arg = Arg { short: None,       long: "absolute",    takes_value: TakesValue::Optional(Some(ABSOLUTE_MODES), "on") };
 const ABSOLUTE_MODES: &[&str] = &["on", "follow", "off"];
+const TREE_FORMATS: &[&str] = &["tree", "indent"];
+const INDICATOR_STYLES: &[&str] = &["none", "slash", "classify"];
 
-pub static COLOR:  Arg = Arg { short: None, long: "color",  takes_value: TakesValue::Optional(Some(WHEN), "auto") };
-pub static COLOUR: Arg = Arg { short: None, long: "colour", takes_value: TakesValue::Optional(Some(WHEN), "auto") };
+pub static COLOR:  Arg = Arg { short: None, long: "color",  takes_value: TakesValue::Optional(Some(COLOURS), "auto") };
+pub static COLOUR: Arg = Arg { short: None, long: "colour", takes_value: TakesValue::Optional(Some(COLOURS), "auto") };
 const WHEN: &[&str] = &["always", "auto", "never"];
+const COLOURS: &[&str] = &["always", "auto", "never", "html", "pango"];
 
 pub static COLOR_SCALE:  Arg = Arg { short: None, long: "color-scale",  takes_value: TakesValue::Optional(Some(SCALES), "all") };
 pub static COLOUR_SCALE: Arg = Arg { short: None, long: "colour-scale", takes_value: TakesValue::Optional(Some(SCALES), "all") };
 pub static COLOR_SCALE_MODE:  Arg = Arg { short: None, long: "color-scale-mode",  takes_value: TakesValue::Necessary(Some(COLOR_SCALE_MODES))};
 pub static COLOUR_SCALE_MODE: Arg = Arg { short: None, long: "colour-scale-mode", takes_value: TakesValue::Necessary(Some(COLOR_SCALE_MODES))};
-const SCALES: Values = &["all", "size", "age"];
-const COLOR_SCALE_MODES: Values = &["fixed", "gradient"];
+pub static HIGHLIGHT_RISKY: Arg = Arg { short: None, long: "highlight-risky", takes_value: TakesValue::Forbidden };
+const SCALES: Values = &["all", "size", "age", "age:mtime", "age:atime", "age:ctime", "inode", "links", "none"];
+const COLOR_SCALE_MODES: Values = &["fixed", "gradient", "logarithmic"];
 
 // filtering and sorting options
 pub static ALL:         Arg = Arg { short: Some(b'a'), long: "all",         takes_value: TakesValue::Forbidden };
 pub static ALMOST_ALL:  Arg = Arg { short: Some(b'A'), long: "almost-all",  takes_value: TakesValue::Forbidden };
 pub static LIST_DIRS:   Arg = Arg { short: Some(b'd'), long: "list-dirs",   takes_value: TakesValue::Forbidden };
 pub static LEVEL:       Arg = Arg { short: Some(b'L'), long: "level",       takes_value: TakesValue::Necessary(None) };
+pub static SUMMARIZE_DIRS: Arg = Arg { short: None,    long: "summarize-dirs", takes_value: TakesValue::Forbidden };
+pub static SKIP_UNREADABLE: Arg = Arg { short: None,   long: "skip-unreadable", takes_value: TakesValue::Forbidden };
+pub static COLLAPSE:    Arg = Arg { short: None,       long: "collapse",    takes_value: TakesValue::Forbidden };
+pub static PER_DIR_LIMIT: Arg = Arg { short: None,     long: "per-dir-limit", takes_value: TakesValue::Necessary(None) };
+pub static TREE_SUMMARY: Arg = Arg { short: None,      long: "tree-summary", takes_value: TakesValue::Forbidden };
+pub static PROGRESS:     Arg = Arg { short: None,      long: "progress",     takes_value: TakesValue::Forbidden };
+pub static GLOBAL_SORT:  Arg = Arg { short: None,      long: "global-sort",  takes_value: TakesValue::Forbidden };
 pub static REVERSE:     Arg = Arg { short: Some(b'r'), long: "reverse",     takes_value: TakesValue::Forbidden };
 pub static SORT:        Arg = Arg { short: Some(b's'), long: "sort",        takes_value: TakesValue::Necessary(Some(SORTS)) };
 pub static IGNORE_GLOB: Arg = Arg { short: Some(b'I'), long: "ignore-glob", takes_value: TakesValue::Necessary(None) };
+pub static PRUNE_DIRS:  Arg = Arg { short: None,       long: "prune-dirs",  takes_value: TakesValue::Necessary(None) };
 pub static GIT_IGNORE:  Arg = Arg { short: None, long: "git-ignore",           takes_value: TakesValue::Forbidden };
+pub static MARK_IGNORED: Arg = Arg { short: None, long: "mark-ignored",        takes_value: TakesValue::Forbidden };
 pub static DIRS_FIRST:  Arg = Arg { short: None, long: "group-directories-first",  takes_value: TakesValue::Forbidden };
+pub static GDF:         Arg = Arg { short: None, long: "gdf",                      takes_value: TakesValue::Forbidden };
+pub static DIRS_LAST:   Arg = Arg { short: None, long: "group-directories-last",   takes_value: TakesValue::Forbidden };
+pub static GROUP_DIRS_WITH: Arg = Arg { short: None, long: "group-directories",    takes_value: TakesValue::Necessary(Some(GROUP_DIRS)) };
 pub static ONLY_DIRS:   Arg = Arg { short: Some(b'D'), long: "only-dirs", takes_value: TakesValue::Forbidden };
 pub static ONLY_FILES:  Arg = Arg { short: Some(b'f'), long: "only-files", takes_value: TakesValue::Forbidden };
+pub static MIN_LINKS:   Arg = Arg { short: None,       long: "min-links", takes_value: TakesValue::Necessary(None) };
+pub static SEED:        Arg = Arg { short: None,       long: "seed",      takes_value: TakesValue::Necessary(None) };
 const SORTS: Values = &[ "name", "Name", "size", "extension",
                          "Extension", "modified", "changed", "accessed",
-                         "created", "inode", "type", "none" ];
+                         "created", "inode", "type", "git", "owner",
+                         "group", "children", "random", "none" ];
+const GROUP_DIRS: Values = &["dirs", "with-links"];
 
 // display options
 pub static BINARY:      Arg = Arg { short: Some(b'b'), long: "binary",      takes_value: TakesValue::Forbidden };
 pub static BYTES:       Arg = Arg { short: Some(b'B'), long: "bytes",       takes_value: TakesValue::Forbidden };
+pub static SIZE_UNIT:   Arg = Arg { short: None,       long: "size-unit",   takes_value: TakesValue::Necessary(Some(SIZE_UNITS)) };
 pub static GROUP:       Arg = Arg { short: Some(b'g'), long: "group",       takes_value: TakesValue::Forbidden };
 pub static NUMERIC:     Arg = Arg { short: Some(b'n'), long: "numeric",     takes_value: TakesValue::Forbidden };
 pub static HEADER:      Arg = Arg { short: Some(b'h'), long: "header",      takes_value: TakesValue::Forbidden };
 pub static ICONS:       Arg = Arg { short: None,       long: "icons",       takes_value: TakesValue::Optional(Some(WHEN), "auto")};
+pub static ICON_SPACING: Arg = Arg { short: None,      long: "icon-spacing", takes_value: TakesValue::Necessary(None) };
 pub static INODE:       Arg = Arg { short: Some(b'i'), long: "inode",       takes_value: TakesValue::Forbidden };
+pub static INODE_FORMAT: Arg = Arg { short: None,      long: "inode-format", takes_value: TakesValue::Necessary(Some(INODE_FORMATS)) };
 pub static LINKS:       Arg = Arg { short: Some(b'H'), long: "links",       takes_value: TakesValue::Forbidden };
 pub static MODIFIED:    Arg = Arg { short: Some(b'm'), long: "modified",    takes_value: TakesValue::Forbidden };
 pub static CHANGED:     Arg = Arg { short: None,       long: "changed",     takes_value: TakesValue::Forbidden };
+pub static ALL_TIMES:   Arg = Arg { short: None,       long: "all-times",   takes_value: TakesValue::Forbidden };
 pub static BLOCKSIZE:   Arg = Arg { short: Some(b'S'), long: "blocksize",   takes_value: TakesValue::Forbidden };
+pub static IO_BLOCKSIZE: Arg = Arg { short: None,      long: "io-blocksize", takes_value: TakesValue::Forbidden };
 pub static TOTAL_SIZE:  Arg = Arg { short: None,       long: "total-size",  takes_value: TakesValue::Forbidden };
+pub static DIR_SIZE:    Arg = Arg { short: None,       long: "dir-size",    takes_value: TakesValue::Necessary(Some(DIR_SIZES)) };
 pub static TIME:        Arg = Arg { short: Some(b't'), long: "time",        takes_value: TakesValue::Necessary(Some(TIMES)) };
 pub static ACCESSED:    Arg = Arg { short: Some(b'u'), long: "accessed",    takes_value: TakesValue::Forbidden };
 pub static CREATED:     Arg = Arg { short: Some(b'U'), long: "created",     takes_value: TakesValue::Forbidden };
 pub static TIME_STYLE:  Arg = Arg { short: None,       long: "time-style",  takes_value: TakesValue::Necessary(Some(TIME_STYLES)) };
-pub static HYPERLINK:   Arg = Arg { short: None,       long: "hyperlink",   takes_value: TakesValue::Forbidden };
+pub static NO_RECENT_STYLE: Arg = Arg { short: None,   long: "no-recent-style", takes_value: TakesValue::Forbidden };
+pub static HYPERLINK:   Arg = Arg { short: None,       long: "hyperlink",   takes_value: TakesValue::Optional(Some(WHEN), "auto") };
 pub static MOUNTS:      Arg = Arg { short: Some(b'M'), long: "mounts",      takes_value: TakesValue::Forbidden };
 pub static SMART_GROUP: Arg = Arg { short: None,       long: "smart-group", takes_value: TakesValue::Forbidden };
-const TIMES: Values = &["modified", "changed", "accessed", "created"];
-const TIME_STYLES: Values = &["default", "long-iso", "full-iso", "iso", "relative"];
+pub static OWNER_FORMAT: Arg = Arg { short: None,      long: "owner-format", takes_value: TakesValue::Necessary(Some(OWNER_FORMATS)) };
+pub static GRID_ROWS:   Arg = Arg { short: None,       long: "grid-rows-threshold", takes_value: TakesValue::Necessary(None) };
+pub static ALIGN:       Arg = Arg { short: None,       long: "align",       takes_value: TakesValue::Necessary(None) };
+pub static COLUMNS:     Arg = Arg { short: None,       long: "columns",     takes_value: TakesValue::Necessary(None) };
+pub static PEEK_ARCHIVES: Arg = Arg { short: None,     long: "peek-archives", takes_value: TakesValue::Forbidden };
+pub static DEPTH:       Arg = Arg { short: None,       long: "depth",       takes_value: TakesValue::Forbidden };
+pub static MIME:        Arg = Arg { short: None,       long: "mime",        takes_value: TakesValue::Forbidden };
+pub static CHECKSUM:    Arg = Arg { short: None,       long: "checksum",    takes_value: TakesValue::Necessary(Some(CHECKSUMS)) };
+pub static NAME_WIDTH:  Arg = Arg { short: None,       long: "name-width",  takes_value: TakesValue::Necessary(None) };
+pub static ELLIPSIS:    Arg = Arg { short: None,       long: "ellipsis",    takes_value: TakesValue::Necessary(None) };
+pub static MAX_OUTPUT:  Arg = Arg { short: None,       long: "max-output",  takes_value: TakesValue::Necessary(None) };
+const TIMES: Values = &["modified", "changed", "accessed", "created", "all"];
+const TIME_STYLES: Values = &["default", "long-iso", "full-iso", "iso", "relative", "relative-aligned", "combined", "utc"];
+const INODE_FORMATS: Values = &["dec", "hex"];
+const DIR_SIZES: Values = &["none", "inode", "total"];
+const OWNER_FORMATS: Values = &["separate", "combined"];
+const SIZE_UNITS: Values = &["k", "m", "g", "t", "ki", "mi", "gi", "ti"];
+const CHECKSUMS: Values = &["crc32", "md5", "sha256"];
+const GIT_COLS: Values = &["default", "left"];
+const FLAGS_FORMATS: Values = &["long", "short"];
 
 // suppressing columns
 pub static NO_PERMISSIONS: Arg = Arg { short: None, long: "no-permissions", takes_value: TakesValue::Forbidden };
 pub static NO_FILESIZE: Arg = Arg { short: None, long: "no-filesize", takes_value: TakesValue::Forbidden };
 pub static NO_USER: Arg = Arg { short: None, long: "no-user", takes_value: TakesValue::Forbidden };
 pub static NO_TIME: Arg = Arg { short: None, long: "no-time", takes_value: TakesValue::Forbidden };
+pub static NO_GROUP: Arg = Arg { short: None, long: "no-group", takes_value: TakesValue::Forbidden };
+pub static NO_INODE: Arg = Arg { short: None, long: "no-inode", takes_value: TakesValue::Forbidden };
+pub static NO_LINKS: Arg = Arg { short: None, long: "no-links", takes_value: TakesValue::Forbidden };
+pub static NO_FLAGS: Arg = Arg { short: None, long: "no-flags", takes_value: TakesValue::Forbidden };
 
 // optional feature options
 pub static GIT:               Arg = Arg { short: None,       long: "git",                  takes_value: TakesValue::Forbidden };
 pub static NO_GIT:            Arg = Arg { short: None,       long: "no-git",               takes_value: TakesValue::Forbidden };
 pub static GIT_REPOS:         Arg = Arg { short: None,       long: "git-repos",            takes_value: TakesValue::Forbidden };
 pub static GIT_REPOS_NO_STAT: Arg = Arg { short: None,       long: "git-repos-no-status",  takes_value: TakesValue::Forbidden };
+pub static GIT_COL:           Arg = Arg { short: None,       long: "git-col",              takes_value: TakesValue::Necessary(Some(GIT_COLS)) };
+pub static GIT_SUMMARY:       Arg = Arg { short: None,       long: "git-summary",          takes_value: TakesValue::Optional(Some(WHEN), "auto") };
 pub static EXTENDED:          Arg = Arg { short: Some(b'@'), long: "extended",             takes_value: TakesValue::Forbidden };
 pub static OCTAL:             Arg = Arg { short: Some(b'o'), long: "octal-permissions",    takes_value: TakesValue::Forbidden };
 pub static SECURITY_CONTEXT:  Arg = Arg { short: Some(b'Z'), long: "context",              takes_value: TakesValue::Forbidden };
 pub static STDIN:             Arg = Arg { short: None,       long: "stdin",                takes_value: TakesValue::Forbidden };
+pub static FROM_TREE:         Arg = Arg { short: None,       long: "from-tree",            takes_value: TakesValue::Necessary(None) };
 pub static FILE_FLAGS:        Arg = Arg { short: Some(b'O'), long: "flags",                takes_value: TakesValue::Forbidden };
+pub static FLAGS_FORMAT:      Arg = Arg { short: None,       long: "flags-format",         takes_value: TakesValue::Necessary(Some(FLAGS_FORMATS)) };
+pub static FINDER_TAGS:       Arg = Arg { short: None,       long: "finder-tags",          takes_value: TakesValue::Forbidden };
 
 pub static ALL_ARGS: Args = Args(&[
-    &VERSION, &HELP,
+    &VERSION, &HELP, &EXPLAIN,
 
-    &ONE_LINE, &LONG, &GRID, &ACROSS, &RECURSE, &TREE, &CLASSIFY, &DEREF_LINKS,
-    &COLOR, &COLOUR, &COLOR_SCALE, &COLOUR_SCALE, &COLOR_SCALE_MODE, &COLOUR_SCALE_MODE,
-    &WIDTH, &NO_QUOTES, &ABSOLUTE,
+    &ONE_LINE, &LONG, &GRID, &ACROSS, &RECURSE, &TREE, &TREE_FORMAT, &FORMAT_TEMPLATE, &CSV, &CSV_DELIMITER, &CLASSIFY, &CLASSIFY_LINK_TARGETS, &CLASSIFY_XATTRS, &INDICATOR_STYLE, &RESOLVE_LINK_TARGETS, &DEREF_LINKS,
+    &COLOR, &COLOUR, &COLOR_SCALE, &COLOUR_SCALE, &COLOR_SCALE_MODE, &COLOUR_SCALE_MODE, &HIGHLIGHT_RISKY,
+    &WIDTH, &MIN_WIDTH, &MAX_WIDTH, &NO_QUOTES, &ABSOLUTE, &FULL_PATHS,
 
-    &ALL, &ALMOST_ALL, &LIST_DIRS, &LEVEL, &REVERSE, &SORT, &DIRS_FIRST,
-    &IGNORE_GLOB, &GIT_IGNORE, &ONLY_DIRS, &ONLY_FILES,
+    &ALL, &ALMOST_ALL, &LIST_DIRS, &LEVEL, &SUMMARIZE_DIRS, &SKIP_UNREADABLE, &COLLAPSE, &PER_DIR_LIMIT, &TREE_SUMMARY, &PROGRESS, &GLOBAL_SORT, &REVERSE, &SORT, &SEED, &DIRS_FIRST, &GDF, &DIRS_LAST, &GROUP_DIRS_WITH,
+    &IGNORE_GLOB, &PRUNE_DIRS, &GIT_IGNORE, &MARK_IGNORED, &ONLY_DIRS, &ONLY_FILES, &MIN_LINKS,
 
-    &BINARY, &BYTES, &GROUP, &NUMERIC, &HEADER, &ICONS, &INODE, &LINKS, &MODIFIED, &CHANGED,
-    &BLOCKSIZE, &TOTAL_SIZE, &TIME, &ACCESSED, &CREATED, &TIME_STYLE, &HYPERLINK, &MOUNTS,
-    &NO_PERMISSIONS, &NO_FILESIZE, &NO_USER, &NO_TIME, &SMART_GROUP,
+    &BINARY, &BYTES, &SIZE_UNIT, &GROUP, &NUMERIC, &HEADER, &ICONS, &ICON_SPACING, &INODE, &INODE_FORMAT, &LINKS, &MODIFIED, &CHANGED, &ALL_TIMES,
+    &BLOCKSIZE, &IO_BLOCKSIZE, &TOTAL_SIZE, &DIR_SIZE, &TIME, &ACCESSED, &CREATED, &TIME_STYLE, &NO_RECENT_STYLE, &HYPERLINK, &MOUNTS,
+    &NO_PERMISSIONS, &NO_FILESIZE, &NO_USER, &NO_TIME,
+    &NO_GROUP, &NO_INODE, &NO_LINKS, &NO_FLAGS, &SMART_GROUP, &OWNER_FORMAT, &GRID_ROWS,
+    &ALIGN, &COLUMNS, &PEEK_ARCHIVES, &DEPTH, &MIME, &CHECKSUM, &NAME_WIDTH, &ELLIPSIS, &MAX_OUTPUT,
 
-    &GIT, &NO_GIT, &GIT_REPOS, &GIT_REPOS_NO_STAT,
-    &EXTENDED, &OCTAL, &SECURITY_CONTEXT, &STDIN, &FILE_FLAGS
+    &GIT, &NO_GIT, &GIT_REPOS, &GIT_REPOS_NO_STAT, &GIT_COL, &GIT_SUMMARY,
+    &EXTENDED, &OCTAL, &SECURITY_CONTEXT, &STDIN, &FROM_TREE, &FILE_FLAGS, &FLAGS_FORMAT,
+    &FINDER_TAGS
 ]);
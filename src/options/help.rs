@@ -10,6 +10,8 @@ static USAGE_PART1: &str = "Usage:
 META OPTIONS
   --help                     show list of command-line options
   -v, --version              show version of eza
+  --explain                  show the options eza would use, and where they
+                             came from, instead of listing files
 
 DISPLAY OPTIONS
   -1, --oneline              display one entry per line
@@ -18,78 +20,174 @@ DISPLAY OPTIONS
   -x, --across               sort the grid across, rather than downwards
   -R, --recurse              recurse into directories
   -T, --tree                 recurse into directories as a tree
+  --collapse                 with --tree, show directories holding only one
+                             directory on the same line, as a/b/c
+  --tree-format=FORMAT       write a tree drawn (tree) or indented to be read
+                             back in with --from-tree (indent)
+  --format-template TEMPLATE write one line per file, filling in placeholders
+                             such as '{name} {size} {mtime}'
+  --csv                      write the long view's columns as CSV
+  --csv-delimiter CHAR       separate CSV fields with CHAR, or '\t' for tabs
   -X, --dereference          dereference symbolic links when displaying information
   -F, --classify=WHEN        display type indicator by file names (always, auto, never)
-  --colo[u]r=WHEN            when to use terminal colours (always, auto, never)
-  --colo[u]r-scale           highlight levels of 'field' distinctly(all, age, size)
-  --colo[u]r-scale-mode      use gradient or fixed colors in --color-scale (fixed, gradient)
-  --icons=WHEN               when to display icons (always, auto, never)
+  --classify-link-targets    with --classify, follow a symlink's indicator with
+                             that of the file it points to
+  --classify-xattrs          with --classify, add '@' to files with extended
+                             attributes
+  --resolve-link-targets     show relative symlink targets as paths from the
+                             current directory
+  -p, --indicator-style=WHAT display a slash after directory names only
+                             (slash, classify, none)
+  --colo[u]r=WHEN            when to use terminal colours (always, auto, never),
+                             or 'html' or 'pango' to write markup instead
+  --colo[u]r-scale           highlight levels of 'field' distinctly(all, age, size,
+                             inode, links, none), with age:atime, age:ctime, or
+                             age:mtime to shade ages by that time
+  --colo[u]r-scale-mode      use gradient or fixed colors in --color-scale
+                             (fixed, gradient, logarithmic)
+  --highlight-risky          highlight world-writable, setuid, and setgid files
+  --icons=WHEN               when to display icons (always, auto, never), or
+                             'ascii' to use basic symbols instead
+  --icon-spacing N           put N spaces between icons and file names
   --no-quotes                don't quote file names with spaces
-  --hyperlink                display entries as hyperlinks
+  --hyperlink=WHEN           display entries as hyperlinks (always, auto, never)
   --absolute                 display entries with their absolute path (on, follow, off)
-  -w, --width COLS           set screen width in columns
+  --full-paths               with --recurse, show the path of each entry
+                             rather than just its name
+  -w, --width COLS           set screen width in columns (or max to use the
+                             full terminal width, ignoring $COLUMNS, or a
+                             negative number to leave that many columns free)
+  --min-width COLS           never use fewer than this many columns, however
+                             narrow the terminal or $COLUMNS is
+  --max-width COLS           never use more than this many columns, however
+                             wide the terminal or --width is
+  --max-output BYTES         stop printing after about this many bytes
 
 
 FILTERING AND SORTING OPTIONS
   -a, --all                  show hidden and 'dot' files. Use this twice to also
-                             show the '.' and '..' directories
+                             show the '.' and '..' directories, and three times
+                             to also show files ignored by --git-ignore
   -A, --almost-all           equivalent to --all; included for compatibility with `ls -A`
   -d, --list-dirs            list directories as files; don't list their contents
   -L, --level DEPTH          limit the depth of recursion
+  --summarize-dirs           with --recurse, follow each directory's contents
+                             with its number of entries and their total size
+  --skip-unreadable          when recursing, skip directories that can't be read
+                             instead of reporting errors for them
+  --per-dir-limit N          when recursing, show at most N entries for each
+                             directory, with a note of how many more there are
+  --tree-summary             with --recurse, end with a tree of the directories
+  --progress                 when recursing, count the directories scanned so
+                             far on stderr, if it's a terminal
+  --global-sort              with --recurse, sort every entry together in one
+                             list instead of directory by directory
   -r, --reverse              reverse the sort order
-  -s, --sort SORT_FIELD      which field to sort by
-  --group-directories-first  list directories before other files
+  -s, --sort SORT_FIELD      which field to sort by, or a comma-separated list
+                             of fields, each optionally followed by :asc or
+                             :desc (such as size:desc,name)
+  --seed N                   shuffle the same way every time with --sort=random
+  --group-directories-first  list directories before other files (or --gdf)
+  --group-directories-last   list directories after other files
+  --group-directories WHICH  what to group as directories (dirs, with-links)
   -D, --only-dirs            list only directories
   -f, --only-files           list only files
-  -I, --ignore-glob GLOBS    glob patterns (pipe-separated) of files to ignore";
+  --min-links N              list only files with at least N hard links,
+                             highlighting their link counts
+  -I, --ignore-glob GLOBS    glob patterns (pipe-separated) of files to ignore
+  --prune-dirs GLOBS         glob patterns (pipe-separated) of directories
+                             to show but not recurse into";
 
 static GIT_FILTER_HELP: &str = "  \
-  --git-ignore               ignore files mentioned in '.gitignore'";
+  --git-ignore               ignore files mentioned in '.gitignore'
+  --mark-ignored             dim files mentioned in '.gitignore' rather than
+                             hiding them";
 
 static USAGE_PART2: &str = "  \
   Valid sort fields:         name, Name, extension, Extension, size, type,
-                             modified, accessed, created, inode, and none.
+                             modified, accessed, created, inode, git, owner,
+                             group, children, random, and none.
                              date, time, old, and new all refer to modified.
 
 LONG VIEW OPTIONS
   -b, --binary               list file sizes with binary prefixes
   -B, --bytes                list file sizes in bytes, without any prefixes
+  --size-unit UNIT           list every file size in the same unit
+                             (k, m, g, t, ki, mi, gi, ti)
   -g, --group                list each file's group
   --smart-group              only show group if it has a different name from owner
+  --owner-format FORMAT      show user and group separate or combined as user:group
   -h, --header               add a header row to each column
+  --depth                    list how many levels below the arguments each file is
+  --name-width N             pad or truncate file names to exactly N columns
+  --ellipsis STR             what to end names truncated by --name-width with
   -H, --links                list each file's number of hard links
   -i, --inode                list each file's inode number
+  --inode-format BASE        how to list inode numbers (dec, hex)
   -m, --modified             use the modified timestamp field
   -M, --mounts               show mount details (Linux and Mac only)
   -n, --numeric              list numeric user and group IDs
   -O, --flags                list file flags (Mac, BSD, and Windows only)
+  --flags-format FORMAT      how to list Windows attributes (long, short)
   -S, --blocksize            show size of allocated file system blocks
-  -t, --time FIELD           which timestamp field to list (modified, accessed, created)
+  --io-blocksize             show the preferred I/O block size of files
+  -t, --time FIELD           which timestamp field to list (modified, accessed, created),
+                             a comma-separated list of them, or all
   -u, --accessed             use the accessed timestamp field
   -U, --created              use the created timestamp field
   --changed                  use the changed timestamp field
+  --all-times                use every timestamp field
   --time-style               how to format timestamps (default, iso, long-iso,
-                             full-iso, relative, or a custom style '+<FORMAT>'
-                             like '+%Y-%m-%d %H:%M')
+                             full-iso, relative, relative-aligned, combined,
+                             utc, or a custom style '+<FORMAT>' like
+                             '+%Y-%m-%d %H:%M'); add ',utc' to show times
+                             in UTC
+  --no-recent-style          use the first line of a custom time style for
+                             recent files too
   --total-size               show the size of a directory as the size of all
                              files and directories inside (unix only)
+  --dir-size WHICH           what to show as the size of a directory
+                             (none, inode, total)
+  --grid-rows-threshold ROWS only use the grid-details view (--grid --long)
+                             if it would produce at least ROWS rows
+  --align COL:ALIGN,...      override the alignment (left, right) of the named
+                             columns, such as 'size:left'
+  --columns COL,...          show exactly the named columns, in that order,
+                             such as 'permissions,size,modified,name'
+  --peek-archives            list the top-level contents of .zip and .tar files
+  --mime                     list each regular file's MIME type, from its
+                             first few bytes
+  --checksum ALGO            list a digest of each regular file's contents
+                             (crc32, md5, sha256); this reads every file
   --no-permissions           suppress the permissions field
   -o, --octal-permissions    list each file's permission in octal format
   --no-filesize              suppress the filesize field
   --no-user                  suppress the user field
   --no-time                  suppress the time field
+  --no-group                 suppress the group field
+  --no-inode                 suppress the inode field
+  --no-links                 suppress the hard links field
+  --no-flags                 suppress the file flags field
   --stdin                    read file names from stdin, one per line or other separator 
-                             specified in environment";
+                             specified in environment
+  --from-tree FILE           draw a tree written with --tree-format=indent,
+                             instead of listing files";
 
 static GIT_VIEW_HELP: &str = "  \
   --git                      list each file's Git status, if tracked or ignored
   --no-git                   suppress Git status (always overrides --git,
                              --git-repos, --git-repos-no-status)
-  --git-repos                list root of git-tree status";
+  --git-repos                list root of git-tree status
+  --git-col=WHERE            where to put the --git column (default, or left
+                             to put it just before the name)
+  --git-summary=WHEN         follow --git with a count of files by status
+                             (always, auto, never)";
 static EXTENDED_HELP: &str = "  \
   -@, --extended             list each file's extended attributes and sizes";
 static SECATTR_HELP: &str = "  \
   -Z, --context              list each file's security context";
+static TAGS_HELP: &str = "  \
+  --finder-tags              list each file's Finder tags";
 
 /// All the information needed to display the help text, which depends
 /// on which features are enabled and whether the user only wants to
@@ -135,6 +233,10 @@ impl fmt::Display for HelpString {
             write!(f, "\n{SECATTR_HELP}")?;
         }
 
+        if xattr::ENABLED && cfg!(target_os = "macos") {
+            write!(f, "\n{TAGS_HELP}")?;
+        }
+
         writeln!(f)
     }
 }
@@ -68,13 +68,18 @@
 //! --grid --long` shouldn’t complain about `--long` being given twice when
 //! it’s clear what the user wants.
 
-use std::ffi::OsStr;
+use std::ffi::{OsStr, OsString};
+#[cfg(feature = "git")]
+use std::path::PathBuf;
 
 use crate::fs::dir_action::DirAction;
-use crate::fs::filter::{FileFilter, GitIgnore};
+use crate::fs::filter::{FileFilter, GitIgnore, SortField};
 use crate::options::stdin::FilesInput;
+use crate::options::view::DefaultView;
+use crate::output::template::Field;
 use crate::output::{details, grid_details, Mode, View};
 use crate::theme::Options as ThemeOptions;
+use log::*;
 
 mod dir_action;
 mod file_name;
@@ -90,12 +95,16 @@ pub use self::error::{NumberSource, OptionsError};
 mod help;
 use self::help::HelpString;
 
+mod explain;
+use self::explain::Explanation;
+
 mod parser;
 use self::parser::MatchedFlags;
 
 pub mod vars;
 pub use self::vars::Vars;
 
+pub mod default_args;
 pub mod stdin;
 mod version;
 
@@ -123,14 +132,33 @@ pub struct Options {
 
     /// Whether to read file names from stdin instead of the command-line
     pub stdin: FilesInput,
+
+    /// The number of tracked files above which a Git repository is too
+    /// large to query for statuses, if a limit has been set.
+    pub git_max_files: Option<usize>,
 }
 
 impl Options {
     /// Parse the given iterator of command-line strings into an Options
     /// struct and a list of free filenames, using the environment variables
     /// for extra options.
-    #[allow(unused_results)]
     pub fn parse<'args, I, V>(args: I, vars: &V) -> OptionsResult<'args>
+    where
+        I: IntoIterator<Item = &'args OsStr>,
+        V: Vars,
+    {
+        Self::parse_with_defaults(&[], args, vars)
+    }
+
+    /// Parse the command-line strings as above, with the default arguments
+    /// from `EZA_DEFAULT_ARGS` in front of them. They get parsed on their
+    /// own, so that the command-line can override them even in strict mode.
+    #[allow(unused_results)]
+    pub fn parse_with_defaults<'args, I, V>(
+        defaults: &'args [OsString],
+        args: I,
+        vars: &V,
+    ) -> OptionsResult<'args>
     where
         I: IntoIterator<Item = &'args OsStr>,
         V: Vars,
@@ -144,7 +172,12 @@ impl Options {
             Some(_)                      => Strictness::ComplainAboutRedundantArguments,
         };
 
-        let Matches { flags, frees } = match flags::ALL_ARGS.parse(args, strictness) {
+        let defaults = defaults.iter().map(OsString::as_os_str);
+        let matches = flags::ALL_ARGS.parse(defaults, strictness).and_then(|d| {
+            let matches = flags::ALL_ARGS.parse(args, strictness)?;
+            Ok(matches.after_defaults(d))
+        });
+        let Matches { flags, frees } = match matches {
             Ok(m) => m,
             Err(pe) => return OptionsResult::InvalidOptions(OptionsError::Parse(pe)),
         };
@@ -158,7 +191,10 @@ impl Options {
         }
 
         match Self::deduce(&flags, vars) {
-            Ok(options) => OptionsResult::Ok(options, frees),
+            Ok(options) => match Explanation::deduce(&flags, &options, &frees, vars) {
+                Some(explanation) => OptionsResult::Explain(explanation),
+                None => OptionsResult::Ok(options, frees),
+            },
             Err(oe) => OptionsResult::InvalidOptions(oe),
         }
     }
@@ -167,7 +203,10 @@ impl Options {
     /// status column. It’s only worth trying to discover a repository if the
     /// results will end up being displayed.
     pub fn should_scan_for_git(&self) -> bool {
-        if self.filter.git_ignore == GitIgnore::CheckAndIgnore {
+        if self.filter.git_ignore == GitIgnore::CheckAndIgnore
+            || self.view.file_style.mark_ignored
+            || self.filter.sort_keys.contains(SortField::GitStatus)
+        {
             return true;
         }
 
@@ -184,28 +223,79 @@ impl Options {
                     },
                 ..
             }) => table.columns.git,
+            Mode::Template(ref template) => template.template.uses(Field::Git),
+            Mode::Csv(ref csv) => csv.table.columns.git,
+            _ => false,
+        }
+    }
+
+    /// Whether any of the given paths, or the files directly inside them,
+    /// are Git repositories. This is only checked when the view has a
+    /// column for the status of each repository.
+    #[cfg(feature = "git")]
+    pub fn git_repos(&self, args: &[&OsStr]) -> bool {
+        let option_enabled = match self.view.mode {
+            Mode::Details(details::Options {
+                table: Some(ref table),
+                ..
+            })
+            | Mode::GridDetails(grid_details::Options {
+                details:
+                    details::Options {
+                        table: Some(ref table),
+                        ..
+                    },
+                ..
+            }) => table.columns.subdir_git_repos || table.columns.subdir_git_repos_no_stat,
             _ => false,
+        };
+        if option_enabled {
+            let paths: Vec<PathBuf> = args.iter().map(PathBuf::from).collect::<Vec<PathBuf>>();
+            let mut files: Vec<PathBuf> = Vec::new();
+            for path in paths {
+                get_files_in_dir(&mut files, path);
+            }
+            let repos: Vec<bool> = files
+                .iter()
+                .map(git2::Repository::open)
+                .map(|repo| repo.is_ok())
+                .collect();
+            repos.contains(&true)
+        } else {
+            false
         }
     }
 
+    #[cfg(not(feature = "git"))]
+    pub fn git_repos(&self, _args: &[&OsStr]) -> bool {
+        false
+    }
+
     /// Determines the complete set of options based on the given command-line
     /// arguments, after they’ve been parsed.
     fn deduce<V: Vars>(matches: &MatchedFlags<'_>, vars: &V) -> Result<Self, OptionsError> {
         if cfg!(not(feature = "git"))
             && matches
-                .has_where_any(|f| f.matches(&flags::GIT) || f.matches(&flags::GIT_IGNORE))
+                .has_where_any(|f| {
+                    f.matches(&flags::GIT)
+                        || f.matches(&flags::GIT_IGNORE)
+                        || f.matches(&flags::MARK_IGNORED)
+                })
                 .is_some()
         {
             return Err(OptionsError::Unsupported(String::from(
-                "Options --git and --git-ignore can't be used because `git` feature was disabled in this build of exa"
+                "Options --git, --git-ignore, and --mark-ignored can't be used because `git` feature was disabled in this build of exa"
             )));
         }
 
         let view = View::deduce(matches, vars)?;
-        let dir_action = DirAction::deduce(matches, matches!(view.mode, Mode::Details(_)))?;
+        let default_tree = DefaultView::deduce(matches, vars)? == Some(DefaultView::Tree);
+        let dir_action =
+            DirAction::deduce(matches, matches!(view.mode, Mode::Details(_)), default_tree)?;
         let filter = FileFilter::deduce(matches)?;
         let theme = ThemeOptions::deduce(matches, vars)?;
         let stdin = FilesInput::deduce(matches, vars)?;
+        let git_max_files = Self::deduce_git_max_files(matches, vars)?;
 
         Ok(Self {
             dir_action,
@@ -213,8 +303,52 @@ impl Options {
             view,
             theme,
             stdin,
+            git_max_files,
         })
     }
+
+    /// Determines the limit on the number of files in a Git repository from
+    /// the `EZA_GIT_MAX_FILES` environment variable. A value that isn’t a
+    /// number is ignored, unless being strict about it.
+    fn deduce_git_max_files<V: Vars>(
+        matches: &MatchedFlags<'_>,
+        vars: &V,
+    ) -> Result<Option<usize>, OptionsError> {
+        let Some(var) = vars.get(vars::EZA_GIT_MAX_FILES) else {
+            return Ok(None);
+        };
+
+        let var = var.to_string_lossy().into_owned();
+        match var.parse() {
+            Ok(max_files) => Ok(Some(max_files)),
+            Err(e) if matches.is_strict() => Err(OptionsError::FailedParse(
+                var,
+                NumberSource::Env(vars::EZA_GIT_MAX_FILES),
+                e,
+            )),
+            Err(e) => {
+                warn!("Ignoring {}={:?}: {}", vars::EZA_GIT_MAX_FILES, var, e);
+                Ok(None)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "git")]
+fn get_files_in_dir(paths: &mut Vec<PathBuf>, path: PathBuf) {
+    let temp_paths = if path.is_dir() {
+        match path.read_dir() {
+            Err(_) => {
+                vec![path]
+            }
+            Ok(d) => d
+                .filter_map(|entry| entry.ok().map(|e| e.path()))
+                .collect::<Vec<PathBuf>>(),
+        }
+    } else {
+        vec![path]
+    };
+    paths.extend(temp_paths);
 }
 
 /// The result of the `Options::parse` function.
@@ -240,6 +374,9 @@ pub enum OptionsResult<'args> {
 
     /// One of the arguments was `--version`, so display the version number.
     Version(VersionString),
+
+    /// One of the arguments was `--explain`, so display the deduced options.
+    Explain(Explanation),
 }
 
 #[cfg(test)]
@@ -287,4 +424,45 @@ pub mod test {
 
         result
     }
+
+    mod git_max_files {
+        use super::*;
+        use crate::options::{NumberSource, Options, OptionsError};
+        use std::ffi::OsString;
+
+        fn max_files(
+            value: &str,
+            strictnesses: Strictnesses,
+        ) -> Vec<Result<Option<usize>, OptionsError>> {
+            let vars = Some(OsString::from(value));
+            parse_for_test(&[], &[], strictnesses, |mf| {
+                Options::deduce_git_max_files(mf, &vars)
+            })
+        }
+
+        #[test]
+        fn number() {
+            for result in max_files("1000", Strictnesses::Both) {
+                assert_eq!(result, Ok(Some(1000)));
+            }
+        }
+
+        #[test]
+        fn not_a_number() {
+            assert_eq!(max_files("lots", Strictnesses::Last), vec![Ok(None)]);
+        }
+
+        #[test]
+        fn not_a_number_strict() {
+            let error = "lots".parse::<usize>().unwrap_err();
+            assert_eq!(
+                max_files("lots", Strictnesses::Complain),
+                vec![Err(OptionsError::FailedParse(
+                    String::from("lots"),
+                    NumberSource::Env("EZA_GIT_MAX_FILES"),
+                    error
+                ))]
+            );
+        }
+    }
 }
@@ -273,6 +273,10 @@ impl Args {
                 //   -abx def  => ‘a’, ‘b’, ‘x=def’
                 //   -abx      =>  error
                 //
+                // An argument that can optionally take a value only takes the
+                // rest of the string if it’s one of its values; otherwise the
+                // rest is parsed as more short arguments.
+                //
                 else {
                     for (index, byte) in bytes.iter().enumerate().skip(1) {
                         let arg = self.lookup_short(*byte)?;
@@ -304,10 +308,14 @@ impl Args {
                                     let remnants = bytes_to_os_str(&bytes[index + 1..]);
                                     if is_optional_arg(remnants, values) {
                                         result_flags.push((flag, Some(remnants)));
+                                        break;
+                                    } else if self.lookup_short(bytes[index + 1]).is_ok() {
+                                        // The rest is more short arguments, as in
+                                        // `-il`, so this one gets its default.
+                                        result_flags.push((flag, Some(OsStr::new(default))));
                                     } else {
                                         return Err(ParseError::ForbiddenValue { flag });
                                     }
-                                    break;
                                 } else if let Some(next_arg) = inputs.peek() {
                                     if is_optional_arg(next_arg, values) {
                                         result_flags.push((flag, Some(inputs.next().unwrap())));
@@ -339,6 +347,7 @@ impl Args {
             frees,
             flags: MatchedFlags {
                 flags: result_flags,
+                defaults: 0,
                 strictness,
             },
         })
@@ -389,10 +398,36 @@ pub struct MatchedFlags<'args> {
     /// we need to know where they are in relation to one another.
     flags: Vec<(Flag, Option<&'args OsStr>)>,
 
+    /// How many of the flags at the start came from the default arguments,
+    /// rather than the command-line.
+    defaults: usize,
+
     /// Whether to check for duplicate or redundant arguments.
     strictness: Strictness,
 }
 
+impl<'args> Matches<'args> {
+    /// Puts the matches from the default arguments in front of these ones,
+    /// so anything given here takes precedence over them.
+    pub fn after_defaults(self, defaults: Self) -> Self {
+        let mut flags = defaults.flags.flags;
+        let defaults_len = flags.len();
+        flags.extend(self.flags.flags);
+
+        let mut frees = defaults.frees;
+        frees.extend(self.frees);
+
+        Self {
+            flags: MatchedFlags {
+                flags,
+                defaults: defaults_len,
+                strictness: self.flags.strictness,
+            },
+            frees,
+        }
+    }
+}
+
 impl<'a> MatchedFlags<'a> {
     /// Whether the given argument was specified.
     /// Returns `true` if it was, `false` if it wasn’t, and an error in
@@ -412,11 +447,7 @@ impl<'a> MatchedFlags<'a> {
         P: Fn(&Flag) -> bool,
     {
         if self.is_strict() {
-            let all = self
-                .flags
-                .iter()
-                .filter(|tuple| tuple.1.is_none() && predicate(&tuple.0))
-                .collect::<Vec<_>>();
+            let all = self.strict_matches(|tuple| tuple.1.is_none() && predicate(&tuple.0));
 
             if all.len() < 2 {
                 Ok(all.first().map(|t| &t.0))
@@ -443,6 +474,32 @@ impl<'a> MatchedFlags<'a> {
             .map(|tuple| &tuple.0)
     }
 
+    /// Returns the last argument that satisfies the predicate, whether or
+    /// not it was given a value, with strict mode having no effect.
+    pub fn last_where<P>(&self, predicate: P) -> Option<&Flag>
+    where
+        P: Fn(&Flag) -> bool,
+    {
+        self.flags
+            .iter()
+            .rev()
+            .find(|tuple| predicate(&tuple.0))
+            .map(|tuple| &tuple.0)
+    }
+
+    /// Returns the last argument that satisfies the predicate, as above,
+    /// along with whether it came from the default arguments rather than
+    /// the command-line.
+    pub fn last_where_with_origin<P>(&self, predicate: P) -> Option<(&Flag, bool)>
+    where
+        P: Fn(&Flag) -> bool,
+    {
+        self.flags
+            .iter()
+            .rposition(|tuple| predicate(&tuple.0))
+            .map(|index| (&self.flags[index].0, index < self.defaults))
+    }
+
     // This code could probably be better.
     // Both ‘has’ and ‘get’ immediately begin with a conditional, which makes
     // me think the functionality could be moved to inside Strictness.
@@ -464,11 +521,7 @@ impl<'a> MatchedFlags<'a> {
         P: Fn(&Flag) -> bool,
     {
         if self.is_strict() {
-            let those = self
-                .flags
-                .iter()
-                .filter(|tuple| tuple.1.is_some() && predicate(&tuple.0))
-                .collect::<Vec<_>>();
+            let those = self.strict_matches(|tuple| tuple.1.is_some() && predicate(&tuple.0));
 
             if those.len() < 2 {
                 Ok(those.first().copied().map(|t| t.1.unwrap()))
@@ -486,6 +539,34 @@ impl<'a> MatchedFlags<'a> {
         }
     }
 
+    /// Returns the flags that satisfy the predicate, to check for duplicates
+    /// in strict mode. Flags on the command-line override the default
+    /// arguments rather than clashing with them, so the defaults only get
+    /// looked at when none of the command-line flags match.
+    fn strict_matches<P>(&self, predicate: P) -> Vec<&(Flag, Option<&'a OsStr>)>
+    where
+        P: Fn(&(Flag, Option<&'a OsStr>)) -> bool,
+    {
+        let (defaults, given) = self.flags.split_at(self.defaults);
+        let given = given.iter().filter(|t| predicate(t)).collect::<Vec<_>>();
+        if given.is_empty() {
+            defaults.iter().filter(|t| predicate(t)).collect()
+        } else {
+            given
+        }
+    }
+
+    /// Returns the values of every occurrence of the given argument, in the
+    /// order they were given, even in strict mode. This is for arguments
+    /// that can be repeated to build up a list.
+    pub fn get_all(&self, arg: &Arg) -> Vec<&OsStr> {
+        self.flags
+            .iter()
+            .filter(|tuple| tuple.0.matches(arg))
+            .filter_map(|tuple| tuple.1)
+            .collect()
+    }
+
     // It’s annoying that ‘has’ and ‘get’ won’t work when accidentally given
     // flags that do/don’t take values, but this should be caught by tests.
 
@@ -639,7 +720,11 @@ mod parse_test {
 
                 let strictness = Strictness::UseLastArguments; // this isn’t even used
                 let got = Args(TEST_ARGS).parse(inputs, strictness);
-                let flags = MatchedFlags { flags, strictness };
+                let flags = MatchedFlags {
+                    flags,
+                    defaults: 0,
+                    strictness,
+                };
 
                 let expected = Ok(Matches { frees, flags });
                 assert_eq!(got, expected);
@@ -736,6 +821,8 @@ mod parse_test {
     test!(short_opt:        ["-o"]                 => frees: [], flags: [(Flag::Short(b'o'), Some(OsStr::new("all")))]);
     test!(short_opt_value:  ["-onone"]             => frees: [], flags: [(Flag::Short(b'o'), Some(OsStr::new("none")))]);
     test!(short_forbidden:  ["-opath"]             => error ForbiddenValue  { flag: Flag::Short(b'o') });
+    test!(short_opt_first:  ["-olv"]               => frees: [], flags: [(Flag::Short(b'o'), Some(OsStr::new("all"))), (Flag::Short(b'l'), None), (Flag::Short(b'v'), None)]);
+    test!(short_opt_middle: ["-lov"]               => frees: [], flags: [(Flag::Short(b'l'), None), (Flag::Short(b'o'), Some(OsStr::new("all"))), (Flag::Short(b'v'), None)]);
     test!(short_allowed:    ["-o","path"]          => frees: ["path"], flags: [(Flag::Short(b'o'), Some(OsStr::new("all")))]);
 }
 
@@ -749,6 +836,7 @@ mod matches_test {
             fn $name() {
                 let flags = MatchedFlags {
                     flags: $input.to_vec(),
+                    defaults: 0,
                     strictness: Strictness::UseLastArguments,
                 };
 
@@ -781,6 +869,7 @@ mod matches_test {
 
         let flags = MatchedFlags {
             flags: vec![(Flag::Short(b'c'), Some(&*everything))],
+            defaults: 0,
             strictness: Strictness::UseLastArguments,
         };
 
@@ -797,16 +886,46 @@ mod matches_test {
                 (Flag::Short(b'c'), Some(&*everything)),
                 (Flag::Short(b'c'), Some(&*nothing)),
             ],
+            defaults: 0,
             strictness: Strictness::UseLastArguments,
         };
 
         assert_eq!(flags.get(&COUNT), Ok(Some(&*nothing)));
     }
 
+    #[test]
+    fn default_overridden_strictly() {
+        let flags = MatchedFlags {
+            flags: vec![(Flag::Short(b'v'), None), (Flag::Long("verbose"), None)],
+            defaults: 1,
+            strictness: Strictness::ComplainAboutRedundantArguments,
+        };
+
+        assert_eq!(flags.has(&VERBOSE), Ok(true));
+    }
+
+    #[test]
+    fn given_twice_strictly() {
+        let flags = MatchedFlags {
+            flags: vec![(Flag::Short(b'v'), None), (Flag::Long("verbose"), None)],
+            defaults: 0,
+            strictness: Strictness::ComplainAboutRedundantArguments,
+        };
+
+        assert_eq!(
+            flags.has(&VERBOSE),
+            Err(OptionsError::Duplicate(
+                Flag::Short(b'v'),
+                Flag::Long("verbose")
+            ))
+        );
+    }
+
     #[test]
     fn no_count() {
         let flags = MatchedFlags {
             flags: Vec::new(),
+            defaults: 0,
             strictness: Strictness::UseLastArguments,
         };
 
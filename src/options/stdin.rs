@@ -9,10 +9,18 @@ use std::io::IsTerminal;
 pub enum FilesInput {
     Stdin(OsString),
     Args,
+
+    /// Render a tree previously written with `--tree-format=indent` from
+    /// this file, instead of looking at the filesystem.
+    Tree(OsString),
 }
 
 impl FilesInput {
     pub fn deduce<V: Vars>(matches: &MatchedFlags<'_>, vars: &V) -> Result<Self, OptionsError> {
+        if let Some(path) = matches.get(&flags::FROM_TREE)? {
+            return Ok(FilesInput::Tree(path.to_os_string()));
+        }
+
         Ok(
             if io::stdin().is_terminal() || !matches.has(&flags::STDIN)? {
                 FilesInput::Args
@@ -24,9 +24,10 @@ impl Options {
 
 impl UseColours {
     fn deduce<V: Vars>(matches: &MatchedFlags<'_>, vars: &V) -> Result<Self, OptionsError> {
-        let default_value = match vars.get(vars::NO_COLOR) {
-            Some(_) => Self::Never,
-            None => Self::Automatic,
+        let default_value = if vars.get(vars::NO_COLOR).is_some() || Self::dumb_terminal(vars) {
+            Self::Never
+        } else {
+            Self::Automatic
         };
 
         let Some(word) =
@@ -41,10 +42,24 @@ impl UseColours {
             Ok(Self::Automatic)
         } else if word == "never" {
             Ok(Self::Never)
+        } else if word == "html" {
+            Ok(Self::Html)
+        } else if word == "pango" {
+            Ok(Self::Pango)
         } else {
             Err(OptionsError::BadArgument(&flags::COLOR, word.into()))
         }
     }
+
+    /// Whether `TERM` says the terminal can’t show colours: it’s `dumb`, as
+    /// in editors that run commands in a buffer, or not set at all. Windows
+    /// consoles don’t set it, so only a `dumb` one counts there.
+    pub(super) fn dumb_terminal<V: Vars>(vars: &V) -> bool {
+        match vars.get(vars::TERM) {
+            Some(term) if !term.is_empty() => term == "dumb",
+            _ => cfg!(unix),
+        }
+    }
 }
 
 impl Definitions {
@@ -129,14 +144,17 @@ mod terminal_test {
         ls: &'static str,
         exa: &'static str,
         no_color: &'static str,
+        term: &'static str,
     }
 
     impl MockVars {
+        /// Nothing set, apart from the type of an ordinary terminal.
         fn empty() -> MockVars {
             MockVars {
                 ls: "",
                 exa: "",
                 no_color: "",
+                term: "xterm-256color",
             }
         }
         fn with_no_color() -> MockVars {
@@ -144,6 +162,15 @@ mod terminal_test {
                 ls: "",
                 exa: "",
                 no_color: "true",
+                term: "xterm-256color",
+            }
+        }
+        fn with_term(term: &'static str) -> MockVars {
+            MockVars {
+                ls: "",
+                exa: "",
+                no_color: "",
+                term,
             }
         }
     }
@@ -158,6 +185,8 @@ mod terminal_test {
                 Some(OsString::from(self.exa))
             } else if name == vars::NO_COLOR && !self.no_color.is_empty() {
                 Some(OsString::from(self.no_color))
+            } else if name == vars::TERM && !self.term.is_empty() {
+                Some(OsString::from(self.term))
             } else {
                 None
             }
@@ -168,6 +197,14 @@ mod terminal_test {
     test!(empty:         UseColours <- [], MockVars::empty();                     Both => Ok(UseColours::Automatic));
     test!(empty_with_no_color: UseColours <- [], MockVars::with_no_color();             Both => Ok(UseColours::Never));
 
+    // Terminals that can’t show colours
+    test!(dumb_term:     UseColours <- [], MockVars::with_term("dumb");           Both => Ok(UseColours::Never));
+    test!(normal_term:   UseColours <- [], MockVars::with_term("xterm-256color"); Both => Ok(UseColours::Automatic));
+    #[cfg(unix)]
+    test!(unset_term:    UseColours <- [], MockVars::with_term("");               Both => Ok(UseColours::Never));
+    test!(dumb_always:   UseColours <- ["--color=always"], MockVars::with_term("dumb"); Both => Ok(UseColours::Always));
+    test!(dumb_never:    UseColours <- ["--color=never"], MockVars::with_term("dumb");  Both => Ok(UseColours::Never));
+
     // --colour
     test!(u_always:      UseColours <- ["--colour=always"], MockVars::empty();    Both => Ok(UseColours::Always));
     test!(u_auto:        UseColours <- ["--colour", "auto"], MockVars::empty();   Both => Ok(UseColours::Automatic));
@@ -177,6 +214,9 @@ mod terminal_test {
     test!(no_u_always:   UseColours <- ["--color", "always"], MockVars::empty();  Both => Ok(UseColours::Always));
     test!(no_u_auto:     UseColours <- ["--color=auto"], MockVars::empty();       Both => Ok(UseColours::Automatic));
     test!(no_u_never:    UseColours <- ["--color", "never"], MockVars::empty();   Both => Ok(UseColours::Never));
+    test!(no_u_html:     UseColours <- ["--color=html"], MockVars::empty();       Both => Ok(UseColours::Html));
+    test!(html_no_color: UseColours <- ["--color=html"], MockVars::with_no_color(); Both => Ok(UseColours::Html));
+    test!(no_u_pango:    UseColours <- ["--color=pango"], MockVars::empty();      Both => Ok(UseColours::Pango));
 
     // Errors
     test!(no_u_error:    UseColours <- ["--color=upstream"], MockVars::empty();   Both => err OptionsError::BadArgument(&flags::COLOR, OsString::from("upstream"))); // the error is for --color
@@ -11,6 +11,18 @@ pub static LS_COLORS: &str = "LS_COLORS";
 /// characters.
 pub static COLUMNS: &str = "COLUMNS";
 
+/// Environment variable used to pick what truncated file names end with,
+/// for fonts that can’t show the `…` character.
+pub static EZA_ELLIPSIS: &str = "EZA_ELLIPSIS";
+
+/// Environment variable used to set the fewest columns to use, in
+/// characters, so a tiny terminal or `COLUMNS` doesn’t squash the listing.
+pub static EZA_MIN_WIDTH: &str = "EZA_MIN_WIDTH";
+
+/// Environment variable used to cap the width of the terminal, in
+/// characters, so listings on very wide terminals stay easy to scan.
+pub static EZA_MAX_WIDTH: &str = "EZA_MAX_WIDTH";
+
 /// Environment variable used to datetime format.
 pub static TIME_STYLE: &str = "TIME_STYLE";
 
@@ -18,6 +30,10 @@ pub static TIME_STYLE: &str = "TIME_STYLE";
 /// See: <https://no-color.org/>
 pub static NO_COLOR: &str = "NO_COLOR";
 
+/// Environment variable naming the type of terminal. A `dumb` terminal,
+/// or none at all, can’t show colours.
+pub static TERM: &str = "TERM";
+
 // exa-specific variables
 
 /// Environment variable used to colour exa’s interface when colours are
@@ -45,6 +61,11 @@ pub static EZA_DEBUG: &str = "EZA_DEBUG";
 pub static EXA_GRID_ROWS: &str = "EXA_GRID_ROWS";
 pub static EZA_GRID_ROWS: &str = "EZA_GRID_ROWS";
 
+/// Environment variable used to limit the grid-details view so it’s only
+/// activated if the grid would fit at least the given number of columns.
+/// The rows variable takes precedence over this one if both are set.
+pub static EZA_GRID_COLS: &str = "EZA_GRID_COLS";
+
 /// Environment variable used to specify how many spaces to print between an
 /// icon and its file name. Different terminals display icons differently,
 /// with 1 space bringing them too close together or 2 spaces putting them too
@@ -55,17 +76,54 @@ pub static EZA_ICON_SPACING: &str = "EZA_ICON_SPACING";
 pub static EXA_OVERRIDE_GIT: &str = "EXA_OVERRIDE_GIT";
 pub static EZA_OVERRIDE_GIT: &str = "EZA_OVERRIDE_GIT";
 
+/// Environment variable used to skip Git statuses in repositories tracking
+/// more than this number of files, as querying them can take a long time.
+pub static EZA_GIT_MAX_FILES: &str = "EZA_GIT_MAX_FILES";
+
 /// Enviroment variable used to set the minimum luminance in `color_scale`. It's value
 /// can be between -100 and 100
 pub static EXA_MIN_LUMINANCE: &str = "EXA_MIN_LUMINANCE";
 pub static EZA_MIN_LUMINANCE: &str = "EZA_MIN_LUMINANCE";
 
+/// Environment variable used to set the maximum luminance in `color_scale`,
+/// which can be between the minimum and 100.
+pub static EXA_MAX_LUMINANCE: &str = "EXA_MAX_LUMINANCE";
+pub static EZA_MAX_LUMINANCE: &str = "EZA_MAX_LUMINANCE";
+
+/// Environment variable holding a list of hex colours for `color_scale` to
+/// blend between, instead of changing the luminance of each column’s colour.
+pub static EZA_COLOR_SCALE_GRADIENT: &str = "EZA_COLOR_SCALE_GRADIENT";
+
+/// Environment variable holding how new a file has to be to count as recent,
+/// such as `7d`, for both the age colour scale and custom time styles.
+pub static EZA_RECENT_DURATION: &str = "EZA_RECENT_DURATION";
+
+/// The older name for `EZA_RECENT_DURATION`, only read when that isn’t set.
+pub static EZA_RECENT_THRESHOLD: &str = "EZA_RECENT_THRESHOLD";
+
 /// Environment variable used to automate the same behavior as `--icons=auto` if set.
 /// Any explicit use of `--icons=WHEN` overrides this behavior.
 pub static EZA_ICONS_AUTO: &str = "EZA_ICONS_AUTO";
 
+/// Environment variable holding several icon settings at once, separated by
+/// commas, such as `auto,ascii,spacing=2`. Flags override each setting.
+pub static EZA_ICONS: &str = "EZA_ICONS";
+
+/// Environment variable used to pick the set of icons to display. Setting it
+/// to `ascii` uses basic symbols that don’t need a Nerd Font.
+pub static EZA_ICON_SET: &str = "EZA_ICON_SET";
+
 pub static EZA_STDIN_SEPARATOR: &str = "EZA_STDIN_SEPARATOR";
 
+/// Environment variable holding arguments to put before the ones given on
+/// the command-line, split on whitespace with simple quoting. Anything given
+/// on the command-line takes precedence over them.
+pub static EZA_DEFAULT_ARGS: &str = "EZA_DEFAULT_ARGS";
+
+/// Environment variable used to pick the view to use when none of the flags
+/// that pick one, such as `--long` or `--tree`, are given.
+pub static EZA_DEFAULT_VIEW: &str = "EZA_DEFAULT_VIEW";
+
 /// Environment variable used to choose how windows attributes are displayed.
 /// Short will display a single character for each set attribute, long will
 /// display a comma separated list of descriptions.
@@ -101,6 +159,7 @@ impl Vars for Option<OsString> {
 
 #[cfg(test)]
 #[allow(dead_code)]
+#[derive(Default)]
 pub struct MockVars {
     columns: OsString,
     colors: OsString,
@@ -111,6 +170,7 @@ pub struct MockVars {
     icon_spacing: OsString,
     luminance: OsString,
     icons: OsString,
+    windows_attributes: OsString,
 }
 
 #[cfg(test)]
@@ -125,6 +185,7 @@ impl Vars for MockVars {
             "EXA_ICON_SPACING" | "EZA_ICON_SPACING" => Some(self.icon_spacing.clone()),
             "EXA_MIN_LUMINANCE" | "EZA_MIN_LUMINANCE" => Some(self.luminance.clone()),
             "EZA_ICONS_AUTO" => Some(self.icons.clone()),
+            "EZA_WINDOWS_ATTRIBUTES" => Some(self.windows_attributes.clone()),
             "COLUMNS" => Some(self.columns.clone()),
             "NO_COLOR" => Some(self.no_colors.clone()),
             _ => None,
@@ -144,6 +205,7 @@ impl MockVars {
             "EXA_ICON_SPACING" | "EZA_ICON_SPACING" => self.icon_spacing = value.clone(),
             "EXA_MIN_LUMINANCE" | "EZA_MIN_LUMINANCE" => self.luminance = value.clone(),
             "EZA_ICONS_AUTO" => self.icons = value.clone(),
+            "EZA_WINDOWS_ATTRIBUTES" => self.windows_attributes = value.clone(),
             "COLUMNS" => self.columns = value.clone(),
             "NO_COLOR" => self.no_colors = value.clone(),
             _ => (),
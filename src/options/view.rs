@@ -1,32 +1,64 @@
-use std::ffi::OsString;
+use std::ffi::OsStr;
+use std::num::NonZeroUsize;
+use std::ops::RangeInclusive;
+use std::time::Duration;
 
+use crate::fs::feature::checksum::Algorithm;
 use crate::fs::feature::xattr;
-use crate::options::parser::MatchedFlags;
+use crate::fs::filter::{FileFilter, SortKeys};
+use crate::options::parser::{Arg, MatchedFlags, TakesValue};
 use crate::options::{flags, vars, NumberSource, OptionsError, Vars};
-use crate::output::color_scale::{ColorScaleMode, ColorScaleOptions};
+use crate::output::color_scale::{ColorScaleMode, ColorScaleOptions, GradientStops};
 use crate::output::file_name::Options as FileStyle;
 use crate::output::grid_details::{self, RowThreshold};
 use crate::output::table::{
-    Columns, FlagsFormat, GroupFormat, Options as TableOptions, SizeFormat, TimeTypes, UserFormat,
+    Alignment, Column, ColumnAlignments, ColumnKind, Columns, DirSize, FlagsFormat, GroupFormat,
+    InodeFormat, Options as TableOptions, SizeFormat, SizeUnit, TimeType, TimeTypes, UserFormat,
 };
 use crate::output::time::TimeFormat;
-use crate::output::{details, grid, Mode, TerminalWidth, View};
+use crate::output::{csv, details, grid, template, Mode, TerminalWidth, View, ELLIPSIS};
 
 impl View {
     pub fn deduce<V: Vars>(matches: &MatchedFlags<'_>, vars: &V) -> Result<Self, OptionsError> {
         let mode = Mode::deduce(matches, vars)?;
         let deref_links = matches.has(&flags::DEREF_LINKS)?;
-        let total_size = matches.has(&flags::TOTAL_SIZE)?;
+        let total_size =
+            matches.has(&flags::TOTAL_SIZE)? || DirSize::deduce(matches)? == DirSize::Total;
         let width = TerminalWidth::deduce(matches, vars)?;
-        let file_style = FileStyle::deduce(matches, vars, width.actual_terminal_width().is_some())?;
+        let min_width = TerminalWidth::deduce_min(matches, vars)?;
+        let max_width = TerminalWidth::deduce_max(matches, vars)?;
+        let is_tty = width.actual_terminal_width(min_width, max_width).is_some();
+        let file_style = FileStyle::deduce(matches, vars, is_tty)?;
+        let max_output = Self::deduce_max_output(matches)?;
         Ok(Self {
             mode,
             width,
+            min_width,
+            max_width,
             file_style,
             deref_links,
             total_size,
+            max_output,
         })
     }
+
+    /// The number of bytes after which to stop printing, from
+    /// `--max-output`. A limit of zero would stop before printing anything.
+    fn deduce_max_output(matches: &MatchedFlags<'_>) -> Result<Option<usize>, OptionsError> {
+        let Some(limit) = matches.get(&flags::MAX_OUTPUT)? else {
+            return Ok(None);
+        };
+
+        let arg_str = limit.to_string_lossy();
+        match arg_str.parse() {
+            Ok(0) => Err(OptionsError::BadArgument(&flags::MAX_OUTPUT, limit.into())),
+            Ok(n) => Ok(Some(n)),
+            Err(e) => {
+                let source = NumberSource::Arg(&flags::MAX_OUTPUT);
+                Err(OptionsError::FailedParse(arg_str.to_string(), source, e))
+            }
+        }
+    }
 }
 
 impl Mode {
@@ -39,6 +71,30 @@ impl Mode {
     /// This is complicated a little by the fact that `--grid` and `--tree`
     /// can also combine with `--long`, so care has to be taken to use the
     pub fn deduce<V: Vars>(matches: &MatchedFlags<'_>, vars: &V) -> Result<Self, OptionsError> {
+        Self::strict_check_repeated_flags(matches)?;
+
+        // A template picks its own fields, so it takes the place of any
+        // other view.
+        if let Some(word) = matches.get(&flags::FORMAT_TEMPLATE)? {
+            let template = template::Template::parse(&word.to_string_lossy())
+                .map_err(OptionsError::BadTemplate)?;
+            let table = TableOptions::deduce(matches, vars)?;
+            return Ok(Self::Template(template::Options { template, table }));
+        }
+
+        // So does CSV, which uses the long view’s columns.
+        if matches.has(&flags::CSV)? {
+            let delimiter = Self::deduce_csv_delimiter(matches)?;
+            let table = TableOptions::deduce(matches, vars)?;
+            return Ok(Self::Csv(csv::Options { delimiter, table }));
+        } else if matches.is_strict() && matches.count(&flags::CSV_DELIMITER) > 0 {
+            return Err(OptionsError::Useless(
+                &flags::CSV_DELIMITER,
+                false,
+                &flags::CSV,
+            ));
+        }
+
         let flag = matches.has_where_any(|f| {
             f.matches(&flags::LONG)
                 || f.matches(&flags::ONE_LINE)
@@ -47,9 +103,26 @@ impl Mode {
         });
 
         let Some(flag) = flag else {
-            Self::strict_check_long_flags(matches)?;
-            let grid = grid::Options::deduce(matches)?;
-            return Ok(Self::Grid(grid));
+            return match DefaultView::deduce(matches, vars)? {
+                Some(DefaultView::Long) => {
+                    let details = details::Options::deduce_long(matches, vars)?;
+                    Ok(Self::Details(details))
+                }
+                Some(DefaultView::Tree) => {
+                    Self::strict_check_long_flags(matches)?;
+                    let details = details::Options::deduce_tree(matches, vars)?;
+                    Ok(Self::Details(details))
+                }
+                Some(DefaultView::Oneline) => {
+                    Self::strict_check_long_flags(matches)?;
+                    Ok(Self::Lines)
+                }
+                Some(DefaultView::Grid) | None => {
+                    Self::strict_check_long_flags(matches)?;
+                    let grid = grid::Options::deduce(matches)?;
+                    Ok(Self::Grid(grid))
+                }
+            };
         };
 
         if flag.matches(&flags::LONG)
@@ -64,7 +137,7 @@ impl Mode {
 
             if flag.is_some() && flag.unwrap().matches(&flags::GRID) {
                 let _ = matches.has(&flags::GRID)?;
-                let row_threshold = RowThreshold::deduce(vars)?;
+                let row_threshold = RowThreshold::deduce(matches, vars)?;
                 let grid_details = grid_details::Options {
                     details,
                     row_threshold,
@@ -97,35 +170,104 @@ impl Mode {
         // If --long hasn’t been passed, then check if we need to warn the
         // user about flags that won’t have any effect.
         if matches.is_strict() {
+            // --numeric also picks how --sort=owner and --sort=group order
+            // the files, which doesn’t need --long.
+            let sorts_by_owner = SortKeys::deduce(matches)?.sorts_by_owner();
+            let mut useless = Vec::new();
+
             for option in &[
                 &flags::BINARY,
                 &flags::BYTES,
+                &flags::SIZE_UNIT,
                 &flags::INODE,
+                &flags::INODE_FORMAT,
                 &flags::LINKS,
                 &flags::HEADER,
                 &flags::BLOCKSIZE,
+                &flags::IO_BLOCKSIZE,
                 &flags::TIME,
+                &flags::ALL_TIMES,
                 &flags::GROUP,
                 &flags::NUMERIC,
                 &flags::MOUNTS,
+                &flags::COLUMNS,
+                &flags::MIME,
+                &flags::CHECKSUM,
+                &flags::FLAGS_FORMAT,
+                &flags::GIT_COL,
             ] {
-                if matches.has(option)? {
-                    return Err(OptionsError::Useless(option, false, &flags::LONG));
+                let given = matches.has(option)? || matches.get(option)?.is_some();
+                if given && !(sorts_by_owner && *option == &flags::NUMERIC) {
+                    useless.push(OptionsError::Useless(option, false, &flags::LONG));
+                }
+            }
+
+            for option in &[&flags::ELLIPSIS, &flags::NAME_WIDTH, &flags::GIT_SUMMARY] {
+                if matches.get(option)?.is_some() {
+                    useless.push(OptionsError::Useless(option, false, &flags::LONG));
                 }
             }
 
             if matches.has(&flags::GIT)? && !matches.has(&flags::NO_GIT)? {
-                return Err(OptionsError::Useless(&flags::GIT, false, &flags::LONG));
-            } else if matches.has(&flags::LEVEL)?
+                useless.push(OptionsError::Useless(&flags::GIT, false, &flags::LONG));
+            }
+
+            if matches.has(&flags::LEVEL)?
                 && !matches.has(&flags::RECURSE)?
                 && !matches.has(&flags::TREE)?
             {
-                return Err(OptionsError::Useless2(
+                useless.push(OptionsError::Useless2(
                     &flags::LEVEL,
                     &flags::RECURSE,
                     &flags::TREE,
                 ));
             }
+
+            // Report every flag that does nothing in one go, rather than
+            // making the user fix them one run at a time.
+            if useless.len() > 1 {
+                return Err(OptionsError::MultipleUseless(useless));
+            } else if let Some(error) = useless.pop() {
+                return Err(error);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Determines the character to put between fields with `--csv`, which
+    /// has to be a single character other than a quote or a line break.
+    /// `\t` stands for a tab, as a real one is awkward to type.
+    fn deduce_csv_delimiter(matches: &MatchedFlags<'_>) -> Result<char, OptionsError> {
+        let Some(word) = matches.get(&flags::CSV_DELIMITER)? else {
+            return Ok(',');
+        };
+
+        let delimiter = match word.to_str() {
+            Some("\\t") => Some('\t'),
+            Some(s) => {
+                let mut chars = s.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) if !matches!(c, '"' | '\n' | '\r') => Some(c),
+                    _ => None,
+                }
+            }
+            None => None,
+        };
+
+        delimiter.ok_or_else(|| OptionsError::BadArgument(&flags::CSV_DELIMITER, word.into()))
+    }
+
+    fn strict_check_repeated_flags(matches: &MatchedFlags<'_>) -> Result<(), OptionsError> {
+        // Boolean flags don’t do anything more when given twice, so in strict
+        // mode a repeat is most likely an alias clashing with the command
+        // line. `--all` is the exception, as it can be counted.
+        if matches.is_strict() {
+            for option in flags::ALL_ARGS.0 {
+                if option.takes_value == TakesValue::Forbidden && *option != &flags::ALL {
+                    let _ = matches.has(option)?;
+                }
+            }
         }
 
         Ok(())
@@ -150,6 +292,11 @@ impl details::Options {
             xattr: xattr::ENABLED && matches.has(&flags::EXTENDED)?,
             secattr: xattr::ENABLED && matches.has(&flags::SECURITY_CONTEXT)?,
             mounts: matches.has(&flags::MOUNTS)?,
+            archives: matches.has(&flags::PEEK_ARCHIVES)?,
+            indented: Self::deduce_indented(matches)?,
+            name_width: None,
+            ellipsis: String::from(ELLIPSIS),
+            git_summary: false,
             color_scale: ColorScaleOptions::deduce(matches, vars)?,
         };
 
@@ -171,27 +318,214 @@ impl details::Options {
             xattr: xattr::ENABLED && matches.has(&flags::EXTENDED)?,
             secattr: xattr::ENABLED && matches.has(&flags::SECURITY_CONTEXT)?,
             mounts: matches.has(&flags::MOUNTS)?,
+            archives: matches.has(&flags::PEEK_ARCHIVES)?,
+            indented: Self::deduce_indented(matches)?,
+            name_width: Self::deduce_name_width(matches)?,
+            ellipsis: Self::deduce_ellipsis(matches, vars)?,
+            git_summary: Self::deduce_git_summary(matches, vars)?,
             color_scale: ColorScaleOptions::deduce(matches, vars)?,
         })
     }
+
+    /// Whether `--tree-format=indent` was given to write the tree in a form
+    /// that can be read back in, rather than drawing it.
+    fn deduce_indented(matches: &MatchedFlags<'_>) -> Result<bool, OptionsError> {
+        match matches.get(&flags::TREE_FORMAT)? {
+            None => Ok(false),
+            Some(word) if word == "tree" => Ok(false),
+            Some(word) if word == "indent" => Ok(true),
+            Some(word) => Err(OptionsError::BadArgument(&flags::TREE_FORMAT, word.into())),
+        }
+    }
+
+    /// What to end truncated names with, from the `--ellipsis` flag,
+    /// falling back to the `EZA_ELLIPSIS` environment variable, for fonts
+    /// that can’t show `…`.
+    fn deduce_ellipsis<V: Vars>(
+        matches: &MatchedFlags<'_>,
+        vars: &V,
+    ) -> Result<String, OptionsError> {
+        if let Some(ellipsis) = matches.get(&flags::ELLIPSIS)? {
+            if matches.is_strict() && matches.get(&flags::NAME_WIDTH)?.is_none() {
+                return Err(OptionsError::Useless(
+                    &flags::ELLIPSIS,
+                    false,
+                    &flags::NAME_WIDTH,
+                ));
+            }
+
+            return Ok(ellipsis.to_string_lossy().into_owned());
+        }
+
+        let ellipsis = vars.get(vars::EZA_ELLIPSIS);
+        Ok(ellipsis.map_or_else(
+            || String::from(ELLIPSIS),
+            |e| e.to_string_lossy().into_owned(),
+        ))
+    }
+
+    /// The fixed width of the name column from `--name-width`, if one was
+    /// given. A width of zero would leave no room for any name at all.
+    fn deduce_name_width(matches: &MatchedFlags<'_>) -> Result<Option<usize>, OptionsError> {
+        let Some(width) = matches.get(&flags::NAME_WIDTH)? else {
+            return Ok(None);
+        };
+
+        let arg_str = width.to_string_lossy();
+        match arg_str.parse() {
+            Ok(0) => Err(OptionsError::BadArgument(&flags::NAME_WIDTH, width.into())),
+            Ok(w) => Ok(Some(w)),
+            Err(e) => {
+                let source = NumberSource::Arg(&flags::NAME_WIDTH);
+                Err(OptionsError::FailedParse(arg_str.to_string(), source, e))
+            }
+        }
+    }
+
+    /// Whether to count the files by Git status after the table. As with
+    /// icons, `auto` only shows the count when writing to a terminal. The
+    /// count needs `--git` to have anything to count.
+    fn deduce_git_summary<V: Vars>(
+        matches: &MatchedFlags<'_>,
+        vars: &V,
+    ) -> Result<bool, OptionsError> {
+        let Some(word) = matches.get(&flags::GIT_SUMMARY)? else {
+            return Ok(false);
+        };
+
+        let show = match word.to_str() {
+            Some("always") => true,
+            Some("auto" | "automatic") => TerminalWidth::deduce(matches, vars)?
+                .actual_terminal_width(None, None)
+                .is_some(),
+            Some("never") => false,
+            _ => return Err(OptionsError::BadArgument(&flags::GIT_SUMMARY, word.into())),
+        };
+
+        if matches.is_strict() && !matches.has(&flags::GIT)? {
+            return Err(OptionsError::Useless(
+                &flags::GIT_SUMMARY,
+                false,
+                &flags::GIT,
+            ));
+        }
+
+        Ok(show)
+    }
 }
 
 impl TerminalWidth {
+    /// Determine the width of the terminal from the `--width` flag, falling
+    /// back to the `COLUMNS` environment variable. Passing `--width=max`,
+    /// `--width=auto`, or `--width=0` looks up the real width of the
+    /// terminal, ignoring `COLUMNS`, which can be stale, and a negative
+    /// number looks it up and takes that many columns off, to leave a
+    /// margin. A `--width` value that’s none of these gets its own error,
+    /// as it can be a keyword.
+    ///
+    /// A width that’s set, either way, is raised to the minimum width and
+    /// capped to the maximum width, if there are any. A width looked up from
+    /// the terminal gets the same once it’s known.
     fn deduce<V: Vars>(matches: &MatchedFlags<'_>, vars: &V) -> Result<Self, OptionsError> {
+        let width = Self::deduce_uncapped(matches, vars)?;
+        let min = Self::deduce_min(matches, vars)?;
+        let max = Self::deduce_max(matches, vars)?;
+        match width {
+            Self::Set(width) => {
+                let width = min.map_or(width, |min| width.max(min));
+                Ok(Self::Set(max.map_or(width, |max| width.min(max))))
+            }
+            width => Ok(width),
+        }
+    }
+
+    /// Determine the fewest columns to use, from the `--min-width` flag,
+    /// falling back to the `EZA_MIN_WIDTH` environment variable, so a tiny
+    /// width doesn’t squash the listing. A minimum that’s more than the
+    /// maximum, or than a width given with `--width`, is an error, as there’s
+    /// no way to satisfy both.
+    fn deduce_min<V: Vars>(
+        matches: &MatchedFlags<'_>,
+        vars: &V,
+    ) -> Result<Option<usize>, OptionsError> {
+        let (min, source) = if let Some(min) = matches.get(&flags::MIN_WIDTH)? {
+            (
+                min.to_string_lossy().into_owned(),
+                NumberSource::Arg(&flags::MIN_WIDTH),
+            )
+        } else if let Some(min) = vars
+            .get(vars::EZA_MIN_WIDTH)
+            .and_then(|s| s.into_string().ok())
+        {
+            (min, NumberSource::Env(vars::EZA_MIN_WIDTH))
+        } else {
+            return Ok(None);
+        };
+
+        let min_width = match min.parse::<NonZeroUsize>() {
+            Ok(min_width) => min_width.get(),
+            Err(e) => return Err(OptionsError::FailedParse(min, source, e)),
+        };
+
+        let explicit = match Self::deduce_uncapped(matches, vars)? {
+            Self::Set(width) if matches.get(&flags::WIDTH)?.is_some() => Some(width),
+            _ => None,
+        };
+        let highest = match (explicit, Self::deduce_max(matches, vars)?) {
+            (Some(width), Some(max)) => Some(width.min(max)),
+            (width, max) => width.or(max),
+        };
+
+        match highest {
+            Some(highest) if min_width > highest => {
+                Err(OptionsError::OutOfRange(min, source, 1..=highest as isize))
+            }
+            _ => Ok(Some(min_width)),
+        }
+    }
+
+    /// Determine the most columns to use, from the `--max-width` flag,
+    /// falling back to the `EZA_MAX_WIDTH` environment variable. A maximum
+    /// of zero would leave no room for anything, so it’s an error.
+    fn deduce_max<V: Vars>(
+        matches: &MatchedFlags<'_>,
+        vars: &V,
+    ) -> Result<Option<usize>, OptionsError> {
+        let (max, source) = if let Some(max) = matches.get(&flags::MAX_WIDTH)? {
+            (
+                max.to_string_lossy().into_owned(),
+                NumberSource::Arg(&flags::MAX_WIDTH),
+            )
+        } else if let Some(max) = vars
+            .get(vars::EZA_MAX_WIDTH)
+            .and_then(|s| s.into_string().ok())
+        {
+            (max, NumberSource::Env(vars::EZA_MAX_WIDTH))
+        } else {
+            return Ok(None);
+        };
+
+        match max.parse::<NonZeroUsize>() {
+            Ok(max) => Ok(Some(max.get())),
+            Err(e) => Err(OptionsError::FailedParse(max, source, e)),
+        }
+    }
+
+    fn deduce_uncapped<V: Vars>(
+        matches: &MatchedFlags<'_>,
+        vars: &V,
+    ) -> Result<Self, OptionsError> {
         if let Some(width) = matches.get(&flags::WIDTH)? {
             let arg_str = width.to_string_lossy();
-            match arg_str.parse() {
-                Ok(w) => {
-                    if w >= 1 {
-                        Ok(Self::Set(w))
-                    } else {
-                        Ok(Self::Automatic)
-                    }
-                }
-                Err(e) => {
-                    let source = NumberSource::Arg(&flags::WIDTH);
-                    Err(OptionsError::FailedParse(arg_str.to_string(), source, e))
-                }
+            if arg_str == "max" || arg_str == "auto" {
+                return Ok(Self::Automatic);
+            }
+
+            match arg_str.parse::<isize>() {
+                Ok(w) if w >= 1 => Ok(Self::Set(w.unsigned_abs())),
+                Ok(0) => Ok(Self::Automatic),
+                Ok(w) => Ok(Self::Offset(w)),
+                Err(_) => Err(OptionsError::BadWidth(arg_str.to_string())),
             }
         } else if let Some(columns) = vars.get(vars::COLUMNS).and_then(|s| s.into_string().ok()) {
             match columns.parse() {
@@ -207,9 +541,66 @@ impl TerminalWidth {
     }
 }
 
+/// A view picked with the `EZA_DEFAULT_VIEW` environment variable, for when
+/// none of the flags that pick a view are given.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum DefaultView {
+    Long,
+    Oneline,
+    Grid,
+    Tree,
+}
+
+impl DefaultView {
+    /// Determine the view to use from `EZA_DEFAULT_VIEW`, unless one of
+    /// `--long`, `--oneline`, `--grid`, or `--tree` was given, as anything
+    /// on the command-line takes precedence over it.
+    pub fn deduce<V: Vars>(
+        matches: &MatchedFlags<'_>,
+        vars: &V,
+    ) -> Result<Option<Self>, OptionsError> {
+        let given = matches.has_where_any(|f| {
+            f.matches(&flags::LONG)
+                || f.matches(&flags::ONE_LINE)
+                || f.matches(&flags::GRID)
+                || f.matches(&flags::TREE)
+        });
+
+        let Some(var) = vars.get(vars::EZA_DEFAULT_VIEW) else {
+            return Ok(None);
+        };
+        if given.is_some() {
+            return Ok(None);
+        }
+
+        match var.to_string_lossy().trim() {
+            "" => Ok(None),
+            "long" => Ok(Some(Self::Long)),
+            "oneline" => Ok(Some(Self::Oneline)),
+            "grid" => Ok(Some(Self::Grid)),
+            "tree" => Ok(Some(Self::Tree)),
+            other => Err(OptionsError::BadDefaultView(other.to_string())),
+        }
+    }
+}
+
 impl RowThreshold {
-    fn deduce<V: Vars>(vars: &V) -> Result<Self, OptionsError> {
-        if let Some(columns) = vars
+    /// Determine the minimum number of rows the grid-details view needs
+    /// before it gets used, from the `--grid-rows-threshold` flag, or the
+    /// `EZA_GRID_ROWS` environment variable if that isn’t given. Failing
+    /// both, the `EZA_GRID_COLS` variable sets a minimum number of columns
+    /// instead.
+    fn deduce<V: Vars>(matches: &MatchedFlags<'_>, vars: &V) -> Result<Self, OptionsError> {
+        if let Some(rows) = matches.get(&flags::GRID_ROWS)? {
+            let arg_str = rows.to_string_lossy();
+            match arg_str.parse() {
+                Ok(rows) => Ok(Self::MinimumRows(rows)),
+                Err(e) => {
+                    let source = NumberSource::Arg(&flags::GRID_ROWS);
+                    Err(OptionsError::FailedParse(arg_str.to_string(), source, e))
+                }
+            }
+        } else if let Some(columns) = vars
             .get_with_fallback(vars::EZA_GRID_ROWS, vars::EXA_GRID_ROWS)
             .and_then(|s| s.into_string().ok())
         {
@@ -223,6 +614,17 @@ impl RowThreshold {
                     Err(OptionsError::FailedParse(columns, source, e))
                 }
             }
+        } else if let Some(columns) = vars
+            .get(vars::EZA_GRID_COLS)
+            .and_then(|s| s.into_string().ok())
+        {
+            match columns.parse() {
+                Ok(columns) => Ok(Self::MinimumColumns(columns)),
+                Err(e) => {
+                    let source = NumberSource::Env(vars::EZA_GRID_COLS);
+                    Err(OptionsError::FailedParse(columns, source, e))
+                }
+            }
         } else {
             Ok(Self::AlwaysGrid)
         }
@@ -235,19 +637,58 @@ impl TableOptions {
         let size_format = SizeFormat::deduce(matches)?;
         let user_format = UserFormat::deduce(matches)?;
         let group_format = GroupFormat::deduce(matches)?;
-        let flags_format = FlagsFormat::deduce(vars);
+        let flags_format = FlagsFormat::deduce(matches, vars)?;
+        let inode_format = InodeFormat::deduce(matches)?;
+        let dir_size = DirSize::deduce(matches)?;
+        let min_links = FileFilter::deduce_min_links(matches)?;
         let columns = Columns::deduce(matches, vars)?;
+        let alignments = ColumnAlignments::deduce(matches)?;
         Ok(Self {
             size_format,
             time_format,
             user_format,
             group_format,
             flags_format,
+            inode_format,
+            dir_size,
+            min_links,
             columns,
+            alignments,
         })
     }
 }
 
+impl ColumnAlignments {
+    /// Determine which columns should have their alignment overridden, from
+    /// the `--align` flag’s comma-separated list of `COLUMN:ALIGNMENT`
+    /// pairs, such as `size:left,user:right`. Naming a column that doesn’t
+    /// exist, or an alignment other than `left` or `right`, is an error.
+    fn deduce(matches: &MatchedFlags<'_>) -> Result<Self, OptionsError> {
+        let Some(word) = matches.get(&flags::ALIGN)? else {
+            return Ok(Self::default());
+        };
+
+        let mut alignments = Vec::new();
+        for pair in word.to_string_lossy().split(',') {
+            let bad_argument = || OptionsError::BadArgument(&flags::ALIGN, pair.into());
+
+            let (column, alignment) = pair.split_once(':').ok_or_else(bad_argument)?;
+            let column = Column::names()
+                .find(|name| *name == column)
+                .ok_or_else(bad_argument)?;
+            let alignment = match alignment {
+                "left" => Alignment::Left,
+                "right" => Alignment::Right,
+                _ => return Err(bad_argument()),
+            };
+
+            alignments.push((column, alignment));
+        }
+
+        Ok(Self(alignments))
+    }
+}
+
 impl Columns {
     fn deduce<V: Vars>(matches: &MatchedFlags<'_>, vars: &V) -> Result<Self, OptionsError> {
         let time_types = TimeTypes::deduce(matches)?;
@@ -265,22 +706,34 @@ impl Columns {
             && !no_git_env;
 
         let blocksize = matches.has(&flags::BLOCKSIZE)?;
-        let group = matches.has(&flags::GROUP)?;
-        let inode = matches.has(&flags::INODE)?;
-        let links = matches.has(&flags::LINKS)?;
+        let io_blocksize = matches.has(&flags::IO_BLOCKSIZE)?;
+        let depth = matches.has(&flags::DEPTH)?;
+        let combined_owner = Self::deduce_combined_owner(matches)?;
+        let group = Self::toggled(matches, &flags::GROUP, &flags::NO_GROUP)?
+            || (combined_owner && !Self::given(matches, &flags::GROUP, &flags::NO_GROUP));
+        let inode = Self::toggled(matches, &flags::INODE, &flags::NO_INODE)?;
+        let links = Self::toggled(matches, &flags::LINKS, &flags::NO_LINKS)?;
         let octal = matches.has(&flags::OCTAL)?;
         let security_context = xattr::ENABLED && matches.has(&flags::SECURITY_CONTEXT)?;
-        let file_flags = matches.has(&flags::FILE_FLAGS)?;
+        let finder_tags =
+            xattr::ENABLED && cfg!(target_os = "macos") && matches.has(&flags::FINDER_TAGS)?;
+        let file_flags = Self::toggled(matches, &flags::FILE_FLAGS, &flags::NO_FLAGS)?;
+        let mime = matches.has(&flags::MIME)?;
+        let checksum = Self::deduce_checksum(matches)?;
 
         let permissions = !matches.has(&flags::NO_PERMISSIONS)?;
         let filesize = !matches.has(&flags::NO_FILESIZE)?;
         let user = !matches.has(&flags::NO_USER)?;
+        let git_left = Self::deduce_git_left(matches)?;
+        let order = ColumnKind::deduce(matches)?;
 
         Ok(Self {
             time_types,
+            depth,
             inode,
             links,
             blocksize,
+            io_blocksize,
             group,
             git,
             subdir_git_repos,
@@ -288,11 +741,109 @@ impl Columns {
             octal,
             security_context,
             file_flags,
+            finder_tags,
+            mime,
+            checksum,
             permissions,
             filesize,
             user,
+            combined_owner,
+            git_left,
+            order,
         })
     }
+
+    /// Whether a column that’s turned on by one flag and off by its
+    /// `--no-` negation should be shown. Whichever of the two is nearest
+    /// the end of the arguments wins, so an alias that turns a column on
+    /// can have it turned back off again.
+    fn toggled(
+        matches: &MatchedFlags<'_>,
+        on: &'static Arg,
+        off: &'static Arg,
+    ) -> Result<bool, OptionsError> {
+        // Each flag on its own still can’t be given twice in strict mode.
+        matches.has(on)?;
+        matches.has(off)?;
+
+        let last = matches.last_where(|f| f.matches(on) || f.matches(off));
+        Ok(matches!(last, Some(f) if f.matches(on)))
+    }
+
+    /// Whether either of a column’s toggling flags was given at all.
+    fn given(matches: &MatchedFlags<'_>, on: &'static Arg, off: &'static Arg) -> bool {
+        matches
+            .last_where(|f| f.matches(on) || f.matches(off))
+            .is_some()
+    }
+
+    /// Which digest to show for each file, if any, from the `--checksum`
+    /// flag. Reading every file is slow, so there’s no checksum column
+    /// unless it’s asked for.
+    fn deduce_checksum(matches: &MatchedFlags<'_>) -> Result<Option<Algorithm>, OptionsError> {
+        match matches.get(&flags::CHECKSUM)? {
+            None => Ok(None),
+            Some(word) if word == "crc32" => Ok(Some(Algorithm::Crc32)),
+            Some(word) if word == "md5" => Ok(Some(Algorithm::Md5)),
+            Some(word) if word == "sha256" => Ok(Some(Algorithm::Sha256)),
+            Some(word) => Err(OptionsError::BadArgument(&flags::CHECKSUM, word.into())),
+        }
+    }
+
+    /// Whether the user and group should share one `user:group` column,
+    /// from the `--owner-format` flag. Combining them shows the group even
+    /// without `--group`, unless `--no-group` turns it off.
+    fn deduce_combined_owner(matches: &MatchedFlags<'_>) -> Result<bool, OptionsError> {
+        match matches.get(&flags::OWNER_FORMAT)? {
+            None => Ok(false),
+            Some(word) if word == "separate" => Ok(false),
+            Some(word) if word == "combined" => Ok(true),
+            Some(word) => Err(OptionsError::BadArgument(&flags::OWNER_FORMAT, word.into())),
+        }
+    }
+
+    /// Whether the Git status column should be moved next to the file
+    /// name, from the `--git-col` flag, which does nothing without `--git`.
+    fn deduce_git_left(matches: &MatchedFlags<'_>) -> Result<bool, OptionsError> {
+        let left = match matches.get(&flags::GIT_COL)? {
+            None => return Ok(false),
+            Some(word) if word == "default" => false,
+            Some(word) if word == "left" => true,
+            Some(word) => return Err(OptionsError::BadArgument(&flags::GIT_COL, word.into())),
+        };
+
+        if matches.is_strict() && !matches.has(&flags::GIT)? {
+            return Err(OptionsError::Useless(&flags::GIT_COL, false, &flags::GIT));
+        }
+
+        Ok(left)
+    }
+}
+
+impl ColumnKind {
+    /// Determine which columns to show, and in which order, from the
+    /// `--columns` flag’s comma-separated list of column names, such as
+    /// `permissions,size,modified,name`. The file name always comes last,
+    /// so `name` is allowed in the list but doesn’t move it. Naming a
+    /// column that doesn’t exist is an error.
+    fn deduce(matches: &MatchedFlags<'_>) -> Result<Option<Vec<Self>>, OptionsError> {
+        let Some(word) = matches.get(&flags::COLUMNS)? else {
+            return Ok(None);
+        };
+
+        let mut kinds = Vec::new();
+        for name in word.to_string_lossy().split(',') {
+            if name == "name" {
+                continue;
+            }
+
+            let kind = Self::from_name(name)
+                .ok_or_else(|| OptionsError::BadArgument(&flags::COLUMNS, name.into()))?;
+            kinds.push(kind);
+        }
+
+        Ok(Some(kinds))
+    }
 }
 
 impl SizeFormat {
@@ -302,19 +853,60 @@ impl SizeFormat {
     /// The default mode is to use the decimal prefixes, as they are the
     /// most commonly-understood, and don’t involve trying to parse large
     /// strings of digits in your head. Changing the format to anything else
-    /// involves the `--binary` or `--bytes` flags, and these conflict with
-    /// each other.
+    /// involves the `--binary`, `--bytes`, or `--size-unit` flags, and these
+    /// conflict with each other: in strict mode, giving more than one is an
+    /// error, and otherwise the one nearest the end of the arguments wins.
     fn deduce(matches: &MatchedFlags<'_>) -> Result<Self, OptionsError> {
-        let flag = matches.has_where(|f| f.matches(&flags::BINARY) || f.matches(&flags::BYTES))?;
+        let binary = matches.has(&flags::BINARY)?;
+        let bytes = matches.has(&flags::BYTES)?;
+        let unit = match matches.get(&flags::SIZE_UNIT)? {
+            Some(word) => Some(SizeUnit::deduce(word)?),
+            None => None,
+        };
+
+        if matches.is_strict() {
+            if binary && bytes {
+                return Err(OptionsError::Conflict(&flags::BINARY, &flags::BYTES));
+            } else if binary && unit.is_some() {
+                return Err(OptionsError::Conflict(&flags::SIZE_UNIT, &flags::BINARY));
+            } else if bytes && unit.is_some() {
+                return Err(OptionsError::Conflict(&flags::SIZE_UNIT, &flags::BYTES));
+            }
+        }
 
-        Ok(match flag {
-            Some(f) if f.matches(&flags::BINARY) => Self::BinaryBytes,
-            Some(f) if f.matches(&flags::BYTES) => Self::JustBytes,
+        let flag = matches.last_where(|f| {
+            f.matches(&flags::BINARY) || f.matches(&flags::BYTES) || f.matches(&flags::SIZE_UNIT)
+        });
+
+        Ok(match (flag, unit) {
+            (Some(f), _) if f.matches(&flags::BINARY) => Self::BinaryBytes,
+            (Some(f), _) if f.matches(&flags::BYTES) => Self::JustBytes,
+            (Some(_), Some(unit)) => Self::FixedUnit(unit),
             _ => Self::DecimalBytes,
         })
     }
 }
 
+impl SizeUnit {
+    /// Determine which unit `--size-unit` asks for, where a single letter
+    /// is a decimal unit and adding an ‘i’ makes it a binary one.
+    fn deduce(word: &OsStr) -> Result<Self, OptionsError> {
+        let unit = match word.to_string_lossy().to_ascii_lowercase().as_str() {
+            "k" => Self::Kilo,
+            "m" => Self::Mega,
+            "g" => Self::Giga,
+            "t" => Self::Tera,
+            "ki" => Self::Kibi,
+            "mi" => Self::Mebi,
+            "gi" => Self::Gibi,
+            "ti" => Self::Tebi,
+            _ => return Err(OptionsError::BadArgument(&flags::SIZE_UNIT, word.into())),
+        };
+
+        Ok(unit)
+    }
+}
+
 impl TimeFormat {
     /// Determine how time should be formatted in timestamp columns.
     fn deduce<V: Vars>(matches: &MatchedFlags<'_>, vars: &V) -> Result<Self, OptionsError> {
@@ -323,16 +915,30 @@ impl TimeFormat {
         } else {
             match vars.get(vars::TIME_STYLE) {
                 Some(ref t) if !t.is_empty() => t.clone(),
-                _ => return Ok(Self::DefaultFormat),
+                _ => return Ok(Self::DefaultFormat { utc: false }),
             }
         };
 
-        match word.to_string_lossy().as_ref() {
-            "default" => Ok(Self::DefaultFormat),
-            "relative" => Ok(Self::Relative),
-            "iso" => Ok(Self::ISOFormat),
-            "long-iso" => Ok(Self::LongISO),
-            "full-iso" => Ok(Self::FullISO),
+        // Times are shown in local time, unless the style is `utc` on its
+        // own or has `,utc` on the end. Relative times don’t depend on the
+        // timezone, so they can’t have it.
+        let style = word.to_string_lossy().into_owned();
+        let (style, utc) = match style.as_str() {
+            "utc" => ("default", true),
+            style => match style.strip_suffix(",utc") {
+                Some(style) => (style, true),
+                None => (style, false),
+            },
+        };
+
+        match style {
+            "default" => Ok(Self::DefaultFormat { utc }),
+            "relative" if !utc => Ok(Self::Relative),
+            "relative-aligned" if !utc => Ok(Self::AlignedRelative),
+            "combined" if !utc => Ok(Self::Combined),
+            "iso" => Ok(Self::ISOFormat { utc }),
+            "long-iso" => Ok(Self::LongISO { utc }),
+            "full-iso" => Ok(Self::FullISO { utc }),
             fmt if fmt.starts_with('+') => {
                 let mut lines = fmt[1..].lines();
 
@@ -340,13 +946,9 @@ impl TimeFormat {
                 //   - there is nothing after `+`
                 // line 1 will be empty when:
                 //   - `+` is followed immediately by `\n`
-                let empty_non_recent_format_msg = "Custom timestamp format is empty, \
-                    please supply a chrono format string after the plus sign.";
-                let non_recent = lines.next().expect(empty_non_recent_format_msg);
-                let non_recent = if non_recent.is_empty() {
-                    panic!("{}", empty_non_recent_format_msg)
-                } else {
-                    non_recent.to_owned()
+                let non_recent = match lines.next() {
+                    Some(line) if !line.is_empty() => line.to_owned(),
+                    _ => return Err(OptionsError::EmptyTimeFormat { recent: false }),
                 };
 
                 // line 2 will be None when:
@@ -354,19 +956,28 @@ impl TimeFormat {
                 //   - there is nothing after the first `\n`
                 // line 2 will be empty when:
                 //   - there exist at least 2 `\n`, and no content between the 1st and 2nd `\n`
-                let empty_recent_format_msg = "Custom timestamp format for recent files is empty, \
-                    please supply a chrono format string at the second line.";
-                let recent = lines.next().map(|rec| {
-                    if rec.is_empty() {
-                        panic!("{}", empty_recent_format_msg)
-                    } else {
-                        rec.to_owned()
-                    }
-                });
+                let recent = match lines.next() {
+                    Some("") => return Err(OptionsError::EmptyTimeFormat { recent: true }),
+                    line => line.map(str::to_owned),
+                };
 
-                Ok(Self::Custom { non_recent, recent })
+                // With `--no-recent-style`, the first line is used for every
+                // file, however recent.
+                let recent = if matches.has(&flags::NO_RECENT_STYLE)? {
+                    None
+                } else {
+                    recent
+                };
+
+                let recent_threshold = deduce_recent_threshold(vars)?;
+                Ok(Self::Custom {
+                    non_recent,
+                    recent,
+                    recent_threshold,
+                    utc,
+                })
             }
-            _ => Err(OptionsError::BadArgument(&flags::TIME_STYLE, word)),
+            _ => Err(OptionsError::bad_argument(&flags::TIME_STYLE, word)),
         }
     }
 }
@@ -378,10 +989,82 @@ impl UserFormat {
     }
 }
 
+impl InodeFormat {
+    fn deduce(matches: &MatchedFlags<'_>) -> Result<Self, OptionsError> {
+        match matches.get(&flags::INODE_FORMAT)? {
+            None => Ok(Self::Decimal),
+            Some(word) if word == "dec" => Ok(Self::Decimal),
+            Some(word) if word == "hex" => Ok(Self::Hexadecimal),
+            Some(word) => Err(OptionsError::BadArgument(&flags::INODE_FORMAT, word.into())),
+        }
+    }
+}
+
+impl DirSize {
+    /// What to show for directories in the size column. Without
+    /// `--dir-size`, that’s their total size when `--total-size` is given,
+    /// and nothing otherwise.
+    fn deduce(matches: &MatchedFlags<'_>) -> Result<Self, OptionsError> {
+        let total_size = matches.has(&flags::TOTAL_SIZE)?;
+        let dir_size = match matches.get(&flags::DIR_SIZE)? {
+            None if total_size => return Ok(Self::Total),
+            None => return Ok(Self::None),
+            Some(word) if word == "none" => Self::None,
+            Some(word) if word == "inode" => Self::Inode,
+            Some(word) if word == "total" => Self::Total,
+            Some(word) => return Err(OptionsError::BadArgument(&flags::DIR_SIZE, word.into())),
+        };
+
+        if matches.is_strict() && total_size && dir_size != Self::Total {
+            return Err(OptionsError::Conflict(&flags::DIR_SIZE, &flags::TOTAL_SIZE));
+        }
+
+        Ok(dir_size)
+    }
+}
+
 impl GroupFormat {
+    /// Whether to show groups as names, as numbers with `--numeric`, or to
+    /// leave out the ones named after their user with `--smart-group`.
+    ///
+    /// `--smart-group` takes precedence when both are given. The groups that
+    /// are shown still appear as numbers, as `--numeric` also picks the
+    /// user format, which the group column follows.
     fn deduce(matches: &MatchedFlags<'_>) -> Result<Self, OptionsError> {
-        let flag = matches.has(&flags::SMART_GROUP)?;
-        Ok(if flag { Self::Smart } else { Self::Regular })
+        if matches.has(&flags::SMART_GROUP)? {
+            Ok(Self::Smart)
+        } else if matches.has(&flags::NUMERIC)? {
+            Ok(Self::Numeric)
+        } else {
+            Ok(Self::Regular)
+        }
+    }
+}
+
+impl FlagsFormat {
+    /// Determine how file flags should be displayed, from the
+    /// `--flags-format` argument, or the `EZA_WINDOWS_ATTRIBUTES`
+    /// environment variable if that isn’t given.
+    ///
+    /// An unknown value in the argument is an error, but one in the
+    /// environment variable is ignored, as it always has been.
+    fn deduce<V: Vars>(matches: &MatchedFlags<'_>, vars: &V) -> Result<Self, OptionsError> {
+        if let Some(word) = matches.get(&flags::FLAGS_FORMAT)? {
+            return match word.to_str() {
+                Some("short") => Ok(Self::Short),
+                Some("long") => Ok(Self::Long),
+                _ => Err(OptionsError::BadArgument(&flags::FLAGS_FORMAT, word.into())),
+            };
+        }
+
+        let format = vars.get(vars::EZA_WINDOWS_ATTRIBUTES).and_then(|v| {
+            match v.to_ascii_lowercase().to_str() {
+                Some("short") => Some(Self::Short),
+                Some("long") => Some(Self::Long),
+                _ => None,
+            }
+        });
+        Ok(format.unwrap_or_default())
     }
 }
 
@@ -394,14 +1077,17 @@ impl TimeTypes {
     /// `--time=modified`). An error is signaled if both ways are used.
     ///
     /// It’s valid to show more than one column by passing in more than one
-    /// option, but passing *no* options means that the user just wants to
-    /// see the default set.
+    /// flag, or a comma-separated list of fields such as
+    /// `--time=modified,created`, or `--time=all` or `--all-times` for every
+    /// one of them, but passing *no* options means that the user just wants
+    /// to see the default set.
     fn deduce(matches: &MatchedFlags<'_>) -> Result<Self, OptionsError> {
         let possible_word = matches.get(&flags::TIME)?;
         let modified = matches.has(&flags::MODIFIED)?;
         let changed = matches.has(&flags::CHANGED)?;
         let accessed = matches.has(&flags::ACCESSED)?;
         let created = matches.has(&flags::CREATED)?;
+        let all_times = matches.has(&flags::ALL_TIMES)?;
 
         let no_time = matches.has(&flags::NO_TIME)?;
 
@@ -422,17 +1108,32 @@ impl TimeTypes {
                 return Err(OptionsError::Useless(&flags::ACCESSED, true, &flags::TIME));
             } else if created {
                 return Err(OptionsError::Useless(&flags::CREATED, true, &flags::TIME));
-            } else if word == "mod" || word == "modified" {
-                Self { modified: true,  changed: false, accessed: false, created: false }
-            } else if word == "ch" || word == "changed" {
-                Self { modified: false, changed: true,  accessed: false, created: false }
-            } else if word == "acc" || word == "accessed" {
-                Self { modified: false, changed: false, accessed: true,  created: false }
-            } else if word == "cr" || word == "created" {
-                Self { modified: false, changed: false, accessed: false, created: true  }
+            } else if all_times {
+                return Err(OptionsError::Useless(&flags::ALL_TIMES, true, &flags::TIME));
             } else {
-                return Err(OptionsError::BadArgument(&flags::TIME, word.into()));
+                let mut time_types = Self { modified: false, changed: false, accessed: false, created: false };
+                let list = word.to_string_lossy();
+
+                for token in list.split(',') {
+                    // Be lenient about case and surrounding whitespace, which
+                    // can sneak in through quoted aliases. An empty token,
+                    // such as from a trailing comma, is reported along with
+                    // the rest of the list, as it has no name of its own.
+                    match token.trim().to_lowercase().as_str() {
+                        "mod" | "modified" => time_types.modified = true,
+                        "ch" | "changed"   => time_types.changed  = true,
+                        "acc" | "accessed" => time_types.accessed = true,
+                        "cr" | "created"   => time_types.created  = true,
+                        "all"              => time_types = Self { modified: true, changed: true, accessed: true, created: true },
+                        ""                 => return Err(OptionsError::BadArgument(&flags::TIME, word.into())),
+                        _                  => return Err(OptionsError::bad_argument(&flags::TIME, token)),
+                    }
+                }
+
+                time_types
             }
+        } else if all_times {
+            Self { modified: true, changed: true, accessed: true, created: true }
         } else if modified || changed || accessed || created {
             Self {
                 modified,
@@ -448,16 +1149,84 @@ impl TimeTypes {
     }
 }
 
+/// A luminance from the first of the given environment variables that’s
+/// set. A value that isn’t a number in the range falls back to the default,
+/// unless being strict about it.
+fn deduce_luminance<V: Vars>(
+    matches: &MatchedFlags<'_>,
+    vars: &V,
+    (eza, exa): (&'static str, &'static str),
+    range: RangeInclusive<isize>,
+    default: isize,
+) -> Result<isize, OptionsError> {
+    let Some(var) = vars.get_with_fallback(eza, exa) else {
+        return Ok(default);
+    };
+    let var = var.to_string_lossy().into_owned();
+    let source = || NumberSource::Env(vars.source(eza, exa).unwrap());
+
+    match var.parse() {
+        Ok(luminance) if range.contains(&luminance) => Ok(luminance),
+        Ok(_) if matches.is_strict() => Err(OptionsError::OutOfRange(var, source(), range)),
+        Err(e) if matches.is_strict() => Err(OptionsError::FailedParse(var, source(), e)),
+        _ => Ok(default),
+    }
+}
+
+/// How new a file has to be to count as recent, from `EZA_RECENT_DURATION`,
+/// or from its older name `EZA_RECENT_THRESHOLD` when that isn’t set. Both
+/// are read the same way, so a duration that can’t be parsed is an error
+/// whichever one it came from.
+fn deduce_recent_threshold<V: Vars>(vars: &V) -> Result<Option<Duration>, OptionsError> {
+    for name in [vars::EZA_RECENT_DURATION, vars::EZA_RECENT_THRESHOLD] {
+        if let Some(var) = vars.get(name).filter(|var| !var.is_empty()) {
+            let var = var.to_string_lossy();
+            return match parse_duration(&var) {
+                Some(duration) => Ok(Some(duration)),
+                None => Err(OptionsError::BadRecentDuration(name, var.into_owned())),
+            };
+        }
+    }
+
+    Ok(None)
+}
+
+/// Parses a duration written as a number followed by one of the units `s`,
+/// `m`, `h`, `d`, or `w`, such as `7d`.
+fn parse_duration(var: &str) -> Option<Duration> {
+    let var = var.trim();
+
+    let unit = match var.chars().last()? {
+        's' => 1,
+        'm' => 60,
+        'h' => 60 * 60,
+        'd' => 24 * 60 * 60,
+        'w' => 7 * 24 * 60 * 60,
+        _ => return None,
+    };
+    let count: u64 = var[..var.len() - 1].parse().ok()?;
+    Some(Duration::from_secs(count.checked_mul(unit)?))
+}
+
 impl ColorScaleOptions {
     pub fn deduce<V: Vars>(matches: &MatchedFlags<'_>, vars: &V) -> Result<Self, OptionsError> {
-        let min_luminance =
-            match vars.get_with_fallback(vars::EZA_MIN_LUMINANCE, vars::EXA_MIN_LUMINANCE) {
-                Some(var) => match var.to_string_lossy().parse() {
-                    Ok(luminance) if (-100..=100).contains(&luminance) => luminance,
-                    _ => 40,
-                },
-                None => 40,
-            };
+        let min_luminance = deduce_luminance(
+            matches,
+            vars,
+            (vars::EZA_MIN_LUMINANCE, vars::EXA_MIN_LUMINANCE),
+            -100..=100,
+            40,
+        )?;
+
+        // The maximum can’t be below the minimum, so a range that’s the
+        // wrong way round is treated like any other unusable value.
+        let max_luminance = deduce_luminance(
+            matches,
+            vars,
+            (vars::EZA_MAX_LUMINANCE, vars::EXA_MAX_LUMINANCE),
+            min_luminance..=100,
+            100,
+        )?;
 
         let mode = if let Some(w) = matches
             .get(&flags::COLOR_SCALE_MODE)?
@@ -466,7 +1235,8 @@ impl ColorScaleOptions {
             match w.to_str() {
                 Some("fixed") => ColorScaleMode::Fixed,
                 Some("gradient") => ColorScaleMode::Gradient,
-                _ => Err(OptionsError::BadArgument(
+                Some("logarithmic") => ColorScaleMode::Logarithmic,
+                _ => Err(OptionsError::bad_argument(
                     &flags::COLOR_SCALE_MODE,
                     w.to_os_string(),
                 ))?,
@@ -475,11 +1245,33 @@ impl ColorScaleOptions {
             ColorScaleMode::Gradient
         };
 
+        // An unusable gradient falls back to the usual colours, unless being
+        // strict about it.
+        let gradient = match vars.get(vars::EZA_COLOR_SCALE_GRADIENT) {
+            Some(var) => {
+                let var = var.to_string_lossy();
+                match GradientStops::parse(&var) {
+                    Some(gradient) => Some(gradient),
+                    None if matches.is_strict() => {
+                        return Err(OptionsError::BadColorScaleGradient(var.into_owned()))
+                    }
+                    None => None,
+                }
+            }
+            None => None,
+        };
+
         let mut options = ColorScaleOptions {
             mode,
             min_luminance,
+            max_luminance,
             size: false,
             age: false,
+            inode: false,
+            links: false,
+            age_time: None,
+            gradient,
+            recent_threshold: deduce_recent_threshold(vars)?,
         };
 
         let words = if let Some(w) = matches
@@ -491,21 +1283,47 @@ impl ColorScaleOptions {
             return Ok(options);
         };
 
+        // Be lenient about case and whitespace around each field, and skip
+        // empty fields left behind by stray commas.
+        let mut none = false;
         for word in words.to_string_lossy().split(',') {
-            match word {
+            match word.trim().to_lowercase().as_str() {
+                "" => {}
+                "none" => none = true,
                 "all" => {
                     options.size = true;
                     options.age = true;
+                    options.inode = true;
+                    options.links = true;
                 }
                 "age" => options.age = true,
+                // The age scale can go by one time in every column.
+                time if time.starts_with("age:") => {
+                    options.age = true;
+                    options.age_time = Some(match &time[4..] {
+                        "mtime" => TimeType::Modified,
+                        "atime" => TimeType::Accessed,
+                        "ctime" => TimeType::Changed,
+                        _ => Err(OptionsError::bad_argument(&flags::COLOR_SCALE, word))?,
+                    });
+                }
                 "size" => options.size = true,
-                _ => Err(OptionsError::BadArgument(
-                    &flags::COLOR_SCALE,
-                    OsString::from(word),
-                ))?,
+                "inode" => options.inode = true,
+                "links" => options.links = true,
+                _ => Err(OptionsError::bad_argument(&flags::COLOR_SCALE, word))?,
             };
         }
 
+        // `none` turns scaling off wherever it is in the list, so it can
+        // undo a scale that was set in an alias.
+        if none {
+            options.size = false;
+            options.age = false;
+            options.age_time = None;
+            options.inode = false;
+            options.links = false;
+        }
+
         Ok(options)
     }
 }
@@ -515,33 +1333,87 @@ mod test {
     use super::*;
     use crate::options::flags;
     use crate::options::parser::{Arg, Flag};
+    use crate::output::table::{Column, TimeType};
+    use crate::output::template::TemplateError;
     use std::ffi::OsString;
 
     use crate::options::test::parse_for_test;
     use crate::options::test::Strictnesses::*;
 
+    /// Environment variables with only the given ones set.
+    struct Env(&'static [(&'static str, &'static str)]);
+
+    impl Vars for Env {
+        fn get(&self, name: &'static str) -> Option<OsString> {
+            self.0
+                .iter()
+                .find(|(n, _)| *n == name)
+                .map(|(_, value)| OsString::from(value))
+        }
+    }
+
     static TEST_ARGS: &[&Arg] = &[
         &flags::BINARY,
         &flags::BYTES,
+        &flags::SIZE_UNIT,
         &flags::TIME_STYLE,
+        &flags::NO_RECENT_STYLE,
+        &flags::FORMAT_TEMPLATE,
+        &flags::CSV,
+        &flags::CSV_DELIMITER,
         &flags::TIME,
         &flags::MODIFIED,
         &flags::CHANGED,
+        &flags::ALL_TIMES,
+        &flags::NO_TIME,
         &flags::CREATED,
         &flags::ACCESSED,
         &flags::HEADER,
         &flags::GROUP,
         &flags::INODE,
+        &flags::INODE_FORMAT,
+        &flags::TOTAL_SIZE,
+        &flags::DIR_SIZE,
         &flags::GIT,
         &flags::LINKS,
         &flags::BLOCKSIZE,
+        &flags::IO_BLOCKSIZE,
         &flags::LONG,
         &flags::LEVEL,
         &flags::GRID,
         &flags::ACROSS,
         &flags::ONE_LINE,
         &flags::TREE,
+        &flags::TREE_FORMAT,
         &flags::NUMERIC,
+        &flags::SMART_GROUP,
+        &flags::OWNER_FORMAT,
+        &flags::COLOR_SCALE,
+        &flags::COLOUR_SCALE,
+        &flags::COLOR_SCALE_MODE,
+        &flags::COLOUR_SCALE_MODE,
+        &flags::WIDTH,
+        &flags::MIN_WIDTH,
+        &flags::MAX_WIDTH,
+        &flags::GRID_ROWS,
+        &flags::ALIGN,
+        &flags::NO_GROUP,
+        &flags::NO_USER,
+        &flags::NO_INODE,
+        &flags::NO_LINKS,
+        &flags::FILE_FLAGS,
+        &flags::NO_FLAGS,
+        &flags::FLAGS_FORMAT,
+        &flags::DEPTH,
+        &flags::MIME,
+        &flags::CHECKSUM,
+        &flags::NAME_WIDTH,
+        &flags::ELLIPSIS,
+        &flags::MAX_OUTPUT,
+        &flags::GIT_SUMMARY,
+        &flags::GIT_COL,
+        &flags::SORT,
+        &flags::COLUMNS,
     ];
 
     #[allow(unused_macro_rules)]
@@ -635,9 +1507,314 @@ mod test {
         test!(both_4:  SizeFormat <- ["--bytes",  "--bytes"];   Last => Ok(SizeFormat::JustBytes));
 
         test!(both_5:  SizeFormat <- ["--binary", "--binary"];  Complain => err OptionsError::Duplicate(Flag::Long("binary"), Flag::Long("binary")));
-        test!(both_6:  SizeFormat <- ["--bytes",  "--binary"];  Complain => err OptionsError::Duplicate(Flag::Long("bytes"),  Flag::Long("binary")));
-        test!(both_7:  SizeFormat <- ["--binary", "--bytes"];   Complain => err OptionsError::Duplicate(Flag::Long("binary"), Flag::Long("bytes")));
+        test!(both_6:  SizeFormat <- ["--bytes",  "--binary"];  Complain => err OptionsError::Conflict(&flags::BINARY, &flags::BYTES));
+        test!(both_7:  SizeFormat <- ["--binary", "--bytes"];   Complain => err OptionsError::Conflict(&flags::BINARY, &flags::BYTES));
         test!(both_8:  SizeFormat <- ["--bytes",  "--bytes"];   Complain => err OptionsError::Duplicate(Flag::Long("bytes"),  Flag::Long("bytes")));
+
+        // Short flags
+        test!(short_1: SizeFormat <- ["-bB"];                   Last => Ok(SizeFormat::JustBytes));
+        test!(short_2: SizeFormat <- ["-Bb"];                   Last => Ok(SizeFormat::BinaryBytes));
+        test!(short_3: SizeFormat <- ["-bB"];                   Complain => err OptionsError::Conflict(&flags::BINARY, &flags::BYTES));
+        test!(short_4: SizeFormat <- ["-B", "--binary"];        Complain => err OptionsError::Conflict(&flags::BINARY, &flags::BYTES));
+
+        // Fixed units
+        test!(unit_k:  SizeFormat <- ["--size-unit=k"];         Both => Ok(SizeFormat::FixedUnit(SizeUnit::Kilo)));
+        test!(unit_m:  SizeFormat <- ["--size-unit=m"];         Both => Ok(SizeFormat::FixedUnit(SizeUnit::Mega)));
+        test!(unit_g:  SizeFormat <- ["--size-unit=g"];         Both => Ok(SizeFormat::FixedUnit(SizeUnit::Giga)));
+        test!(unit_t:  SizeFormat <- ["--size-unit=t"];         Both => Ok(SizeFormat::FixedUnit(SizeUnit::Tera)));
+        test!(unit_ki: SizeFormat <- ["--size-unit=ki"];        Both => Ok(SizeFormat::FixedUnit(SizeUnit::Kibi)));
+        test!(unit_mi: SizeFormat <- ["--size-unit", "mi"];     Both => Ok(SizeFormat::FixedUnit(SizeUnit::Mebi)));
+        test!(unit_gi: SizeFormat <- ["--size-unit=gi"];        Both => Ok(SizeFormat::FixedUnit(SizeUnit::Gibi)));
+        test!(unit_ti: SizeFormat <- ["--size-unit=ti"];        Both => Ok(SizeFormat::FixedUnit(SizeUnit::Tebi)));
+        test!(unit_up: SizeFormat <- ["--size-unit=Mi"];        Both => Ok(SizeFormat::FixedUnit(SizeUnit::Mebi)));
+        test!(unit_bad: SizeFormat <- ["--size-unit=kb"];       Both => err OptionsError::BadArgument(&flags::SIZE_UNIT, OsString::from("kb")));
+
+        // Fixed units against the other formats
+        test!(unit_1:  SizeFormat <- ["--binary", "--size-unit=k"];  Last => Ok(SizeFormat::FixedUnit(SizeUnit::Kilo)));
+        test!(unit_2:  SizeFormat <- ["--size-unit=k", "--binary"];  Last => Ok(SizeFormat::BinaryBytes));
+        test!(unit_3:  SizeFormat <- ["--size-unit=k", "-B"];        Last => Ok(SizeFormat::JustBytes));
+        test!(unit_4:  SizeFormat <- ["--size-unit=k", "--size-unit=gi"]; Last => Ok(SizeFormat::FixedUnit(SizeUnit::Gibi)));
+        test!(unit_5:  SizeFormat <- ["--binary", "--size-unit=k"];  Complain => err OptionsError::Conflict(&flags::SIZE_UNIT, &flags::BINARY));
+        test!(unit_6:  SizeFormat <- ["--size-unit=k", "--bytes"];   Complain => err OptionsError::Conflict(&flags::SIZE_UNIT, &flags::BYTES));
+        test!(unit_7:  SizeFormat <- ["--size-unit=k", "--size-unit=m"]; Complain => err OptionsError::Duplicate(Flag::Long("size-unit"), Flag::Long("size-unit")));
+    }
+
+    mod user_formats {
+        use super::*;
+
+        test!(empty:    UserFormat <- [];                            Both => Ok(UserFormat::Name));
+        test!(numeric:  UserFormat <- ["--numeric"];                 Both => Ok(UserFormat::Numeric));
+        test!(n:        UserFormat <- ["-n"];                        Both => Ok(UserFormat::Numeric));
+        test!(smart:    UserFormat <- ["--numeric", "--smart-group"]; Both => Ok(UserFormat::Numeric));
+    }
+
+    mod group_formats {
+        use super::*;
+
+        test!(empty:    GroupFormat <- [];                            Both => Ok(GroupFormat::Regular));
+        test!(numeric:  GroupFormat <- ["--numeric"];                 Both => Ok(GroupFormat::Numeric));
+        test!(n:        GroupFormat <- ["-n"];                        Both => Ok(GroupFormat::Numeric));
+        test!(smart:    GroupFormat <- ["--smart-group"];             Both => Ok(GroupFormat::Smart));
+
+        // --smart-group wins, whichever way round they’re given
+        test!(smart_numeric:  GroupFormat <- ["--smart-group", "--numeric"];  Both => Ok(GroupFormat::Smart));
+        test!(numeric_smart:  GroupFormat <- ["-n", "--smart-group"];         Both => Ok(GroupFormat::Smart));
+    }
+
+    mod inode_formats {
+        use super::*;
+
+        test!(empty:     InodeFormat <- [];                              Both => Ok(InodeFormat::Decimal));
+        test!(plain:     InodeFormat <- ["--inode"];                     Both => Ok(InodeFormat::Decimal));
+        test!(dec:       InodeFormat <- ["--inode-format=dec"];          Both => Ok(InodeFormat::Decimal));
+        test!(hex:       InodeFormat <- ["--inode-format=hex"];          Both => Ok(InodeFormat::Hexadecimal));
+        test!(hex_next:  InodeFormat <- ["--inode-format", "hex"];       Both => Ok(InodeFormat::Hexadecimal));
+
+        test!(twice:     InodeFormat <- ["--inode-format=dec", "--inode-format=hex"];  Last => Ok(InodeFormat::Hexadecimal));
+        test!(twice_2:   InodeFormat <- ["--inode-format=dec", "--inode-format=hex"];  Complain => err OptionsError::Duplicate(Flag::Long("inode-format"), Flag::Long("inode-format")));
+
+        test!(octal:     InodeFormat <- ["--inode-format=oct"];          Both => err OptionsError::BadArgument(&flags::INODE_FORMAT, OsString::from("oct")));
+    }
+
+    mod dir_sizes {
+        use super::*;
+
+        test!(empty:       DirSize <- [];                                    Both => Ok(DirSize::None));
+        test!(total_size:  DirSize <- ["--total-size"];                      Both => Ok(DirSize::Total));
+        test!(none:        DirSize <- ["--dir-size=none"];                   Both => Ok(DirSize::None));
+        test!(inode:       DirSize <- ["--dir-size", "inode"];               Both => Ok(DirSize::Inode));
+        test!(total:       DirSize <- ["--dir-size=total"];                  Both => Ok(DirSize::Total));
+        test!(both:        DirSize <- ["--dir-size=total", "--total-size"];  Both => Ok(DirSize::Total));
+
+        test!(conflict:    DirSize <- ["--dir-size=inode", "--total-size"];  Last => Ok(DirSize::Inode));
+        test!(conflict_2:  DirSize <- ["--dir-size=inode", "--total-size"];  Complain => err OptionsError::Conflict(&flags::DIR_SIZE, &flags::TOTAL_SIZE));
+
+        test!(bad:         DirSize <- ["--dir-size=blocks"];                 Both => err OptionsError::BadArgument(&flags::DIR_SIZE, OsString::from("blocks")));
+    }
+
+    mod columns {
+        use super::*;
+
+        // Positive flags
+        test!(group:       Columns <- ["--group"], None;                Both => like Ok(Columns { group: true, .. }));
+        test!(inode:       Columns <- ["--inode"], None;                Both => like Ok(Columns { inode: true, .. }));
+        test!(links:       Columns <- ["--links"], None;                Both => like Ok(Columns { links: true, .. }));
+        test!(file_flags:  Columns <- ["--flags"], None;                Both => like Ok(Columns { file_flags: true, .. }));
+
+        // Negations override the positive flags before them
+        test!(no_group:    Columns <- ["-g", "--no-group"], None;       Both => like Ok(Columns { group: false, .. }));
+        test!(no_inode:    Columns <- ["-i", "--no-inode"], None;       Both => like Ok(Columns { inode: false, .. }));
+        test!(no_links:    Columns <- ["-H", "--no-links"], None;       Both => like Ok(Columns { links: false, .. }));
+        test!(no_flags:    Columns <- ["-O", "--no-flags"], None;       Both => like Ok(Columns { file_flags: false, .. }));
+
+        // ...but not the ones after them
+        test!(group_again: Columns <- ["--no-group", "-g"], None;       Both => like Ok(Columns { group: true, .. }));
+        test!(inode_again: Columns <- ["--no-inode", "--inode"], None;  Both => like Ok(Columns { inode: true, .. }));
+
+        // Repeats are still caught in strict mode
+        test!(no_group_2:  Columns <- ["--no-group", "--no-group"], None; Complain => like Err(OptionsError::Duplicate(..)));
+
+        // User and group in one column
+        test!(separate:    Columns <- [], None;                         Both => like Ok(Columns { combined_owner: false, .. }));
+        test!(combined:    Columns <- ["--owner-format=combined"], None; Both => like Ok(Columns { combined_owner: true, .. }));
+        test!(separate_2:  Columns <- ["--owner-format", "separate"], None; Both => like Ok(Columns { combined_owner: false, group: false, .. }));
+        test!(owner_group: Columns <- ["--owner-format=combined"], None; Both => like Ok(Columns { group: true, user: true, .. }));
+        test!(owner_user:  Columns <- ["--owner-format=combined", "--no-group"], None; Both => like Ok(Columns { combined_owner: true, group: false, .. }));
+        test!(owner_grp:   Columns <- ["--owner-format=combined", "--no-user"], None; Both => like Ok(Columns { combined_owner: true, user: false, group: true, .. }));
+        test!(owner_what:  Columns <- ["--owner-format=both"], None;    Both => err OptionsError::BadArgument(&flags::OWNER_FORMAT, OsString::from("both")));
+
+        // Depth below the arguments
+        test!(no_depth:    Columns <- [], None;                         Both => like Ok(Columns { depth: false, .. }));
+        test!(depth:       Columns <- ["--depth"], None;                Both => like Ok(Columns { depth: true, .. }));
+
+        // MIME types
+        test!(no_mime:     Columns <- [], None;                         Both => like Ok(Columns { mime: false, .. }));
+        test!(mime:        Columns <- ["--mime"], None;                 Both => like Ok(Columns { mime: true, .. }));
+
+        // Checksums
+        test!(no_checksum: Columns <- [], None;                         Both => like Ok(Columns { checksum: None, .. }));
+        test!(crc32:       Columns <- ["--checksum=crc32"], None;       Both => like Ok(Columns { checksum: Some(Algorithm::Crc32), .. }));
+        test!(sha256:      Columns <- ["--checksum", "sha256"], None;   Both => like Ok(Columns { checksum: Some(Algorithm::Sha256), .. }));
+        test!(checksum_what: Columns <- ["--checksum=sha1"], None;      Both => err OptionsError::BadArgument(&flags::CHECKSUM, OsString::from("sha1")));
+
+        // Where the Git column goes
+        test!(git_default: Columns <- ["--git"], None;                  Both => like Ok(Columns { git_left: false, .. }));
+        test!(git_left:    Columns <- ["--git", "--git-col=left"], None; Both => like Ok(Columns { git_left: true, .. }));
+        test!(git_again:   Columns <- ["--git", "--git-col", "default"], None; Both => like Ok(Columns { git_left: false, .. }));
+        test!(git_what:    Columns <- ["--git", "--git-col=right"], None; Both => err OptionsError::BadArgument(&flags::GIT_COL, OsString::from("right")));
+        test!(git_col_alone: Columns <- ["--git-col=left"], None;       Last => like Ok(Columns { git_left: true, .. }));
+        test!(git_col_alone_2: Columns <- ["--git-col=left"], None;     Complain => err OptionsError::Useless(&flags::GIT_COL, false, &flags::GIT));
+
+        // An exact list of columns
+        test!(no_order:    Columns <- [], None;                         Both => like Ok(Columns { order: None, .. }));
+        test!(order:       Columns <- ["--columns=size"], None;         Both => like Ok(Columns { order: Some(_), .. }));
+
+        /// The names of the columns that would be shown for the given
+        /// arguments and environment, with Git support available.
+        fn collected(inputs: &[&str], vars: Env) -> Vec<&'static str> {
+            parse_for_test(inputs, TEST_ARGS, Last, |mf| Columns::deduce(mf, &vars))
+                .remove(0)
+                .unwrap()
+                .collect(true, true)
+                .into_iter()
+                .map(Column::name)
+                .collect()
+        }
+
+        #[test]
+        fn ordered() {
+            assert_eq!(
+                collected(&["--columns=permissions,size,modified,name"], Env(&[])),
+                vec!["permissions", "size", "modified"]
+            );
+            assert_eq!(
+                collected(&["--columns", "modified,permissions", "--inode"], Env(&[])),
+                vec!["modified", "permissions"]
+            );
+        }
+
+        #[test]
+        fn git_left() {
+            assert_eq!(
+                collected(&["--git", "--git-repos"], Env(&[])),
+                vec!["permissions", "size", "user", "modified", "git", "repo"]
+            );
+            assert_eq!(
+                collected(&["--git", "--git-repos", "--git-col=left"], Env(&[])),
+                vec!["permissions", "size", "user", "modified", "repo", "git"]
+            );
+            assert_eq!(
+                collected(&["--columns=git,size", "--git", "--git-col=left"], Env(&[])),
+                vec!["size", "git"]
+            );
+        }
+
+        #[test]
+        fn ordered_git() {
+            assert_eq!(collected(&["--columns=git,size"], Env(&[])), vec!["size"]);
+            assert_eq!(
+                collected(&["--columns=git,size", "--git"], Env(&[])),
+                vec!["git", "size"]
+            );
+            assert_eq!(
+                collected(
+                    &["--columns=git,size", "--git"],
+                    Env(&[("EZA_OVERRIDE_GIT", "1")])
+                ),
+                vec!["size"]
+            );
+        }
+    }
+
+    mod column_kinds {
+        use super::*;
+
+        test!(none:     ColumnKind <- [];                                   Both => Ok(None));
+        test!(one:      ColumnKind <- ["--columns=size"];                   Both => Ok(Some(vec![ColumnKind::Size])));
+        test!(order:    ColumnKind <- ["--columns", "modified,size,owner"]; Both => Ok(Some(vec![ColumnKind::Timestamp(TimeType::Modified), ColumnKind::Size, ColumnKind::Owner])));
+        test!(name:     ColumnKind <- ["--columns=size,name"];              Both => Ok(Some(vec![ColumnKind::Size])));
+        test!(last:     ColumnKind <- ["--columns=size", "--columns=user"]; Last => Ok(Some(vec![ColumnKind::User])));
+
+        test!(unknown:  ColumnKind <- ["--columns=size,colour"];            Both => err OptionsError::BadArgument(&flags::COLUMNS, OsString::from("colour")));
+        test!(empty:    ColumnKind <- ["--columns="];                       Both => err OptionsError::BadArgument(&flags::COLUMNS, OsString::from("")));
+        test!(times:    ColumnKind <- ["--columns=times"];                  Both => err OptionsError::BadArgument(&flags::COLUMNS, OsString::from("times")));
+    }
+
+    mod column_alignments {
+        use super::*;
+
+        test!(empty:    ColumnAlignments <- [];                                Both => Ok(ColumnAlignments::default()));
+        test!(size:     ColumnAlignments <- ["--align=size:left"];             Both => Ok(ColumnAlignments(vec![("size", Alignment::Left)])));
+        test!(two:      ColumnAlignments <- ["--align", "size:left,user:right"]; Both => Ok(ColumnAlignments(vec![("size", Alignment::Left), ("user", Alignment::Right)])));
+        test!(last:     ColumnAlignments <- ["--align=size:left", "--align=size:right"]; Last => Ok(ColumnAlignments(vec![("size", Alignment::Right)])));
+        test!(times:    ColumnAlignments <- ["--align=times:right,checksum:right"]; Both => Ok(ColumnAlignments(vec![("times", Alignment::Right), ("checksum", Alignment::Right)])));
+
+        test!(unknown:  ColumnAlignments <- ["--align=colour:left"];           Both => err OptionsError::BadArgument(&flags::ALIGN, OsString::from("colour:left")));
+        test!(centre:   ColumnAlignments <- ["--align=size:centre"];           Both => err OptionsError::BadArgument(&flags::ALIGN, OsString::from("size:centre")));
+        test!(no_colon: ColumnAlignments <- ["--align=size"];                  Both => err OptionsError::BadArgument(&flags::ALIGN, OsString::from("size")));
+    }
+
+    mod flags_formats {
+        use super::*;
+        use crate::options::vars::MockVars;
+
+        fn env(value: &str) -> MockVars {
+            let mut vars = MockVars::default();
+            vars.set(vars::EZA_WINDOWS_ATTRIBUTES, &OsString::from(value));
+            vars
+        }
+
+        test!(empty:     FlagsFormat <- [], env("");                             Both => like Ok(FlagsFormat::Long));
+        test!(short:     FlagsFormat <- ["--flags-format=short"], env("");       Both => like Ok(FlagsFormat::Short));
+        test!(long:      FlagsFormat <- ["--flags-format", "long"], env("");     Both => like Ok(FlagsFormat::Long));
+
+        // The argument takes precedence over the environment variable.
+        test!(use_env:   FlagsFormat <- [], env("SHORT");                        Both => like Ok(FlagsFormat::Short));
+        test!(arg_wins:  FlagsFormat <- ["--flags-format=long"], env("short");   Both => like Ok(FlagsFormat::Long));
+        test!(bad_env:   FlagsFormat <- [], env("tiny");                         Both => like Ok(FlagsFormat::Long));
+
+        // Overriding
+        test!(overridden:   FlagsFormat <- ["--flags-format=long", "--flags-format=short"], env("");  Last => like Ok(FlagsFormat::Short));
+        test!(overridden_2: FlagsFormat <- ["--flags-format=long", "--flags-format=short"], env("");  Complain => err OptionsError::Duplicate(Flag::Long("flags-format"), Flag::Long("flags-format")));
+
+        // Errors
+        test!(bad_arg:   FlagsFormat <- ["--flags-format=tiny"], env("short");   Both => err OptionsError::BadArgument(&flags::FLAGS_FORMAT, OsString::from("tiny")));
+    }
+
+    mod ellipses {
+        use super::*;
+
+        fn ellipsis(inputs: &[&str], vars: &Env) -> Vec<Result<String, OptionsError>> {
+            parse_for_test(inputs, TEST_ARGS, Both, |mf| {
+                details::Options::deduce_long(mf, vars).map(|details| details.ellipsis)
+            })
+        }
+
+        #[test]
+        fn default() {
+            for result in ellipsis(&["--name-width=8"], &Env(&[])) {
+                assert_eq!(result, Ok(String::from("…")));
+            }
+        }
+
+        #[test]
+        fn flag() {
+            for result in ellipsis(&["--name-width=8", "--ellipsis=..."], &Env(&[])) {
+                assert_eq!(result, Ok(String::from("...")));
+            }
+        }
+
+        #[test]
+        fn env() {
+            let vars = Env(&[("EZA_ELLIPSIS", "~")]);
+            for result in ellipsis(&["--name-width=8"], &vars) {
+                assert_eq!(result, Ok(String::from("~")));
+            }
+        }
+
+        #[test]
+        fn flag_wins() {
+            let vars = Env(&[("EZA_ELLIPSIS", "~")]);
+            for result in ellipsis(&["--name-width=8", "--ellipsis", ">>"], &vars) {
+                assert_eq!(result, Ok(String::from(">>")));
+            }
+        }
+
+        #[test]
+        fn without_name_width() {
+            let results = ellipsis(&["--ellipsis=..."], &Env(&[]));
+            assert_eq!(
+                results,
+                vec![
+                    Ok(String::from("...")),
+                    Err(OptionsError::Useless(
+                        &flags::ELLIPSIS,
+                        false,
+                        &flags::NAME_WIDTH
+                    )),
+                ]
+            );
+        }
     }
 
     mod time_formats {
@@ -647,34 +1824,149 @@ mod test {
         // implement PartialEq.
 
         // Default behaviour
-        test!(empty:     TimeFormat <- [], None;                            Both => like Ok(TimeFormat::DefaultFormat));
+        test!(empty:     TimeFormat <- [], None;                            Both => like Ok(TimeFormat::DefaultFormat { utc: false }));
 
         // Individual settings
-        test!(default:                TimeFormat <- ["--time-style=default"], None;               Both => like Ok(TimeFormat::DefaultFormat));
-        test!(iso:                    TimeFormat <- ["--time-style", "iso"], None;                Both => like Ok(TimeFormat::ISOFormat));
+        test!(default:                TimeFormat <- ["--time-style=default"], None;               Both => like Ok(TimeFormat::DefaultFormat { utc: false }));
+        test!(iso:                    TimeFormat <- ["--time-style", "iso"], None;                Both => like Ok(TimeFormat::ISOFormat { utc: false }));
         test!(relative:               TimeFormat <- ["--time-style", "relative"], None;           Both => like Ok(TimeFormat::Relative));
-        test!(long_iso:               TimeFormat <- ["--time-style=long-iso"], None;              Both => like Ok(TimeFormat::LongISO));
-        test!(full_iso:               TimeFormat <- ["--time-style", "full-iso"], None;           Both => like Ok(TimeFormat::FullISO));
+        test!(relative_aligned:       TimeFormat <- ["--time-style", "relative-aligned"], None;   Both => like Ok(TimeFormat::AlignedRelative));
+        test!(combined:               TimeFormat <- ["--time-style=combined"], None;              Both => like Ok(TimeFormat::Combined));
+        test!(long_iso:               TimeFormat <- ["--time-style=long-iso"], None;              Both => like Ok(TimeFormat::LongISO { utc: false }));
+        test!(full_iso:               TimeFormat <- ["--time-style", "full-iso"], None;           Both => like Ok(TimeFormat::FullISO { utc: false }));
         test!(custom_style:           TimeFormat <- ["--time-style", "+%Y/%m/%d"], None;          Both => like Ok(TimeFormat::Custom { recent: None, .. }));
         test!(custom_style_multiline: TimeFormat <- ["--time-style", "+%Y/%m/%d\n--%m-%d"], None; Both => like Ok(TimeFormat::Custom { recent: Some(_), .. }));
+        test!(no_recent_style:        TimeFormat <- ["--time-style", "+%Y/%m/%d\n--%m-%d", "--no-recent-style"], None; Both => like Ok(TimeFormat::Custom { recent: None, .. }));
+        test!(no_recent_default:      TimeFormat <- ["--time-style=iso", "--no-recent-style"], None;                   Both => like Ok(TimeFormat::ISOFormat { utc: false }));
+        test!(utc:                    TimeFormat <- ["--time-style=utc"], None;                   Both => like Ok(TimeFormat::DefaultFormat { utc: true }));
+        test!(iso_utc:                TimeFormat <- ["--time-style", "long-iso,utc"], None;       Both => like Ok(TimeFormat::LongISO { utc: true }));
+        test!(custom_utc:             TimeFormat <- ["--time-style", "+%Y/%m/%d %H:%M,utc"], None; Both => like Ok(TimeFormat::Custom { utc: true, recent: None, .. }));
+        test!(custom_utc_multiline:   TimeFormat <- ["--time-style", "+%Y/%m/%d\n%m-%d,utc"], None; Both => like Ok(TimeFormat::Custom { utc: true, recent: Some(_), .. }));
+        test!(custom_local:           TimeFormat <- ["--time-style", "+%Y/%m/%d"], None;          Both => like Ok(TimeFormat::Custom { utc: false, .. }));
         test!(bad_custom_style:       TimeFormat <- ["--time-style", "%Y/%m/%d"], None;           Both => err OptionsError::BadArgument(&flags::TIME_STYLE, OsString::from("%Y/%m/%d")));
 
         // Overriding
-        test!(actually:  TimeFormat <- ["--time-style=default", "--time-style", "iso"], None;  Last => like Ok(TimeFormat::ISOFormat));
+        test!(actually:  TimeFormat <- ["--time-style=default", "--time-style", "iso"], None;  Last => like Ok(TimeFormat::ISOFormat { utc: false }));
         test!(actual_2:  TimeFormat <- ["--time-style=default", "--time-style", "iso"], None;  Complain => err OptionsError::Duplicate(Flag::Long("time-style"), Flag::Long("time-style")));
 
-        test!(nevermind: TimeFormat <- ["--time-style", "long-iso", "--time-style=full-iso"], None;  Last => like Ok(TimeFormat::FullISO));
+        test!(nevermind: TimeFormat <- ["--time-style", "long-iso", "--time-style=full-iso"], None;  Last => like Ok(TimeFormat::FullISO { utc: false }));
         test!(nevermore: TimeFormat <- ["--time-style", "long-iso", "--time-style=full-iso"], None;  Complain => err OptionsError::Duplicate(Flag::Long("time-style"), Flag::Long("time-style")));
 
         // Errors
         test!(daily:     TimeFormat <- ["--time-style=24-hour"], None;  Both => err OptionsError::BadArgument(&flags::TIME_STYLE, OsString::from("24-hour")));
+        test!(utc_relative:  TimeFormat <- ["--time-style=relative,utc"], None;  Both => err OptionsError::BadArgument(&flags::TIME_STYLE, OsString::from("relative,utc")));
+        test!(utc_twice:     TimeFormat <- ["--time-style=utc,utc"], None;       Both => err OptionsError::BadArgument(&flags::TIME_STYLE, OsString::from("utc,utc")));
+
+        // Typos
+        test!(typo:          TimeFormat <- ["--time-style=realtive"], None;      Both => err OptionsError::BadArgumentWithSuggestion(&flags::TIME_STYLE, OsString::from("realtive"), String::from("relative")));
+        test!(typo_iso:      TimeFormat <- ["--time-style=long-isp"], None;      Both => err OptionsError::BadArgumentWithSuggestion(&flags::TIME_STYLE, OsString::from("long-isp"), String::from("long-iso")));
+        test!(no_typo:       TimeFormat <- ["--time-style=yesterday"], None;     Both => err OptionsError::BadArgument(&flags::TIME_STYLE, OsString::from("yesterday")));
+        test!(empty_custom:      TimeFormat <- ["--time-style=+"], None;                  Both => err OptionsError::EmptyTimeFormat { recent: false });
+        test!(empty_custom_line: TimeFormat <- ["--time-style", "+\n%m-%d"], None;       Both => err OptionsError::EmptyTimeFormat { recent: false });
+        test!(empty_custom_env:  TimeFormat <- [], Some(OsString::from("+"));             Both => err OptionsError::EmptyTimeFormat { recent: false });
+        test!(empty_recent:      TimeFormat <- ["--time-style", "+%Y\n\n"], None;        Both => err OptionsError::EmptyTimeFormat { recent: true });
+        test!(empty_recent_env:  TimeFormat <- [], Some(OsString::from("+%Y\n\n%m"));    Both => err OptionsError::EmptyTimeFormat { recent: true });
+        test!(trailing_newline:  TimeFormat <- ["--time-style", "+%Y\n"], None;          Both => like Ok(TimeFormat::Custom { recent: None, .. }));
 
         // `TIME_STYLE` environment variable is defined.
         // If the time-style argument is not given, `TIME_STYLE` is used.
-        test!(use_env:     TimeFormat <- [], Some("long-iso".into());  Both => like Ok(TimeFormat::LongISO));
+        test!(use_env:     TimeFormat <- [], Some("long-iso".into());  Both => like Ok(TimeFormat::LongISO { utc: false }));
 
         // If the time-style argument is given, `TIME_STYLE` is overriding.
-        test!(override_env:     TimeFormat <- ["--time-style=full-iso"], Some("long-iso".into());  Both => like Ok(TimeFormat::FullISO));
+        test!(override_env:     TimeFormat <- ["--time-style=full-iso"], Some("long-iso".into());  Both => like Ok(TimeFormat::FullISO { utc: false }));
+
+        // `EZA_RECENT_THRESHOLD` decides which custom format recent files use.
+        test!(recent_threshold:    TimeFormat <- ["--time-style", "+%Y\n%m"], Some(OsString::from("2w"));     Both => like Ok(TimeFormat::Custom { recent_threshold: Some(_), .. }));
+        test!(no_recent_threshold: TimeFormat <- ["--time-style", "+%Y\n%m"], None;                           Both => like Ok(TimeFormat::Custom { recent_threshold: None, .. }));
+
+        // `EZA_RECENT_DURATION` does the same, and a duration it can’t
+        // parse is an error.
+        test!(recent_duration:     TimeFormat <- ["--time-style", "+%Y\n%m"], Env(&[("EZA_RECENT_DURATION", "24h")]);  Both => like Ok(TimeFormat::Custom { recent_threshold: Some(_), .. }));
+        test!(bad_recent_duration: TimeFormat <- ["--time-style", "+%Y\n%m"], Env(&[("EZA_RECENT_DURATION", "24")]);   Both => err OptionsError::BadRecentDuration("EZA_RECENT_DURATION", String::from("24")));
+        test!(unused_duration:     TimeFormat <- ["--time-style=iso"], Env(&[("EZA_RECENT_DURATION", "24")]);          Both => like Ok(TimeFormat::ISOFormat { utc: false }));
+    }
+
+    mod recent_thresholds {
+        use super::*;
+
+        fn threshold(var: &'static str) -> Result<Option<Duration>, OptionsError> {
+            deduce_recent_threshold(&Env(&[("EZA_RECENT_THRESHOLD", var)]))
+        }
+
+        fn duration(var: &'static str) -> Result<Option<Duration>, OptionsError> {
+            deduce_recent_threshold(&Env(&[("EZA_RECENT_DURATION", var)]))
+        }
+
+        #[test]
+        fn units() {
+            assert_eq!(threshold("90s"), Ok(Some(Duration::from_secs(90))));
+            assert_eq!(threshold("30m"), Ok(Some(Duration::from_secs(30 * 60))));
+            assert_eq!(
+                threshold("12h"),
+                Ok(Some(Duration::from_secs(12 * 60 * 60)))
+            );
+            assert_eq!(
+                threshold(" 7d "),
+                Ok(Some(Duration::from_secs(7 * 24 * 60 * 60)))
+            );
+            assert_eq!(
+                threshold("2w"),
+                Ok(Some(Duration::from_secs(14 * 24 * 60 * 60)))
+            );
+        }
+
+        #[test]
+        fn unset() {
+            assert_eq!(deduce_recent_threshold(&None::<OsString>), Ok(None));
+            assert_eq!(threshold(""), Ok(None));
+        }
+
+        #[test]
+        fn duration_units() {
+            let day = Duration::from_secs(24 * 60 * 60);
+            assert_eq!(duration("7d"), Ok(Some(7 * day)));
+            assert_eq!(duration("24h"), Ok(Some(day)));
+            assert_eq!(duration("30m"), Ok(Some(Duration::from_secs(30 * 60))));
+            assert_eq!(duration(""), Ok(None));
+        }
+
+        #[test]
+        fn duration_rejected() {
+            for var in ["7", "d", "7 days", "-7d", "7y"] {
+                assert_eq!(
+                    duration(var),
+                    Err(OptionsError::BadRecentDuration(
+                        "EZA_RECENT_DURATION",
+                        String::from(var)
+                    ))
+                );
+            }
+        }
+
+        #[test]
+        fn threshold_rejected() {
+            for var in ["7", "d", "7 days", "-7d", "7y"] {
+                assert_eq!(
+                    threshold(var),
+                    Err(OptionsError::BadRecentDuration(
+                        "EZA_RECENT_THRESHOLD",
+                        String::from(var)
+                    ))
+                );
+            }
+        }
+
+        #[test]
+        fn duration_first() {
+            let vars = Env(&[
+                ("EZA_RECENT_DURATION", "1h"),
+                ("EZA_RECENT_THRESHOLD", "7d"),
+            ]);
+            assert_eq!(
+                deduce_recent_threshold(&vars),
+                Ok(Some(Duration::from_secs(60 * 60)))
+            );
+        }
     }
 
     mod time_types {
@@ -715,10 +2007,287 @@ mod test {
         // Errors
         test!(time_tea:  TimeTypes <- ["--time=tea"];          Both => err OptionsError::BadArgument(&flags::TIME, OsString::from("tea")));
         test!(t_ea:      TimeTypes <- ["-tea"];                Both => err OptionsError::BadArgument(&flags::TIME, OsString::from("ea")));
+        test!(time_tab:  TimeTypes <- ["--time", " Tea\t"];    Both => err OptionsError::BadArgument(&flags::TIME, OsString::from(" Tea\t")));
+
+        // Case and whitespace
+        test!(time_mod_uc: TimeTypes <- ["--time=Modified"];    Both => Ok(TimeTypes { modified: true,  changed: false, accessed: false, created: false }));
+        test!(time_acc_ws: TimeTypes <- ["--time", " accessed "]; Both => Ok(TimeTypes { modified: false, changed: false, accessed: true,  created: false }));
+        test!(t_cr_uc:     TimeTypes <- ["-tCR"];               Both => Ok(TimeTypes { modified: false, changed: false, accessed: false, created: true  }));
 
         // Overriding
         test!(overridden:   TimeTypes <- ["-tcr", "-tmod"];    Last => Ok(TimeTypes { modified: true,  changed: false, accessed: false, created: false }));
         test!(overridden_2: TimeTypes <- ["-tcr", "-tmod"];    Complain => err OptionsError::Duplicate(Flag::Short(b't'), Flag::Short(b't')));
+
+        // Lists of fields
+        test!(time_list:    TimeTypes <- ["--time=modified,created"];  Both => Ok(TimeTypes { modified: true,  changed: false, accessed: false, created: true  }));
+        test!(t_list:       TimeTypes <- ["-tacc,cr"];                 Both => Ok(TimeTypes { modified: false, changed: false, accessed: true,  created: true  }));
+        test!(time_list_ws: TimeTypes <- ["--time", "Mod, Accessed"];  Both => Ok(TimeTypes { modified: true,  changed: false, accessed: true,  created: false }));
+        test!(time_list_dup: TimeTypes <- ["--time=cr,created,cr"];    Both => Ok(TimeTypes { modified: false, changed: false, accessed: false, created: true  }));
+        #[cfg(target_family = "unix")]
+        test!(time_list_all: TimeTypes <- ["--time=mod,ch,acc,cr"];    Both => Ok(TimeTypes { modified: true,  changed: true,  accessed: true,  created: true  }));
+
+        // List errors
+        test!(time_list_tea:   TimeTypes <- ["--time=modified,tea"];   Both => err OptionsError::BadArgument(&flags::TIME, OsString::from("tea")));
+        test!(t_list_ea:       TimeTypes <- ["-tea,mod"];              Both => err OptionsError::BadArgument(&flags::TIME, OsString::from("ea")));
+        test!(time_list_comma: TimeTypes <- ["--time=modified,"];      Both => err OptionsError::BadArgument(&flags::TIME, OsString::from("modified,")));
+        test!(time_list_empty: TimeTypes <- ["--time=,created"];       Both => err OptionsError::BadArgument(&flags::TIME, OsString::from(",created")));
+
+        // Typos
+        test!(time_typo:       TimeTypes <- ["--time=modfied"];        Both => err OptionsError::BadArgumentWithSuggestion(&flags::TIME, OsString::from("modfied"), String::from("modified")));
+        test!(time_list_typo:  TimeTypes <- ["--time=changed,acessed"]; Both => err OptionsError::BadArgumentWithSuggestion(&flags::TIME, OsString::from("acessed"), String::from("accessed")));
+        test!(time_list_flag:  TimeTypes <- ["--time=mod,cr", "-u"];   Both => err OptionsError::Useless(&flags::ACCESSED, true, &flags::TIME));
+
+        // Every field at once
+        #[cfg(target_family = "unix")]
+        test!(time_all:        TimeTypes <- ["--time=all"];            Both => Ok(TimeTypes { modified: true,  changed: true,  accessed: true,  created: true  }));
+        #[cfg(target_family = "unix")]
+        test!(t_all:           TimeTypes <- ["-t", "ALL"];             Both => Ok(TimeTypes { modified: true,  changed: true,  accessed: true,  created: true  }));
+        #[cfg(target_family = "unix")]
+        test!(time_all_list:   TimeTypes <- ["--time=mod,all"];        Both => Ok(TimeTypes { modified: true,  changed: true,  accessed: true,  created: true  }));
+        test!(time_all_flag:   TimeTypes <- ["--time=all", "--modified"]; Both => err OptionsError::Useless(&flags::MODIFIED, true, &flags::TIME));
+        test!(time_all_flag_2: TimeTypes <- ["--created", "--time=all"];  Both => err OptionsError::Useless(&flags::CREATED, true, &flags::TIME));
+
+        // All times with a flag
+        test!(all_times:       TimeTypes <- ["--all-times"];                  Both => Ok(TimeTypes { modified: true,  changed: true,  accessed: true,  created: true  }));
+        test!(all_times_m:     TimeTypes <- ["--all-times", "-m"];            Both => Ok(TimeTypes { modified: true,  changed: true,  accessed: true,  created: true  }));
+        test!(all_times_none:  TimeTypes <- ["--all-times", "--no-time"];     Both => Ok(TimeTypes { modified: false, changed: false, accessed: false, created: false }));
+        test!(all_times_time:  TimeTypes <- ["--all-times", "--time=modified"]; Both => err OptionsError::Useless(&flags::ALL_TIMES, true, &flags::TIME));
+    }
+
+    mod color_scales {
+        use super::*;
+
+        // Default behaviour
+        test!(empty:      ColorScaleOptions <- [], None;                             Both => like Ok(ColorScaleOptions { size: false, age: false, .. }));
+
+        // Individual fields
+        test!(all:        ColorScaleOptions <- ["--color-scale"], None;              Both => like Ok(ColorScaleOptions { size: true,  age: true,  inode: true,  links: true,  .. }));
+        test!(size:       ColorScaleOptions <- ["--color-scale=size"], None;         Both => like Ok(ColorScaleOptions { size: true,  age: false, .. }));
+        test!(age:        ColorScaleOptions <- ["--colour-scale", "age"], None;      Both => like Ok(ColorScaleOptions { size: false, age: true,  .. }));
+        test!(inode:      ColorScaleOptions <- ["--color-scale=inode"], None;        Both => like Ok(ColorScaleOptions { size: false, age: false, inode: true,  links: false, .. }));
+        test!(links:      ColorScaleOptions <- ["--color-scale", "links"], None;     Both => like Ok(ColorScaleOptions { size: false, age: false, inode: false, links: true,  .. }));
+
+        // Case, whitespace, and stray commas
+        test!(mixed:      ColorScaleOptions <- ["--color-scale=Size, Age"], None;    Both => like Ok(ColorScaleOptions { size: true,  age: true,  .. }));
+        test!(numbers:    ColorScaleOptions <- ["--color-scale=Inode, LINKS"], None; Both => like Ok(ColorScaleOptions { size: false, age: false, inode: true,  links: true,  .. }));
+        test!(trailing:   ColorScaleOptions <- ["--color-scale=size,"], None;        Both => like Ok(ColorScaleOptions { size: true,  age: false, .. }));
+        test!(blanks:     ColorScaleOptions <- ["--color-scale= ,AGE,,"], None;      Both => like Ok(ColorScaleOptions { size: false, age: true,  .. }));
+
+        // Turning it off
+        test!(none:       ColorScaleOptions <- ["--color-scale=none"], None;         Both => like Ok(ColorScaleOptions { size: false, age: false, inode: false, links: false, .. }));
+        test!(none_size:  ColorScaleOptions <- ["--color-scale=none,size"], None;    Both => like Ok(ColorScaleOptions { size: false, age: false, inode: false, links: false, .. }));
+        test!(all_none:   ColorScaleOptions <- ["--color-scale=all,None"], None;     Both => like Ok(ColorScaleOptions { size: false, age: false, inode: false, links: false, .. }));
+        test!(none_bad:   ColorScaleOptions <- ["--color-scale=none,name"], None;    Both => err OptionsError::BadArgument(&flags::COLOR_SCALE, OsString::from("name")));
+
+        // Errors
+        test!(unknown:    ColorScaleOptions <- ["--color-scale=size,Name"], None;    Both => err OptionsError::BadArgument(&flags::COLOR_SCALE, OsString::from("Name")));
+
+        test!(age_mtime:  ColorScaleOptions <- ["--color-scale=age"], None;          Both => like Ok(ColorScaleOptions { age: true, age_time: None, .. }));
+        test!(age_atime:  ColorScaleOptions <- ["--color-scale=age:atime"], None;    Both => like Ok(ColorScaleOptions { size: false, age: true, age_time: Some(TimeType::Accessed), .. }));
+        test!(age_ctime:  ColorScaleOptions <- ["--color-scale=all,Age:CTime"], None; Both => like Ok(ColorScaleOptions { size: true, age: true, age_time: Some(TimeType::Changed), .. }));
+        test!(age_none:   ColorScaleOptions <- ["--color-scale=age:atime,none"], None; Both => like Ok(ColorScaleOptions { age: false, age_time: None, .. }));
+        test!(age_size:   ColorScaleOptions <- ["--color-scale=age:size"], None;     Both => err OptionsError::BadArgument(&flags::COLOR_SCALE, OsString::from("age:size")));
+
+        // Gradients
+        test!(no_gradient:    ColorScaleOptions <- [], None;                                    Both => like Ok(ColorScaleOptions { gradient: None, .. }));
+        test!(two_stops:      ColorScaleOptions <- [], Env(&[("EZA_COLOR_SCALE_GRADIENT", "#000000,#ffffff")]);        Both => like Ok(ColorScaleOptions { gradient: Some(_), .. }));
+        test!(three_stops:    ColorScaleOptions <- [], Env(&[("EZA_COLOR_SCALE_GRADIENT", "1e90ff, ffd700, ff4500")]); Both => like Ok(ColorScaleOptions { gradient: Some(_), .. }));
+        test!(one_stop:       ColorScaleOptions <- [], Env(&[("EZA_COLOR_SCALE_GRADIENT", "#000000")]); Last => like Ok(ColorScaleOptions { gradient: None, .. }));
+        test!(one_stop_2:     ColorScaleOptions <- [], Env(&[("EZA_COLOR_SCALE_GRADIENT", "#000000")]); Complain => err OptionsError::BadColorScaleGradient(String::from("#000000")));
+        test!(not_hex:        ColorScaleOptions <- [], Env(&[("EZA_COLOR_SCALE_GRADIENT", "#000000,blue")]); Last => like Ok(ColorScaleOptions { gradient: None, .. }));
+        test!(not_hex_2:      ColorScaleOptions <- [], Env(&[("EZA_COLOR_SCALE_GRADIENT", "#000000,blue")]); Complain => err OptionsError::BadColorScaleGradient(String::from("#000000,blue")));
+
+        // Recent thresholds
+        test!(no_recent:      ColorScaleOptions <- ["--color-scale=age"], None;                         Both => like Ok(ColorScaleOptions { recent_threshold: None, .. }));
+        test!(recent:         ColorScaleOptions <- ["--color-scale=age"], Some(OsString::from("7d"));   Last => like Ok(ColorScaleOptions { recent_threshold: Some(_), .. }));
+        test!(bad_recent:     ColorScaleOptions <- ["--color-scale=age"], Env(&[("EZA_RECENT_DURATION", "week")]);  Both => err OptionsError::BadRecentDuration("EZA_RECENT_DURATION", String::from("week")));
+
+        // Minimum luminance
+        test!(luminance:      ColorScaleOptions <- [], Env(&[("EZA_MIN_LUMINANCE", "-20")]);  Both => like Ok(ColorScaleOptions { min_luminance: -20, .. }));
+        test!(luminance_exa:  ColorScaleOptions <- [], Env(&[("EXA_MIN_LUMINANCE", "100")]);  Both => like Ok(ColorScaleOptions { min_luminance: 100, .. }));
+        test!(luminance_both: ColorScaleOptions <- [], Env(&[("EZA_MIN_LUMINANCE", "60"), ("EXA_MIN_LUMINANCE", "nope")]);  Both => like Ok(ColorScaleOptions { min_luminance: 60, .. }));
+        test!(bad_luminance:    ColorScaleOptions <- [], Env(&[("EZA_MIN_LUMINANCE", "bright")]);  Last => like Ok(ColorScaleOptions { min_luminance: 40, .. }));
+        test!(bad_luminance_2:  ColorScaleOptions <- [], Env(&[("EZA_MIN_LUMINANCE", "bright")]);  Complain => err OptionsError::FailedParse(String::from("bright"), NumberSource::Env("EZA_MIN_LUMINANCE"), "bright".parse::<isize>().unwrap_err()));
+        test!(high_luminance:   ColorScaleOptions <- [], Env(&[("EZA_MIN_LUMINANCE", "150")]);     Last => like Ok(ColorScaleOptions { min_luminance: 40, .. }));
+        test!(high_luminance_2: ColorScaleOptions <- [], Env(&[("EZA_MIN_LUMINANCE", "150")]);     Complain => err OptionsError::OutOfRange(String::from("150"), NumberSource::Env("EZA_MIN_LUMINANCE"), -100..=100));
+        test!(low_luminance_2:  ColorScaleOptions <- [], Env(&[("EXA_MIN_LUMINANCE", "-101")]);    Complain => err OptionsError::OutOfRange(String::from("-101"), NumberSource::Env("EXA_MIN_LUMINANCE"), -100..=100));
+
+        test!(max_luminance:      ColorScaleOptions <- [], Env(&[("EZA_MAX_LUMINANCE", "70")]);  Both => like Ok(ColorScaleOptions { min_luminance: 40, max_luminance: 70, .. }));
+        test!(max_luminance_exa:  ColorScaleOptions <- [], Env(&[("EXA_MAX_LUMINANCE", "-50"), ("EZA_MIN_LUMINANCE", "-80")]);  Both => like Ok(ColorScaleOptions { min_luminance: -80, max_luminance: -50, .. }));
+        test!(max_luminance_same: ColorScaleOptions <- [], Env(&[("EZA_MIN_LUMINANCE", "60"), ("EZA_MAX_LUMINANCE", "60")]);  Both => like Ok(ColorScaleOptions { min_luminance: 60, max_luminance: 60, .. }));
+        test!(max_luminance_none: ColorScaleOptions <- [], None;  Both => like Ok(ColorScaleOptions { min_luminance: 40, max_luminance: 100, .. }));
+        test!(inverted_luminance:   ColorScaleOptions <- [], Env(&[("EZA_MIN_LUMINANCE", "60"), ("EZA_MAX_LUMINANCE", "20")]);  Last => like Ok(ColorScaleOptions { min_luminance: 60, max_luminance: 100, .. }));
+        test!(inverted_luminance_2: ColorScaleOptions <- [], Env(&[("EZA_MIN_LUMINANCE", "60"), ("EZA_MAX_LUMINANCE", "20")]);  Complain => err OptionsError::OutOfRange(String::from("20"), NumberSource::Env("EZA_MAX_LUMINANCE"), 60..=100));
+        test!(high_max_luminance_2: ColorScaleOptions <- [], Env(&[("EZA_MAX_LUMINANCE", "101")]);  Complain => err OptionsError::OutOfRange(String::from("101"), NumberSource::Env("EZA_MAX_LUMINANCE"), 40..=100));
+
+        // Modes
+        test!(mode_default:   ColorScaleOptions <- [], None;                                    Both => like Ok(ColorScaleOptions { mode: ColorScaleMode::Gradient, .. }));
+        test!(mode_fixed:     ColorScaleOptions <- ["--color-scale-mode=fixed"], None;          Both => like Ok(ColorScaleOptions { mode: ColorScaleMode::Fixed, .. }));
+        test!(mode_gradient:  ColorScaleOptions <- ["--color-scale-mode", "gradient"], None;    Both => like Ok(ColorScaleOptions { mode: ColorScaleMode::Gradient, .. }));
+        test!(mode_log:       ColorScaleOptions <- ["--color-scale-mode=logarithmic"], None;    Both => like Ok(ColorScaleOptions { mode: ColorScaleMode::Logarithmic, .. }));
+        test!(mode_log_2:     ColorScaleOptions <- ["--colour-scale-mode", "logarithmic"], None; Both => like Ok(ColorScaleOptions { mode: ColorScaleMode::Logarithmic, min_luminance: 40, .. }));
+        test!(mode_log_lum:   ColorScaleOptions <- ["--color-scale-mode=logarithmic"], Env(&[("EZA_MIN_LUMINANCE", "60")]); Both => like Ok(ColorScaleOptions { mode: ColorScaleMode::Logarithmic, min_luminance: 60, .. }));
+        test!(mode_unknown:   ColorScaleOptions <- ["--color-scale-mode=log"], None;            Both => err OptionsError::BadArgument(&flags::COLOR_SCALE_MODE, OsString::from("log")));
+        test!(mode_typo:      ColorScaleOptions <- ["--color-scale-mode=gradeint"], None;       Both => err OptionsError::BadArgumentWithSuggestion(&flags::COLOR_SCALE_MODE, OsString::from("gradeint"), String::from("gradient")));
+        test!(scale_typo:     ColorScaleOptions <- ["--color-scale=size,lnks"], None;           Both => err OptionsError::BadArgumentWithSuggestion(&flags::COLOR_SCALE, OsString::from("lnks"), String::from("links")));
+    }
+
+    mod terminal_widths {
+        use super::*;
+
+        // Default behaviour
+        test!(empty:      TerminalWidth <- [], None;                             Both => like Ok(TerminalWidth::Automatic));
+        test!(columns:    TerminalWidth <- [], Some("120".into());               Both => like Ok(TerminalWidth::Set(120)));
+
+        // Setting the width
+        test!(set:        TerminalWidth <- ["--width=80"], None;                 Both => like Ok(TerminalWidth::Set(80)));
+        test!(set_env:    TerminalWidth <- ["-w", "80"], Some("120".into());     Both => like Ok(TerminalWidth::Set(80)));
+
+        // Looking up the real width, even when `COLUMNS` is set
+        test!(zero:       TerminalWidth <- ["--width=0"], Some("120".into());    Both => like Ok(TerminalWidth::Automatic));
+        test!(max:        TerminalWidth <- ["--width=max"], Some("120".into());  Both => like Ok(TerminalWidth::Automatic));
+        test!(auto:       TerminalWidth <- ["-w", "auto"], Some("120".into());   Both => like Ok(TerminalWidth::Automatic));
+
+        // Leaving a margin
+        test!(negative:   TerminalWidth <- ["--width=-10"], None;                Both => like Ok(TerminalWidth::Offset(-10)));
+        test!(neg_env:    TerminalWidth <- ["-w", "-10"], Some(OsString::from("120"));    Both => like Ok(TerminalWidth::Offset(-10)));
+
+        // Errors
+        test!(bad:        TerminalWidth <- ["--width=wide"], None;               Both => err OptionsError::BadWidth(String::from("wide")));
+        test!(bad_neg:    TerminalWidth <- ["--width=-ten"], None;               Both => err OptionsError::BadWidth(String::from("-ten")));
+        test!(bad_env:    TerminalWidth <- [], Some("wide".into());              Both => like Err(OptionsError::FailedParse(_, NumberSource::Env(_), _)));
+
+        // Capping the width
+        test!(capped:     TerminalWidth <- ["--width=200", "--max-width=120"], None;      Both => like Ok(TerminalWidth::Set(120)));
+        test!(under_cap:  TerminalWidth <- ["--width=80", "--max-width=120"], None;       Both => like Ok(TerminalWidth::Set(80)));
+        test!(capped_env: TerminalWidth <- ["--width=200"], Env(&[("EZA_MAX_WIDTH", "120")]);                    Both => like Ok(TerminalWidth::Set(120)));
+        test!(cols_cap:   TerminalWidth <- [], Env(&[("COLUMNS", "400"), ("EZA_MAX_WIDTH", "120")]);              Both => like Ok(TerminalWidth::Set(120)));
+        test!(arg_cap:    TerminalWidth <- ["--width=200", "--max-width=100"], Env(&[("EZA_MAX_WIDTH", "120")]); Both => like Ok(TerminalWidth::Set(100)));
+        test!(auto_cap:   TerminalWidth <- ["--max-width=120"], None;                     Both => like Ok(TerminalWidth::Automatic));
+        test!(bad_max:    TerminalWidth <- [], Env(&[("EZA_MAX_WIDTH", "wide")]);         Both => like Err(OptionsError::FailedParse(_, NumberSource::Env("EZA_MAX_WIDTH"), _)));
+        test!(zero_max:   TerminalWidth <- [], Env(&[("EZA_MAX_WIDTH", "0")]);            Both => like Err(OptionsError::FailedParse(_, NumberSource::Env("EZA_MAX_WIDTH"), _)));
+        test!(bad_max_arg: TerminalWidth <- ["--max-width=wide"], None;                   Both => like Err(OptionsError::FailedParse(_, NumberSource::Arg(_), _)));
+
+        // Flooring the width
+        test!(floored:    TerminalWidth <- [], Env(&[("COLUMNS", "10"), ("EZA_MIN_WIDTH", "40")]);               Both => like Ok(TerminalWidth::Set(40)));
+        test!(over_floor: TerminalWidth <- [], Env(&[("COLUMNS", "100"), ("EZA_MIN_WIDTH", "40")]);              Both => like Ok(TerminalWidth::Set(100)));
+        test!(floor_arg:  TerminalWidth <- ["--min-width=60"], Env(&[("COLUMNS", "10"), ("EZA_MIN_WIDTH", "40")]); Both => like Ok(TerminalWidth::Set(60)));
+        test!(floor_both: TerminalWidth <- ["--min-width=40", "--max-width=120"], Env(&[("COLUMNS", "10")]);     Both => like Ok(TerminalWidth::Set(40)));
+        test!(auto_floor: TerminalWidth <- ["--min-width=40"], None;                      Both => like Ok(TerminalWidth::Automatic));
+        test!(bad_min:    TerminalWidth <- [], Env(&[("EZA_MIN_WIDTH", "narrow")]);       Both => like Err(OptionsError::FailedParse(_, NumberSource::Env("EZA_MIN_WIDTH"), _)));
+        test!(zero_min:   TerminalWidth <- ["--min-width=0"], None;                       Both => like Err(OptionsError::FailedParse(_, NumberSource::Arg(_), _)));
+        test!(min_over_width: TerminalWidth <- ["--width=30", "--min-width=40"], None;    Both => err OptionsError::OutOfRange(String::from("40"), NumberSource::Arg(&flags::MIN_WIDTH), 1..=30));
+        test!(min_over_max:   TerminalWidth <- ["--max-width=30"], Env(&[("EZA_MIN_WIDTH", "40")]); Both => err OptionsError::OutOfRange(String::from("40"), NumberSource::Env("EZA_MIN_WIDTH"), 1..=30));
+
+        #[test]
+        fn resolving() {
+            assert_eq!(
+                TerminalWidth::Automatic.resolve(Some(80), None, None),
+                Some(80)
+            );
+            assert_eq!(
+                TerminalWidth::Set(100).resolve(Some(80), None, None),
+                Some(100)
+            );
+            assert_eq!(TerminalWidth::Set(100).resolve(None, None, None), Some(100));
+            assert_eq!(
+                TerminalWidth::Offset(-10).resolve(Some(80), None, None),
+                Some(70)
+            );
+            assert_eq!(
+                TerminalWidth::Offset(-100).resolve(Some(80), None, None),
+                Some(1)
+            );
+
+            // Without a terminal, there’s nothing to take the margin off,
+            // the same as when no width was asked for.
+            assert_eq!(TerminalWidth::Offset(-10).resolve(None, None, None), None);
+        }
+
+        #[test]
+        fn resolving_capped() {
+            assert_eq!(
+                TerminalWidth::Automatic.resolve(Some(400), None, Some(120)),
+                Some(120)
+            );
+            assert_eq!(
+                TerminalWidth::Automatic.resolve(Some(80), None, Some(120)),
+                Some(80)
+            );
+            assert_eq!(
+                TerminalWidth::Offset(-10).resolve(Some(400), None, Some(120)),
+                Some(120)
+            );
+            assert_eq!(
+                TerminalWidth::Set(200).resolve(None, None, Some(120)),
+                Some(120)
+            );
+
+            // There’s still no width at all without a terminal.
+            assert_eq!(
+                TerminalWidth::Automatic.resolve(None, None, Some(120)),
+                None
+            );
+        }
+
+        #[test]
+        fn resolving_floored() {
+            assert_eq!(
+                TerminalWidth::Automatic.resolve(Some(10), Some(40), None),
+                Some(40)
+            );
+            assert_eq!(
+                TerminalWidth::Automatic.resolve(Some(80), Some(40), Some(120)),
+                Some(80)
+            );
+            assert_eq!(
+                TerminalWidth::Offset(-10).resolve(Some(30), Some(40), None),
+                Some(40)
+            );
+
+            // Without a terminal, there’s nothing to raise.
+            assert_eq!(TerminalWidth::Automatic.resolve(None, Some(40), None), None);
+        }
+
+        #[test]
+        fn bad_message() {
+            let error = OptionsError::BadWidth(String::from("wide"));
+            assert_eq!(
+                error.to_string(),
+                "Value \"wide\" not valid for option --width: expected a number of columns, \"auto\", or \"max\""
+            );
+        }
+    }
+
+    mod row_thresholds {
+        use super::*;
+
+        // Default behaviour
+        test!(empty:      RowThreshold <- [], None;                                       Both => like Ok(RowThreshold::AlwaysGrid));
+        test!(env:        RowThreshold <- [], Some("4".into());                           Both => like Ok(RowThreshold::MinimumRows(4)));
+
+        // Command-line flag, which overrides the environment
+        test!(flag:       RowThreshold <- ["--grid-rows-threshold=3"], None;              Both => like Ok(RowThreshold::MinimumRows(3)));
+        test!(flag_env:   RowThreshold <- ["--grid-rows-threshold", "3"], Some("4".into()); Both => like Ok(RowThreshold::MinimumRows(3)));
+
+        // Errors
+        test!(bad:        RowThreshold <- ["--grid-rows-threshold=many"], None;           Both => like Err(OptionsError::FailedParse(_, NumberSource::Arg(_), _)));
+        test!(bad_env:    RowThreshold <- [], Env(&[("EZA_GRID_ROWS", "many")]);          Both => like Err(OptionsError::FailedParse(_, NumberSource::Env("EZA_GRID_ROWS"), _)));
+
+        // Columns, which the rows take precedence over
+        test!(cols:       RowThreshold <- [], Env(&[("EZA_GRID_COLS", "3")]);             Both => like Ok(RowThreshold::MinimumColumns(3)));
+        test!(rows_cols:  RowThreshold <- [], Env(&[("EZA_GRID_ROWS", "4"), ("EZA_GRID_COLS", "3")]); Both => like Ok(RowThreshold::MinimumRows(4)));
+        test!(flag_cols:  RowThreshold <- ["--grid-rows-threshold=2"], Env(&[("EZA_GRID_COLS", "3")]); Both => like Ok(RowThreshold::MinimumRows(2)));
+        test!(bad_cols:   RowThreshold <- [], Env(&[("EZA_GRID_COLS", "wide")]);          Both => like Err(OptionsError::FailedParse(_, NumberSource::Env("EZA_GRID_COLS"), _)));
+    }
+
+    mod max_outputs {
+        use super::*;
+
+        test!(empty:      View <- [], None;                        Both => like Ok(View { max_output: None, .. }));
+        test!(set:        View <- ["--max-output=4096"], None;     Both => like Ok(View { max_output: Some(4096), .. }));
+        test!(zero:       View <- ["--max-output", "0"], None;     Both => err OptionsError::BadArgument(&flags::MAX_OUTPUT, OsString::from("0")));
+        test!(bad:        View <- ["--max-output=lots"], None;     Both => like Err(OptionsError::FailedParse(_, NumberSource::Arg(_), _)));
     }
 
     mod views {
@@ -756,6 +2325,7 @@ mod test {
         test!(just_inode:    Mode <- ["--inode"],     None;  Last => like Ok(Mode::Grid(_)));
         test!(just_links:    Mode <- ["--links"],     None;  Last => like Ok(Mode::Grid(_)));
         test!(just_blocks:   Mode <- ["--blocksize"], None;  Last => like Ok(Mode::Grid(_)));
+        test!(just_io_blocks: Mode <- ["--io-blocksize"], None;  Last => like Ok(Mode::Grid(_)));
         test!(just_binary:   Mode <- ["--binary"],    None;  Last => like Ok(Mode::Grid(_)));
         test!(just_bytes:    Mode <- ["--bytes"],     None;  Last => like Ok(Mode::Grid(_)));
         test!(just_numeric:  Mode <- ["--numeric"],   None;  Last => like Ok(Mode::Grid(_)));
@@ -766,14 +2336,26 @@ mod test {
         test!(just_header_2: Mode <- ["--header"],    None;  Complain => err OptionsError::Useless(&flags::HEADER,  false, &flags::LONG));
         test!(just_group_2:  Mode <- ["--group"],     None;  Complain => err OptionsError::Useless(&flags::GROUP,   false, &flags::LONG));
         test!(just_inode_2:  Mode <- ["--inode"],     None;  Complain => err OptionsError::Useless(&flags::INODE,   false, &flags::LONG));
+        test!(just_inode_format_2: Mode <- ["--inode-format=hex"], None;  Complain => err OptionsError::Useless(&flags::INODE_FORMAT, false, &flags::LONG));
+        test!(just_all_times_2: Mode <- ["--all-times"], None;  Complain => err OptionsError::Useless(&flags::ALL_TIMES, false, &flags::LONG));
         test!(just_links_2:  Mode <- ["--links"],     None;  Complain => err OptionsError::Useless(&flags::LINKS,   false, &flags::LONG));
         test!(just_blocks_2: Mode <- ["--blocksize"], None;  Complain => err OptionsError::Useless(&flags::BLOCKSIZE,  false, &flags::LONG));
+        test!(just_io_blocks_2: Mode <- ["--io-blocksize"], None;  Complain => err OptionsError::Useless(&flags::IO_BLOCKSIZE, false, &flags::LONG));
         test!(just_binary_2: Mode <- ["--binary"],    None;  Complain => err OptionsError::Useless(&flags::BINARY,  false, &flags::LONG));
         test!(just_bytes_2:  Mode <- ["--bytes"],     None;  Complain => err OptionsError::Useless(&flags::BYTES,   false, &flags::LONG));
+        test!(just_unit_2:   Mode <- ["--size-unit=m"], None;  Complain => err OptionsError::Useless(&flags::SIZE_UNIT, false, &flags::LONG));
         test!(just_numeric2: Mode <- ["--numeric"],   None;  Complain => err OptionsError::Useless(&flags::NUMERIC, false, &flags::LONG));
 
+        // --numeric also affects sorting by owner
+        #[cfg(unix)]
+        test!(numeric_sort:  Mode <- ["--numeric", "--sort=owner"],  None;  Complain => like Ok(Mode::Grid(_)));
+        test!(numeric_size:  Mode <- ["--numeric", "--sort=size"],   None;  Complain => err OptionsError::Useless(&flags::NUMERIC, false, &flags::LONG));
+
         #[cfg(feature = "git")]
         test!(just_git_2:    Mode <- ["--git"],    None;  Complain => err OptionsError::Useless(&flags::GIT,    false, &flags::LONG));
+        test!(just_checksum: Mode <- ["--checksum=md5"], None;  Complain => err OptionsError::Useless(&flags::CHECKSUM, false, &flags::LONG));
+        test!(just_mime:     Mode <- ["--mime"], None;          Complain => err OptionsError::Useless(&flags::MIME, false, &flags::LONG));
+        test!(just_several:  Mode <- ["--binary", "--header", "--level=2"], None;  Complain => err OptionsError::MultipleUseless(vec![OptionsError::Useless(&flags::BINARY, false, &flags::LONG), OptionsError::Useless(&flags::HEADER, false, &flags::LONG), OptionsError::Useless2(&flags::LEVEL, &flags::RECURSE, &flags::TREE)]));
 
         // Contradictions and combinations
         test!(lgo:           Mode <- ["--long", "--grid", "--oneline"], None;  Both => like Ok(Mode::Lines));
@@ -783,5 +2365,70 @@ mod test {
         test!(ot:            Mode <- ["--oneline", "--tree"],           None;  Both => like Ok(Mode::Details(_)));
         test!(og:            Mode <- ["--oneline", "--grid"],           None;  Both => like Ok(Mode::Grid(_)));
         test!(tg:            Mode <- ["--tree", "--grid"],              None;  Both => like Ok(Mode::Grid(_)));
+
+        // Tree formats
+        test!(tree:          Mode <- ["--tree"],                    None;  Both => like Ok(Mode::Details(details::Options { indented: false, .. })));
+        test!(tree_art:      Mode <- ["--tree", "--tree-format=tree"],    None;  Both => like Ok(Mode::Details(details::Options { indented: false, .. })));
+        test!(tree_indent:   Mode <- ["--tree", "--tree-format=indent"],  None;  Both => like Ok(Mode::Details(details::Options { indented: true,  .. })));
+        test!(long_indent:   Mode <- ["-lT", "--tree-format", "indent"],  None;  Both => like Ok(Mode::Details(details::Options { indented: true,  .. })));
+        test!(tree_json:     Mode <- ["--tree", "--tree-format=json"],    None;  Both => err OptionsError::BadArgument(&flags::TREE_FORMAT, OsString::from("json")));
+
+        // Templates
+        test!(template:      Mode <- ["--format-template", "{name} {size}"],      None;  Both => like Ok(Mode::Template(_)));
+        test!(template_long: Mode <- ["--long", "--format-template={name}"],      None;  Both => like Ok(Mode::Template(_)));
+        test!(template_bad:  Mode <- ["--format-template={name} {colour}"],       None;  Both => err OptionsError::BadTemplate(TemplateError::UnknownField(String::from("colour"))));
+        test!(template_open: Mode <- ["--format-template={name"],                 None;  Both => err OptionsError::BadTemplate(TemplateError::Unclosed(String::from("name"))));
+
+        // CSV
+        test!(csv:           Mode <- ["--csv"],                                   None;  Both => like Ok(Mode::Csv(csv::Options { delimiter: ',', .. })));
+        test!(csv_long:      Mode <- ["--csv", "-l", "--inode"],                  None;  Both => like Ok(Mode::Csv(csv::Options { table: TableOptions { columns: Columns { inode: true, .. }, .. }, .. })));
+        test!(csv_tab:       Mode <- ["--csv", "--csv-delimiter=\\t"],            None;  Both => like Ok(Mode::Csv(csv::Options { delimiter: '\t', .. })));
+        test!(csv_semicolon: Mode <- ["--csv", "--csv-delimiter", ";"],           None;  Both => like Ok(Mode::Csv(csv::Options { delimiter: ';', .. })));
+        test!(csv_empty:     Mode <- ["--csv", "--csv-delimiter", ""],            None;  Both => err OptionsError::BadArgument(&flags::CSV_DELIMITER, OsString::new()));
+        test!(csv_quote:     Mode <- ["--csv", "--csv-delimiter=\""],             None;  Both => err OptionsError::BadArgument(&flags::CSV_DELIMITER, OsString::from("\"")));
+        test!(csv_two:       Mode <- ["--csv", "--csv-delimiter=::"],             None;  Both => err OptionsError::BadArgument(&flags::CSV_DELIMITER, OsString::from("::")));
+        test!(just_delim:    Mode <- ["--csv-delimiter=;"],                       None;  Last => like Ok(Mode::Grid(_)));
+        test!(just_delim_2:  Mode <- ["--csv-delimiter=;"],                       None;  Complain => err OptionsError::Useless(&flags::CSV_DELIMITER, false, &flags::CSV));
+
+        // Picking the view from the environment
+        test!(env_long:      Mode <- [], Env(&[("EZA_DEFAULT_VIEW", "long")]);     Both => like Ok(Mode::Details(details::Options { table: Some(_), .. })));
+        test!(env_oneline:   Mode <- [], Env(&[("EZA_DEFAULT_VIEW", "oneline")]);  Both => like Ok(Mode::Lines));
+        test!(env_grid:      Mode <- [], Env(&[("EZA_DEFAULT_VIEW", "grid")]);     Both => like Ok(Mode::Grid(_)));
+        test!(env_tree:      Mode <- [], Env(&[("EZA_DEFAULT_VIEW", "tree")]);     Both => like Ok(Mode::Details(details::Options { table: None, .. })));
+        test!(env_empty:     Mode <- [], Env(&[("EZA_DEFAULT_VIEW", "")]);         Both => like Ok(Mode::Grid(_)));
+        test!(env_bad:       Mode <- [], Env(&[("EZA_DEFAULT_VIEW", "wide")]);     Both => err OptionsError::BadDefaultView(String::from("wide")));
+        test!(env_long_x:    Mode <- ["--across"], Env(&[("EZA_DEFAULT_VIEW", "long")]);   Last => like Ok(Mode::Details(_)));
+        test!(env_long_h:    Mode <- ["--header"], Env(&[("EZA_DEFAULT_VIEW", "long")]);   Both => like Ok(Mode::Details(details::Options { header: true, .. })));
+
+        // Flags on the command-line override the environment
+        test!(env_flag_1:    Mode <- ["--oneline"], Env(&[("EZA_DEFAULT_VIEW", "long")]);  Both => like Ok(Mode::Lines));
+        test!(env_flag_2:    Mode <- ["--grid"],    Env(&[("EZA_DEFAULT_VIEW", "long")]);  Both => like Ok(Mode::Grid(_)));
+        test!(env_flag_3:    Mode <- ["--long"],    Env(&[("EZA_DEFAULT_VIEW", "tree")]);  Both => like Ok(Mode::Details(details::Options { table: Some(_), .. })));
+        test!(env_flag_4:    Mode <- ["--tree"],    Env(&[("EZA_DEFAULT_VIEW", "grid")]);  Both => like Ok(Mode::Details(_)));
+        test!(env_flag_bad:  Mode <- ["--long"],    Env(&[("EZA_DEFAULT_VIEW", "wide")]);  Both => like Ok(Mode::Details(_)));
+
+        // Name widths
+        test!(no_name_width:   Mode <- ["--long"],                      None;  Both => like Ok(Mode::Details(details::Options { name_width: None, .. })));
+        test!(name_width:      Mode <- ["--long", "--name-width=20"],   None;  Both => like Ok(Mode::Details(details::Options { name_width: Some(20), .. })));
+        test!(name_width_zero: Mode <- ["--long", "--name-width", "0"], None;  Both => err OptionsError::BadArgument(&flags::NAME_WIDTH, OsString::from("0")));
+        test!(name_width_bad:  Mode <- ["--long", "--name-width=wide"], None;  Both => like Err(OptionsError::FailedParse(_, NumberSource::Arg(_), _)));
+        test!(name_width_grid: Mode <- ["--name-width=20"],             None;  Last => like Ok(Mode::Grid(_)));
+        test!(name_width_grid_2: Mode <- ["--name-width=20"],           None;  Complain => err OptionsError::Useless(&flags::NAME_WIDTH, false, &flags::LONG));
+        test!(ellipsis_grid:     Mode <- ["--ellipsis=..."],            None;  Complain => err OptionsError::Useless(&flags::ELLIPSIS, false, &flags::LONG));
+
+        // Git summaries
+        test!(no_git_summary:     Mode <- ["--long", "--git"],                            None;  Both => like Ok(Mode::Details(details::Options { git_summary: false, .. })));
+        test!(git_summary:        Mode <- ["--long", "--git", "--git-summary=always"],    None;  Both => like Ok(Mode::Details(details::Options { git_summary: true, .. })));
+        test!(git_summary_never:  Mode <- ["--long", "--git", "--git-summary=never"],     None;  Both => like Ok(Mode::Details(details::Options { git_summary: false, .. })));
+        test!(git_summary_bad:    Mode <- ["--long", "--git", "--git-summary=sometimes"], None;  Both => err OptionsError::BadArgument(&flags::GIT_SUMMARY, OsString::from("sometimes")));
+        test!(git_summary_no_git: Mode <- ["--long", "--git-summary=always"],             None;  Last => like Ok(Mode::Details(details::Options { git_summary: true, .. })));
+        test!(git_summary_no_git_2: Mode <- ["--long", "--git-summary=always"],           None;  Complain => err OptionsError::Useless(&flags::GIT_SUMMARY, false, &flags::GIT));
+        test!(git_summary_grid:   Mode <- ["--git-summary=always"],                       None;  Complain => err OptionsError::Useless(&flags::GIT_SUMMARY, false, &flags::LONG));
+
+        // Repeated flags
+        test!(long_long:     Mode <- ["--long", "--long"],   None;  Last => like Ok(Mode::Details(_)));
+        test!(long_long_2:   Mode <- ["--long", "--long"],   None;  Complain => err OptionsError::Duplicate(Flag::Long("long"), Flag::Long("long")));
+        test!(long_ell:      Mode <- ["--long", "-l"],       None;  Complain => err OptionsError::Duplicate(Flag::Long("long"), Flag::Short(b'l')));
+        test!(inode_inode:   Mode <- ["-li", "--inode"],     None;  Complain => err OptionsError::Duplicate(Flag::Short(b'i'), Flag::Long("inode")));
     }
 }
@@ -4,7 +4,11 @@ use std::iter::Sum;
 use std::ops::{Add, Deref, DerefMut};
 
 use nu_ansi_term::{AnsiString as ANSIString, AnsiStrings as ANSIStrings, Style};
-use unicode_width::UnicodeWidthStr;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// What a truncated cell ends with, unless another ellipsis is picked with
+/// `--ellipsis` or `EZA_ELLIPSIS`.
+pub const ELLIPSIS: &str = "…";
 
 /// An individual cell that holds text in a table, used in the details and
 /// lines views to store ANSI-terminal-formatted data before it is printed.
@@ -91,6 +95,92 @@ impl TextCell {
         (*self.width) += *other.width;
         self.contents.0.extend(other.contents.0);
     }
+
+    /// Pads this cell with spaces, or truncates it, so that it’s exactly the
+    /// given width. A truncated cell ends with the given ellipsis in the
+    /// style of the text that was cut off, with room left for however many
+    /// columns the ellipsis takes up. Characters are never split in two, and
+    /// zero-width ones, such as combining accents, stay with the character
+    /// before them.
+    ///
+    /// Strings that are escape sequences, such as the ones that start and
+    /// end a hyperlink, take up no columns, and are kept whole even after
+    /// the cut, so that a hyperlink still gets closed.
+    pub fn fit_to_width(&mut self, width: usize, ellipsis: &str) {
+        let visible = self
+            .contents
+            .0
+            .iter()
+            .filter(|string| !is_escape_sequence(string))
+            .map(|string| UnicodeWidthStr::width(string.as_str()))
+            .sum::<usize>();
+        if visible <= width {
+            self.add_spaces(width - visible);
+            self.width = DisplayWidth::from(width);
+            return;
+        }
+
+        let strings = std::mem::take(&mut self.contents.0);
+        self.width = DisplayWidth::from(0);
+        if width == 0 {
+            return;
+        }
+
+        // Leave enough columns for the ellipsis.
+        let name_room = width.saturating_sub(UnicodeWidthStr::width(ellipsis));
+        let mut used = 0;
+        let mut cut = false;
+
+        for string in strings {
+            if is_escape_sequence(&string) {
+                self.push(string, 0);
+                continue;
+            } else if cut {
+                continue;
+            }
+
+            let style = *string.style_ref();
+            let mut kept = String::new();
+
+            for c in string.as_str().chars() {
+                let char_width = UnicodeWidthChar::width(c).unwrap_or(0);
+                if used + char_width > name_room {
+                    cut = true;
+                    break;
+                }
+
+                used += char_width;
+                kept.push(c);
+            }
+
+            self.push(style.paint(kept), 0);
+
+            if cut {
+                // An ellipsis wider than the whole cell gets cut short itself.
+                let mut shown = String::new();
+                for c in ellipsis.chars() {
+                    let char_width = UnicodeWidthChar::width(c).unwrap_or(0);
+                    if used + char_width > width {
+                        break;
+                    }
+
+                    used += char_width;
+                    shown.push(c);
+                }
+
+                self.push(style.paint(shown), 0);
+            }
+        }
+
+        self.width = DisplayWidth::from(used);
+        self.add_spaces(width - used);
+    }
+}
+
+/// Whether the given string is a terminal escape sequence, rather than text
+/// to be displayed.
+fn is_escape_sequence(string: &ANSIString<'_>) -> bool {
+    string.as_str().starts_with('\x1B')
 }
 
 // I’d like to eventually abstract cells so that instead of *every* cell
@@ -268,3 +358,65 @@ mod width_unit_test {
         assert_eq!(*(cell + 8), 17);
     }
 }
+
+#[cfg(test)]
+mod fit_test {
+    use super::*;
+
+    fn fitted(text: &'static str, width: usize, ellipsis: &str) -> (String, usize) {
+        let mut cell = TextCell::paint_str(Style::default(), text);
+        cell.fit_to_width(width, ellipsis);
+        (cell.strings().to_string(), *cell.width)
+    }
+
+    #[test]
+    fn default_ellipsis() {
+        assert_eq!(fitted("abcdefgh", 4, ELLIPSIS), ("abc…".into(), 4));
+        assert_eq!(fitted("abc", 4, ELLIPSIS), ("abc ".into(), 4));
+    }
+
+    #[test]
+    fn multi_char_ellipsis() {
+        assert_eq!(fitted("abcdefgh", 6, "..."), ("abc...".into(), 6));
+        assert_eq!(fitted("abcdef", 6, "..."), ("abcdef".into(), 6));
+        assert_eq!(fitted("日本語です", 6, "..."), ("日... ".into(), 6));
+    }
+
+    #[test]
+    fn wide_ellipsis() {
+        assert_eq!(fitted("abcdefgh", 5, "〜"), ("abc〜".into(), 5));
+        assert_eq!(fitted("abcdefgh", 5, "〜〜"), ("a〜〜".into(), 5));
+    }
+
+    #[test]
+    fn narrower_than_ellipsis() {
+        assert_eq!(fitted("abcdefgh", 2, "..."), ("..".into(), 2));
+        assert_eq!(fitted("abcdefgh", 1, "〜"), (" ".into(), 1));
+    }
+
+    #[test]
+    fn empty_ellipsis() {
+        assert_eq!(fitted("abcdefgh", 3, ""), ("abc".into(), 3));
+    }
+
+    fn hyperlinked(text: &'static str, width: usize) -> (String, usize) {
+        let mut cell = TextCell::default();
+        cell.push(ANSIString::from("\x1B]8;;file:///tmp/a\x1B\\"), 0);
+        cell.append(TextCell::paint_str(Style::default(), text));
+        cell.push(ANSIString::from("\x1B]8;;\x1B\\"), 0);
+        cell.fit_to_width(width, ELLIPSIS);
+        (cell.strings().to_string(), *cell.width)
+    }
+
+    #[test]
+    fn hyperlink_kept_whole() {
+        assert_eq!(
+            hyperlinked("abcdefgh", 4),
+            ("\x1B]8;;file:///tmp/a\x1B\\abc…\x1B]8;;\x1B\\".into(), 4)
+        );
+        assert_eq!(
+            hyperlinked("abc", 4),
+            ("\x1B]8;;file:///tmp/a\x1B\\abc\x1B]8;;\x1B\\ ".into(), 4)
+        );
+    }
+}
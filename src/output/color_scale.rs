@@ -1,3 +1,6 @@
+use std::time::Duration;
+
+use chrono::Utc;
 use log::trace;
 use nu_ansi_term::{Color as Colour, Style};
 use palette::{FromColor, LinSrgb, Oklab, Srgb};
@@ -12,14 +15,38 @@ pub struct ColorScaleOptions {
     pub mode: ColorScaleMode,
     pub min_luminance: isize,
 
+    /// The luminance of the newest or largest values, from
+    /// `EZA_MAX_LUMINANCE`. It’s never below `min_luminance`.
+    pub max_luminance: isize,
+
     pub size: bool,
     pub age: bool,
+    pub inode: bool,
+    pub links: bool,
+
+    /// The time that the age scale goes by in every timestamp column, such
+    /// as the access time with `age:atime`, rather than each column going
+    /// by its own time.
+    pub age_time: Option<TimeType>,
+
+    /// The colours to use for the gradient instead of making each column’s
+    /// own colour lighter or darker, if any were given.
+    pub gradient: Option<GradientStops>,
+
+    /// How new a time has to be to count as recent, from
+    /// `EZA_RECENT_THRESHOLD`. When given, the age scale runs from that
+    /// long ago up to now, and anything older gets the oldest colour.
+    pub recent_threshold: Option<Duration>,
 }
 
 #[derive(PartialEq, Eq, Debug, Copy, Clone)]
 pub enum ColorScaleMode {
     Fixed,
     Gradient,
+
+    /// Like `Gradient`, but going by orders of magnitude, so a few huge or
+    /// old files don’t leave all the others the same colour.
+    Logarithmic,
 }
 
 #[derive(Copy, Clone, Debug)]
@@ -32,6 +59,8 @@ pub struct ColorScaleInformation {
     pub modified: Option<Extremes>,
 
     pub size: Option<Extremes>,
+    pub inode: Option<Extremes>,
+    pub links: Option<Extremes>,
 }
 
 impl ColorScaleInformation {
@@ -53,6 +82,8 @@ impl ColorScaleInformation {
                 created: None,
                 modified: None,
                 size: None,
+                inode: None,
+                links: None,
             };
 
             update_information_recursively(
@@ -65,28 +96,61 @@ impl ColorScaleInformation {
                 r,
             );
 
+            if let (true, Some(threshold)) = (color_scale.age, color_scale.recent_threshold) {
+                let recent = Some(Extremes::recent(threshold));
+                information.accessed = recent;
+                information.changed = recent;
+                information.created = recent;
+                information.modified = recent;
+            }
+
             Some(information)
         }
     }
 
-    pub fn adjust_style(&self, mut style: Style, value: f32, range: Option<Extremes>) -> Style {
-        if let (Some(fg), Some(range)) = (style.foreground, range) {
-            let mut ratio = ((value - range.min) / (range.max - range.min)).clamp(0.0, 1.0);
-            if ratio.is_nan() {
-                ratio = 1.0;
-            }
+    pub fn adjust_style(&self, style: Style, value: f32, range: Option<Extremes>) -> Style {
+        match range {
+            Some(range) => self.adjust_style_by_ratio(style, self.ratio(value, range)),
+            None => style,
+        }
+    }
+
+    /// How far along the range the value is, from 0 at its minimum to 1 at
+    /// its maximum. On a logarithmic scale, each order of magnitude of the
+    /// distance from the minimum counts the same.
+    fn ratio(&self, value: f32, range: Extremes) -> f32 {
+        let offset = value - range.min;
+        let span = range.max - range.min;
+        let ratio = match self.options.mode {
+            ColorScaleMode::Logarithmic => offset.max(0.0).ln_1p() / span.ln_1p(),
+            ColorScaleMode::Fixed | ColorScaleMode::Gradient => offset / span,
+        };
+
+        if ratio.is_nan() {
+            1.0
+        } else {
+            ratio.clamp(0.0, 1.0)
+        }
+    }
 
-            style.foreground = Some(adjust_luminance(
-                fg,
-                ratio,
-                self.options.min_luminance as f32 / 100.0,
-            ));
+    fn adjust_style_by_ratio(&self, mut style: Style, ratio: f32) -> Style {
+        if let Some(fg) = style.foreground {
+            style.foreground = Some(match self.options.gradient {
+                Some(gradient) => gradient.colour_at(ratio),
+                None => adjust_luminance(
+                    fg,
+                    ratio,
+                    self.options.min_luminance as f32 / 100.0,
+                    self.options.max_luminance as f32 / 100.0,
+                ),
+            });
         }
 
         style
     }
 
     pub fn apply_time_gradient(&self, style: Style, file: &File<'_>, time_type: TimeType) -> Style {
+        let time_type = self.options.age_time.unwrap_or(time_type);
         let range = match time_type {
             TimeType::Modified => self.modified,
             TimeType::Changed => self.changed,
@@ -94,10 +158,25 @@ impl ColorScaleInformation {
             TimeType::Created => self.created,
         };
 
-        if let Some(file_time) = time_type.get_corresponding_time(file) {
-            self.adjust_style(style, file_time.and_utc().timestamp_millis() as f32, range)
-        } else {
-            style
+        let Some(file_time) = time_type.get_corresponding_time(file) else {
+            return style;
+        };
+        let value = file_time.and_utc().timestamp_millis() as f32;
+
+        match range {
+            // On a logarithmic scale, it’s how long ago each time was that
+            // counts, so the newest times are the most spread out.
+            Some(range)
+                if self.options.mode == ColorScaleMode::Logarithmic && range.max > range.min =>
+            {
+                let ages = Extremes {
+                    max: range.max - range.min,
+                    min: 0.0,
+                };
+                let ratio = 1.0 - self.ratio(range.max - value, ages);
+                self.adjust_style_by_ratio(style, ratio)
+            }
+            _ => self.adjust_style(style, value, range),
         }
     }
 }
@@ -143,6 +222,16 @@ fn update_information_recursively(
             Extremes::update(size, &mut information.size);
         }
 
+        #[cfg(unix)]
+        if information.options.inode {
+            Extremes::update(Some(file.inode().0 as f32), &mut information.inode);
+        }
+
+        #[cfg(unix)]
+        if information.options.links {
+            Extremes::update(Some(file.links().count as f32), &mut information.links);
+        }
+
         // We don't want to recurse into . and .., but still want to list them, therefore bypass
         // the dot_filter.
         if file.is_directory()
@@ -180,6 +269,16 @@ pub struct Extremes {
 }
 
 impl Extremes {
+    /// The range of times, as used for the age scale, from the given
+    /// threshold ago up to now.
+    fn recent(threshold: Duration) -> Self {
+        let now = Utc::now().timestamp_millis();
+        Self {
+            max: now as f32,
+            min: (now - threshold.as_millis() as i64) as f32,
+        }
+    }
+
     fn update(maybe_value: Option<f32>, maybe_range: &mut Option<Extremes>) {
         match (maybe_value, maybe_range) {
             (Some(value), Some(range)) => {
@@ -202,7 +301,75 @@ impl Extremes {
     }
 }
 
-fn adjust_luminance(color: Colour, x: f32, min_l: f32) -> Colour {
+/// The colours a custom gradient passes through, in order, from the smallest
+/// or oldest value to the largest or newest. They’re kept in an array so the
+/// options can stay `Copy`.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub struct GradientStops {
+    stops: [(u8, u8, u8); Self::MAX],
+    len: usize,
+}
+
+impl GradientStops {
+    /// The most colours a gradient can have.
+    pub const MAX: usize = 8;
+
+    /// Parses a list of hex colours, such as `#1e90ff,#ff4500`, separated by
+    /// commas or spaces, with or without a leading `#`. Returns `None` if any
+    /// of them isn’t a colour, or if there are fewer than two or too many.
+    pub fn parse(input: &str) -> Option<Self> {
+        let mut stops = [(0, 0, 0); Self::MAX];
+        let mut len = 0;
+
+        for word in input.split([',', ' ']).filter(|w| !w.is_empty()) {
+            let hex = word.strip_prefix('#').unwrap_or(word);
+            if hex.len() != 6 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+                return None;
+            }
+
+            let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+            *stops.get_mut(len)? = (channel(0)?, channel(2)?, channel(4)?);
+            len += 1;
+        }
+
+        (len >= 2).then_some(Self { stops, len })
+    }
+
+    /// The colour at the given point along the gradient, from 0 at the
+    /// first stop to 1 at the last. Colours between two stops are mixed in
+    /// the Oklab colour space, so the steps look even.
+    pub fn colour_at(&self, ratio: f32) -> Colour {
+        let position = ratio.clamp(0.0, 1.0) * (self.len - 1) as f32;
+        let index = (position.floor() as usize).min(self.len - 2);
+        let t = position - index as f32;
+
+        let from = Self::oklab(self.stops[index]);
+        let to = Self::oklab(self.stops[index + 1]);
+        let mixed = Oklab::new(
+            from.l + (to.l - from.l) * t,
+            from.a + (to.a - from.a) * t,
+            from.b + (to.b - from.b) * t,
+        );
+
+        let rgb: Srgb<f32> = Srgb::from_color(mixed);
+        Colour::Rgb(
+            (rgb.red.clamp(0.0, 1.0) * 255.0).round() as u8,
+            (rgb.green.clamp(0.0, 1.0) * 255.0).round() as u8,
+            (rgb.blue.clamp(0.0, 1.0) * 255.0).round() as u8,
+        )
+    }
+
+    fn oklab((r, g, b): (u8, u8, u8)) -> Oklab {
+        let rgb = Srgb::new(
+            f32::from(r) / 255.0,
+            f32::from(g) / 255.0,
+            f32::from(b) / 255.0,
+        );
+        Oklab::from_color(rgb)
+    }
+}
+
+fn adjust_luminance(color: Colour, x: f32, min_l: f32, max_l: f32) -> Colour {
     let rgb_color = match color {
         Colour::Rgb(r, g, b) => LinSrgb::new(
             f32::from(r) / 255.0,
@@ -236,7 +403,7 @@ fn adjust_luminance(color: Colour, x: f32, min_l: f32) -> Colour {
     };
 
     let mut lab: Oklab = Oklab::from_color(rgb_color);
-    lab.l = (min_l + (1.0 - min_l) * (-4.0 * (1.0 - x)).exp()).clamp(0.0, 1.0);
+    lab.l = (min_l + (max_l - min_l) * (-4.0 * (1.0 - x)).exp()).clamp(0.0, 1.0);
 
     let adjusted_rgb: Srgb<f32> = Srgb::from_color(lab);
     Colour::Rgb(
@@ -245,3 +412,276 @@ fn adjust_luminance(color: Colour, x: f32, min_l: f32) -> Colour {
         (adjusted_rgb.blue * 255.0).round() as u8,
     )
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fs::test_dir::TestDir;
+
+    #[test]
+    fn two_stops() {
+        let gradient = GradientStops::parse("#000000,#ffffff").unwrap();
+        assert_eq!(gradient.colour_at(0.0), Colour::Rgb(0, 0, 0));
+        assert_eq!(gradient.colour_at(1.0), Colour::Rgb(255, 255, 255));
+    }
+
+    #[test]
+    fn three_stops() {
+        let gradient = GradientStops::parse("1e90ff #ffd700 ff4500").unwrap();
+        assert_eq!(gradient.colour_at(0.0), Colour::Rgb(0x1e, 0x90, 0xff));
+        assert_eq!(gradient.colour_at(0.5), Colour::Rgb(0xff, 0xd7, 0x00));
+        assert_eq!(gradient.colour_at(1.0), Colour::Rgb(0xff, 0x45, 0x00));
+    }
+
+    #[test]
+    fn out_of_range() {
+        let gradient = GradientStops::parse("#000000,#ffffff").unwrap();
+        assert_eq!(gradient.colour_at(-1.0), Colour::Rgb(0, 0, 0));
+        assert_eq!(gradient.colour_at(2.0), Colour::Rgb(255, 255, 255));
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(GradientStops::parse("#000000"), None);
+        assert_eq!(GradientStops::parse("#000000,red"), None);
+        assert_eq!(GradientStops::parse("#000000,#fffffff"), None);
+        assert_eq!(GradientStops::parse(&"#000000,".repeat(9)), None);
+    }
+
+    /// Information for scaling sizes in the given mode, without a custom
+    /// gradient.
+    fn size_information(mode: ColorScaleMode) -> ColorScaleInformation {
+        ColorScaleInformation {
+            options: ColorScaleOptions {
+                mode,
+                min_luminance: 40,
+                max_luminance: 100,
+                size: true,
+                age: false,
+                inode: false,
+                links: false,
+                age_time: None,
+                gradient: None,
+                recent_threshold: None,
+            },
+            accessed: None,
+            changed: None,
+            created: None,
+            modified: None,
+            size: None,
+            inode: None,
+            links: None,
+        }
+    }
+
+    #[test]
+    fn logarithmic_ratio() {
+        let range = Extremes { min: 0.0, max: 1e9 };
+        let linear = size_information(ColorScaleMode::Gradient);
+        let log = size_information(ColorScaleMode::Logarithmic);
+
+        // A megabyte is barely along a linear scale up to a gigabyte, but
+        // two thirds of the way along a logarithmic one.
+        assert!(linear.ratio(1e6, range) < 0.01);
+        assert!(log.ratio(1e6, range) > 0.6);
+        assert_eq!(log.ratio(0.0, range), 0.0);
+        assert_eq!(log.ratio(1e9, range), 1.0);
+        assert_eq!(log.ratio(5.0, Extremes { min: 5.0, max: 5.0 }), 1.0);
+    }
+
+    #[test]
+    fn logarithmic_min_luminance() {
+        // The smallest value still gets the minimum luminance, the same as
+        // it would on a linear scale.
+        let range = Some(Extremes { min: 0.0, max: 1e9 });
+        let style = Colour::Blue.normal();
+        let log = size_information(ColorScaleMode::Logarithmic).adjust_style(style, 0.0, range);
+        let linear = size_information(ColorScaleMode::Gradient).adjust_style(style, 0.0, range);
+        assert_eq!(log.foreground, linear.foreground);
+        assert_ne!(log.foreground, Some(Colour::Rgb(0, 0, 0)));
+    }
+
+    #[test]
+    fn max_luminance() {
+        let range = Some(Extremes { min: 0.0, max: 1e9 });
+        let style = Colour::White.normal();
+        let mut information = size_information(ColorScaleMode::Gradient);
+        let brightest = information.adjust_style(style, 1e9, range);
+
+        // A lower maximum makes the largest value dimmer.
+        information.options.max_luminance = 70;
+        let dimmer = information.adjust_style(style, 1e9, range);
+        assert_ne!(brightest.foreground, dimmer.foreground);
+
+        // With no room between the two, every value gets the same colour.
+        information.options.max_luminance = 40;
+        assert_eq!(
+            information.adjust_style(style, 0.0, range).foreground,
+            information.adjust_style(style, 1e9, range).foreground
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn inode_and_links_gradient() {
+        use crate::fs::fields as f;
+        use crate::output::cell::TextCell;
+        use crate::output::render::LinksColours;
+        use crate::output::table::InodeFormat;
+
+        struct Plain;
+
+        impl LinksColours for Plain {
+            fn normal(&self) -> Style {
+                Colour::Blue.normal()
+            }
+            fn multi_link_file(&self) -> Style {
+                Colour::Blue.normal()
+            }
+            fn min_links_file(&self) -> Style {
+                Colour::Blue.normal()
+            }
+        }
+
+        let gradient = GradientStops::parse("#000000,#ffffff").unwrap();
+        let mut information = size_information(ColorScaleMode::Gradient);
+        information.options.gradient = Some(gradient);
+        information.inode = Some(Extremes {
+            min: 100.0,
+            max: 300.0,
+        });
+        information.links = Some(Extremes { min: 1.0, max: 5.0 });
+
+        // The smallest value gets the first colour, the largest gets the
+        // last, and everything else falls in between.
+        for (inode, ratio) in [(100, 0.0), (150, 0.25), (200, 0.5), (300, 1.0)] {
+            let expected = TextCell::paint(gradient.colour_at(ratio).normal(), inode.to_string());
+            let cell = f::Inode(inode).render(
+                Colour::Blue.normal(),
+                InodeFormat::Decimal,
+                Some(information),
+            );
+            assert_eq!(cell, expected);
+        }
+
+        for (count, ratio) in [(1, 0.0), (3, 0.5), (5, 1.0)] {
+            let links = f::Links {
+                count,
+                multiple: true,
+            };
+            let expected = TextCell::paint(gradient.colour_at(ratio).normal(), count.to_string());
+            let cell = links.render(&Plain, &locale::Numeric::english(), None, Some(information));
+            assert_eq!(cell, expected);
+        }
+
+        // Without a range, which is when the column wasn’t asked for, the
+        // colour stays as it is.
+        information.inode = None;
+        let cell = f::Inode(200).render(
+            Colour::Blue.normal(),
+            InodeFormat::Decimal,
+            Some(information),
+        );
+        assert_eq!(cell, TextCell::paint_str(Colour::Blue.normal(), "200"));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn age_by_access_time() {
+        let dir = TestDir::new("age-atime");
+        for (name, accessed) in [("older", "202101010000"), ("newer", "202301010000")] {
+            let path = dir.join(name);
+            std::fs::write(&path, "").unwrap();
+            for (flag, time) in [("-m", "202001010000"), ("-a", accessed)] {
+                let status = std::process::Command::new("touch")
+                    .args([flag, "-t", time])
+                    .arg(&path)
+                    .status()
+                    .unwrap();
+                assert!(status.success());
+            }
+        }
+
+        let files = ["older", "newer"]
+            .iter()
+            .map(|name| File::from_args(dir.join(name), None, None, false, false).unwrap())
+            .collect::<Vec<_>>();
+        let colours = |age_time| {
+            let mut options = size_information(ColorScaleMode::Gradient).options;
+            options.size = false;
+            options.age = true;
+            options.age_time = age_time;
+            let information = ColorScaleInformation::from_color_scale(
+                options,
+                &files,
+                DotFilter::default(),
+                None,
+                false,
+                None,
+            )
+            .unwrap();
+            files
+                .iter()
+                .map(|file| {
+                    information
+                        .apply_time_gradient(Colour::Blue.normal(), file, TimeType::Modified)
+                        .foreground
+                })
+                .collect::<Vec<_>>()
+        };
+        let by_modified = colours(None);
+        let by_accessed = colours(Some(TimeType::Accessed));
+
+        // The modified times are the same, but the access times aren’t.
+        assert_eq!(by_modified[0], by_modified[1]);
+        assert_ne!(by_accessed[0], by_accessed[1]);
+    }
+
+    #[test]
+    fn recent_threshold() {
+        use crate::output::time::is_recent;
+        use chrono::{DateTime, FixedOffset, Local};
+
+        let week = Duration::from_secs(7 * 86_400);
+        let options = ColorScaleOptions {
+            mode: ColorScaleMode::Gradient,
+            min_luminance: 40,
+            max_luminance: 100,
+            size: false,
+            age: true,
+            inode: false,
+            links: false,
+            age_time: None,
+            gradient: GradientStops::parse("#000000,#ffffff"),
+            recent_threshold: Some(week),
+        };
+        let information = ColorScaleInformation::from_color_scale(
+            options,
+            &[],
+            DotFilter::default(),
+            None,
+            false,
+            None,
+        )
+        .unwrap();
+
+        let now: DateTime<FixedOffset> = Local::now().into();
+        let colour = |days| {
+            let time = now - chrono::Duration::days(days);
+            let style = Colour::Blue.normal();
+            let value = time.timestamp_millis() as f32;
+            let colour = information.adjust_style(style, value, information.modified);
+            (is_recent(&time, Some(week)), colour.foreground)
+        };
+
+        // Just inside the threshold, a file is recent for time styles and
+        // gets a newer colour than the oldest; just outside, it’s neither.
+        let (inside, inside_colour) = colour(6);
+        let (outside, outside_colour) = colour(8);
+        let (_, oldest_colour) = colour(365);
+        assert!(inside);
+        assert_ne!(inside_colour, oldest_colour);
+        assert!(!outside);
+        assert_eq!(outside_colour, oldest_colour);
+    }
+}
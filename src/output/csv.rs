@@ -0,0 +1,110 @@
+//! The CSV view, which writes a header row and then one row per file, with
+//! the long view’s columns as delimited fields, for importing into a
+//! spreadsheet. The fields are quoted as in RFC 4180.
+//!
+//! The values are the same ones the details view would show, but without
+//! any colours, padding, or icons.
+
+use std::io::{self, Write};
+
+use crate::fs::feature::git::GitCache;
+use crate::fs::filter::FileFilter;
+use crate::fs::File;
+use crate::output::table::{Options as TableOptions, Table};
+use crate::output::{encode_name, TextCellContents};
+use crate::theme::Theme;
+
+#[derive(PartialEq, Eq, Debug)]
+pub struct Options {
+    /// The character to put between fields, a comma unless
+    /// `--csv-delimiter` says otherwise.
+    pub delimiter: char,
+
+    /// Which columns to write, and how to format them, as in the details
+    /// view.
+    pub table: TableOptions,
+}
+
+pub struct Render<'a> {
+    pub files: Vec<File<'a>>,
+    pub theme: &'a Theme,
+    pub opts: &'a Options,
+    pub filter: &'a FileFilter,
+    pub git: Option<&'a GitCache>,
+    pub git_repos: bool,
+}
+
+impl<'a> Render<'a> {
+    pub fn render<W: Write>(mut self, w: &mut W) -> io::Result<()> {
+        self.filter.sort_files(&mut self.files, self.git);
+        let table = Table::new(&self.opts.table, self.git, self.theme, self.git_repos);
+        let columns = table.columns();
+
+        let headers = columns.iter().map(|c| c.header()).chain(["Name"]);
+        self.write_row(w, headers)?;
+
+        for file in &self.files {
+            let cells = columns
+                .iter()
+                .map(|column| plain(&table.cell_for_file(file, *column).contents))
+                .collect::<Vec<_>>();
+
+            // Files given as arguments are written with their whole path,
+            // as they are in the other views.
+            let name = if file.parent_dir.is_none() {
+                encode_name(file.path.as_os_str())
+            } else {
+                encode_name(file.os_name())
+            };
+
+            self.write_row(w, cells.iter().map(String::as_str).chain([name.as_str()]))?;
+        }
+
+        Ok(())
+    }
+
+    fn write_row<'f, W: Write>(
+        &self,
+        w: &mut W,
+        fields: impl Iterator<Item = &'f str>,
+    ) -> io::Result<()> {
+        let fields = fields
+            .map(|field| quote(field, self.opts.delimiter))
+            .collect::<Vec<_>>();
+        writeln!(w, "{}", fields.join(&self.opts.delimiter.to_string()))
+    }
+}
+
+/// The text of a cell without any of its styles.
+fn plain(contents: &TextCellContents) -> String {
+    contents.iter().map(|s| s.as_str()).collect()
+}
+
+/// Quotes a field if it has the delimiter, a quote, or a line break in it,
+/// doubling any quotes inside it.
+fn quote(field: &str, delimiter: char) -> String {
+    if field.contains([delimiter, '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn unquoted() {
+        assert_eq!(quote("plain text", ','), "plain text");
+        assert_eq!(quote("a,b", '\t'), "a,b");
+    }
+
+    #[test]
+    fn quoted() {
+        assert_eq!(quote("a,b", ','), "\"a,b\"");
+        assert_eq!(quote("a\tb", '\t'), "\"a\tb\"");
+        assert_eq!(quote("say \"hi\"", ','), "\"say \"\"hi\"\"\"");
+        assert_eq!(quote("two\nlines", ','), "\"two\nlines\"");
+    }
+}
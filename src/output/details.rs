@@ -59,8 +59,9 @@
 //! means that we must wait until every row has been added to the table before it
 //! can be displayed, in order to make sure that every column is wide enough.
 
+use std::ffi::OsString;
 use std::io::{self, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::vec::IntoIter as VecIntoIter;
 
 use nu_ansi_term::Style;
@@ -69,16 +70,20 @@ use rayon::prelude::*;
 use log::*;
 
 use crate::fs::dir_action::RecurseOptions;
+use crate::fs::feature::archive::{self, ArchiveEntry};
 use crate::fs::feature::git::GitCache;
 use crate::fs::feature::xattr::Attribute;
 use crate::fs::fields::SecurityContextType;
 use crate::fs::filter::FileFilter;
-use crate::fs::{Dir, File};
-use crate::output::cell::TextCell;
+use crate::fs::{Ancestors, Dir, DirId, File};
+use crate::output::cell::{TextCell, TextCellContents};
 use crate::output::color_scale::{ColorScaleInformation, ColorScaleOptions};
-use crate::output::file_name::Options as FileStyle;
+use crate::output::file_name::{Options as FileStyle, QuoteStyle};
+use crate::output::git_summary::GitSummary;
+use crate::output::progress::Progress;
 use crate::output::table::{Options as TableOptions, Row as TableRow, Table};
-use crate::output::tree::{TreeDepth, TreeParams, TreeTrunk};
+use crate::output::tree::{IndentedEntry, TreeDepth, TreeParams, TreeTrunk};
+use crate::output::{escape, escape_os};
 use crate::theme::Theme;
 
 /// With the **Details** view, the output gets formatted into columns, with
@@ -114,6 +119,23 @@ pub struct Options {
     /// Whether to show a directory's mounted filesystem details
     pub mounts: bool,
 
+    /// Whether to list the top-level entries of archives underneath them.
+    pub archives: bool,
+
+    /// Whether to write the tree in the indented format, which can be read
+    /// back in later, instead of drawing it with a table and tree lines.
+    pub indented: bool,
+
+    /// The exact width to pad or truncate each file’s name to, if any.
+    pub name_width: Option<usize>,
+
+    /// What to end names that have been truncated to fit with.
+    pub ellipsis: String,
+
+    /// Whether to follow the table with a count of the files by their Git
+    /// status, when Git is being used.
+    pub git_summary: bool,
+
     pub color_scale: ColorScaleOptions,
 }
 
@@ -138,12 +160,21 @@ pub struct Render<'a> {
     pub git: Option<&'a GitCache>,
 
     pub git_repos: bool,
+
+    /// How many levels below the command-line arguments the files are,
+    /// for the depth column. Tree views add their own depth to this.
+    pub depth: usize,
+
+    /// The counter to add each directory to as it’s read, for
+    /// `--progress`, which has to be cleared before the rows get written.
+    pub progress: Option<&'a Progress>,
 }
 
 #[rustfmt::skip]
 struct Egg<'a> {
     table_row: Option<TableRow>,
     xattrs:    &'a [Attribute],
+    archive:   Vec<ArchiveEntry>,
     errors:    Vec<(io::Error, Option<PathBuf>)>,
     dir:       Option<Dir>,
     file:      &'a File<'a>,
@@ -159,6 +190,10 @@ impl<'a> Render<'a> {
     pub fn render<W: Write>(mut self, w: &mut W) -> io::Result<()> {
         let mut rows = Vec::new();
 
+        // The directory being listed counts as entered already, so a hard
+        // link back to it isn’t followed either.
+        let ancestors = Ancestors::default().with(self.dir.and_then(|d| DirId::from_path(&d.path)));
+
         let color_scale_info = ColorScaleInformation::from_color_scale(
             self.opts.color_scale,
             &self.files,
@@ -168,7 +203,23 @@ impl<'a> Render<'a> {
             self.recurse,
         );
 
-        if let Some(ref table) = self.opts.table {
+        if self.opts.indented {
+            self.add_files_to_table(
+                &mut None,
+                &mut rows,
+                &mut None,
+                &self.files,
+                TreeDepth::root(),
+                0,
+                &ancestors,
+                color_scale_info,
+            );
+            self.clear_progress();
+
+            for row in rows {
+                writeln!(w, "{}", row.name.strings())?;
+            }
+        } else if let Some(ref table) = self.opts.table {
             match (self.git, self.dir) {
                 (Some(g), Some(d)) => {
                     if !g.has_anything_for(&d.path) {
@@ -194,25 +245,39 @@ impl<'a> Render<'a> {
             // This is weird, but I can’t find a way around it:
             // https://internals.rust-lang.org/t/should-option-mut-t-implement-copy/3715/6
             let mut table = Some(table);
+            let mut git_summary =
+                (self.opts.git_summary && self.git.is_some()).then(GitSummary::default);
             self.add_files_to_table(
                 &mut table,
                 &mut rows,
+                &mut git_summary,
                 &self.files,
                 TreeDepth::root(),
+                0,
+                &ancestors,
                 color_scale_info,
             );
+            self.clear_progress();
 
             for row in self.iterate_with_table(table.unwrap(), rows) {
                 writeln!(w, "{}", row.strings())?;
             }
+
+            if let Some(summary) = git_summary {
+                writeln!(w, "{summary}")?;
+            }
         } else {
             self.add_files_to_table(
                 &mut None,
                 &mut rows,
+                &mut None,
                 &self.files,
                 TreeDepth::root(),
+                0,
+                &ancestors,
                 color_scale_info,
             );
+            self.clear_progress();
 
             for row in self.iterate(rows) {
                 writeln!(w, "{}", row.strings())?;
@@ -222,6 +287,12 @@ impl<'a> Render<'a> {
         Ok(())
     }
 
+    fn clear_progress(&self) {
+        if let Some(progress) = self.progress {
+            progress.clear();
+        }
+    }
+
     /// Whether to show the extended attribute hint
     pub fn show_xattr_hint(&self, file: &File<'_>) -> bool {
         // Do not show the hint '@' if the only extended attribute is the security
@@ -236,13 +307,20 @@ impl<'a> Render<'a> {
     }
 
     /// Adds files to the table, possibly recursively. This is easily
-    /// parallelisable, and uses a pool of threads.
+    /// parallelisable, and uses a pool of threads. The Git status of each
+    /// file is counted in the summary, if there is one. The files are
+    /// `folded` levels deeper than their depth in the tree, as that many
+    /// directories above them were collapsed onto other lines.
+    #[allow(clippy::too_many_arguments)]
     fn add_files_to_table<'dir>(
         &self,
         table: &mut Option<Table<'a>>,
         rows: &mut Vec<Row>,
+        git_summary: &mut Option<GitSummary>,
         src: &[File<'dir>],
         depth: TreeDepth,
+        folded: usize,
+        ancestors: &Ancestors,
         color_scale_info: Option<ColorScaleInformation>,
     ) {
         use crate::fs::feature::xattr;
@@ -274,26 +352,52 @@ impl<'a> Render<'a> {
                 // printed unless the user passes --extended to signify
                 // that they want to see them.
 
-                let xattrs: &[Attribute] = if xattr::ENABLED && self.opts.xattr {
-                    file.extended_attributes()
-                } else {
-                    &[]
-                };
-
-                let table_row = table
-                    .as_ref()
-                    .map(|t| t.row_for_file(file, self.show_xattr_hint(file), color_scale_info));
+                let xattrs: &[Attribute] =
+                    if xattr::ENABLED && self.opts.xattr && !self.opts.indented {
+                        file.extended_attributes()
+                    } else {
+                        &[]
+                    };
+
+                let table_row = table.as_ref().map(|t| {
+                    t.row_for_file(
+                        file,
+                        self.depth + depth.0 + folded,
+                        self.show_xattr_hint(file),
+                        color_scale_info,
+                    )
+                });
+
+                // Archives are only peeked into when asked, as it means
+                // opening and reading every one of them.
+                let mut archive = Vec::new();
+                if self.opts.archives && !self.opts.indented && file.is_file() {
+                    match archive::top_level_entries(&file.path, file.ext.as_deref()) {
+                        Some(Ok(entries)) => archive = entries,
+                        Some(Err(e)) => errors.push((e, None)),
+                        None => {}
+                    }
+                }
 
                 let mut dir = None;
                 if let Some(r) = self.recurse {
-                    if file.is_directory() && r.tree && !r.is_too_deep(depth.0) {
-                        trace!("matching on to_dir");
-                        match file.to_dir() {
-                            Ok(d) => {
-                                dir = Some(d);
-                            }
-                            Err(e) => {
-                                errors.push((e, None));
+                    if file.is_directory()
+                        && r.tree
+                        && !r.is_too_deep(depth.0 + folded)
+                        && !self.filter.prune_patterns.is_pruned(file)
+                    {
+                        if ancestors.contains(DirId::from_metadata(&file.metadata)) {
+                            errors.push((Ancestors::loop_error(), None));
+                        } else {
+                            trace!("matching on to_dir");
+                            match file.to_dir() {
+                                Ok(d) => {
+                                    dir = Some(d);
+                                }
+                                Err(e) if r.skips_error(&e) => {}
+                                Err(e) => {
+                                    errors.push((e, None));
+                                }
                             }
                         }
                     }
@@ -302,6 +406,7 @@ impl<'a> Render<'a> {
                 Egg {
                     table_row,
                     xattrs,
+                    archive,
                     errors,
                     dir,
                     file,
@@ -310,9 +415,26 @@ impl<'a> Render<'a> {
             .collect();
 
         // this is safe because all entries have been initialized above
-        self.filter.sort_files(&mut file_eggs);
+        self.filter.sort_files(&mut file_eggs, self.git);
+
+        // Trees cut each directory down to the per-directory limit here,
+        // including the directory being listed, but not the files given as
+        // arguments. The other views have it done before they’re rendered.
+        let hidden = match self.recurse {
+            Some(r) if r.tree && (depth.0 > 0 || self.dir.is_some()) => {
+                r.limit_entries(&mut file_eggs)
+            }
+            _ => 0,
+        };
 
         for (tree_params, egg) in depth.iterate_over(file_eggs.into_iter()) {
+            // The note about the left-out entries comes last instead.
+            let tree_params = if hidden > 0 {
+                TreeParams::new(depth, false)
+            } else {
+                tree_params
+            };
+
             let mut files = Vec::new();
             let mut errors = egg.errors;
 
@@ -320,13 +442,53 @@ impl<'a> Render<'a> {
                 t.add_widths(row);
             }
 
-            let file_name = self
-                .file_style
-                .for_file(egg.file, self.theme)
-                .with_link_paths()
-                .with_mount_details(self.opts.mounts)
-                .paint()
-                .promote();
+            let mut dir = egg.dir;
+            let mut dir_ancestors = ancestors.with(DirId::from_metadata(&egg.file.metadata));
+            // Collapsed names can’t be read back in from the indented
+            // format, as they’d look like paths.
+            let chain = match (dir.as_mut(), self.recurse) {
+                (Some(dir), Some(r)) if r.collapse && !self.opts.indented => self.collapse(
+                    dir,
+                    egg.file.deref_links,
+                    &mut dir_ancestors,
+                    r,
+                    depth.0 + folded,
+                ),
+                _ => Vec::new(),
+            };
+            let folded_below = folded + chain.len();
+
+            let file_name = if self.opts.indented {
+                let entry = IndentedEntry {
+                    depth: depth.0,
+                    name: egg.file.os_name().to_owned(),
+                    is_directory: egg.file.is_directory(),
+                };
+                TextCell::paint(Style::default(), entry.to_string())
+            } else {
+                let mut file_name = self
+                    .file_style
+                    .for_file(egg.file, self.theme)
+                    .with_link_paths()
+                    .with_mount_details(self.opts.mounts)
+                    .with_git_ignored(self.git)
+                    .paint()
+                    .promote();
+
+                for (link, style) in chain {
+                    let mut bits = vec![style.paint("/")];
+                    escape_os(
+                        &link,
+                        &mut bits,
+                        style,
+                        self.theme.ui.control_char,
+                        self.file_style.quote_style,
+                    );
+                    file_name.append(TextCellContents::from(bits).promote());
+                }
+
+                file_name
+            };
 
             debug!("file_name {:?}", file_name);
 
@@ -338,7 +500,15 @@ impl<'a> Render<'a> {
 
             rows.push(row);
 
-            if let Some(ref dir) = egg.dir {
+            // Directories take their status from the files inside them, so
+            // counting them as well would count those changes twice.
+            if let (Some(summary), Some(git)) = (git_summary.as_mut(), self.git) {
+                if !egg.file.is_directory() {
+                    summary.add(git.get(&egg.file.path, false));
+                }
+            }
+
+            if let Some(ref dir) = dir {
                 for file_to_add in dir.files(
                     self.filter.dot_filter,
                     self.git,
@@ -357,6 +527,13 @@ impl<'a> Render<'a> {
                 }
 
                 self.filter.filter_child_files(&mut files);
+                if let Some(progress) = self.progress {
+                    progress.add_dir(files.len());
+                }
+
+                if self.opts.indented {
+                    Self::report_errors(&mut errors, &egg.file.path);
+                }
 
                 if !files.is_empty() {
                     for xattr in egg.xattrs {
@@ -371,16 +548,39 @@ impl<'a> Render<'a> {
                         ));
                     }
 
-                    self.add_files_to_table(table, rows, &files, depth.deeper(), color_scale_info);
+                    self.add_files_to_table(
+                        table,
+                        rows,
+                        git_summary,
+                        &files,
+                        depth.deeper(),
+                        folded_below,
+                        &dir_ancestors,
+                        color_scale_info,
+                    );
                     continue;
                 }
             }
 
+            if self.opts.indented {
+                Self::report_errors(&mut errors, &egg.file.path);
+            }
+
             let count = egg.xattrs.len();
             for (index, xattr) in egg.xattrs.iter().enumerate() {
+                let params = TreeParams::new(
+                    depth.deeper(),
+                    errors.is_empty() && egg.archive.is_empty() && index == count - 1,
+                );
+                let r = self.render_xattr(xattr, params);
+                rows.push(r);
+            }
+
+            let count = egg.archive.len();
+            for (index, entry) in egg.archive.iter().enumerate() {
                 let params =
                     TreeParams::new(depth.deeper(), errors.is_empty() && index == count - 1);
-                let r = self.render_xattr(xattr, params);
+                let r = self.render_archive_entry(entry, params);
                 rows.push(r);
             }
 
@@ -391,6 +591,78 @@ impl<'a> Render<'a> {
                 rows.push(r);
             }
         }
+
+        // The indented format only holds entries, so it’s left out there.
+        if hidden > 0 && !self.opts.indented {
+            rows.push(self.render_hidden_note(hidden, depth));
+        }
+    }
+
+    /// Follows a directory down through any directories that hold nothing
+    /// but one other directory, for `--collapse`. This replaces `dir` with
+    /// the last directory reached, and returns the names of the ones passed
+    /// through on the way, with their styles, so they can be shown on the
+    /// same line as the first. The directory is `depth` levels down, and
+    /// the chain stops before any directory too deep to recurse into.
+    fn collapse(
+        &self,
+        dir: &mut Dir,
+        deref_links: bool,
+        ancestors: &mut Ancestors,
+        recurse: RecurseOptions,
+        depth: usize,
+    ) -> Vec<(OsString, Style)> {
+        use crate::output::file_name::Colours;
+
+        let mut chain = Vec::new();
+
+        loop {
+            let Ok(mut files) = dir
+                .files(
+                    self.filter.dot_filter,
+                    self.git,
+                    self.git_ignoring,
+                    deref_links,
+                    false,
+                )
+                .collect::<Result<Vec<_>, _>>()
+            else {
+                break;
+            };
+            self.filter.filter_child_files(&mut files);
+
+            let [only] = &files[..] else {
+                break;
+            };
+            let id = DirId::from_metadata(&only.metadata);
+            if !only.is_directory()
+                || recurse.is_too_deep(depth + chain.len() + 1)
+                || self.filter.prune_patterns.is_pruned(only)
+                || ancestors.contains(id)
+            {
+                break;
+            }
+
+            let Ok(next) = only.to_dir() else {
+                break;
+            };
+            chain.push((only.os_name().to_owned(), self.theme.colour_file(only)));
+            *ancestors = ancestors.with(id);
+
+            drop(files);
+            *dir = next;
+        }
+
+        chain
+    }
+
+    /// Writes errors to standard error, for when they can’t be shown in the
+    /// tree itself without breaking its format.
+    fn report_errors(errors: &mut Vec<(io::Error, Option<PathBuf>)>, file_path: &Path) {
+        for (error, path) in errors.drain(..) {
+            let path = path.as_deref().unwrap_or(file_path);
+            eprintln!("{}: {}", path.display(), error);
+        }
     }
 
     pub fn render_header(&self, header: TableRow) -> Row {
@@ -420,6 +692,14 @@ impl<'a> Render<'a> {
         }
     }
 
+    fn render_hidden_note(&self, hidden: usize, depth: TreeDepth) -> Row {
+        Row {
+            cells: None,
+            name: TextCell::paint(self.theme.ui.punctuation, hidden_note(hidden)),
+            tree: TreeParams::new(depth, true),
+        }
+    }
+
     fn render_xattr(&self, xattr: &Attribute, tree: TreeParams) -> Row {
         let name = TextCell::paint(self.theme.ui.perms.attribute, format!("{xattr}"));
         Row {
@@ -429,6 +709,34 @@ impl<'a> Render<'a> {
         }
     }
 
+    fn render_archive_entry(&self, entry: &ArchiveEntry, tree: TreeParams) -> Row {
+        let style = if entry.is_directory {
+            self.theme.ui.filekinds.directory
+        } else {
+            self.theme.ui.filekinds.normal
+        };
+
+        // The names come from the archive’s headers, so they can have any
+        // control characters in them, which get escaped like file names.
+        let mut bits = Vec::new();
+        escape(
+            entry.name.clone(),
+            &mut bits,
+            style,
+            self.theme.ui.control_char,
+            self.file_style.quote_style,
+        );
+        if entry.is_directory {
+            bits.push(style.paint("/"));
+        }
+
+        Row {
+            cells: None,
+            name: TextCellContents::from(bits).promote(),
+            tree,
+        }
+    }
+
     pub fn iterate_with_table(&'a self, table: Table<'a>, rows: Vec<Row>) -> TableIter<'a> {
         TableIter {
             tree_trunk: TreeTrunk::default(),
@@ -436,6 +744,8 @@ impl<'a> Render<'a> {
             table,
             inner: rows.into_iter(),
             tree_style: self.theme.ui.punctuation,
+            name_width: self.opts.name_width,
+            ellipsis: &self.opts.ellipsis,
         }
     }
 
@@ -448,6 +758,60 @@ impl<'a> Render<'a> {
     }
 }
 
+/// The note saying how many of a directory’s entries were left out by
+/// `--per-dir-limit`.
+pub fn hidden_note(hidden: usize) -> String {
+    format!("(+{hidden} more)")
+}
+
+/// Draws a tree that was read back in from the indented format. There are
+/// no files to look at, so only the names are shown, coloured by whether
+/// they are directories.
+pub fn render_indented<W: Write>(
+    entries: Vec<(TreeParams, IndentedEntry)>,
+    theme: &Theme,
+    quote_style: QuoteStyle,
+    w: &mut W,
+) -> io::Result<()> {
+    let rows = entries
+        .into_iter()
+        .map(|(tree, entry)| {
+            let style = if entry.is_directory {
+                theme.ui.filekinds.directory
+            } else {
+                theme.ui.filekinds.normal
+            };
+
+            let mut bits = Vec::new();
+            escape_os(
+                &entry.name,
+                &mut bits,
+                style,
+                theme.ui.control_char,
+                quote_style,
+            );
+
+            Row {
+                cells: None,
+                name: TextCellContents::from(bits).promote(),
+                tree,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let iter = Iter {
+        tree_trunk: TreeTrunk::default(),
+        tree_style: theme.ui.punctuation,
+        inner: rows.into_iter(),
+    };
+
+    for cell in iter {
+        writeln!(w, "{}", cell.strings())?;
+    }
+
+    Ok(())
+}
+
 pub struct Row {
     /// Vector of cells to display.
     ///
@@ -473,6 +837,8 @@ pub struct TableIter<'a> {
     total_width: usize,
     tree_style:  Style,
     tree_trunk:  TreeTrunk,
+    name_width:  Option<usize>,
+    ellipsis:    &'a str,
 }
 
 impl<'a> Iterator for TableIter<'a> {
@@ -498,7 +864,12 @@ impl<'a> Iterator for TableIter<'a> {
                 cell.add_spaces(1);
             }
 
-            cell.append(row.name);
+            let mut name = row.name;
+            if let Some(width) = self.name_width {
+                name.fit_to_width(width, self.ellipsis);
+            }
+
+            cell.append(name);
             cell
         })
     }
@@ -532,3 +903,456 @@ impl Iterator for Iter {
         })
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fs::test_dir::TestDir;
+    use crate::options::{Options, OptionsResult};
+    use crate::output::Mode;
+    use std::ffi::OsStr;
+
+    /// Makes a tree with files and directories mixed together at two
+    /// levels, named so that sorting by name interleaves them.
+    fn mixed_tree(name: &str) -> TestDir {
+        let root = TestDir::new(name);
+        std::fs::create_dir_all(root.join("a_dir/y_dir")).unwrap();
+        std::fs::create_dir_all(root.join("c_dir")).unwrap();
+        for file in ["a_dir/m_file", "a_dir/y_dir/x_file", "b_file", "d_file"] {
+            std::fs::write(root.join(file), "").unwrap();
+        }
+        root
+    }
+
+    /// Renders the given directory as an indented tree.
+    fn render_tree(root: &Path, args: &[&str]) -> String {
+        render_with(
+            root,
+            &[&["--tree", "--tree-format=indent"][..], args].concat(),
+        )
+    }
+
+    /// Renders the given directory with the given arguments, which should
+    /// select a details view.
+    fn render_with(root: &Path, args: &[&str]) -> String {
+        render_with_git(root, args, None)
+    }
+
+    /// Renders the given directory as above, looking up Git statuses in the
+    /// given cache.
+    fn render_with_git(root: &Path, args: &[&str], git: Option<&GitCache>) -> String {
+        let OptionsResult::Ok(options, _) = Options::parse(args.iter().map(OsStr::new), &None)
+        else {
+            panic!("Failed to parse");
+        };
+        let Mode::Details(ref opts) = options.view.mode else {
+            panic!("Not a details view");
+        };
+
+        let theme = options.theme.to_theme(false);
+        let file =
+            File::from_args(root.to_path_buf(), None, "root".to_string(), false, false).unwrap();
+        let render = Render {
+            dir: None,
+            files: vec![file],
+            theme: &theme,
+            file_style: &options.view.file_style,
+            opts,
+            recurse: options.dir_action.recurse_options(),
+            filter: &options.filter,
+            git_ignoring: false,
+            git,
+            git_repos: false,
+            depth: 0,
+            progress: None,
+        };
+
+        let mut output = Vec::new();
+        render.render(&mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn archive_names_escaped() {
+        let args = ["--tree", "--peek-archives", "--color=never"];
+        let OptionsResult::Ok(options, _) = Options::parse(args.iter().map(OsStr::new), &None)
+        else {
+            panic!("Failed to parse");
+        };
+        let Mode::Details(ref opts) = options.view.mode else {
+            panic!("Not a details view");
+        };
+
+        let theme = options.theme.to_theme(false);
+        let render = Render {
+            dir: None,
+            files: Vec::new(),
+            theme: &theme,
+            file_style: &options.view.file_style,
+            opts,
+            recurse: options.dir_action.recurse_options(),
+            filter: &options.filter,
+            git_ignoring: false,
+            git: None,
+            git_repos: false,
+            depth: 0,
+            progress: None,
+        };
+
+        let params = TreeParams::new(TreeDepth::root(), true);
+        let file = ArchiveEntry {
+            name: String::from("evil\x1b]0;title\x07"),
+            is_directory: false,
+        };
+        let dir = ArchiveEntry {
+            name: String::from("dir\x1b[2J"),
+            is_directory: true,
+        };
+
+        let file_name = render.render_archive_entry(&file, params).name;
+        let dir_name = render.render_archive_entry(&dir, params).name;
+        assert_eq!(file_name.strings().to_string(), "evil\\u{1b}]0;title\\u{7}");
+        assert_eq!(dir_name.strings().to_string(), "dir\\u{1b}[2J/");
+    }
+
+    #[test]
+    fn tree_sorted_by_name() {
+        let root = mixed_tree("tree-by-name");
+        let output = render_tree(&root, &[]);
+
+        assert_eq!(
+            output,
+            "root/\n  a_dir/\n    m_file\n    y_dir/\n      x_file\n  b_file\n  c_dir/\n  d_file\n"
+        );
+    }
+
+    #[test]
+    fn tree_directories_first() {
+        let root = mixed_tree("tree-dirs-first");
+        let output = render_tree(&root, &["--group-directories-first"]);
+
+        assert_eq!(
+            output,
+            "root/\n  a_dir/\n    y_dir/\n      x_file\n    m_file\n  c_dir/\n  b_file\n  d_file\n"
+        );
+    }
+
+    #[test]
+    fn tree_directories_last() {
+        let root = mixed_tree("tree-dirs-last");
+        let output = render_tree(&root, &["--group-directories-last"]);
+
+        assert_eq!(
+            output,
+            "root/\n  b_file\n  d_file\n  a_dir/\n    m_file\n    y_dir/\n      x_file\n  c_dir/\n"
+        );
+    }
+
+    #[test]
+    fn tree_per_dir_limit() {
+        let root = mixed_tree("tree-per-dir-limit");
+        let output = render_tree(&root, &["--per-dir-limit=2"]);
+
+        // Only entries go in the indented format, so there’s no note.
+        assert_eq!(
+            output,
+            "root/\n  a_dir/\n    m_file\n    y_dir/\n      x_file\n  b_file\n"
+        );
+    }
+
+    #[test]
+    fn tree_per_dir_limit_lines() {
+        let root = mixed_tree("tree-per-dir-limit-lines");
+        let args = [
+            "--tree",
+            "--long",
+            "--no-permissions",
+            "--no-filesize",
+            "--no-user",
+            "--no-time",
+            "--per-dir-limit=1",
+        ];
+        let output = render_with(&root, &args);
+
+        // The hidden y_dir isn’t looked into, and each note is drawn as the
+        // last entry of its directory.
+        let lines = output.lines().map(str::trim).collect::<Vec<_>>();
+        assert_eq!(
+            lines,
+            [
+                "root",
+                "├── a_dir",
+                "│  ├── m_file",
+                "│  └── (+1 more)",
+                "└── (+3 more)"
+            ]
+        );
+    }
+
+    /// Renders the tree with only the name column, fixed to the given
+    /// width, and returns the name part of each line.
+    fn fixed_names(root: &Path, width: usize) -> Vec<String> {
+        fixed_names_with(root, width, &[])
+    }
+
+    /// As above, with some more arguments.
+    fn fixed_names_with(root: &Path, width: usize, extra: &[&str]) -> Vec<String> {
+        let name_width = format!("--name-width={width}");
+        let args = [
+            "--tree",
+            "--long",
+            "--no-permissions",
+            "--no-filesize",
+            "--no-user",
+            "--no-time",
+            &name_width,
+        ];
+
+        render_with(root, &[&args[..], extra].concat())
+            .lines()
+            .map(|line| {
+                line.rsplit_once("── ")
+                    .map_or(line, |(_, name)| name)
+                    .to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn name_width_pads() {
+        let root = mixed_tree("name-width-pads");
+        let names = fixed_names(&root, 6);
+
+        assert_eq!(
+            names,
+            ["root  ", "a_dir ", "m_file", "y_dir ", "x_file", "b_file", "c_dir ", "d_file"]
+        );
+    }
+
+    #[test]
+    fn name_width_truncates() {
+        let root = mixed_tree("name-width-truncates");
+        let names = fixed_names(&root, 4);
+
+        assert_eq!(
+            names,
+            ["root", "a_d…", "m_f…", "y_d…", "x_f…", "b_f…", "c_d…", "d_f…"]
+        );
+    }
+
+    #[test]
+    fn name_width_hyperlinks() {
+        let root = mixed_tree("name-width-hyperlinks");
+        let names = fixed_names_with(&root, 4, &["--hyperlink=always"]);
+
+        // The escapes around the name take up no room, and the one at the
+        // end is kept, so the link gets closed.
+        let b_file = names
+            .iter()
+            .filter_map(|name| name.strip_prefix("\x1B]8;;file://")?.split_once("\x1B\\"))
+            .find(|(path, _)| path.ends_with("/b_file"))
+            .map(|(_, rest)| rest);
+        assert_eq!(b_file, Some("b_f…\x1B]8;;\x1B\\"), "{names:?}");
+    }
+
+    #[test]
+    fn name_width_custom_ellipsis() {
+        let root = mixed_tree("name-width-ellipsis");
+        let names = fixed_names_with(&root, 5, &["--ellipsis=..."]);
+        let wide = fixed_names_with(&root, 5, &["--ellipsis=〜"]);
+
+        // The ellipsis takes up as many columns as it’s wide, so fewer of
+        // them are left for the name.
+        assert_eq!(
+            names,
+            ["root ", "a_...", "m_...", "y_...", "x_...", "b_...", "c_...", "d_..."]
+        );
+        assert_eq!(
+            wide,
+            ["root ", "a_d〜", "m_f〜", "y_d〜", "x_f〜", "b_f〜", "c_d〜", "d_f〜"]
+        );
+    }
+
+    #[test]
+    fn name_width_wide_and_combining() {
+        let root = TestDir::new("name-width-wide");
+        std::fs::write(root.join("e\u{301}e\u{301}e\u{301}"), "").unwrap();
+        std::fs::write(root.join("日本語"), "").unwrap();
+
+        let names = fixed_names(&root, 3);
+        let narrow = fixed_names(&root, 2);
+
+        // Widths count columns, so a wide character that doesn’t fit leaves
+        // a space, and accents stay with their letters.
+        assert_eq!(names, ["ro…", "e\u{301}e\u{301}e\u{301}", "日…"]);
+        assert_eq!(narrow, ["r…", "e\u{301}…", "… "]);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn tree_skips_unreadable() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let root = TestDir::new("tree-unreadable");
+        std::fs::create_dir_all(root.join("locked/inner")).unwrap();
+        std::fs::create_dir_all(root.join("open")).unwrap();
+        std::fs::write(root.join("open/file"), "").unwrap();
+
+        let locked = root.join("locked");
+        std::fs::set_permissions(&locked, std::fs::Permissions::from_mode(0o000)).unwrap();
+
+        // Root can read the directory anyway, so there’s nothing to skip.
+        // Which errors get skipped is tested without the filesystem in
+        // `fs::dir_action`, so this only checks they’re skipped in a tree.
+        let readable = std::fs::read_dir(&locked).is_ok();
+        let reported = render_with(&root, &["--tree"]);
+        let skipped = render_with(&root, &["--tree", "--skip-unreadable"]);
+
+        std::fs::set_permissions(&locked, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        if readable {
+            return;
+        }
+
+        assert!(reported.contains("Permission denied"), "{reported}");
+        assert!(!skipped.contains("Permission denied"), "{skipped}");
+        assert!(skipped.contains("locked"), "{skipped}");
+        assert!(skipped.contains("file"), "{skipped}");
+    }
+
+    #[test]
+    fn tree_depths() {
+        let root = mixed_tree("tree-depths");
+        let args = [
+            "--tree",
+            "--long",
+            "--depth",
+            "--no-permissions",
+            "--no-filesize",
+            "--no-user",
+            "--no-time",
+        ];
+        let output = render_with(&root, &args);
+
+        let depths = output
+            .lines()
+            .map(|line| {
+                let words = line.split_whitespace().collect::<Vec<_>>();
+                (words[0], words[words.len() - 1])
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            depths,
+            [
+                ("0", "root"),
+                ("1", "a_dir"),
+                ("2", "m_file"),
+                ("2", "y_dir"),
+                ("3", "x_file"),
+                ("1", "b_file"),
+                ("1", "c_dir"),
+                ("1", "d_file"),
+            ]
+        );
+    }
+
+    #[test]
+    fn tree_collapsed() {
+        let root = TestDir::new("tree-collapse");
+        std::fs::create_dir_all(root.join("a/b/c/d")).unwrap();
+        std::fs::create_dir_all(root.join("e_dir/g_dir")).unwrap();
+        std::fs::create_dir_all(root.join("z_dir")).unwrap();
+        for file in [
+            "a/b/c/d/file",
+            "a/b/c/d/other",
+            "e_dir/f_file",
+            "z_dir/only_file",
+        ] {
+            std::fs::write(root.join(file), "").unwrap();
+        }
+
+        let collapsed = drawn_names(&root, &["--collapse"]);
+        let expanded = drawn_names(&root, &[]);
+        let limited = drawn_names(&root, &["--collapse", "--level=3"]);
+        let indented = render_tree(&root, &["--collapse"]);
+
+        assert_eq!(
+            collapsed,
+            [
+                "root",
+                "a/b/c/d",
+                "file",
+                "other",
+                "e_dir",
+                "f_file",
+                "g_dir",
+                "z_dir",
+                "only_file"
+            ]
+        );
+        assert_eq!(expanded[..5], ["root", "a", "b", "c", "d"]);
+
+        // The chain stops where the recursion would have, so `c` is listed,
+        // but not looked into.
+        assert_eq!(limited[..3], ["root", "a/b", "c"]);
+        assert_eq!(limited[3], "e_dir");
+
+        // The indented format has to be read back in, so nothing in it gets
+        // collapsed.
+        assert_eq!(indented, render_tree(&root, &[]));
+    }
+
+    /// The names in the tree drawn for the given directory, without the
+    /// lines of the tree.
+    fn drawn_names(root: &Path, args: &[&str]) -> Vec<String> {
+        render_with(root, &[&["--tree"][..], args].concat())
+            .lines()
+            .map(|line| {
+                line.trim_start_matches(['│', '├', '└', '─', ' '])
+                    .to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    #[cfg(feature = "git")]
+    fn git_summary_footer() {
+        let root = TestDir::new("git-summary");
+        std::fs::create_dir_all(root.join("sub")).unwrap();
+
+        // Commit three files, then change two of them and add a fourth.
+        let repo = git2::Repository::init(&root).unwrap();
+        let committed = ["clean", "changed", "sub/changed"];
+        for file in committed {
+            std::fs::write(root.join(file), "before").unwrap();
+        }
+        let mut index = repo.index().unwrap();
+        for file in committed {
+            index.add_path(Path::new(file)).unwrap();
+        }
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = git2::Signature::now("eza", "eza@example.com").unwrap();
+        repo.commit(Some("HEAD"), &signature, &signature, "Initial", &tree, &[])
+            .unwrap();
+        std::fs::write(root.join("changed"), "after").unwrap();
+        std::fs::write(root.join("sub/changed"), "after").unwrap();
+        std::fs::write(root.join("untracked"), "new").unwrap();
+
+        let git: GitCache = [root.to_path_buf()].into_iter().collect();
+        let args = ["--long", "--tree", "--git", "--no-permissions", "--no-user"];
+        let with_summary = render_with_git(
+            &root,
+            &[&args[..], &["--git-summary=always"]].concat(),
+            Some(&git),
+        );
+        let without_summary = render_with_git(&root, &args, Some(&git));
+
+        assert_eq!(
+            with_summary.lines().last(),
+            Some("2 modified, 1 untracked, 0 conflicts")
+        );
+        assert_eq!(with_summary.lines().count(), 7);
+        assert_eq!(without_summary.lines().count(), 6);
+    }
+}
@@ -1,3 +1,7 @@
+use std::borrow::Cow;
+use std::ffi::OsStr;
+use std::io::{self, Write};
+
 use super::file_name::QuoteStyle;
 use nu_ansi_term::{AnsiString as ANSIString, Style};
 
@@ -12,10 +16,54 @@ pub fn escape(
     let needs_quotes = string.contains(' ') || string.contains('\'');
     let quote_bit = good.paint(if string.contains('\'') { "\"" } else { "\'" });
 
-    if string
-        .chars()
-        .all(|c| c >= 0x20 as char && c != 0x7f as char)
-    {
+    escape_chars(string, bits, good, bad);
+
+    if quote_style != QuoteStyle::NoQuotes && needs_quotes {
+        bits.insert(bits_starting_length, quote_bit.clone());
+        bits.push(quote_bit);
+    }
+}
+
+/// Escapes a name straight from the filesystem, which might not be valid
+/// UTF-8. Each byte that isn’t part of a valid UTF-8 sequence is kept as it
+/// is, rather than all being replaced with the same replacement character,
+/// so the name gets written out with the bytes it has on the filesystem.
+///
+/// While the name is being laid out, each of those bytes is held as a
+/// stand-in character one column wide, as terminals show an invalid byte
+/// as a single replacement character. [`RawBytesWriter`] turns them back
+/// into the bytes on the way out.
+pub fn escape_os(
+    name: &OsStr,
+    bits: &mut Vec<ANSIString<'_>>,
+    good: Style,
+    bad: Style,
+    quote_style: QuoteStyle,
+) {
+    if let Some(string) = name.to_str() {
+        return escape(string.to_owned(), bits, good, bad, quote_style);
+    }
+
+    let lossy = name.to_string_lossy();
+    let bits_starting_length = bits.len();
+    let needs_quotes = lossy.contains(' ') || lossy.contains('\'');
+    let quote_bit = good.paint(if lossy.contains('\'') { "\"" } else { "\'" });
+
+    for part in name_parts(name) {
+        match part {
+            NamePart::Text(text) => escape_chars(text.into_owned(), bits, good, bad),
+            NamePart::Byte(byte) => bits.push(good.paint(stand_in(byte).to_string())),
+        }
+    }
+
+    if quote_style != QuoteStyle::NoQuotes && needs_quotes {
+        bits.insert(bits_starting_length, quote_bit.clone());
+        bits.push(quote_bit);
+    }
+}
+
+fn escape_chars(string: String, bits: &mut Vec<ANSIString<'_>>, good: Style, bad: Style) {
+    if string.chars().all(is_printable) {
         bits.push(good.paint(string));
     } else {
         for c in string.chars() {
@@ -24,16 +72,299 @@ pub fn escape(
 
             // TODO: This allocates way too much,
             // hence the `all` check above.
-            if c >= 0x20 as char && c != 0x7f as char {
+            if is_printable(c) {
                 bits.push(good.paint(c.to_string()));
             } else {
                 bits.push(bad.paint(c.escape_default().to_string()));
             }
         }
     }
+}
 
-    if quote_style != QuoteStyle::NoQuotes && needs_quotes {
-        bits.insert(bits_starting_length, quote_bit.clone());
-        bits.push(quote_bit);
+/// Writes a name as text for the structured outputs, such as CSV, which
+/// can’t hold raw bytes. Each byte that isn’t part of a valid UTF-8
+/// sequence is written as a `\xNN` escape, and each backslash is doubled, so
+/// an escape can’t be mistaken for part of the name.
+#[cfg(unix)]
+pub fn encode_name(name: &OsStr) -> String {
+    let mut encoded = String::new();
+
+    for part in name_parts(name) {
+        match part {
+            NamePart::Text(text) => encoded.push_str(&text.replace('\\', "\\\\")),
+            NamePart::Byte(byte) => encoded.push_str(&format!("\\x{byte:02x}")),
+        }
+    }
+
+    encoded
+}
+
+/// Writes a name as text for the structured outputs, such as CSV. Names off
+/// Unix aren’t made of bytes, so they’re written as they are.
+#[cfg(not(unix))]
+pub fn encode_name(name: &OsStr) -> String {
+    name.to_string_lossy().into_owned()
+}
+
+/// Whether a character can be written as it is. A name that really has one
+/// of the stand-in characters in it gets it escaped, so it can’t be mistaken
+/// for an invalid byte.
+fn is_printable(c: char) -> bool {
+    c >= 0x20 as char && c != 0x7f as char && stand_in_byte(c).is_none()
+}
+
+/// The first of the characters that stand in for bytes that aren’t valid
+/// UTF-8 while a name is being laid out. They come from the end of the last
+/// private use plane, which no real file name should be using.
+const STAND_IN_BASE: u32 = 0x10_FF00;
+
+/// The UTF-8 encoding of the stand-in characters, U+10FF00 to U+10FFFF, is
+/// these two bytes followed by a byte from 0xBC to 0xBF and a continuation
+/// byte.
+const STAND_IN_LEAD: [u8; 2] = [0xF4, 0x8F];
+
+/// The character that stands in for an invalid byte.
+fn stand_in(byte: u8) -> char {
+    char::from_u32(STAND_IN_BASE + u32::from(byte)).unwrap_or(char::REPLACEMENT_CHARACTER)
+}
+
+/// The byte a stand-in character is standing in for, if it is one.
+pub fn stand_in_byte(c: char) -> Option<u8> {
+    u8::try_from(u32::from(c).checked_sub(STAND_IN_BASE)?).ok()
+}
+
+/// Whether some bytes could be the start of the UTF-8 encoding of a stand-in
+/// character, or all of it.
+fn could_be_stand_in(bytes: &[u8]) -> bool {
+    bytes.iter().enumerate().all(|(index, byte)| match index {
+        0 | 1 => *byte == STAND_IN_LEAD[index],
+        2 => (0xBC..=0xBF).contains(byte),
+        _ => (0x80..=0xBF).contains(byte),
+    })
+}
+
+/// A writer that turns the stand-in characters from [`escape_os`] back into
+/// the bytes they stand for, so names that aren’t valid UTF-8 are written
+/// out exactly as they are on the filesystem.
+pub struct RawBytesWriter<W> {
+    inner: W,
+
+    /// The end of the last write, if it could be the start of a stand-in
+    /// character that the next write finishes.
+    partial: Vec<u8>,
+}
+
+impl<W: Write> RawBytesWriter<W> {
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner,
+            partial: Vec::new(),
+        }
+    }
+
+    /// The writer being written to. Anything held back in case it was the
+    /// start of a stand-in character has to be flushed first.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: Write> Write for RawBytesWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.partial.is_empty() && !buf.contains(&STAND_IN_LEAD[0]) {
+            return self.inner.write(buf);
+        }
+
+        let mut bytes = std::mem::take(&mut self.partial);
+        bytes.extend_from_slice(buf);
+
+        let mut out = Vec::with_capacity(bytes.len());
+        let mut rest = &bytes[..];
+        while let Some(start) = rest.iter().position(|&b| b == STAND_IN_LEAD[0]) {
+            out.extend_from_slice(&rest[..start]);
+            rest = &rest[start..];
+
+            let candidate = &rest[..rest.len().min(4)];
+            if !could_be_stand_in(candidate) {
+                out.push(rest[0]);
+                rest = &rest[1..];
+            } else if candidate.len() < 4 {
+                self.partial = candidate.to_vec();
+                rest = &[];
+            } else {
+                out.push(((candidate[2] - 0xBC) << 6) | (candidate[3] & 0x3F));
+                rest = &rest[4..];
+            }
+        }
+        out.extend_from_slice(rest);
+
+        self.inner.write_all(&out)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        let partial = std::mem::take(&mut self.partial);
+        self.inner.write_all(&partial)?;
+        self.inner.flush()
+    }
+}
+
+/// A piece of a name that might not be valid UTF-8: either a run of valid
+/// text, or a single byte that isn’t part of any valid UTF-8 sequence.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum NamePart<'a> {
+    Text(Cow<'a, str>),
+    Byte(u8),
+}
+
+/// Splits a name into runs of valid UTF-8 text and the invalid bytes
+/// between them.
+#[cfg(unix)]
+pub fn name_parts(name: &OsStr) -> Vec<NamePart<'_>> {
+    use std::os::unix::ffi::OsStrExt;
+
+    let mut parts = Vec::new();
+    let mut bytes = name.as_bytes();
+
+    while !bytes.is_empty() {
+        match std::str::from_utf8(bytes) {
+            Ok(text) => {
+                parts.push(NamePart::Text(Cow::Borrowed(text)));
+                break;
+            }
+            Err(e) => {
+                let (valid, rest) = bytes.split_at(e.valid_up_to());
+                if !valid.is_empty() {
+                    parts.push(NamePart::Text(String::from_utf8_lossy(valid)));
+                }
+
+                // A sequence cut off by the end of the name has no error
+                // length, so the rest of the bytes are all invalid.
+                let invalid = e.error_len().unwrap_or(rest.len());
+                parts.extend(rest[..invalid].iter().map(|b| NamePart::Byte(*b)));
+                bytes = &rest[invalid..];
+            }
+        }
+    }
+
+    parts
+}
+
+/// Splits a name into runs of valid UTF-8 text and the invalid bytes
+/// between them. Names off Unix aren’t made of bytes, so anything invalid
+/// is converted lossily.
+#[cfg(not(unix))]
+pub fn name_parts(name: &OsStr) -> Vec<NamePart<'_>> {
+    vec![NamePart::Text(name.to_string_lossy())]
+}
+
+/// Turns bytes read back from a `\xNN` escape, along with the rest of the
+/// name, into a name that can be used on the filesystem.
+#[cfg(unix)]
+pub fn name_from_bytes(bytes: Vec<u8>) -> std::ffi::OsString {
+    use std::os::unix::ffi::OsStringExt;
+
+    std::ffi::OsString::from_vec(bytes)
+}
+
+/// Turns bytes read back from a `\xNN` escape, along with the rest of the
+/// name, into a name. Off Unix, any invalid bytes are converted lossily.
+#[cfg(not(unix))]
+pub fn name_from_bytes(bytes: Vec<u8>) -> std::ffi::OsString {
+    String::from_utf8_lossy(&bytes).into_owned().into()
+}
+
+#[cfg(test)]
+#[cfg(unix)]
+mod test {
+    use super::*;
+    use std::os::unix::ffi::OsStrExt;
+
+    fn laid_out(name: &[u8]) -> String {
+        let mut bits = Vec::new();
+        let plain = Style::default();
+        escape_os(
+            OsStr::from_bytes(name),
+            &mut bits,
+            plain,
+            plain,
+            QuoteStyle::QuoteSpaces,
+        );
+        nu_ansi_term::AnsiStrings(&bits).to_string()
+    }
+
+    fn written(text: &str, split_at: usize) -> Vec<u8> {
+        let mut writer = RawBytesWriter::new(Vec::new());
+        let (first, second) = text.as_bytes().split_at(split_at);
+        writer.write_all(first).unwrap();
+        writer.write_all(second).unwrap();
+        writer.flush().unwrap();
+        writer.into_inner()
+    }
+
+    fn escaped(name: &[u8]) -> Vec<u8> {
+        written(&laid_out(name), 0)
+    }
+
+    #[test]
+    fn valid() {
+        assert_eq!(escaped(b"caf\xc3\xa9"), b"caf\xc3\xa9");
+        assert_eq!(escaped(b"tab\there"), b"tab\\there");
+    }
+
+    #[test]
+    fn invalid_bytes() {
+        assert_eq!(escaped(b"caf\xe9"), b"caf\xe9");
+        assert_eq!(escaped(b"\xff\xfe"), b"\xff\xfe");
+        assert_eq!(escaped(b"a\xc3"), b"a\xc3");
+    }
+
+    #[test]
+    fn invalid_quoted() {
+        assert_eq!(escaped(b"two words\xff"), b"'two words\xff'");
+    }
+
+    #[test]
+    fn invalid_width() {
+        use unicode_width::UnicodeWidthStr;
+
+        assert_eq!(UnicodeWidthStr::width(laid_out(b"caf\xe9").as_str()), 4);
+        assert_eq!(UnicodeWidthStr::width(laid_out(b"\xff\xfe").as_str()), 2);
+    }
+
+    #[test]
+    fn split_writes() {
+        let text = laid_out(b"a\xff");
+        for split_at in 0..=text.len() {
+            assert_eq!(written(&text, split_at), b"a\xff");
+        }
+    }
+
+    #[test]
+    fn real_stand_in() {
+        assert_eq!(escaped("\u{10ff41}".as_bytes()), b"\\u{10ff41}");
+        assert_eq!(written("\u{100000}", 2), "\u{100000}".as_bytes());
+    }
+
+    #[test]
+    fn encoded() {
+        assert_eq!(encode_name(OsStr::new("plain")), "plain");
+        assert_eq!(encode_name(OsStr::from_bytes(b"caf\xe9")), "caf\\xe9");
+        assert_eq!(encode_name(OsStr::new("caf\\xe9")), "caf\\\\xe9");
+    }
+
+    #[test]
+    fn parts() {
+        let name = OsStr::from_bytes(b"ok\xffstill ok\xc3");
+        assert_eq!(
+            name_parts(name),
+            vec![
+                NamePart::Text(Cow::Borrowed("ok")),
+                NamePart::Byte(0xff),
+                NamePart::Text(Cow::Borrowed("still ok")),
+                NamePart::Byte(0xc3),
+            ]
+        );
+        assert_eq!(name_from_bytes(name.as_bytes().to_vec()), name);
     }
 }
@@ -1,14 +1,17 @@
+use std::ffi::OsString;
 use std::fmt::Debug;
-use std::path::Path;
+use std::path::{Component, Path, PathBuf};
 
 use nu_ansi_term::{AnsiString as ANSIString, Style};
 use path_clean;
 use unicode_width::UnicodeWidthStr;
 
+use crate::fs::feature::git::GitCache;
+use crate::fs::fields::{GitStatus, Lock};
 use crate::fs::{File, FileTarget};
 use crate::output::cell::TextCellContents;
-use crate::output::escape;
-use crate::output::icons::{icon_for_file, iconify_style};
+use crate::output::escape_os;
+use crate::output::icons::{icon_for_file, iconify_style, IconSet};
 use crate::output::render::FiletypeColours;
 
 /// Basically a file name factory.
@@ -17,9 +20,20 @@ pub struct Options {
     /// Whether to append file class characters to file names.
     pub classify: Classify,
 
+    /// Whether to also append the class character of a symlink’s target,
+    /// after the symlink’s own, when classifying.
+    pub classify_link_targets: bool,
+
+    /// Whether to add an `@` after the indicator of files with extended
+    /// attributes, when classifying.
+    pub classify_xattrs: bool,
+
     /// Whether to prepend icon characters before file names.
     pub show_icons: ShowIcons,
 
+    /// Which set of icons to use, when icons are shown.
+    pub icon_set: IconSet,
+
     /// How to display file names with spaces (with or without quotes).
     pub quote_style: QuoteStyle,
 
@@ -29,6 +43,22 @@ pub struct Options {
     /// Whether to display files with their absolute path.
     pub absolute: Absolute,
 
+    /// Whether to display the files inside listed directories with their
+    /// whole path, rather than just their name, with `--full-paths`.
+    pub full_paths: bool,
+
+    /// Whether to show relative symlink targets as paths from the current
+    /// directory, rather than from the directory the link is in.
+    pub resolve_link_targets: bool,
+
+    /// Whether to paint files with risky permissions in the warning style,
+    /// whatever kind of file they are.
+    pub highlight_risky: bool,
+
+    /// Whether to dim the names of files that Git ignores, rather than
+    /// hiding them, with `--mark-ignored`.
+    pub mark_ignored: bool,
+
     /// Whether we are in a console or redirecting the output
     pub is_a_tty: bool,
 }
@@ -52,6 +82,7 @@ impl Options {
                 None
             },
             mount_style: MountStyle::JustDirectoryNames,
+            ignored: false,
         }
     }
 }
@@ -83,6 +114,9 @@ pub enum Classify {
 
     // Like previous, but only when output is going to a terminal, not otherwise.
     AutomaticAddFileIndicators,
+
+    /// Add a slash after directories, and nothing after any other file.
+    AddDirectorySlashes,
 }
 
 /// When displaying a directory name, there needs to be some way to handle
@@ -156,6 +190,9 @@ pub struct FileName<'a, 'dir, C> {
 
     /// How to handle displaying a mounted filesystem.
     mount_style: MountStyle,
+
+    /// Whether Git ignores this file, so its name gets dimmed.
+    ignored: bool,
 }
 
 impl<'a, 'dir, C> FileName<'a, 'dir, C> {
@@ -178,6 +215,18 @@ impl<'a, 'dir, C> FileName<'a, 'dir, C> {
         };
         self
     }
+
+    /// Sets the flag on this file name to dim it if Git ignores it, when
+    /// ignored files are being marked instead of hidden.
+    pub fn with_git_ignored(mut self, git: Option<&GitCache>) -> Self {
+        if self.options.mark_ignored {
+            let status = git
+                .map(|g| g.get(&self.file.path, false))
+                .unwrap_or_default();
+            self.ignored = status.unstaged == GitStatus::Ignored;
+        }
+        self
+    }
 }
 
 impl<'a, 'dir, C: Colours> FileName<'a, 'dir, C> {
@@ -204,7 +253,7 @@ impl<'a, 'dir, C: Colours> FileName<'a, 'dir, C> {
 
         if let Some(spaces_count) = spaces_count_opt {
             let style = iconify_style(self.style());
-            let file_icon = icon_for_file(self.file).to_string();
+            let file_icon = icon_for_file(self.file, self.options.icon_set).to_string();
             bits.push(style.paint(file_icon));
             bits.push(style.paint(" ".repeat(spaces_count as usize)));
         }
@@ -213,6 +262,12 @@ impl<'a, 'dir, C: Colours> FileName<'a, 'dir, C> {
             if let Some(parent) = self.file.path.parent() {
                 self.add_parent_bits(&mut bits, parent);
             }
+        } else if self.options.full_paths && self.options.absolute == Absolute::Off {
+            if let Some(parent) = self.file.path.parent() {
+                // Directories listed without an argument are read from `.`,
+                // which would put a `./` at the start of every path.
+                self.add_parent_bits(&mut bits, parent.strip_prefix(".").unwrap_or(parent));
+            }
         }
 
         if !self.file.name.is_empty() {
@@ -234,18 +289,26 @@ impl<'a, 'dir, C: Colours> FileName<'a, 'dir, C> {
                     bits.push(self.colours.normal_arrow().paint("->"));
                     bits.push(Style::default().paint(" "));
 
-                    if let Some(parent) = target.path.parent() {
+                    let resolved = self.resolved_target_dir(&target.path);
+                    if let Some(parent) = resolved.as_deref().or(target.path.parent()) {
                         self.add_parent_bits(&mut bits, parent);
                     }
 
                     if !target.name.is_empty() {
                         let target_options = Options {
                             classify: Classify::JustFilenames,
+                            classify_link_targets: false,
+                            classify_xattrs: false,
                             quote_style: QuoteStyle::QuoteSpaces,
                             show_icons: ShowIcons::Never,
+                            icon_set: self.options.icon_set,
                             embed_hyperlinks: EmbedHyperlinks::Off,
                             is_a_tty: self.options.is_a_tty,
                             absolute: Absolute::Off,
+                            full_paths: false,
+                            resolve_link_targets: false,
+                            highlight_risky: self.options.highlight_risky,
+                            mark_ignored: false,
                         };
 
                         let target_name = FileName {
@@ -255,6 +318,7 @@ impl<'a, 'dir, C: Colours> FileName<'a, 'dir, C> {
                             link_style: LinkStyle::FullLinkPaths,
                             options: target_options,
                             mount_style: MountStyle::JustDirectoryNames,
+                            ignored: false,
                         };
 
                         for bit in target_name.escaped_file_name() {
@@ -265,6 +329,10 @@ impl<'a, 'dir, C: Colours> FileName<'a, 'dir, C> {
                             if let Some(class) = self.classify_char(target) {
                                 bits.push(Style::default().paint(class));
                             }
+
+                            if let Some(xattrs) = self.xattrs_char(target) {
+                                bits.push(Style::default().paint(xattrs));
+                            }
                         }
                     }
                 }
@@ -274,8 +342,14 @@ impl<'a, 'dir, C: Colours> FileName<'a, 'dir, C> {
                     bits.push(self.colours.broken_symlink().paint("->"));
                     bits.push(Style::default().paint(" "));
 
-                    escape(
-                        broken_path.display().to_string(),
+                    let resolved = self.resolved_target_dir(broken_path);
+                    let broken_path = match (resolved, broken_path.file_name()) {
+                        (Some(dir), Some(name)) => dir.join(name),
+                        _ => broken_path.clone(),
+                    };
+
+                    escape_os(
+                        broken_path.as_os_str(),
                         &mut bits,
                         self.colours.broken_filename(),
                         self.colours.broken_control_char(),
@@ -291,6 +365,17 @@ impl<'a, 'dir, C: Colours> FileName<'a, 'dir, C> {
             if let Some(class) = self.classify_char(self.file) {
                 bits.push(Style::default().paint(class));
             }
+
+            if let Some(class) = self.target_classify_char() {
+                bits.push(Style::default().paint(class));
+            }
+
+            if let Some(xattrs) = self.xattrs_char(self.file) {
+                bits.push(Style::default().paint(xattrs));
+            }
+        } else if self.options.classify == Classify::AddDirectorySlashes && self.file.is_directory()
+        {
+            bits.push(Style::default().paint("/"));
         }
 
         if self.mount_style == MountStyle::MountInfo {
@@ -319,8 +404,8 @@ impl<'a, 'dir, C: Colours> FileName<'a, 'dir, C> {
                     .paint(std::path::MAIN_SEPARATOR.to_string()),
             );
         } else if coconut >= 1 {
-            escape(
-                parent.to_string_lossy().to_string(),
+            escape_os(
+                parent.as_os_str(),
                 bits,
                 self.colours.symlink_path(),
                 self.colours.control_char(),
@@ -334,6 +419,37 @@ impl<'a, 'dir, C: Colours> FileName<'a, 'dir, C> {
         }
     }
 
+    /// The directory to show a symlink’s target in, rewritten to be relative
+    /// to the current directory, when link targets are being resolved. Only
+    /// relative targets get rewritten, and the path is cleaned up without
+    /// following any more links. The target’s own name is left alone.
+    fn resolved_target_dir(&self, target: &Path) -> Option<PathBuf> {
+        if !self.options.resolve_link_targets || target.is_absolute() {
+            return None;
+        }
+
+        let link_dir = self.file.path.parent()?;
+        let dir = path_clean::clean(link_dir.join(target.parent()?));
+
+        // A link given by its absolute path has its target’s directory
+        // walked back to from the current directory, with `..` where needed.
+        let dir = if dir.is_absolute() {
+            std::env::current_dir()
+                .ok()
+                .and_then(|cwd| relative_to(&dir, &path_clean::clean(cwd)))
+                .unwrap_or(dir)
+        } else {
+            dir
+        };
+
+        // A target in the current directory needs no path before its name.
+        if dir == Path::new(".") {
+            Some(PathBuf::new())
+        } else {
+            Some(dir)
+        }
+    }
+
     /// The character to be displayed after a file when classifying is on, if
     /// the file’s type has one associated with it.
     #[cfg(unix)]
@@ -353,6 +469,26 @@ impl<'a, 'dir, C: Colours> FileName<'a, 'dir, C> {
         }
     }
 
+    /// The character for the type of file that a symlink points to, when
+    /// link targets are being classified. Broken links don’t get one, so
+    /// they only have their `@`.
+    fn target_classify_char(&self) -> Option<&'static str> {
+        if !self.options.classify_link_targets {
+            return None;
+        }
+
+        match &self.target {
+            Some(FileTarget::Ok(target)) => self.classify_char(target),
+            _ => None,
+        }
+    }
+
+    /// The `@` added after the indicator of a file with extended attributes,
+    /// when they’re being classified too.
+    fn xattrs_char(&self, file: &File<'_>) -> Option<&'static str> {
+        (self.options.classify_xattrs && file.has_extended_attributes()).then_some("@")
+    }
+
     #[cfg(windows)]
     pub(crate) fn classify_char(&self, file: &File<'_>) -> Option<&'static str> {
         if file.is_directory() {
@@ -406,8 +542,8 @@ impl<'a, 'dir, C: Colours> FileName<'a, 'dir, C> {
             }
         }
 
-        escape(
-            self.display_name(),
+        escape_os(
+            &self.display_name(),
             &mut bits,
             file_style,
             self.colours.control_char(),
@@ -424,28 +560,33 @@ impl<'a, 'dir, C: Colours> FileName<'a, 'dir, C> {
     }
 
     /// Returns the string that should be displayed as the file's name.
-    fn display_name(&self) -> String {
+    fn display_name(&self) -> OsString {
         match self.options.absolute {
-            Absolute::On => std::env::current_dir().ok().and_then(|p| {
-                path_clean::clean(p.join(&self.file.path))
-                    .to_str()
-                    .map(std::borrow::ToOwned::to_owned)
-            }),
-            Absolute::Follow => self
-                .file
-                .absolute_path()
-                .and_then(|p| p.to_str())
-                .map(std::borrow::ToOwned::to_owned),
+            Absolute::On => std::env::current_dir()
+                .ok()
+                .map(|p| path_clean::clean(p.join(&self.file.path)).into_os_string()),
+            Absolute::Follow => self.file.absolute_path().map(|p| p.as_os_str().to_owned()),
             Absolute::Off => None,
         }
-        .unwrap_or(self.file.name.clone())
+        .unwrap_or_else(|| self.file.os_name().to_owned())
     }
 
     /// Figures out which colour to paint the filename part of the output,
     /// depending on which “type” of file it appears to be — either from the
     /// class on the filesystem or from its name. (Or the broken link colour,
-    /// if there’s nowhere else for that fact to be shown.)
+    /// if there’s nowhere else for that fact to be shown.) Files that Git
+    /// ignores get dimmed on top of that.
     pub fn style(&self) -> Style {
+        let style = self.kind_style();
+
+        if self.ignored {
+            self.colours.git_ignored(style)
+        } else {
+            style
+        }
+    }
+
+    fn kind_style(&self) -> Style {
         if let LinkStyle::JustFilenames = self.link_style {
             if let Some(ref target) = self.target {
                 if target.is_broken() {
@@ -454,6 +595,23 @@ impl<'a, 'dir, C: Colours> FileName<'a, 'dir, C> {
             }
         }
 
+        #[cfg(unix)]
+        if self.options.highlight_risky && self.file.has_risky_permissions() {
+            return self.colours.risky();
+        }
+
+        // Looking the flags up can mean opening the file, so don’t bother
+        // when there’s no style to paint them in.
+        let (immutable, append_only) = (self.colours.immutable(), self.colours.append_only());
+        if immutable != Style::default() || append_only != Style::default() {
+            // A lock without a style of its own gets the file kind’s colour.
+            match self.file.lock() {
+                Some(Lock::Immutable) if immutable != Style::default() => return immutable,
+                Some(Lock::AppendOnly) if append_only != Style::default() => return append_only,
+                _ => {}
+            }
+        }
+
         #[rustfmt::skip]
         return match self.file {
             f if f.is_mount_point()      => self.colours.mount_point(),
@@ -480,6 +638,37 @@ impl<'a, 'dir, C: Colours> FileName<'a, 'dir, C> {
     }
 }
 
+/// The path that leads from the `base` directory to `path`, going up with
+/// `..` past the components they don’t share. Both paths should be absolute
+/// and clean; paths on different prefixes, such as Windows drives, have no
+/// relative path between them.
+fn relative_to(path: &Path, base: &Path) -> Option<PathBuf> {
+    let mut path_parts = path.components().peekable();
+    let mut base_parts = base.components().peekable();
+
+    while let (Some(p), Some(b)) = (path_parts.peek(), base_parts.peek()) {
+        if p != b {
+            break;
+        }
+        path_parts.next();
+        base_parts.next();
+    }
+
+    let mut relative = PathBuf::new();
+    for part in base_parts {
+        match part {
+            Component::Normal(_) => relative.push(".."),
+            _ => return None,
+        }
+    }
+    relative.extend(path_parts);
+
+    if relative.as_os_str().is_empty() {
+        relative.push(".");
+    }
+    Some(relative)
+}
+
 /// The set of colours that are needed to paint a file name.
 pub trait Colours: FiletypeColours {
     /// The style to paint the path of a symlink’s target, up to but not
@@ -510,5 +699,549 @@ pub trait Colours: FiletypeColours {
     /// The style to paint a directory that has a filesystem mounted on it.
     fn mount_point(&self) -> Style;
 
+    /// The style to paint a file with risky permissions, with
+    /// `--highlight-risky`.
+    fn risky(&self) -> Style;
+
+    /// The style to paint a file that Git ignores, with `--mark-ignored`,
+    /// given the style it would have otherwise.
+    fn git_ignored(&self, base: Style) -> Style;
+
+    /// The style to paint a file whose flags stop it from being changed.
+    fn immutable(&self) -> Style;
+
+    /// The style to paint a file whose flags only let it be added to.
+    fn append_only(&self) -> Style;
+
     fn colour_file(&self, file: &File<'_>) -> Style;
 }
+
+#[cfg(test)]
+#[cfg(unix)]
+mod test {
+    use super::relative_to;
+    use crate::fs::test_dir::TestDir;
+    use crate::fs::File;
+    use crate::options::{Options, OptionsResult};
+    use std::ffi::OsStr;
+    use std::os::unix::fs::{symlink, PermissionsExt};
+    use std::path::{Path, PathBuf};
+
+    /// Makes a directory holding a directory, a file, an executable, a
+    /// symlink to each of them, and a broken symlink.
+    fn links(name: &str) -> TestDir {
+        let dir = TestDir::new(name);
+        std::fs::create_dir_all(dir.join("dir")).unwrap();
+        std::fs::write(dir.join("file"), "").unwrap();
+        std::fs::write(dir.join("script"), "").unwrap();
+        let executable = std::fs::Permissions::from_mode(0o755);
+        std::fs::set_permissions(dir.join("script"), executable).unwrap();
+        symlink("dir", dir.join("dir_link")).unwrap();
+        symlink("file", dir.join("file_link")).unwrap();
+        symlink("script", dir.join("script_link")).unwrap();
+        symlink("missing", dir.join("broken_link")).unwrap();
+        dir
+    }
+
+    /// Paints the name of the given file, as it would appear in a grid.
+    fn classified(path: &Path, args: &[&str]) -> String {
+        let args = [&["--classify=always"][..], args].concat();
+        let OptionsResult::Ok(options, _) = Options::parse(args.iter().map(OsStr::new), &None)
+        else {
+            panic!("Failed to parse {args:?}");
+        };
+
+        let theme = options.theme.to_theme(false);
+        let file = File::from_args(path.to_path_buf(), None, None, false, false).unwrap();
+        let name = options.view.file_style.for_file(&file, &theme).paint();
+        name.strings().to_string()
+    }
+
+    #[test]
+    fn link_targets_classified() {
+        let dir = links("classify-targets");
+        let args = ["--classify-link-targets"];
+        let dir_link = classified(&dir.join("dir_link"), &args);
+        let file_link = classified(&dir.join("file_link"), &args);
+        let script_link = classified(&dir.join("script_link"), &args);
+        let broken_link = classified(&dir.join("broken_link"), &args);
+
+        assert!(dir_link.ends_with("/dir_link@/"), "{dir_link}");
+        assert!(file_link.ends_with("/file_link@"), "{file_link}");
+        assert!(script_link.ends_with("/script_link@*"), "{script_link}");
+        assert!(broken_link.ends_with("/broken_link@"), "{broken_link}");
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn xattrs_classified() {
+        use std::ffi::CString;
+        use std::os::unix::ffi::OsStrExt;
+
+        let dir = TestDir::new("classify-xattrs");
+        let tagged = dir.join("tagged");
+        std::fs::write(&tagged, "").unwrap();
+        std::fs::write(dir.join("plain"), "").unwrap();
+
+        let path = CString::new(tagged.as_os_str().as_bytes()).unwrap();
+        let name = CString::new("user.eza").unwrap();
+        // SAFETY: Calling C function with valid strings and buffer
+        let result =
+            unsafe { libc::setxattr(path.as_ptr(), name.as_ptr(), b"1".as_ptr().cast(), 1, 0) };
+        if result != 0 {
+            // The filesystem doesn’t support user attributes.
+            return;
+        }
+
+        let args = ["--classify-xattrs"];
+        let tagged_name = classified(&tagged, &args);
+        let plain_name = classified(&dir.join("plain"), &args);
+        let unclassified = classified(&tagged, &[]);
+
+        assert!(tagged_name.ends_with("/tagged@"), "{tagged_name}");
+        assert!(plain_name.ends_with("/plain"), "{plain_name}");
+        assert!(unclassified.ends_with("/tagged"), "{unclassified}");
+    }
+
+    /// Paints the name of the given symlink followed by its target, as it
+    /// would appear in the details view.
+    fn with_target(path: &Path, args: &[&str]) -> String {
+        let OptionsResult::Ok(options, _) = Options::parse(args.iter().map(OsStr::new), &None)
+        else {
+            panic!("Failed to parse {args:?}");
+        };
+
+        let theme = options.theme.to_theme(false);
+        let file = File::from_args(path.to_path_buf(), None, None, false, false).unwrap();
+        let name = options.view.file_style.for_file(&file, &theme);
+        name.with_link_paths().paint().strings().to_string()
+    }
+
+    #[test]
+    fn link_targets_resolved() {
+        let dir = TestDir::new("resolve-targets");
+        std::fs::create_dir_all(dir.join("sub")).unwrap();
+        std::fs::write(dir.join("target"), "").unwrap();
+        symlink("../target", dir.join("sub/link")).unwrap();
+        symlink("../missing", dir.join("sub/broken")).unwrap();
+        symlink("neighbour", dir.join("sub/near")).unwrap();
+
+        let args = ["--resolve-link-targets"];
+        let link = with_target(&dir.join("sub/link"), &args);
+        let broken = with_target(&dir.join("sub/broken"), &args);
+        let near = with_target(&dir.join("sub/near"), &args);
+        let verbatim = with_target(&dir.join("sub/link"), &[]);
+
+        // The targets are shown relative to the current directory, which
+        // is the crate, so they climb out of it to reach the temporary one.
+        let cwd = std::env::current_dir().unwrap();
+        for (shown, expected) in [
+            (&link, dir.join("target")),
+            (&broken, dir.join("missing")),
+            (&near, dir.join("sub/neighbour")),
+        ] {
+            let (_, target) = shown.split_once(" -> ").unwrap();
+            assert!(target.starts_with("../"), "{shown}");
+            assert_eq!(path_clean::clean(cwd.join(target)), expected, "{shown}");
+        }
+        assert!(verbatim.ends_with("/sub/link -> ../target"), "{verbatim}");
+    }
+
+    #[test]
+    fn relative_paths() {
+        let relative = |path: &str, base: &str| relative_to(Path::new(path), Path::new(base));
+
+        assert_eq!(relative("/a/b/c", "/a/b"), Some(PathBuf::from("c")));
+        assert_eq!(relative("/a/c", "/a/b"), Some(PathBuf::from("../c")));
+        assert_eq!(relative("/x/y", "/a/b"), Some(PathBuf::from("../../x/y")));
+        assert_eq!(relative("/a", "/a/b/c"), Some(PathBuf::from("../..")));
+        assert_eq!(relative("/a/b", "/a/b"), Some(PathBuf::from(".")));
+    }
+
+    /// Whether the given file’s name is painted in the warning style.
+    fn highlighted(path: &Path, args: &[&str]) -> bool {
+        let args = [&["--color=always"][..], args].concat();
+        let OptionsResult::Ok(options, _) = Options::parse(args.iter().map(OsStr::new), &None)
+        else {
+            panic!("Failed to parse {args:?}");
+        };
+
+        let theme = options.theme.to_theme(false);
+        let file = File::from_args(path.to_path_buf(), None, None, false, false).unwrap();
+        options.view.file_style.for_file(&file, &theme).style() == theme.ui.risky
+    }
+
+    /// Makes a directory holding one file or directory with each of the
+    /// given modes.
+    fn modes(name: &str, files: &[(&str, u32)], dirs: &[(&str, u32)]) -> TestDir {
+        let dir = TestDir::new(name);
+        for (file, mode) in files {
+            std::fs::write(dir.join(file), "").unwrap();
+            std::fs::set_permissions(dir.join(file), std::fs::Permissions::from_mode(*mode))
+                .unwrap();
+        }
+        for (sub, mode) in dirs {
+            std::fs::create_dir(dir.join(sub)).unwrap();
+            std::fs::set_permissions(dir.join(sub), std::fs::Permissions::from_mode(*mode))
+                .unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn risky_highlighted() {
+        let dir = modes(
+            "risky",
+            &[("writable", 0o666), ("setuid", 0o4755), ("setgid", 0o2755)],
+            &[("open", 0o777)],
+        );
+        let args = ["--highlight-risky"];
+        let writable = highlighted(&dir.join("writable"), &args);
+        let setuid = highlighted(&dir.join("setuid"), &args);
+        let setgid = highlighted(&dir.join("setgid"), &args);
+        let open = highlighted(&dir.join("open"), &args);
+        let without_flag = highlighted(&dir.join("writable"), &[]);
+
+        assert!(writable, "world-writable file");
+        assert!(setuid, "setuid file");
+        assert!(setgid, "setgid file");
+        assert!(open, "world-writable directory");
+        assert!(!without_flag, "without --highlight-risky");
+    }
+
+    #[test]
+    fn safe_not_highlighted() {
+        let dir = modes(
+            "not-risky",
+            &[("plain", 0o644), ("script", 0o755)],
+            &[("sticky", 0o1777), ("private", 0o755)],
+        );
+        symlink("plain", dir.join("link")).unwrap();
+        let args = ["--highlight-risky"];
+        let names = ["plain", "script", "sticky", "private", "link"];
+        let results = names.map(|name| highlighted(&dir.join(name), &args));
+
+        assert_eq!(results, [false; 5]);
+    }
+
+    /// Makes a file with the given `chattr` attribute, returning `None` if
+    /// it can’t be set, such as without root or on a filesystem without
+    /// inode flags.
+    #[cfg(target_os = "linux")]
+    fn chattr(name: &str, attribute: &str) -> Option<(TestDir, PathBuf)> {
+        let dir = TestDir::new(name);
+        let file = dir.join("locked");
+        std::fs::write(&file, "").unwrap();
+        set_attribute(dir, file, attribute)
+    }
+
+    /// Makes a directory with the given `chattr` attribute, as above.
+    #[cfg(target_os = "linux")]
+    fn chattr_dir(name: &str, attribute: &str) -> Option<(TestDir, PathBuf)> {
+        let dir = TestDir::new(name);
+        let locked = dir.join("locked");
+        std::fs::create_dir(&locked).unwrap();
+        set_attribute(dir, locked, attribute)
+    }
+
+    /// Sets the attribute with `chattr`, returning `None` if it failed.
+    #[cfg(target_os = "linux")]
+    fn set_attribute(dir: TestDir, file: PathBuf, attribute: &str) -> Option<(TestDir, PathBuf)> {
+        let set = std::process::Command::new("chattr")
+            .arg(format!("+{attribute}"))
+            .arg(&file)
+            .output()
+            .is_ok_and(|output| output.status.success());
+        set.then_some((dir, file))
+    }
+
+    /// Clears the attribute set by `chattr`, so its directory can be removed.
+    #[cfg(target_os = "linux")]
+    fn unchattr(file: &Path, attribute: &str) {
+        std::process::Command::new("chattr")
+            .arg(format!("-{attribute}"))
+            .arg(file)
+            .status()
+            .unwrap();
+    }
+
+    /// The style of the given file’s name, with colours on.
+    #[cfg(target_os = "linux")]
+    fn name_style(path: &Path) -> (nu_ansi_term::Style, crate::theme::Theme) {
+        name_style_with(path, |_| {})
+    }
+
+    /// The style of the given file’s name, with colours on and the theme
+    /// changed by the given function first.
+    #[cfg(target_os = "linux")]
+    fn name_style_with(
+        path: &Path,
+        change: impl FnOnce(&mut crate::theme::Theme),
+    ) -> (nu_ansi_term::Style, crate::theme::Theme) {
+        let args = ["--color=always"];
+        let OptionsResult::Ok(options, _) = Options::parse(args.iter().map(OsStr::new), &None)
+        else {
+            panic!("Failed to parse {args:?}");
+        };
+
+        let mut theme = options.theme.to_theme(false);
+        change(&mut theme);
+        let file = File::from_args(path.to_path_buf(), None, None, false, false).unwrap();
+        let style = options.view.file_style.for_file(&file, &theme).style();
+        (style, theme)
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn immutable_painted() {
+        // Setting the attribute needs root, so there’s nothing to test
+        // without it.
+        let Some((_dir, file)) = chattr("immutable", "i") else {
+            return;
+        };
+        let (style, theme) = name_style(&file);
+        unchattr(&file, "i");
+
+        assert_eq!(style, theme.ui.immutable);
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn append_only_painted() {
+        let Some((_dir, file)) = chattr("append-only", "a") else {
+            return;
+        };
+        let (style, theme) = name_style(&file);
+        unchattr(&file, "a");
+
+        assert_eq!(style, theme.ui.append_only);
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn append_only_unset_keeps_kind() {
+        // As with only `Im` in `EZA_COLORS` after a reset.
+        let Some((_dir, locked)) = chattr_dir("append-only-unset", "a") else {
+            return;
+        };
+        let (style, theme) = name_style_with(&locked, |theme| {
+            theme.ui.immutable = nu_ansi_term::Color::Yellow.bold();
+            theme.ui.append_only = Style::default();
+        });
+        unchattr(&locked, "a");
+
+        assert_eq!(style, theme.ui.filekinds.directory);
+    }
+
+    /// Paints the name of the file with the given name in a directory
+    /// read with `--recurse`, as it would appear in a grid.
+    fn recursed(dir: &str, name: &str, args: &[&str]) -> String {
+        use crate::fs::{Dir, DotFilter};
+
+        let args = [
+            &["--recurse", "--classify=always", "--color=never"][..],
+            args,
+        ]
+        .concat();
+        let OptionsResult::Ok(options, _) = Options::parse(args.iter().map(OsStr::new), &None)
+        else {
+            panic!("Failed to parse {args:?}");
+        };
+
+        let theme = options.theme.to_theme(false);
+        let dir = Dir::read_dir(PathBuf::from(dir)).unwrap();
+        let file = dir
+            .files(DotFilter::JustFiles, None, false, false, false)
+            .flatten()
+            .find(|file| file.name == name)
+            .unwrap();
+        let name = options.view.file_style.for_file(&file, &theme).paint();
+        name.strings().to_string()
+    }
+
+    #[test]
+    fn full_paths() {
+        let args = ["--full-paths"];
+        assert_eq!(
+            recursed("./src/output", "mod.rs", &args),
+            "src/output/mod.rs"
+        );
+        assert_eq!(
+            recursed("./src/output", "render", &args),
+            "src/output/render/"
+        );
+        assert_eq!(
+            recursed("src/output/render", "size.rs", &args),
+            "src/output/render/size.rs"
+        );
+        assert_eq!(recursed(".", "src", &args), "src/");
+        assert_eq!(recursed("./src/output", "mod.rs", &[]), "mod.rs");
+    }
+
+    /// Paints the name of the given file with icons, returning the text and
+    /// the width it takes up.
+    fn with_icons(path: &Path, spacing: &str) -> (String, usize) {
+        let args = ["--icons=always", "--icon-spacing", spacing];
+        let OptionsResult::Ok(options, _) = Options::parse(args.iter().map(OsStr::new), &None)
+        else {
+            panic!("Failed to parse {args:?}");
+        };
+
+        let theme = options.theme.to_theme(false);
+        let file = File::from_args(path.to_path_buf(), None, None, false, false).unwrap();
+        let name = options.view.file_style.for_file(&file, &theme).paint();
+        (name.strings().to_string(), *name.width())
+    }
+
+    #[test]
+    fn icon_spacing() {
+        let dir = links("icon-spacing");
+        let path = dir.join("file");
+        let (one, one_width) = with_icons(&path, "1");
+        let (three, three_width) = with_icons(&path, "3");
+        let (none, none_width) = with_icons(&path, "0");
+
+        let name = path.to_string_lossy();
+        let icon = one.chars().next().unwrap();
+        assert_eq!(one, format!("{icon} {name}"));
+        assert_eq!(three, format!("{icon}   {name}"));
+        assert_eq!(none, format!("{icon}{name}"));
+        assert_eq!(three_width, one_width + 2);
+        assert_eq!(none_width, one_width - 1);
+    }
+
+    /// Paints the name of the given file with `-p`, as it would appear in a
+    /// grid.
+    fn slashed(path: &Path, args: &[&str]) -> String {
+        let args = [&["-p", "--color=never"][..], args].concat();
+        let OptionsResult::Ok(options, _) = Options::parse(args.iter().map(OsStr::new), &None)
+        else {
+            panic!("Failed to parse {args:?}");
+        };
+
+        let theme = options.theme.to_theme(false);
+        let file = File::from_args(path.to_path_buf(), None, None, false, false).unwrap();
+        let name = options.view.file_style.for_file(&file, &theme).paint();
+        name.strings().to_string()
+    }
+
+    #[test]
+    fn directory_slashes() {
+        let dir = links("slashes");
+        let directory = slashed(&dir.join("dir"), &[]);
+        let file = slashed(&dir.join("file"), &[]);
+        let script = slashed(&dir.join("script"), &[]);
+        let dir_link = slashed(&dir.join("dir_link"), &[]);
+        let with_icon = slashed(&dir.join("dir"), &["--icons=always"]);
+
+        assert!(directory.ends_with("/dir/"), "{directory}");
+        assert!(file.ends_with("/file"), "{file}");
+        assert!(script.ends_with("/script"), "{script}");
+        assert!(dir_link.ends_with("/dir_link"), "{dir_link}");
+
+        let icon = with_icon.chars().next().unwrap();
+        assert_eq!(with_icon, format!("{icon} {directory}"));
+    }
+
+    #[test]
+    fn link_targets_not_classified() {
+        let dir = links("classify-links");
+        let dir_link = classified(&dir.join("dir_link"), &[]);
+        let file_link = classified(&dir.join("file_link"), &[]);
+
+        assert!(dir_link.ends_with("/dir_link@"), "{dir_link}");
+        assert!(file_link.ends_with("/file_link@"), "{file_link}");
+    }
+
+    // Not every Unix filesystem allows names that aren’t valid UTF-8.
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn invalid_utf8() {
+        use crate::fs::{Dir, DotFilter};
+        use crate::output::escape::RawBytesWriter;
+        use std::io::Write;
+        use std::os::unix::ffi::OsStrExt;
+
+        let dir = TestDir::new("invalid-utf8");
+        std::fs::write(dir.join(OsStr::from_bytes(b"caf\xe9")), "").unwrap();
+        std::fs::write(dir.join(OsStr::from_bytes(b"caf\xe8 au lait")), "").unwrap();
+
+        let args = ["--color=never"];
+        let OptionsResult::Ok(options, _) = Options::parse(args.iter().map(OsStr::new), &None)
+        else {
+            panic!("Failed to parse {args:?}");
+        };
+        let theme = options.theme.to_theme(false);
+
+        let listing = Dir::read_dir(dir.to_path_buf()).unwrap();
+        let mut names = listing
+            .files(DotFilter::JustFiles, None, false, false, false)
+            .flatten()
+            .map(|file| {
+                let name = options.view.file_style.for_file(&file, &theme).paint();
+                let mut written = RawBytesWriter::new(Vec::new());
+                write!(written, "{}", name.strings()).unwrap();
+                written.flush().unwrap();
+                (
+                    file.os_name().as_bytes().to_vec(),
+                    written.into_inner(),
+                    *name.width(),
+                )
+            })
+            .collect::<Vec<_>>();
+        names.sort();
+
+        assert_eq!(
+            names,
+            vec![
+                (
+                    b"caf\xe8 au lait".to_vec(),
+                    b"'caf\xe8 au lait'".to_vec(),
+                    14
+                ),
+                (b"caf\xe9".to_vec(), b"caf\xe9".to_vec(), 4),
+            ]
+        );
+    }
+
+    /// Whether the name of the file with the given name in a Git
+    /// repository gets dimmed, with the given arguments.
+    #[cfg(feature = "git")]
+    fn dimmed(dir: &Path, name: &str, args: &[&str]) -> bool {
+        use crate::fs::feature::git::GitCache;
+
+        let args = [&["--color=always"][..], args].concat();
+        let OptionsResult::Ok(options, _) = Options::parse(args.iter().map(OsStr::new), &None)
+        else {
+            panic!("Failed to parse {args:?}");
+        };
+
+        let theme = options.theme.to_theme(false);
+        let git: GitCache = [dir.to_path_buf()].into_iter().collect();
+        let file = File::from_args(dir.join(name), None, None, false, false).unwrap();
+        let name = options.view.file_style.for_file(&file, &theme);
+        name.with_git_ignored(Some(&git)).style().is_dimmed
+    }
+
+    #[test]
+    #[cfg(feature = "git")]
+    fn ignored_dimmed() {
+        let dir = TestDir::new("mark-ignored");
+        let repo = git2::Repository::init(&dir).unwrap();
+        std::fs::write(dir.join(".gitignore"), "ignored\n").unwrap();
+        std::fs::write(dir.join("ignored"), "").unwrap();
+        std::fs::write(dir.join("tracked"), "").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("tracked")).unwrap();
+        index.write().unwrap();
+
+        let args = ["--mark-ignored"];
+        let ignored = dimmed(&dir, "ignored", &args);
+        let tracked = dimmed(&dir, "tracked", &args);
+        let without_flag = dimmed(&dir, "ignored", &[]);
+
+        assert!(ignored, "ignored file");
+        assert!(!tracked, "tracked file");
+        assert!(!without_flag, "without --mark-ignored");
+    }
+}
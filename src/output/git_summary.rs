@@ -0,0 +1,96 @@
+//! The footer printed after a long listing with `--git --git-summary`,
+//! counting the listed files by their Git status.
+
+use std::fmt;
+
+use crate::fs::fields as f;
+
+/// How many of the listed files have been changed, are untracked, or have
+/// conflicts.
+#[derive(PartialEq, Eq, Debug, Default, Copy, Clone)]
+pub struct GitSummary {
+    pub modified: usize,
+    pub untracked: usize,
+    pub conflicts: usize,
+}
+
+impl GitSummary {
+    /// Counts one file’s status. Each file is counted once, under the most
+    /// pressing of its statuses: conflicts first, then changes to tracked
+    /// files, including new files added to the index, then untracked files.
+    pub fn add(&mut self, git: f::Git) {
+        use f::GitStatus::*;
+
+        let statuses = [git.staged, git.unstaged];
+        if statuses.contains(&Conflicted) {
+            self.conflicts += 1;
+        } else if git.staged == New
+            || statuses
+                .iter()
+                .any(|s| matches!(s, Modified | Deleted | Renamed | TypeChange))
+        {
+            self.modified += 1;
+        } else if git.unstaged == New {
+            self.untracked += 1;
+        }
+    }
+}
+
+impl fmt::Display for GitSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let conflicts = if self.conflicts == 1 {
+            "conflict"
+        } else {
+            "conflicts"
+        };
+        write!(
+            f,
+            "{} modified, {} untracked, {} {conflicts}",
+            self.modified, self.untracked, self.conflicts
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fs::fields::GitStatus::*;
+
+    fn summarise(statuses: &[(f::GitStatus, f::GitStatus)]) -> GitSummary {
+        let mut summary = GitSummary::default();
+        for &(staged, unstaged) in statuses {
+            summary.add(f::Git { staged, unstaged });
+        }
+        summary
+    }
+
+    #[test]
+    fn counts() {
+        let summary = summarise(&[
+            (NotModified, NotModified),
+            (NotModified, Modified),
+            (Modified, NotModified),
+            (New, NotModified),
+            (Renamed, Modified),
+            (NotModified, New),
+            (NotModified, Ignored),
+            (Conflicted, Modified),
+        ]);
+
+        assert_eq!(
+            summary,
+            GitSummary {
+                modified: 4,
+                untracked: 1,
+                conflicts: 1,
+            }
+        );
+        assert_eq!(summary.to_string(), "4 modified, 1 untracked, 1 conflict");
+    }
+
+    #[test]
+    fn nothing() {
+        let summary = summarise(&[(NotModified, NotModified)]);
+        assert_eq!(summary.to_string(), "0 modified, 0 untracked, 0 conflicts");
+    }
+}
@@ -2,6 +2,7 @@ use std::io::{self, Write};
 
 use term_grid::{Direction, Filling, Grid, GridOptions};
 
+use crate::fs::feature::git::GitCache;
 use crate::fs::filter::FileFilter;
 use crate::fs::File;
 use crate::output::file_name::Options as FileStyle;
@@ -29,11 +30,12 @@ pub struct Render<'a> {
     pub opts: &'a Options,
     pub console_width: usize,
     pub filter: &'a FileFilter,
+    pub git: Option<&'a GitCache>,
 }
 
 impl<'a> Render<'a> {
     pub fn render<W: Write>(mut self, w: &mut W) -> io::Result<()> {
-        self.filter.sort_files(&mut self.files);
+        self.filter.sort_files(&mut self.files, self.git);
 
         let cells = self
             .files
@@ -41,6 +43,7 @@ impl<'a> Render<'a> {
             .map(|file| {
                 self.file_style
                     .for_file(file, self.theme)
+                    .with_git_ignored(self.git)
                     .paint()
                     .strings()
                     .to_string()
@@ -29,7 +29,8 @@ impl Options {
 }
 
 /// The grid-details view can be configured to revert to just a details view
-/// (with one column) if it wouldn’t produce enough rows of output.
+/// (with one column) if it wouldn’t produce enough rows of output, or if the
+/// terminal isn’t wide enough to fit enough columns.
 ///
 /// Doing this makes the resulting output look a bit better: when listing a
 /// small directory of four files in four columns, the files just look spaced
@@ -41,10 +42,26 @@ pub enum RowThreshold {
     /// rows of output.
     MinimumRows(usize),
 
+    /// Only use grid-details view if it would fit at least this many
+    /// columns side-by-side.
+    MinimumColumns(usize),
+
     /// Use the grid-details view no matter what.
     AlwaysGrid,
 }
 
+impl RowThreshold {
+    /// Whether a grid with the given number of rows and columns is worth
+    /// showing, rather than a details view.
+    fn allows(self, rows: usize, columns: usize) -> bool {
+        match self {
+            Self::MinimumRows(min) => rows >= min,
+            Self::MinimumColumns(min) => columns >= min,
+            Self::AlwaysGrid => true,
+        }
+    }
+}
+
 pub struct Render<'a> {
     /// The directory that’s being rendered here.
     /// We need this to know which columns to put in the output.
@@ -68,8 +85,8 @@ pub struct Render<'a> {
     /// that we recurse into will have to have this applied.
     pub filter: &'a FileFilter,
 
-    /// The minimum number of rows that there need to be before grid-details
-    /// mode is activated.
+    /// The minimum number of rows or columns that there need to be before
+    /// grid-details mode is activated.
     pub row_threshold: RowThreshold,
 
     /// Whether we are skipping Git-ignored files.
@@ -80,6 +97,10 @@ pub struct Render<'a> {
     pub console_width: usize,
 
     pub git_repos: bool,
+
+    /// How many levels below the command-line arguments the files are,
+    /// for the depth column.
+    pub depth: usize,
 }
 
 impl<'a> Render<'a> {
@@ -102,6 +123,28 @@ impl<'a> Render<'a> {
             git_ignoring:  self.git_ignoring,
             git:           self.git,
             git_repos:     self.git_repos,
+            depth:         self.depth,
+            progress:      None,
+        };
+    }
+
+    /// Create a Details render for when the grid wouldn’t be worth showing,
+    /// which lists all the files in one column instead.
+    fn give_up(self) -> DetailsRender<'a> {
+        #[rustfmt::skip]
+        return DetailsRender {
+            dir:           self.dir,
+            files:         self.files,
+            theme:         self.theme,
+            file_style:    self.file_style,
+            opts:          self.details,
+            recurse:       None,
+            filter:        self.filter,
+            git_ignoring:  self.git_ignoring,
+            git:           self.git,
+            git_repos:     self.git_repos,
+            depth:         self.depth,
+            progress:      None,
         };
     }
 
@@ -135,7 +178,12 @@ impl<'a> Render<'a> {
             .files
             .iter()
             .map(|file| {
-                let row = table.row_for_file(file, drender.show_xattr_hint(file), color_scale_info);
+                let row = table.row_for_file(
+                    file,
+                    self.depth,
+                    drender.show_xattr_hint(file),
+                    color_scale_info,
+                );
                 table.add_widths(&row);
                 row
             })
@@ -143,11 +191,12 @@ impl<'a> Render<'a> {
 
         let cells = rows
             .into_iter()
-            .zip(self.files)
+            .zip(&self.files)
             .map(|(row, file)| {
                 let filename = self
                     .file_style
-                    .for_file(&file, self.theme)
+                    .for_file(file, self.theme)
+                    .with_git_ignored(self.git)
                     .paint()
                     .strings()
                     .to_string();
@@ -178,6 +227,13 @@ impl<'a> Render<'a> {
             },
         );
 
+        if !self
+            .row_threshold
+            .allows(grid.row_count(), grid.column_widths().len())
+        {
+            return self.give_up().render(w);
+        }
+
         if self.details.header {
             let row = table.header_row();
             let name = TextCell::paint_str(self.theme.ui.header, "Name")
@@ -1,7 +1,20 @@
 use nu_ansi_term::Style;
 use phf::{phf_map, Map};
 
-use crate::fs::File;
+use crate::fs::{fields as f, File};
+
+/// Which set of icons to display next to file names.
+#[derive(PartialEq, Eq, Debug, Default, Copy, Clone)]
+pub enum IconSet {
+    /// Icons from a Nerd Font, picked by the file’s name or extension. These
+    /// show up as boxes if the terminal’s font doesn’t have them.
+    #[default]
+    NerdFont,
+
+    /// Basic symbols that any font can display, picked only by the type of
+    /// file.
+    Ascii,
+}
 
 #[non_exhaustive]
 struct Icons;
@@ -821,9 +834,41 @@ pub fn iconify_style(style: Style) -> Style {
         .unwrap_or_default()
 }
 
+/// Lookup the icon for a file from the given icon set.
+pub fn icon_for_file(file: &File<'_>, icon_set: IconSet) -> char {
+    match icon_set {
+        IconSet::NerdFont => nerd_font_icon_for_file(file),
+        IconSet::Ascii => ascii_icon_for_file(file),
+    }
+}
+
+/// Mapping from the type of a file to its icon in the basic set. These
+/// mirror the characters that `--classify` puts after file names.
+const fn ascii_icon(file_type: f::Type) -> char {
+    match file_type {
+        f::Type::Directory => '/',
+        f::Type::File => '-',
+        f::Type::Link => '@',
+        f::Type::Pipe => '|',
+        f::Type::Socket => '=',
+        f::Type::CharDevice | f::Type::BlockDevice => '#',
+        f::Type::Special => '?',
+    }
+}
+
+/// Lookup the basic icon for a file, based only on its type. Links to
+/// directories get the directory icon, as they do with Nerd Font icons.
+fn ascii_icon_for_file(file: &File<'_>) -> char {
+    if file.points_to_directory() {
+        ascii_icon(f::Type::Directory)
+    } else {
+        ascii_icon(file.type_char())
+    }
+}
+
 /// Lookup the icon for a file based on the file's name, if the entry is a
 /// directory, or by the lowercase file extension.
-pub fn icon_for_file(file: &File<'_>) -> char {
+fn nerd_font_icon_for_file(file: &File<'_>) -> char {
     if file.points_to_directory() {
         *DIRECTORY_ICONS.get(file.name.as_str()).unwrap_or_else(|| {
             if file.is_empty_dir() {
@@ -840,3 +885,32 @@ pub fn icon_for_file(file: &File<'_>) -> char {
         Icons::FILE_OUTLINE // 
     }
 }
+
+#[cfg(test)]
+#[cfg(unix)]
+mod test {
+    use super::*;
+    use crate::fs::test_dir::TestDir;
+
+    #[test]
+    fn ascii_icons() {
+        let dir = TestDir::new("icons");
+        std::fs::create_dir_all(dir.join("src")).unwrap();
+        std::fs::write(dir.join("Cargo.toml"), "").unwrap();
+        std::os::unix::fs::symlink(dir.join("Cargo.toml"), dir.join("link")).unwrap();
+        std::os::unix::fs::symlink(dir.join("src"), dir.join("src-link")).unwrap();
+
+        let icon = |name: &str, icon_set| {
+            let file = File::from_args(dir.join(name), None, None, false, false).unwrap();
+            icon_for_file(&file, icon_set)
+        };
+
+        assert_eq!(icon("src", IconSet::Ascii), '/');
+        assert_eq!(icon("Cargo.toml", IconSet::Ascii), '-');
+        assert_eq!(icon("link", IconSet::Ascii), '@');
+        assert_eq!(icon("src-link", IconSet::Ascii), '/');
+
+        // The Nerd Font set is still used by default.
+        assert_eq!(icon("Cargo.toml", IconSet::default()), Icons::LANG_RUST);
+    }
+}
@@ -0,0 +1,135 @@
+//! Stopping the output once enough of it has been written, with
+//! `--max-output`.
+
+use std::error::Error;
+use std::fmt;
+use std::io::{self, Write};
+
+/// A writer that passes everything through to another writer until a
+/// number of bytes have been written, then finishes the line it’s on and
+/// refuses to write any more.
+///
+/// Refusing is signalled with an error that [`is_limit_reached`] can pick
+/// out, so the views stop wherever they are, the same as they would for a
+/// closed pipe, and the caller can tell the user the output was cut short.
+pub struct LimitedWriter<W> {
+    inner: W,
+    limit: Option<usize>,
+    written: usize,
+    stopped: bool,
+}
+
+impl<W: Write> LimitedWriter<W> {
+    /// Wraps the given writer, stopping after roughly `limit` bytes, or
+    /// never if there’s no limit.
+    pub fn new(inner: W, limit: Option<usize>) -> Self {
+        Self {
+            inner,
+            limit,
+            written: 0,
+            stopped: false,
+        }
+    }
+
+    /// The writer being written to.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+}
+
+impl<W: Write> Write for LimitedWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let Some(limit) = self.limit else {
+            return self.inner.write(buf);
+        };
+
+        if self.stopped {
+            return Err(io::Error::new(io::ErrorKind::Other, LimitReached));
+        }
+
+        // Once past the limit, only write up to the end of the current line,
+        // so the output never stops halfway through a name or a colour code.
+        let count = if self.written < limit {
+            self.inner.write(buf)?
+        } else if let Some(end) = buf.iter().position(|&b| b == b'\n') {
+            self.inner.write_all(&buf[..=end])?;
+            self.inner.flush()?;
+            self.stopped = true;
+            end + 1
+        } else {
+            self.inner.write(buf)?
+        };
+
+        self.written += count;
+        Ok(count)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// The error returned when writing past the limit.
+#[derive(Debug)]
+struct LimitReached;
+
+impl fmt::Display for LimitReached {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Output limit reached")
+    }
+}
+
+impl Error for LimitReached {}
+
+/// Whether the given error means a [`LimitedWriter`] stopped writing.
+pub fn is_limit_reached(error: &io::Error) -> bool {
+    error
+        .get_ref()
+        .is_some_and(|inner| inner.is::<LimitReached>())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Writes numbered lines until the writer refuses, and returns what
+    /// was written along with the error.
+    fn write_lines(limit: Option<usize>, count: usize) -> (String, Option<io::Error>) {
+        let mut buffer = Vec::new();
+        let mut writer = LimitedWriter::new(&mut buffer, limit);
+        let error = (0..count)
+            .map(|n| writeln!(writer, "line {n:02}"))
+            .find_map(Result::err);
+        (String::from_utf8(buffer).unwrap(), error)
+    }
+
+    #[test]
+    fn unlimited() {
+        let (output, error) = write_lines(None, 100);
+        assert!(error.is_none());
+        assert_eq!(output.lines().count(), 100);
+    }
+
+    #[test]
+    fn under_the_limit() {
+        let (output, error) = write_lines(Some(1000), 10);
+        assert!(error.is_none());
+        assert_eq!(output.lines().count(), 10);
+    }
+
+    #[test]
+    fn truncated_near_the_limit() {
+        // Each line is 8 bytes, so the limit falls in the middle of the
+        // seventh line, which is finished before stopping.
+        let (output, error) = write_lines(Some(50), 100);
+        assert!(error.is_some_and(|e| is_limit_reached(&e)));
+        assert_eq!(output.len(), 56);
+        assert!(output.ends_with("line 06\n"), "{output}");
+    }
+
+    #[test]
+    fn other_errors() {
+        let error = io::Error::new(io::ErrorKind::BrokenPipe, "pipe");
+        assert!(!is_limit_reached(&error));
+    }
+}
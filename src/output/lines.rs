@@ -2,6 +2,7 @@ use std::io::{self, Write};
 
 use nu_ansi_term::AnsiStrings as ANSIStrings;
 
+use crate::fs::feature::git::GitCache;
 use crate::fs::filter::FileFilter;
 use crate::fs::File;
 use crate::output::cell::TextCellContents;
@@ -14,11 +15,12 @@ pub struct Render<'a> {
     pub theme: &'a Theme,
     pub file_style: &'a FileStyle,
     pub filter: &'a FileFilter,
+    pub git: Option<&'a GitCache>,
 }
 
 impl<'a> Render<'a> {
     pub fn render<W: Write>(mut self, w: &mut W) -> io::Result<()> {
-        self.filter.sort_files(&mut self.files);
+        self.filter.sort_files(&mut self.files, self.git);
         for file in &self.files {
             let name_cell = self.render_file(file);
             writeln!(w, "{}", ANSIStrings(&name_cell))?;
@@ -32,6 +34,7 @@ impl<'a> Render<'a> {
             .for_file(file, self.theme)
             .with_link_paths()
             .with_mount_details(false)
+            .with_git_ignored(self.git)
             .paint()
     }
 }
@@ -0,0 +1,628 @@
+//! Writing the listing as markup, with `--color=html` or `--color=pango`.
+//!
+//! The views write their output with ANSI escape codes, just as they would
+//! to a terminal, and this writer translates them on the way out: runs of
+//! styled text become `<span>` elements carrying the style, and with HTML,
+//! hyperlinks become `<a>` elements. Because this happens after the views
+//! have laid everything out, the columns line up the same way they would in
+//! a terminal.
+
+use std::io::{self, Write};
+
+use nu_ansi_term::{Color, Style};
+
+use crate::output::escape::stand_in_byte;
+
+/// Which kind of markup to write.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum Markup {
+    /// An HTML `<pre>` element, with the styles as inline CSS.
+    Html,
+
+    /// Pango markup, for GTK programs, with the styles as span attributes.
+    Pango,
+}
+
+impl Markup {
+    /// The text to write before the listing.
+    fn start(self) -> &'static str {
+        match self {
+            Self::Html => "<pre class=\"eza\">",
+            Self::Pango => "",
+        }
+    }
+
+    /// The text to write after the listing.
+    fn end(self) -> &'static str {
+        match self {
+            Self::Html => "</pre>\n",
+            Self::Pango => "",
+        }
+    }
+
+    /// The attributes for a `<span>` in the given style.
+    fn span_attributes(self, style: Style) -> String {
+        match self {
+            Self::Html => format!("style=\"{}\"", css(style)),
+            Self::Pango => pango_attributes(style),
+        }
+    }
+
+    /// Whether hyperlinks can be written as `<a>` elements. Pango markup
+    /// has no links, so there they’re left out.
+    fn has_links(self) -> bool {
+        self == Self::Html
+    }
+}
+
+/// A writer that turns terminal output into markup, or passes it straight
+/// through if there’s no markup to write.
+pub struct MarkupWriter<W> {
+    inner: W,
+    markup: Option<Markup>,
+
+    /// Whether the start of the listing has been written.
+    started: bool,
+
+    /// Output that hasn’t been translated yet, because it doesn’t end in a
+    /// newline and so could stop halfway through an escape code.
+    line: Vec<u8>,
+
+    /// The style set by the escape codes seen so far.
+    style: Style,
+
+    /// The style of the `<span>` that’s currently open, if any.
+    span: Option<Style>,
+
+    /// Whether an `<a>` element is currently open.
+    link: bool,
+}
+
+impl<W: Write> MarkupWriter<W> {
+    pub fn new(inner: W, markup: Option<Markup>) -> Self {
+        Self {
+            inner,
+            markup,
+            started: false,
+            line: Vec::new(),
+            style: Style::default(),
+            span: None,
+            link: false,
+        }
+    }
+
+    /// Translates anything left over, and closes the listing. This has to
+    /// be called once everything has been written.
+    pub fn finish(&mut self) -> io::Result<()> {
+        let Some(markup) = self.markup else {
+            return self.inner.flush();
+        };
+
+        let line = std::mem::take(&mut self.line);
+        let mut text = self.translate(markup, &String::from_utf8_lossy(&line));
+        self.close_span(&mut text);
+        if self.link {
+            text.push_str("</a>");
+            self.link = false;
+        }
+        text.push_str(markup.end());
+
+        self.write_markup(markup, &text)?;
+        self.inner.flush()
+    }
+
+    /// Writes some translated markup, starting the listing first if this is
+    /// the first thing to be written.
+    fn write_markup(&mut self, markup: Markup, text: &str) -> io::Result<()> {
+        if !self.started {
+            self.inner.write_all(markup.start().as_bytes())?;
+            self.started = true;
+        }
+
+        self.inner.write_all(text.as_bytes())
+    }
+
+    /// Translates some text containing escape codes into markup.
+    fn translate(&mut self, markup: Markup, text: &str) -> String {
+        let mut out = String::with_capacity(text.len());
+        let mut chars = text.chars();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '\x1B' => match chars.next() {
+                    Some('[') => {
+                        let mut params = String::new();
+                        for c in chars.by_ref() {
+                            if c.is_ascii_digit() || c == ';' {
+                                params.push(c);
+                            } else {
+                                if c == 'm' {
+                                    self.select_graphics(&params);
+                                }
+                                break;
+                            }
+                        }
+                    }
+                    Some(']') => {
+                        let mut command = String::new();
+                        while let Some(c) = chars.next() {
+                            match c {
+                                '\x07' => break,
+                                '\x1B' => {
+                                    chars.next();
+                                    break;
+                                }
+                                c => command.push(c),
+                            }
+                        }
+                        if markup.has_links() {
+                            self.operating_system_command(&command, &mut out);
+                        }
+                    }
+                    _ => {}
+                },
+                '\n' => {
+                    self.close_span(&mut out);
+                    out.push('\n');
+                }
+                c => {
+                    self.open_span(markup, &mut out);
+                    push_escaped(&mut out, c);
+                }
+            }
+        }
+
+        out
+    }
+
+    /// Makes sure the open `<span>` matches the current style.
+    fn open_span(&mut self, markup: Markup, out: &mut String) {
+        if self.span == Some(self.style) {
+            return;
+        }
+
+        self.close_span(out);
+        if !self.style.is_plain() {
+            out.push_str("<span ");
+            out.push_str(&markup.span_attributes(self.style));
+            out.push('>');
+            self.span = Some(self.style);
+        }
+    }
+
+    fn close_span(&mut self, out: &mut String) {
+        if self.span.take().is_some() {
+            out.push_str("</span>");
+        }
+    }
+
+    /// Handles an OSC 8 hyperlink, which starts with a URL and ends with an
+    /// empty one. Any other commands are left out.
+    fn operating_system_command(&mut self, command: &str, out: &mut String) {
+        let Some(url) = command
+            .strip_prefix("8;")
+            .and_then(|rest| rest.split_once(';'))
+            .map(|(_, url)| url)
+        else {
+            return;
+        };
+
+        self.close_span(out);
+        if self.link {
+            out.push_str("</a>");
+            self.link = false;
+        }
+
+        if !url.is_empty() {
+            out.push_str("<a href=\"");
+            url.chars().for_each(|c| push_escaped(out, c));
+            out.push_str("\">");
+            self.link = true;
+        }
+    }
+
+    /// Updates the current style from the parameters of an SGR escape code,
+    /// which are the only ones the views write.
+    fn select_graphics(&mut self, params: &str) {
+        let mut codes = params.split(';').map(|p| p.parse::<u8>().unwrap_or(0));
+
+        while let Some(code) = codes.next() {
+            let style = &mut self.style;
+            match code {
+                0 => *style = Style::default(),
+                1 => style.is_bold = true,
+                2 => style.is_dimmed = true,
+                3 => style.is_italic = true,
+                4 => style.is_underline = true,
+                5 => style.is_blink = true,
+                7 => style.is_reverse = true,
+                8 => style.is_hidden = true,
+                9 => style.is_strikethrough = true,
+                30..=37 => style.foreground = Some(basic_color(code - 30)),
+                38 => style.foreground = extended_color(&mut codes),
+                39 => style.foreground = None,
+                40..=47 => style.background = Some(basic_color(code - 40)),
+                48 => style.background = extended_color(&mut codes),
+                49 => style.background = None,
+                90..=97 => style.foreground = Some(basic_color(code - 90 + 8)),
+                100..=107 => style.background = Some(basic_color(code - 100 + 8)),
+                _ => {}
+            }
+        }
+    }
+}
+
+impl<W: Write> Write for MarkupWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let Some(markup) = self.markup else {
+            return self.inner.write(buf);
+        };
+
+        self.line.extend_from_slice(buf);
+        if let Some(end) = self.line.iter().rposition(|&b| b == b'\n') {
+            let rest = self.line.split_off(end + 1);
+            let lines = std::mem::replace(&mut self.line, rest);
+            let text = self.translate(markup, &String::from_utf8_lossy(&lines));
+            self.write_markup(markup, &text)?;
+        }
+
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Adds a character to some markup, escaping it if it has a meaning there.
+/// HTML and Pango markup both use the same entities as XML. Markup has to
+/// be valid UTF-8, so bytes in file names that aren’t are written as `\xNN`
+/// escapes instead.
+fn push_escaped(out: &mut String, c: char) {
+    match c {
+        '&' => out.push_str("&amp;"),
+        '<' => out.push_str("&lt;"),
+        '>' => out.push_str("&gt;"),
+        '"' => out.push_str("&quot;"),
+        '\'' => out.push_str("&#39;"),
+        c => match stand_in_byte(c) {
+            Some(byte) => out.push_str(&format!("\\x{byte:02x}")),
+            None => out.push(c),
+        },
+    }
+}
+
+/// The colour with the given number out of the sixteen basic ones, with the
+/// bright versions numbered 8 to 15.
+fn basic_color(number: u8) -> Color {
+    match number {
+        0 => Color::Black,
+        1 => Color::Red,
+        2 => Color::Green,
+        3 => Color::Yellow,
+        4 => Color::Blue,
+        5 => Color::Purple,
+        6 => Color::Cyan,
+        7 => Color::White,
+        8 => Color::DarkGray,
+        9 => Color::LightRed,
+        10 => Color::LightGreen,
+        11 => Color::LightYellow,
+        12 => Color::LightBlue,
+        13 => Color::LightPurple,
+        14 => Color::LightCyan,
+        _ => Color::LightGray,
+    }
+}
+
+/// Reads the rest of a `38` or `48` code, which picks either one of the 256
+/// colours or an RGB colour.
+fn extended_color(codes: &mut impl Iterator<Item = u8>) -> Option<Color> {
+    match codes.next()? {
+        5 => codes.next().map(Color::Fixed),
+        2 => Some(Color::Rgb(codes.next()?, codes.next()?, codes.next()?)),
+        _ => None,
+    }
+}
+
+/// The foreground and background colours of a style, swapped round if the
+/// style is reversed.
+fn colors(style: Style) -> (Option<Color>, Option<Color>) {
+    if style.is_reverse {
+        (style.background, style.foreground)
+    } else {
+        (style.foreground, style.background)
+    }
+}
+
+/// The inline CSS for a style.
+fn css(style: Style) -> String {
+    let (foreground, background) = colors(style);
+
+    let mut rules = Vec::new();
+    if let Some(color) = foreground.and_then(hex) {
+        rules.push(format!("color:{color}"));
+    }
+    if let Some(color) = background.and_then(hex) {
+        rules.push(format!("background-color:{color}"));
+    }
+    if style.is_bold {
+        rules.push("font-weight:bold".into());
+    }
+    if style.is_dimmed {
+        rules.push("opacity:0.5".into());
+    }
+    if style.is_italic {
+        rules.push("font-style:italic".into());
+    }
+    match (style.is_underline, style.is_strikethrough) {
+        (true, true) => rules.push("text-decoration:underline line-through".into()),
+        (true, false) => rules.push("text-decoration:underline".into()),
+        (false, true) => rules.push("text-decoration:line-through".into()),
+        (false, false) => {}
+    }
+    if style.is_hidden {
+        rules.push("visibility:hidden".into());
+    }
+
+    rules.join(";")
+}
+
+/// The Pango span attributes for a style. Pango can’t hide text, so hidden
+/// text is shown.
+fn pango_attributes(style: Style) -> String {
+    let (foreground, background) = colors(style);
+
+    let mut attributes = Vec::new();
+    if let Some(color) = foreground.and_then(hex) {
+        attributes.push(format!("foreground=\"{color}\""));
+    }
+    if let Some(color) = background.and_then(hex) {
+        attributes.push(format!("background=\"{color}\""));
+    }
+    if style.is_bold {
+        attributes.push("weight=\"bold\"".into());
+    }
+    if style.is_dimmed {
+        attributes.push("alpha=\"50%\"".into());
+    }
+    if style.is_italic {
+        attributes.push("style=\"italic\"".into());
+    }
+    if style.is_underline {
+        attributes.push("underline=\"single\"".into());
+    }
+    if style.is_strikethrough {
+        attributes.push("strikethrough=\"true\"".into());
+    }
+
+    attributes.join(" ")
+}
+
+/// The hex code for a colour, using the xterm palette for the colours the
+/// terminal would otherwise pick itself.
+fn hex(color: Color) -> Option<String> {
+    let (r, g, b) = match color {
+        Color::Default => return None,
+        Color::Rgb(r, g, b) => (r, g, b),
+        Color::Fixed(number) => palette(number),
+        Color::Black => palette(0),
+        Color::Red => palette(1),
+        Color::Green => palette(2),
+        Color::Yellow => palette(3),
+        Color::Blue => palette(4),
+        Color::Purple | Color::Magenta => palette(5),
+        Color::Cyan => palette(6),
+        Color::White => palette(7),
+        Color::DarkGray => palette(8),
+        Color::LightRed => palette(9),
+        Color::LightGreen => palette(10),
+        Color::LightYellow => palette(11),
+        Color::LightBlue => palette(12),
+        Color::LightPurple | Color::LightMagenta => palette(13),
+        Color::LightCyan => palette(14),
+        Color::LightGray => palette(15),
+    };
+
+    Some(format!("#{r:02x}{g:02x}{b:02x}"))
+}
+
+/// The RGB value of one of the 256 terminal colours: sixteen basic ones,
+/// then a 6×6×6 colour cube, then a ramp of greys.
+fn palette(number: u8) -> (u8, u8, u8) {
+    const BASIC: [(u8, u8, u8); 16] = [
+        (0x00, 0x00, 0x00),
+        (0xcd, 0x00, 0x00),
+        (0x00, 0xcd, 0x00),
+        (0xcd, 0xcd, 0x00),
+        (0x00, 0x00, 0xee),
+        (0xcd, 0x00, 0xcd),
+        (0x00, 0xcd, 0xcd),
+        (0xe5, 0xe5, 0xe5),
+        (0x7f, 0x7f, 0x7f),
+        (0xff, 0x00, 0x00),
+        (0x00, 0xff, 0x00),
+        (0xff, 0xff, 0x00),
+        (0x5c, 0x5c, 0xff),
+        (0xff, 0x00, 0xff),
+        (0x00, 0xff, 0xff),
+        (0xff, 0xff, 0xff),
+    ];
+    const LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+    match number {
+        0..=15 => BASIC[usize::from(number)],
+        16..=231 => {
+            let index = usize::from(number - 16);
+            (LEVELS[index / 36], LEVELS[index / 6 % 6], LEVELS[index % 6])
+        }
+        _ => {
+            let grey = 8 + 10 * (number - 232);
+            (grey, grey, grey)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fs::test_dir::TestDir;
+    use crate::fs::{Dir, DotFilter};
+    use crate::options::{Options, OptionsResult};
+    use crate::output::lines;
+    use nu_ansi_term::AnsiStrings;
+    use std::ffi::OsStr;
+
+    fn translate(markup: Markup, output: &str) -> String {
+        let mut buffer = Vec::new();
+        let mut writer = MarkupWriter::new(&mut buffer, Some(markup));
+        writer.write_all(output.as_bytes()).unwrap();
+        writer.finish().unwrap();
+        String::from_utf8(buffer).unwrap()
+    }
+
+    #[test]
+    fn plain_text_escaped() {
+        assert_eq!(
+            translate(Markup::Html, "<a & \"b\">\n"),
+            "<pre class=\"eza\">&lt;a &amp; &quot;b&quot;&gt;\n</pre>\n"
+        );
+    }
+
+    #[test]
+    fn invalid_bytes_escaped() {
+        assert_eq!(translate(Markup::Pango, "caf\u{10ffe9}\n"), "caf\\xe9\n");
+    }
+
+    #[test]
+    fn styles() {
+        let output = AnsiStrings(&[
+            Color::Blue.bold().paint("dir"),
+            Style::default().paint(" "),
+            Color::Fixed(208).on(Color::Rgb(1, 2, 3)).paint("x"),
+        ])
+        .to_string();
+
+        assert_eq!(
+            translate(Markup::Html, &output),
+            "<pre class=\"eza\">\
+             <span style=\"color:#0000ee;font-weight:bold\">dir</span> \
+             <span style=\"color:#ff8700;background-color:#010203\">x</span>\
+             </pre>\n"
+        );
+    }
+
+    #[test]
+    fn hyperlinks() {
+        let output = "\x1B]8;;file:///a&b\x1B\\\x1B[1mname\x1B[0m\x1B]8;;\x1B\\\n";
+        assert_eq!(
+            translate(Markup::Html, output),
+            "<pre class=\"eza\"><a href=\"file:///a&amp;b\">\
+             <span style=\"font-weight:bold\">name</span></a>\n</pre>\n"
+        );
+    }
+
+    #[test]
+    fn split_escape_codes() {
+        let mut buffer = Vec::new();
+        let mut writer = MarkupWriter::new(&mut buffer, Some(Markup::Html));
+        writer.write_all(b"\x1B[3").unwrap();
+        writer.write_all(b"1mred\x1B[0m\n").unwrap();
+        writer.finish().unwrap();
+
+        assert_eq!(
+            String::from_utf8(buffer).unwrap(),
+            "<pre class=\"eza\"><span style=\"color:#cd0000\">red</span>\n</pre>\n"
+        );
+    }
+
+    #[test]
+    fn disabled() {
+        let mut buffer = Vec::new();
+        let mut writer = MarkupWriter::new(&mut buffer, None);
+        writer.write_all(b"\x1B[1m<b>\x1B[0m\n").unwrap();
+        writer.finish().unwrap();
+
+        assert_eq!(buffer, b"\x1B[1m<b>\x1B[0m\n");
+    }
+
+    /// Lists a directory holding `<dir>` and `a&b` one per line, written as
+    /// the given markup.
+    fn listing(name: &str, markup: Markup, args: &[&str]) -> String {
+        let dir = TestDir::new(name);
+        std::fs::create_dir_all(dir.join("<dir>")).unwrap();
+        std::fs::write(dir.join("a&b"), "").unwrap();
+
+        let OptionsResult::Ok(options, _) = Options::parse(args.iter().map(OsStr::new), &None)
+        else {
+            panic!("Failed to parse {args:?}");
+        };
+        let theme = options.theme.to_theme(false);
+
+        let listing = Dir::read_dir(dir.to_path_buf()).unwrap();
+        let files = listing
+            .files(DotFilter::JustFiles, None, false, false, false)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        let render = lines::Render {
+            files,
+            theme: &theme,
+            file_style: &options.view.file_style,
+            filter: &options.filter,
+            git: None,
+        };
+
+        let mut buffer = Vec::new();
+        let mut writer = MarkupWriter::new(&mut buffer, Some(markup));
+        render.render(&mut writer).unwrap();
+        writer.finish().unwrap();
+
+        String::from_utf8(buffer).unwrap()
+    }
+
+    #[test]
+    fn html_listing() {
+        assert_eq!(
+            listing("html", Markup::Html, &["--color=html", "--oneline"]),
+            "<pre class=\"eza\">\
+             <span style=\"color:#0000ee;font-weight:bold\">&lt;dir&gt;</span>\n\
+             a&amp;b\n\
+             </pre>\n"
+        );
+    }
+
+    #[test]
+    fn pango_listing() {
+        assert_eq!(
+            listing("pango", Markup::Pango, &["--color=pango", "--oneline"]),
+            "<span foreground=\"#0000ee\" weight=\"bold\">&lt;dir&gt;</span>\n\
+             a&amp;b\n"
+        );
+    }
+
+    #[test]
+    fn pango_styles() {
+        let output = AnsiStrings(&[
+            Color::Red.italic().paint("it's"),
+            Style::default().paint(" "),
+            Color::Green.reverse().underline().paint("x"),
+            Style::default().paint("\n"),
+        ])
+        .to_string();
+
+        assert_eq!(
+            translate(Markup::Pango, &output),
+            "<span foreground=\"#cd0000\" style=\"italic\">it&#39;s</span> \
+             <span background=\"#00cd00\" underline=\"single\">x</span>\n"
+        );
+    }
+
+    #[test]
+    fn pango_hyperlinks_left_out() {
+        let output = "\x1B]8;;file:///a\x1B\\\x1B[1mname\x1B[0m\x1B]8;;\x1B\\\n";
+        assert_eq!(
+            translate(Markup::Pango, output),
+            "<span weight=\"bold\">name</span>\n"
+        );
+    }
+}
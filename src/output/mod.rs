@@ -1,31 +1,49 @@
 #[cfg_attr(target_os = "netbsd", allow(unused_imports))]
 #[cfg_attr(target_os = "freebsd", allow(unused_imports))]
-pub use self::cell::{DisplayWidth, TextCell, TextCellContents};
-pub use self::escape::escape;
+pub use self::cell::{DisplayWidth, TextCell, TextCellContents, ELLIPSIS};
+pub use self::escape::{encode_name, escape, escape_os, RawBytesWriter};
 
 pub mod color_scale;
+pub mod csv;
 pub mod details;
 pub mod file_name;
+pub mod git_summary;
 pub mod grid;
 pub mod grid_details;
 pub mod icons;
+pub mod limit;
 pub mod lines;
+pub mod markup;
+pub mod progress;
 pub mod render;
+pub mod summary;
 pub mod table;
+pub mod template;
 pub mod time;
 
 mod cell;
 mod escape;
-mod tree;
+pub mod tree;
 
 /// The **view** contains all information about how to format output.
 #[derive(Debug)]
 pub struct View {
     pub mode: Mode,
     pub width: TerminalWidth,
+
+    /// The fewest columns to use, however narrow the terminal is, from
+    /// `--min-width` or `EZA_MIN_WIDTH`.
+    pub min_width: Option<usize>,
+
+    /// The most columns to use, however wide the terminal is, from
+    /// `--max-width` or `EZA_MAX_WIDTH`.
+    pub max_width: Option<usize>,
     pub file_style: file_name::Options,
     pub deref_links: bool,
     pub total_size: bool,
+
+    /// Roughly how many bytes to print before stopping, from `--max-output`.
+    pub max_output: Option<usize>,
 }
 
 /// The **mode** is the “type” of output.
@@ -36,6 +54,8 @@ pub enum Mode {
     Details(details::Options),
     GridDetails(grid_details::Options),
     Lines,
+    Template(template::Options),
+    Csv(csv::Options),
 }
 
 /// The width of the terminal requested by the user.
@@ -44,12 +64,16 @@ pub enum TerminalWidth {
     /// The user requested this specific number of columns.
     Set(usize),
 
+    /// The user requested the real width of the terminal, with this many
+    /// columns added to it, which is negative to leave a margin.
+    Offset(isize),
+
     /// Look up the terminal size at runtime.
     Automatic,
 }
 
 impl TerminalWidth {
-    pub fn actual_terminal_width(self) -> Option<usize> {
+    pub fn actual_terminal_width(self, min: Option<usize>, max: Option<usize>) -> Option<usize> {
         // All of stdin, stdout, and stderr could not be connected to a
         // terminal, but we’re only interested in stdout because it’s
         // where the output goes.
@@ -70,10 +94,28 @@ impl TerminalWidth {
             .map(|(w, _h)| w.0 as _)
         };
 
+        self.resolve(stdout_term_width, min, max)
+    }
+
+    /// Works out the width to use, given the width of the terminal if it
+    /// could be found, raised to the minimum and capped to the maximum if
+    /// there are any. An offset never leaves less than one column.
+    pub fn resolve(
+        self,
+        detected: Option<usize>,
+        min: Option<usize>,
+        max: Option<usize>,
+    ) -> Option<usize> {
         #[rustfmt::skip]
-        return match self {
-            Self::Set(width)  => Some(width),
-            Self::Automatic   => stdout_term_width,
+        let width = match self {
+            Self::Set(width)      => Some(width),
+            Self::Offset(offset)  => detected.map(|w| w.saturating_add_signed(offset).max(1)),
+            Self::Automatic       => detected,
         };
+
+        width.map(|w| {
+            let w = min.map_or(w, |min| w.max(min));
+            max.map_or(w, |max| w.min(max))
+        })
     }
 }
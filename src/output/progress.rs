@@ -0,0 +1,229 @@
+//! The progress counter for `--progress`, which keeps a running count of
+//! the directories scanned and files found on stderr while a recursive
+//! listing is being read, so a slow traversal doesn’t look stuck.
+//!
+//! The counter is redrawn in place with a carriage return, at most once per
+//! interval, and wiped before anything else gets written to the terminal.
+//! There’s no timer of its own: it only gets redrawn when another directory
+//! has been read, so it holds still while one slow directory is being read.
+
+use std::io::{self, IsTerminal, Write};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// How long to wait between redraws of the counter. Listings that finish
+/// within this long never show it at all.
+const INTERVAL: Duration = Duration::from_millis(100);
+
+/// Where the counter gets the time from, so tests can move it along
+/// without having to wait.
+pub trait Clock: Send + Sync {
+    fn now(&self) -> Instant;
+}
+
+/// The real clock.
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+pub struct Progress {
+    state: Mutex<State>,
+    clock: Box<dyn Clock>,
+    interval: Duration,
+}
+
+struct State {
+    writer: Box<dyn Write + Send>,
+    dirs: usize,
+    files: usize,
+
+    /// When the counter was last drawn, or when counting started if it
+    /// hasn’t been drawn yet.
+    last: Instant,
+
+    /// Whether the counter is on screen, and needs clearing.
+    shown: bool,
+}
+
+impl Progress {
+    /// A counter that writes to stderr, as long as stderr is a terminal.
+    /// Otherwise the escape codes would end up in a log file or a pipe, so
+    /// there’s no counter at all.
+    pub fn stderr() -> Option<Self> {
+        if io::stderr().is_terminal() {
+            Some(Self::new(
+                Box::new(io::stderr()),
+                Box::new(SystemClock),
+                INTERVAL,
+            ))
+        } else {
+            None
+        }
+    }
+
+    pub fn new(writer: Box<dyn Write + Send>, clock: Box<dyn Clock>, interval: Duration) -> Self {
+        let last = clock.now();
+        let state = State {
+            writer,
+            dirs: 0,
+            files: 0,
+            last,
+            shown: false,
+        };
+
+        Self {
+            state: Mutex::new(state),
+            clock,
+            interval,
+        }
+    }
+
+    /// Counts a directory that has just been read, along with the number
+    /// of files that were found in it, redrawing the counter if it’s due.
+    pub fn add_dir(&self, files: usize) {
+        let Ok(mut state) = self.state.lock() else {
+            return;
+        };
+
+        state.dirs += 1;
+        state.files += files;
+
+        let now = self.clock.now();
+        if now.duration_since(state.last) < self.interval {
+            return;
+        }
+
+        state.last = now;
+        let line = format!(
+            "\r{} directories scanned, {} files found\x1b[K",
+            state.dirs, state.files
+        );
+
+        // The counter is only a nicety, so failing to draw it shouldn’t
+        // stop the listing.
+        if state.writer.write_all(line.as_bytes()).is_ok() {
+            state.shown = true;
+            let _ = state.writer.flush();
+        }
+    }
+
+    /// Wipes the counter from the screen, if it’s there, so whatever gets
+    /// written next starts on a clean line. It gets redrawn by the next
+    /// directory that’s due.
+    pub fn clear(&self) {
+        let Ok(mut state) = self.state.lock() else {
+            return;
+        };
+
+        if state.shown {
+            state.shown = false;
+            let _ = state.writer.write_all(b"\r\x1b[K");
+            let _ = state.writer.flush();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::sync::Arc;
+
+    /// A clock that only moves when it’s told to.
+    struct MockClock {
+        start: Instant,
+        elapsed: Arc<Mutex<Duration>>,
+    }
+
+    impl Clock for MockClock {
+        fn now(&self) -> Instant {
+            self.start + *self.elapsed.lock().unwrap()
+        }
+    }
+
+    /// A writer that keeps everything written to it where the test can
+    /// still get at it.
+    #[derive(Clone, Default)]
+    struct SharedWriter(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedWriter {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl SharedWriter {
+        fn take(&self) -> String {
+            String::from_utf8(std::mem::take(&mut *self.0.lock().unwrap())).unwrap()
+        }
+    }
+
+    fn progress() -> (Progress, SharedWriter, Arc<Mutex<Duration>>) {
+        let writer = SharedWriter::default();
+        let elapsed = Arc::new(Mutex::new(Duration::ZERO));
+        let clock = MockClock {
+            start: Instant::now(),
+            elapsed: Arc::clone(&elapsed),
+        };
+        let progress = Progress::new(
+            Box::new(writer.clone()),
+            Box::new(clock),
+            Duration::from_millis(100),
+        );
+        (progress, writer, elapsed)
+    }
+
+    #[test]
+    fn quiet_before_interval() {
+        let (progress, writer, elapsed) = progress();
+        progress.add_dir(3);
+        *elapsed.lock().unwrap() = Duration::from_millis(99);
+        progress.add_dir(4);
+        progress.clear();
+        assert_eq!(writer.take(), "");
+    }
+
+    #[test]
+    fn counts_on_timer() {
+        let (progress, writer, elapsed) = progress();
+        progress.add_dir(3);
+        *elapsed.lock().unwrap() = Duration::from_millis(100);
+        progress.add_dir(4);
+        assert_eq!(
+            writer.take(),
+            "\r2 directories scanned, 7 files found\x1b[K"
+        );
+
+        // Not due again until another interval has passed.
+        *elapsed.lock().unwrap() = Duration::from_millis(150);
+        progress.add_dir(1);
+        assert_eq!(writer.take(), "");
+
+        *elapsed.lock().unwrap() = Duration::from_millis(200);
+        progress.add_dir(0);
+        assert_eq!(
+            writer.take(),
+            "\r4 directories scanned, 8 files found\x1b[K"
+        );
+    }
+
+    #[test]
+    fn cleared_once() {
+        let (progress, writer, elapsed) = progress();
+        *elapsed.lock().unwrap() = Duration::from_millis(100);
+        progress.add_dir(2);
+        writer.take();
+
+        progress.clear();
+        assert_eq!(writer.take(), "\r\x1b[K");
+        progress.clear();
+        assert_eq!(writer.take(), "");
+    }
+}
@@ -23,6 +23,7 @@ impl f::Blocksize {
         let result = match size_format {
             SizeFormat::DecimalBytes => NumberPrefix::decimal(size as f64),
             SizeFormat::BinaryBytes => NumberPrefix::binary(size as f64),
+            SizeFormat::FixedUnit(unit) => unit.convert(size),
             SizeFormat::JustBytes => {
                 // Use the binary prefix to select a style.
                 let prefix = match NumberPrefix::binary(size as f64) {
@@ -63,6 +64,15 @@ impl f::Blocksize {
     }
 }
 
+impl f::IoBlocksize {
+    pub fn render<C: Colours>(self, colours: &C) -> TextCell {
+        match self {
+            Self::Some(size) => TextCell::paint(colours.blocksize(None), size.to_string()),
+            Self::None => TextCell::blank(colours.no_blocksize()),
+        }
+    }
+}
+
 #[rustfmt::skip]
 pub trait Colours {
     fn blocksize(&self, prefix: Option<Prefix>) -> Style;
@@ -106,6 +116,15 @@ pub mod test {
         );
     }
 
+    #[test]
+    fn io_blocksize() {
+        let expected = TextCell::paint_str(Fixed(66).normal(), "4096");
+        assert_eq!(expected, f::IoBlocksize::Some(4096).render(&TestColours));
+
+        let expected = TextCell::blank(Black.italic());
+        assert_eq!(expected, f::IoBlocksize::None.render(&TestColours));
+    }
+
     #[test]
     fn file_decimal() {
         let directory = f::Blocksize::Some(2_100_000);
@@ -0,0 +1,40 @@
+use nu_ansi_term::Style;
+
+use crate::fs::fields as f;
+use crate::output::cell::TextCell;
+
+impl f::Checksum {
+    pub fn render(self, style: Style, punctuation: Style) -> TextCell {
+        match self {
+            Self::Some(digest) => TextCell::paint(style, digest),
+            Self::None => TextCell::blank(punctuation),
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use crate::fs::fields as f;
+    use crate::output::cell::{DisplayWidth, TextCell};
+
+    use nu_ansi_term::Color::*;
+
+    #[test]
+    fn digest() {
+        let checksum = f::Checksum::Some(String::from("cbf43926"));
+        let expected = TextCell::paint_str(Green.normal(), "cbf43926");
+        assert_eq!(expected, checksum.render(Green.normal(), Black.bold()));
+    }
+
+    #[test]
+    fn none() {
+        let expected = TextCell {
+            width: DisplayWidth::from(1),
+            contents: vec![Black.bold().paint("-")].into(),
+        };
+        assert_eq!(
+            expected,
+            f::Checksum::None.render(Green.normal(), Black.bold())
+        );
+    }
+}
@@ -51,9 +51,9 @@ impl Render for Option<f::Group> {
             style = colours.root_group();
         }
 
-        let mut group_name = match user_format {
-            UserFormat::Name => group.name().to_string_lossy().into(),
-            UserFormat::Numeric => group.gid().to_string(),
+        let mut group_name = match (user_format, group_format) {
+            (UserFormat::Numeric, _) | (_, GroupFormat::Numeric) => group.gid().to_string(),
+            (UserFormat::Name, _) => group.name().to_string_lossy().into(),
         };
 
         if let GroupFormat::Smart = group_format {
@@ -133,6 +133,26 @@ pub mod test {
         );
     }
 
+    #[test]
+    fn numeric_group() {
+        let mut users = MockUsers::with_current_uid(1000);
+        users.add_group(Group::new(100, "folk"));
+
+        let group = Some(f::Group(100));
+        let file_user = Some(f::User(1000));
+        let expected = TextCell::paint_str(TestColours.not_yours(), "100");
+        assert_eq!(
+            expected,
+            group.render(
+                &TestColours,
+                &users,
+                UserFormat::Name,
+                GroupFormat::Numeric,
+                file_user
+            )
+        );
+    }
+
     #[test]
     fn unnamed() {
         let users = MockUsers::with_current_uid(1000);
@@ -2,10 +2,27 @@ use nu_ansi_term::Style;
 
 use crate::fs::fields as f;
 use crate::output::cell::TextCell;
+use crate::output::color_scale::ColorScaleInformation;
+use crate::output::table::InodeFormat;
 
 impl f::Inode {
-    pub fn render(self, style: Style) -> TextCell {
-        TextCell::paint(style, self.0.to_string())
+    pub fn render(
+        self,
+        style: Style,
+        format: InodeFormat,
+        color_scale_info: Option<ColorScaleInformation>,
+    ) -> TextCell {
+        let style = match color_scale_info {
+            Some(csi) => csi.adjust_style(style, self.0 as f32, csi.inode),
+            None => style,
+        };
+
+        let number = match format {
+            InodeFormat::Decimal => self.0.to_string(),
+            InodeFormat::Hexadecimal => format!("{:#x}", self.0),
+        };
+
+        TextCell::paint(style, number)
     }
 }
 
@@ -13,6 +30,7 @@ impl f::Inode {
 pub mod test {
     use crate::fs::fields as f;
     use crate::output::cell::TextCell;
+    use crate::output::table::InodeFormat;
 
     use nu_ansi_term::Color::*;
 
@@ -20,6 +38,19 @@ pub mod test {
     fn blocklessness() {
         let io = f::Inode(1_414_213);
         let expected = TextCell::paint_str(Cyan.underline(), "1414213");
-        assert_eq!(expected, io.render(Cyan.underline()));
+        assert_eq!(
+            expected,
+            io.render(Cyan.underline(), InodeFormat::Decimal, None)
+        );
+    }
+
+    #[test]
+    fn hexadecimal() {
+        let io = f::Inode(1_414_213);
+        let expected = TextCell::paint_str(Cyan.underline(), "0x159445");
+        assert_eq!(
+            expected,
+            io.render(Cyan.underline(), InodeFormat::Hexadecimal, None)
+        );
     }
 }
@@ -6,16 +6,34 @@ use nu_ansi_term::Style;
 use crate::fs::fields as f;
 #[cfg(unix)]
 use crate::output::cell::TextCell;
+#[cfg(unix)]
+use crate::output::color_scale::ColorScaleInformation;
 
 #[cfg(unix)]
 impl f::Links {
-    pub fn render<C: Colours>(&self, colours: &C, numeric: &NumericLocale) -> TextCell {
-        let style = if self.multiple {
+    /// Renders the link count, highlighting files with at least `min_links`
+    /// links when `--min-links` was given, and shading it by how it compares
+    /// to the other counts in the listing when `--color-scale=links` was.
+    pub fn render<C: Colours>(
+        &self,
+        colours: &C,
+        numeric: &NumericLocale,
+        min_links: Option<f::nlink_t>,
+        color_scale_info: Option<ColorScaleInformation>,
+    ) -> TextCell {
+        let style = if self.multiple && min_links.is_some_and(|min| self.count >= min) {
+            colours.min_links_file()
+        } else if self.multiple {
             colours.multi_link_file()
         } else {
             colours.normal()
         };
 
+        let style = match color_scale_info {
+            Some(csi) => csi.adjust_style(style, self.count as f32, csi.links),
+            None => style,
+        };
+
         TextCell::paint(style, numeric.format_int(self.count))
     }
 }
@@ -23,6 +41,7 @@ impl f::Links {
 pub trait Colours {
     fn normal(&self) -> Style;
     fn multi_link_file(&self) -> Style;
+    fn min_links_file(&self) -> Style;
 }
 
 #[cfg(test)]
@@ -47,6 +66,9 @@ pub mod test {
         fn multi_link_file(&self) -> Style {
             Blue.on(Red)
         }
+        fn min_links_file(&self) -> Style {
+            Red.on(Blue)
+        }
     }
 
     #[test]
@@ -64,7 +86,7 @@ pub mod test {
 
         assert_eq!(
             expected,
-            stati.render(&TestColours, &locale::Numeric::english())
+            stati.render(&TestColours, &locale::Numeric::english(), None, None)
         );
     }
 
@@ -83,7 +105,7 @@ pub mod test {
 
         assert_eq!(
             expected,
-            stati.render(&TestColours, &locale::Numeric::english())
+            stati.render(&TestColours, &locale::Numeric::english(), None, None)
         );
     }
 
@@ -102,7 +124,66 @@ pub mod test {
 
         assert_eq!(
             expected,
-            stati.render(&TestColours, &locale::Numeric::english())
+            stati.render(&TestColours, &locale::Numeric::english(), None, None)
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn over_min_links() {
+        let stati = f::Links {
+            count: 3,
+            multiple: true,
+        };
+
+        let expected = TextCell {
+            width: DisplayWidth::from(1),
+            contents: vec![Red.on(Blue).paint("3")].into(),
+        };
+
+        assert_eq!(
+            expected,
+            stati.render(&TestColours, &locale::Numeric::english(), Some(3), None)
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn under_min_links() {
+        let stati = f::Links {
+            count: 2,
+            multiple: true,
+        };
+
+        let expected = TextCell {
+            width: DisplayWidth::from(1),
+            contents: vec![Blue.on(Red).paint("2")].into(),
+        };
+
+        assert_eq!(
+            expected,
+            stati.render(&TestColours, &locale::Numeric::english(), Some(3), None)
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn directory_over_min_links() {
+        // Directories aren’t filtered by their link count, so they aren’t
+        // highlighted by it either.
+        let stati = f::Links {
+            count: 5,
+            multiple: false,
+        };
+
+        let expected = TextCell {
+            width: DisplayWidth::from(1),
+            contents: vec![Blue.paint("5")].into(),
+        };
+
+        assert_eq!(
+            expected,
+            stati.render(&TestColours, &locale::Numeric::english(), Some(3), None)
         );
     }
 }
@@ -0,0 +1,40 @@
+use nu_ansi_term::Style;
+
+use crate::fs::fields as f;
+use crate::output::cell::TextCell;
+
+impl f::Mime {
+    pub fn render(self, style: Style, punctuation: Style) -> TextCell {
+        match self {
+            Self::Some(mime) => TextCell::paint_str(style, mime),
+            Self::None => TextCell::blank(punctuation),
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use crate::fs::fields as f;
+    use crate::output::cell::{DisplayWidth, TextCell};
+
+    use nu_ansi_term::Color::*;
+
+    #[test]
+    fn mime() {
+        let mime = f::Mime::Some("image/png");
+        let expected = TextCell::paint_str(Purple.normal(), "image/png");
+        assert_eq!(expected, mime.render(Purple.normal(), Black.bold()));
+    }
+
+    #[test]
+    fn none() {
+        let expected = TextCell {
+            width: DisplayWidth::from(1),
+            contents: vec![Black.bold().paint("-")].into(),
+        };
+        assert_eq!(
+            expected,
+            f::Mime::None.render(Purple.normal(), Black.bold())
+        );
+    }
+}
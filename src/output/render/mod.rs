@@ -3,6 +3,9 @@ mod blocks;
 #[cfg(unix)]
 pub use self::blocks::Colours as BlocksColours;
 
+mod checksum;
+// checksum uses just one colour
+
 mod filetype;
 pub use self::filetype::Colours as FiletypeColours;
 
@@ -22,6 +25,9 @@ mod inode;
 mod links;
 pub use self::links::Colours as LinksColours;
 
+mod mime;
+// mime uses just one colour
+
 mod permissions;
 pub use self::permissions::{Colours as PermissionsColours, PermissionsPlusRender};
 
@@ -39,6 +45,11 @@ pub use self::users::Colours as UserColours;
 #[cfg(unix)]
 pub use self::users::Render as UserRender;
 
+#[cfg(unix)]
+mod owner;
+#[cfg(unix)]
+pub use self::owner::{Colours as OwnerColours, Render as OwnerRender};
+
 mod octal;
 pub use self::octal::Render as OctalPermissionsRender;
 // octal uses just one colour
@@ -46,6 +57,11 @@ pub use self::octal::Render as OctalPermissionsRender;
 mod securityctx;
 pub use self::securityctx::Colours as SecurityCtxColours;
 
+#[cfg(target_os = "macos")]
+mod tags;
+#[cfg(target_os = "macos")]
+pub use self::tags::Colours as TagsColours;
+
 #[cfg(any(
     target_os = "macos",
     target_os = "freebsd",
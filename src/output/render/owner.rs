@@ -0,0 +1,269 @@
+use nu_ansi_term::Style;
+use uzers::{Groups, Users};
+
+use super::groups::{Colours as GroupColours, Render as GroupRender};
+use super::users::{Colours as UserColours, Render as UserRender};
+use crate::fs::fields as f;
+use crate::output::cell::TextCell;
+use crate::output::table::{GroupFormat, OwnerParts, UserFormat};
+
+/// Renders a file’s user and group together, as `user:group`. If only one
+/// of the parts is shown, there’s no separator. With `--smart-group`, a
+/// group with the same name as the user is left out, leaving just the user.
+pub trait Render {
+    fn render<C: Colours, U: Users + Groups>(
+        self,
+        colours: &C,
+        users: &U,
+        user_format: UserFormat,
+        group_format: GroupFormat,
+        parts: OwnerParts,
+    ) -> TextCell;
+}
+
+impl Render for (Option<f::User>, Option<f::Group>) {
+    fn render<C: Colours, U: Users + Groups>(
+        self,
+        colours: &C,
+        users: &U,
+        user_format: UserFormat,
+        group_format: GroupFormat,
+        parts: OwnerParts,
+    ) -> TextCell {
+        let (user, group) = self;
+
+        let group_cell = parts
+            .group
+            .then(|| group.render(colours, users, user_format, GroupFormat::Regular, user));
+
+        if !parts.user {
+            return group_cell.unwrap_or_else(|| TextCell::blank(colours.no_group()));
+        }
+
+        let mut cell = user.render(colours, users, user_format);
+        if let Some(group_cell) = group_cell {
+            if !(group_format == GroupFormat::Smart && same_name(users, user, group)) {
+                cell.push(colours.separator().paint(":"), 1);
+                cell.append(group_cell);
+            }
+        }
+
+        cell
+    }
+}
+
+/// Whether the file’s user and group both exist and have the same name.
+fn same_name<U: Users + Groups>(users: &U, user: Option<f::User>, group: Option<f::Group>) -> bool {
+    let (Some(user), Some(group)) = (user, group) else {
+        return false;
+    };
+
+    match (
+        users.get_user_by_uid(user.0),
+        users.get_group_by_gid(group.0),
+    ) {
+        (Some(user), Some(group)) => user.name() == group.name(),
+        _ => false,
+    }
+}
+
+pub trait Colours: UserColours + GroupColours {
+    fn separator(&self) -> Style;
+}
+
+#[cfg(test)]
+#[allow(unused_results)]
+pub mod test {
+    use super::{Colours, Render};
+    use crate::fs::fields as f;
+    use crate::output::cell::TextCell;
+    use crate::output::render::{GroupColours, UserColours};
+    use crate::output::table::{GroupFormat, OwnerParts, UserFormat};
+
+    use nu_ansi_term::Color::*;
+    use nu_ansi_term::Style;
+    use uzers::mock::MockUsers;
+    use uzers::{Group, User};
+
+    struct TestColours;
+
+    #[rustfmt::skip]
+    impl UserColours for TestColours {
+        fn you(&self)     -> Style { Red.bold() }
+        fn other(&self)   -> Style { Blue.underline() }
+        fn root(&self)    -> Style { Blue.underline() }
+        fn no_user(&self) -> Style { Black.italic() }
+    }
+
+    #[rustfmt::skip]
+    impl GroupColours for TestColours {
+        fn yours(&self)      -> Style { Fixed(80).normal() }
+        fn not_yours(&self)  -> Style { Fixed(81).normal() }
+        fn no_group(&self)   -> Style { Black.italic() }
+        fn root_group(&self) -> Style { Fixed(82).normal() }
+    }
+
+    #[rustfmt::skip]
+    impl Colours for TestColours {
+        fn separator(&self) -> Style { Fixed(83).normal() }
+    }
+
+    const BOTH: OwnerParts = OwnerParts {
+        user: true,
+        group: true,
+    };
+
+    fn users() -> MockUsers {
+        let mut users = MockUsers::with_current_uid(1000);
+        users.add_user(User::new(1000, "enoch", 100));
+        users.add_user(User::new(1001, "folk", 101));
+        users.add_group(Group::new(100, "staff"));
+        users.add_group(Group::new(101, "folk"));
+        users
+    }
+
+    fn expected(user: &'static str, group: Option<&'static str>) -> TextCell {
+        let mut cell = TextCell::paint_str(Red.bold(), user);
+        if let Some(group) = group {
+            cell.push(Fixed(83).normal().paint(":"), 1);
+            cell.append(TextCell::paint_str(Fixed(80).normal(), group));
+        }
+        cell
+    }
+
+    #[test]
+    fn named() {
+        let owner = (Some(f::User(1000)), Some(f::Group(100)));
+        assert_eq!(
+            expected("enoch", Some("staff")),
+            owner.render(
+                &TestColours,
+                &users(),
+                UserFormat::Name,
+                GroupFormat::Regular,
+                BOTH
+            )
+        );
+    }
+
+    #[test]
+    fn numeric() {
+        let owner = (Some(f::User(1000)), Some(f::Group(100)));
+        assert_eq!(
+            expected("1000", Some("100")),
+            owner.render(
+                &TestColours,
+                &users(),
+                UserFormat::Numeric,
+                GroupFormat::Regular,
+                BOTH
+            )
+        );
+    }
+
+    #[test]
+    fn unnamed() {
+        let users = MockUsers::with_current_uid(1000);
+        let owner = (Some(f::User(1000)), Some(f::Group(100)));
+
+        let mut cell = TextCell::paint_str(Red.bold(), "1000");
+        cell.push(Fixed(83).normal().paint(":"), 1);
+        cell.append(TextCell::paint_str(Fixed(81).normal(), "100"));
+        assert_eq!(
+            cell,
+            owner.render(
+                &TestColours,
+                &users,
+                UserFormat::Name,
+                GroupFormat::Regular,
+                BOTH
+            )
+        );
+    }
+
+    #[test]
+    fn smart_different_names() {
+        let owner = (Some(f::User(1000)), Some(f::Group(100)));
+        assert_eq!(
+            expected("enoch", Some("staff")),
+            owner.render(
+                &TestColours,
+                &users(),
+                UserFormat::Name,
+                GroupFormat::Smart,
+                BOTH
+            )
+        );
+    }
+
+    #[test]
+    fn smart_same_name() {
+        let owner = (Some(f::User(1001)), Some(f::Group(101)));
+        let expected = TextCell::paint_str(Blue.underline(), "folk");
+        assert_eq!(
+            expected,
+            owner.render(
+                &TestColours,
+                &users(),
+                UserFormat::Name,
+                GroupFormat::Smart,
+                BOTH
+            )
+        );
+    }
+
+    #[test]
+    fn smart_same_name_numeric() {
+        let owner = (Some(f::User(1001)), Some(f::Group(101)));
+        let expected = TextCell::paint_str(Blue.underline(), "1001");
+        assert_eq!(
+            expected,
+            owner.render(
+                &TestColours,
+                &users(),
+                UserFormat::Numeric,
+                GroupFormat::Smart,
+                BOTH
+            )
+        );
+    }
+
+    #[test]
+    fn user_only() {
+        let owner = (Some(f::User(1000)), Some(f::Group(100)));
+        let parts = OwnerParts {
+            user: true,
+            group: false,
+        };
+        assert_eq!(
+            expected("enoch", None),
+            owner.render(
+                &TestColours,
+                &users(),
+                UserFormat::Name,
+                GroupFormat::Regular,
+                parts
+            )
+        );
+    }
+
+    #[test]
+    fn group_only() {
+        let owner = (Some(f::User(1000)), Some(f::Group(100)));
+        let parts = OwnerParts {
+            user: false,
+            group: true,
+        };
+        let expected = TextCell::paint_str(Fixed(80).normal(), "100");
+        assert_eq!(
+            expected,
+            owner.render(
+                &TestColours,
+                &users(),
+                UserFormat::Numeric,
+                GroupFormat::Regular,
+                parts
+            )
+        );
+    }
+}
@@ -24,12 +24,13 @@ impl f::Size {
         };
 
         let is_gradient_mode =
-            color_scale_info.is_some_and(|csi| csi.options.mode == ColorScaleMode::Gradient);
+            color_scale_info.is_some_and(|csi| csi.options.mode != ColorScaleMode::Fixed);
 
         #[rustfmt::skip]
         let result = match size_format {
             SizeFormat::DecimalBytes  => NumberPrefix::decimal(size as f64),
             SizeFormat::BinaryBytes   => NumberPrefix::binary(size as f64),
+            SizeFormat::FixedUnit(u)  => u.convert(size),
             SizeFormat::JustBytes     => {
                 // Use the binary prefix to select a style.
                 let prefix = match NumberPrefix::binary(size as f64) {
@@ -69,10 +70,13 @@ impl f::Size {
         };
 
         let symbol = prefix.symbol();
+        // Sizes are never negative, and a cast to u64 saturates rather than
+        // wrapping, so even the largest size a corrupt filesystem can report
+        // is written as it is.
         let number = if n < 10_f64 {
             numerics.format_float(n, 1)
         } else {
-            numerics.format_int(n.round() as isize)
+            numerics.format_int(n.round() as u64)
         };
 
         TextCell {
@@ -83,7 +87,7 @@ impl f::Size {
                 vec![
                     csi.adjust_style(colours.size(Some(prefix)), size as f32, csi.size)
                         .paint(number),
-                    csi.adjust_style(colours.size(Some(prefix)), size as f32, csi.size)
+                    csi.adjust_style(colours.unit(Some(prefix)), size as f32, csi.size)
                         .paint(symbol),
                 ]
             } else {
@@ -129,6 +133,7 @@ pub mod test {
     use super::Colours;
     use crate::fs::fields as f;
     use crate::output::cell::{DisplayWidth, TextCell};
+    use crate::output::color_scale::{ColorScaleInformation, ColorScaleMode, ColorScaleOptions};
     use crate::output::table::SizeFormat;
 
     use locale::Numeric as NumericLocale;
@@ -183,6 +188,32 @@ pub mod test {
         );
     }
 
+    #[test]
+    fn file_fixed_unit() {
+        use crate::output::table::SizeUnit;
+
+        let render = |size, unit| {
+            f::Size::Some(size).render(
+                &TestColours,
+                SizeFormat::FixedUnit(unit),
+                &NumericLocale::english(),
+                None,
+            )
+        };
+
+        let small = TextCell {
+            width: DisplayWidth::from(5),
+            contents: vec![Fixed(66).paint("0.5"), Fixed(77).bold().paint("Ki")].into(),
+        };
+        assert_eq!(small, render(512, SizeUnit::Kibi));
+
+        let large = TextCell {
+            width: DisplayWidth::from(4),
+            contents: vec![Fixed(66).paint("50"), Fixed(77).bold().paint("Mi")].into(),
+        };
+        assert_eq!(large, render(52_428_800, SizeUnit::Mebi));
+    }
+
     #[test]
     fn file_binary() {
         let directory = f::Size::Some(1_048_576);
@@ -202,6 +233,49 @@ pub mod test {
         );
     }
 
+    #[test]
+    fn file_gradient() {
+        // Without a range of sizes to compare against, the gradient leaves
+        // the styles as they are.
+        let color_scale_info = ColorScaleInformation {
+            options: ColorScaleOptions {
+                mode: ColorScaleMode::Gradient,
+                min_luminance: 40,
+                max_luminance: 100,
+                size: true,
+                age: false,
+                inode: false,
+                links: false,
+                age_time: None,
+                gradient: None,
+                recent_threshold: None,
+            },
+            accessed: None,
+            changed: None,
+            created: None,
+            modified: None,
+            size: None,
+            inode: None,
+            links: None,
+        };
+
+        let directory = f::Size::Some(2_100_000);
+        let expected = TextCell {
+            width: DisplayWidth::from(4),
+            contents: vec![Fixed(66).paint("2.1"), Fixed(77).bold().paint("M")].into(),
+        };
+
+        assert_eq!(
+            expected,
+            directory.render(
+                &TestColours,
+                SizeFormat::DecimalBytes,
+                &NumericLocale::english(),
+                Some(color_scale_info)
+            )
+        );
+    }
+
     #[test]
     fn file_bytes() {
         let directory = f::Size::Some(1_048_576);
@@ -221,6 +295,72 @@ pub mod test {
         );
     }
 
+    #[test]
+    fn largest_size() {
+        let render = |size_format| {
+            f::Size::Some(u64::MAX).render(
+                &TestColours,
+                size_format,
+                &NumericLocale::english(),
+                None,
+            )
+        };
+
+        let bytes = TextCell {
+            width: DisplayWidth::from(26),
+            contents: vec![Fixed(66).paint("18,446,744,073,709,551,615")].into(),
+        };
+        assert_eq!(bytes, render(SizeFormat::JustBytes));
+
+        let decimal = TextCell {
+            width: DisplayWidth::from(3),
+            contents: vec![Fixed(66).paint("18"), Fixed(77).bold().paint("E")].into(),
+        };
+        assert_eq!(decimal, render(SizeFormat::DecimalBytes));
+
+        let binary = TextCell {
+            width: DisplayWidth::from(4),
+            contents: vec![Fixed(66).paint("16"), Fixed(77).bold().paint("Ei")].into(),
+        };
+        assert_eq!(binary, render(SizeFormat::BinaryBytes));
+    }
+
+    #[test]
+    fn largest_size_fixed_unit() {
+        use crate::output::table::SizeUnit;
+
+        let kilo = f::Size::Some(u64::MAX).render(
+            &TestColours,
+            SizeFormat::FixedUnit(SizeUnit::Kilo),
+            &NumericLocale::english(),
+            None,
+        );
+        let expected = TextCell {
+            width: DisplayWidth::from(23),
+            contents: vec![
+                Fixed(66).paint("18,446,744,073,709,552"),
+                Fixed(77).bold().paint("k"),
+            ]
+            .into(),
+        };
+        assert_eq!(expected, kilo);
+    }
+
+    #[test]
+    fn zero_size() {
+        let render = |size_format| {
+            f::Size::Some(0).render(&TestColours, size_format, &NumericLocale::english(), None)
+        };
+
+        let zero = TextCell {
+            width: DisplayWidth::from(1),
+            contents: vec![Fixed(66).paint("0")].into(),
+        };
+        assert_eq!(zero, render(SizeFormat::JustBytes));
+        assert_eq!(zero, render(SizeFormat::DecimalBytes));
+        assert_eq!(zero, render(SizeFormat::BinaryBytes));
+    }
+
     #[test]
     fn device_ids() {
         let directory = f::Size::DeviceIDs(f::DeviceIDs {
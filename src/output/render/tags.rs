@@ -0,0 +1,118 @@
+use nu_ansi_term::Style;
+
+use crate::fs::fields as f;
+use crate::output::cell::{TextCell, TextCellContents};
+use crate::output::escape;
+use crate::output::file_name::QuoteStyle;
+
+impl f::FinderTags {
+    /// Renders each tag’s name in its colour, separated by commas. Files
+    /// without any tags get a blank cell. Tag names can be set to anything,
+    /// so control characters in them are escaped, like in file names.
+    pub fn render<C: Colours>(&self, colours: &C) -> TextCell {
+        if self.0.is_empty() {
+            return TextCell::blank(colours.no_tags());
+        }
+
+        let mut cell = TextCell::default();
+        for (i, tag) in self.0.iter().enumerate() {
+            if i > 0 {
+                cell.push(colours.no_tags().paint(", "), 2);
+            }
+            let mut bits = Vec::new();
+            escape(
+                tag.name.clone(),
+                &mut bits,
+                colours.tag(tag.colour),
+                colours.control_char(),
+                QuoteStyle::NoQuotes,
+            );
+            cell.append(TextCellContents::from(bits).promote());
+        }
+
+        cell
+    }
+}
+
+pub trait Colours {
+    fn tag(&self, colour: f::TagColour) -> Style;
+    fn no_tags(&self) -> Style;
+    fn control_char(&self) -> Style;
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::Colours;
+    use crate::fs::fields as f;
+    use crate::output::cell::TextCell;
+
+    use nu_ansi_term::Color::*;
+    use nu_ansi_term::{AnsiStrings, Style};
+
+    struct TestColours;
+
+    impl Colours for TestColours {
+        fn tag(&self, colour: f::TagColour) -> Style {
+            match colour {
+                f::TagColour::Red => Red.normal(),
+                f::TagColour::Blue => Blue.normal(),
+                _ => Style::default(),
+            }
+        }
+
+        fn no_tags(&self) -> Style {
+            Black.italic()
+        }
+
+        fn control_char(&self) -> Style {
+            Yellow.normal()
+        }
+    }
+
+    fn tag(name: &str, colour: f::TagColour) -> f::Tag {
+        f::Tag {
+            name: name.into(),
+            colour,
+        }
+    }
+
+    #[test]
+    fn untagged() {
+        let tags = f::FinderTags(Vec::new());
+        assert_eq!(TextCell::blank(Black.italic()), tags.render(&TestColours));
+    }
+
+    #[test]
+    fn tagged() {
+        let tags = f::FinderTags(vec![
+            tag("Red", f::TagColour::Red),
+            tag("Work", f::TagColour::Blue),
+        ]);
+
+        let mut expected = TextCell::paint_str(Red.normal(), "Red");
+        expected.push(Black.italic().paint(", "), 2);
+        expected.append(TextCell::paint_str(Blue.normal(), "Work"));
+        assert_eq!(expected, tags.render(&TestColours));
+    }
+
+    #[test]
+    fn control_characters() {
+        let tags = f::FinderTags(vec![tag("a\x1b]0;b\x07", f::TagColour::Red)]);
+
+        let cell = tags.render(&TestColours);
+        assert_eq!(
+            cell.strings().to_string(),
+            AnsiStrings(&[
+                Red.paint("a"),
+                Yellow.paint("\\u{1b}"),
+                Red.paint("]"),
+                Red.paint("0"),
+                Red.paint(";"),
+                Red.paint("b"),
+                Yellow.paint("\\u{7}"),
+            ])
+            .to_string()
+        );
+        assert_eq!(*cell.width, 16);
+    }
+}
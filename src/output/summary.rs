@@ -0,0 +1,132 @@
+//! The one-line summaries printed after each directory’s contents with
+//! `--recurse --summarize-dirs`.
+
+use std::fmt;
+
+use crate::fs::fields as f;
+use crate::fs::File;
+
+/// How many entries were listed for a directory, and how big they are.
+#[derive(PartialEq, Eq, Debug, Default, Copy, Clone)]
+pub struct DirSummary {
+    pub entries: usize,
+    pub size: u64,
+}
+
+impl DirSummary {
+    /// Summarises the files that were listed for a directory. Only files
+    /// with a size count towards the total: directories are included when
+    /// `--total-size` has worked out the size of their contents. The total
+    /// stops at the largest size rather than overflowing.
+    pub fn of(files: &[File<'_>]) -> Self {
+        let size = files
+            .iter()
+            .map(|file| match file.size() {
+                f::Size::Some(bytes) => bytes,
+                _ => 0,
+            })
+            .fold(0, u64::saturating_add);
+
+        Self {
+            entries: files.len(),
+            size,
+        }
+    }
+}
+
+impl fmt::Display for DirSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let entries = if self.entries == 1 {
+            "entry"
+        } else {
+            "entries"
+        };
+        let bytes = if self.size == 1 { "byte" } else { "bytes" };
+        write!(f, "{} {entries}, {} {bytes}", self.entries, self.size)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fs::test_dir::TestDir;
+    use crate::fs::{Dir, DotFilter};
+    use std::path::Path;
+
+    /// Makes a directory holding `a` (3 bytes) and `sub`, which holds `b`
+    /// (5 bytes) and `c` (7 bytes).
+    fn two_dirs(name: &str) -> TestDir {
+        let dir = TestDir::new(name);
+        let sub = dir.join("sub");
+        std::fs::create_dir_all(&sub).unwrap();
+        std::fs::write(dir.join("a"), "aaa").unwrap();
+        std::fs::write(sub.join("b"), "bbbbb").unwrap();
+        std::fs::write(sub.join("c"), "ccccccc").unwrap();
+        dir
+    }
+
+    fn summarise(path: &Path, total_size: bool) -> DirSummary {
+        let dir = Dir::read_dir(path.to_path_buf()).unwrap();
+        let files = dir
+            .files(DotFilter::JustFiles, None, false, false, total_size)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        DirSummary::of(&files)
+    }
+
+    #[test]
+    fn files_only() {
+        let dir = two_dirs("summary-files");
+        let root = summarise(&dir, false);
+        let sub = summarise(&dir.join("sub"), false);
+
+        assert_eq!(
+            root,
+            DirSummary {
+                entries: 2,
+                size: 3
+            }
+        );
+        assert_eq!(
+            sub,
+            DirSummary {
+                entries: 2,
+                size: 12
+            }
+        );
+        assert_eq!(root.to_string(), "2 entries, 3 bytes");
+        assert_eq!(sub.to_string(), "2 entries, 12 bytes");
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn total_size() {
+        let dir = two_dirs("summary-total");
+        let root = summarise(&dir, true);
+        let sub = summarise(&dir.join("sub"), true);
+
+        assert_eq!(
+            root,
+            DirSummary {
+                entries: 2,
+                size: 15
+            }
+        );
+        assert_eq!(
+            sub,
+            DirSummary {
+                entries: 2,
+                size: 12
+            }
+        );
+    }
+
+    #[test]
+    fn singular() {
+        let summary = DirSummary {
+            entries: 1,
+            size: 1,
+        };
+        assert_eq!(summary.to_string(), "1 entry, 1 byte");
+    }
+}
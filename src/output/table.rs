@@ -6,18 +6,21 @@ use std::sync::{Mutex, MutexGuard};
 use chrono::prelude::*;
 
 use log::*;
+use nu_ansi_term::Style;
+use number_prefix::{NumberPrefix, Prefix};
 use once_cell::sync::Lazy;
 #[cfg(unix)]
 use uzers::UsersCache;
 
+use crate::fs::feature::checksum::Algorithm;
 use crate::fs::feature::git::GitCache;
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+use crate::fs::feature::xattr;
 use crate::fs::{fields as f, File};
-use crate::options::vars::EZA_WINDOWS_ATTRIBUTES;
-use crate::options::Vars;
 use crate::output::cell::TextCell;
 use crate::output::color_scale::ColorScaleInformation;
 #[cfg(unix)]
-use crate::output::render::{GroupRender, OctalPermissionsRender, UserRender};
+use crate::output::render::{GroupRender, OctalPermissionsRender, OwnerRender, UserRender};
 use crate::output::render::{PermissionsPlusRender, TimeRender};
 use crate::output::time::TimeFormat;
 use crate::theme::Theme;
@@ -32,20 +35,29 @@ pub struct Options {
     pub user_format: UserFormat,
     pub group_format: GroupFormat,
     pub flags_format: FlagsFormat,
+    pub inode_format: InodeFormat,
+    pub dir_size: DirSize,
+
+    /// The link count to highlight files at, from `--min-links`.
+    pub min_links: Option<f::nlink_t>,
+
     pub columns: Columns,
+    pub alignments: ColumnAlignments,
 }
 
 /// Extra columns to display in the table.
 #[allow(clippy::struct_excessive_bools)]
-#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+#[derive(PartialEq, Eq, Debug, Clone)]
 pub struct Columns {
     /// At least one of these timestamps will be shown.
     pub time_types: TimeTypes,
 
     // The rest are just on/off
+    pub depth: bool,
     pub inode: bool,
     pub links: bool,
     pub blocksize: bool,
+    pub io_blocksize: bool,
     pub group: bool,
     pub git: bool,
     pub subdir_git_repos: bool,
@@ -53,17 +65,60 @@ pub struct Columns {
     pub octal: bool,
     pub security_context: bool,
     pub file_flags: bool,
+    pub finder_tags: bool,
+    pub mime: bool,
+
+    /// The digest to show for each file, from `--checksum`.
+    pub checksum: Option<Algorithm>,
 
     // Defaults to true:
     pub permissions: bool,
     pub filesize: bool,
     pub user: bool,
+
+    /// Whether the user and group share one column, rather than having one
+    /// each.
+    pub combined_owner: bool,
+
+    /// Whether the Git status column goes last, just before the file name,
+    /// rather than before the subdirectory repository column, from
+    /// `--git-col`.
+    pub git_left: bool,
+
+    /// The exact columns to show, in order, from `--columns`. When this is
+    /// set, it’s used instead of the flags above, apart from the Git ones.
+    pub order: Option<Vec<ColumnKind>>,
 }
 
 impl Columns {
     pub fn collect(&self, actually_enable_git: bool, git_repos: bool) -> Vec<Column> {
+        let mut columns = self.collect_in_order(actually_enable_git, git_repos);
+
+        if self.git_left {
+            if let Some(index) = columns.iter().position(|c| matches!(c, Column::GitStatus)) {
+                let git = columns.remove(index);
+                columns.push(git);
+            }
+        }
+
+        columns
+    }
+
+    /// The columns to show, in the order they’d be in without `--git-col`.
+    fn collect_in_order(&self, actually_enable_git: bool, git_repos: bool) -> Vec<Column> {
+        if let Some(order) = &self.order {
+            return order
+                .iter()
+                .filter_map(|kind| self.column_for(*kind, actually_enable_git, git_repos))
+                .collect();
+        }
+
         let mut columns = Vec::with_capacity(4);
 
+        if self.depth {
+            columns.push(Column::Depth);
+        }
+
         if self.inode {
             #[cfg(unix)]
             columns.push(Column::Inode);
@@ -92,14 +147,27 @@ impl Columns {
             columns.push(Column::Blocksize);
         }
 
-        if self.user {
+        if self.io_blocksize {
             #[cfg(unix)]
-            columns.push(Column::User);
+            columns.push(Column::IoBlocksize);
         }
 
-        if self.group {
+        if self.combined_owner && (self.user || self.group) {
             #[cfg(unix)]
-            columns.push(Column::Group);
+            columns.push(Column::Owner(OwnerParts {
+                user: self.user,
+                group: self.group,
+            }));
+        } else {
+            if self.user {
+                #[cfg(unix)]
+                columns.push(Column::User);
+            }
+
+            if self.group {
+                #[cfg(unix)]
+                columns.push(Column::Group);
+            }
         }
 
         if self.file_flags {
@@ -111,6 +179,11 @@ impl Columns {
             columns.push(Column::SecurityContext);
         }
 
+        #[cfg(target_os = "macos")]
+        if self.finder_tags {
+            columns.push(Column::FinderTags);
+        }
+
         if self.time_types.modified {
             columns.push(Column::Timestamp(TimeType::Modified));
         }
@@ -127,6 +200,14 @@ impl Columns {
             columns.push(Column::Timestamp(TimeType::Accessed));
         }
 
+        if self.mime {
+            columns.push(Column::Mime);
+        }
+
+        if let Some(algorithm) = self.checksum {
+            columns.push(Column::Checksum(algorithm));
+        }
+
         if self.git && actually_enable_git {
             columns.push(Column::GitStatus);
         }
@@ -141,21 +222,140 @@ impl Columns {
 
         columns
     }
+
+    /// The column to show for one of the kinds listed with `--columns`, if
+    /// it can be shown at all. The Git columns still need their own flags,
+    /// so they’re left out in the same cases they would be otherwise, as
+    /// are columns that aren’t available on this platform.
+    fn column_for(
+        &self,
+        kind: ColumnKind,
+        actually_enable_git: bool,
+        git_repos: bool,
+    ) -> Option<Column> {
+        match kind {
+            ColumnKind::Depth => Some(Column::Depth),
+            ColumnKind::Permissions => Some(Column::Permissions),
+            ColumnKind::Size => Some(Column::FileSize),
+            ColumnKind::Flags => Some(Column::FileFlags),
+            ColumnKind::Timestamp(time_type) => Some(Column::Timestamp(time_type)),
+            ColumnKind::Mime => Some(Column::Mime),
+            ColumnKind::Checksum => self.checksum.map(Column::Checksum),
+            ColumnKind::Git if self.git && actually_enable_git => Some(Column::GitStatus),
+            ColumnKind::Repo if self.subdir_git_repos && git_repos => {
+                Some(Column::SubdirGitRepo(true))
+            }
+            ColumnKind::Repo if self.subdir_git_repos_no_stat && git_repos => {
+                Some(Column::SubdirGitRepo(false))
+            }
+            #[cfg(unix)]
+            ColumnKind::Inode => Some(Column::Inode),
+            #[cfg(unix)]
+            ColumnKind::Octal => Some(Column::Octal),
+            #[cfg(unix)]
+            ColumnKind::Links => Some(Column::HardLinks),
+            #[cfg(unix)]
+            ColumnKind::Blocksize => Some(Column::Blocksize),
+            #[cfg(unix)]
+            ColumnKind::IoBlocksize => Some(Column::IoBlocksize),
+            #[cfg(unix)]
+            ColumnKind::User => Some(Column::User),
+            #[cfg(unix)]
+            ColumnKind::Group => Some(Column::Group),
+            #[cfg(unix)]
+            ColumnKind::Owner => Some(Column::Owner(OwnerParts {
+                user: true,
+                group: true,
+            })),
+            #[cfg(target_os = "linux")]
+            ColumnKind::Context if xattr::ENABLED => Some(Column::SecurityContext),
+            #[cfg(target_os = "macos")]
+            ColumnKind::Tags if xattr::ENABLED => Some(Column::FinderTags),
+            _ => None,
+        }
+    }
+}
+
+/// A column that can be picked by name with `--columns`, which chooses the
+/// columns to show and the order to show them in.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum ColumnKind {
+    Depth,
+    Inode,
+    Octal,
+    Permissions,
+    Links,
+    Size,
+    Blocksize,
+    IoBlocksize,
+    User,
+    Group,
+    Owner,
+    Flags,
+    Context,
+    Tags,
+    Timestamp(TimeType),
+    Mime,
+    Checksum,
+    Git,
+    Repo,
+}
+
+impl ColumnKind {
+    /// Every kind of column, by the name used for it with `--columns`,
+    /// which is the same as the name used with `--align`.
+    pub const NAMES: &'static [(&'static str, Self)] = &[
+        ("depth", Self::Depth),
+        ("inode", Self::Inode),
+        ("octal", Self::Octal),
+        ("permissions", Self::Permissions),
+        ("links", Self::Links),
+        ("size", Self::Size),
+        ("blocksize", Self::Blocksize),
+        ("io-blocksize", Self::IoBlocksize),
+        ("user", Self::User),
+        ("group", Self::Group),
+        ("owner", Self::Owner),
+        ("flags", Self::Flags),
+        ("context", Self::Context),
+        ("tags", Self::Tags),
+        ("modified", Self::Timestamp(TimeType::Modified)),
+        ("changed", Self::Timestamp(TimeType::Changed)),
+        ("created", Self::Timestamp(TimeType::Created)),
+        ("accessed", Self::Timestamp(TimeType::Accessed)),
+        ("mime", Self::Mime),
+        ("checksum", Self::Checksum),
+        ("git", Self::Git),
+        ("repo", Self::Repo),
+    ];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::NAMES
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, kind)| *kind)
+    }
 }
 
 /// A table contains these.
 #[derive(Debug, Copy, Clone)]
 pub enum Column {
+    Depth,
     Permissions,
     FileSize,
     Timestamp(TimeType),
+    Timestamps(TimeTypes),
     #[cfg(unix)]
     Blocksize,
     #[cfg(unix)]
+    IoBlocksize,
+    #[cfg(unix)]
     User,
     #[cfg(unix)]
     Group,
     #[cfg(unix)]
+    Owner(OwnerParts),
+    #[cfg(unix)]
     HardLinks,
     #[cfg(unix)]
     Inode,
@@ -165,26 +365,113 @@ pub enum Column {
     Octal,
     #[cfg(unix)]
     SecurityContext,
+    #[cfg(target_os = "macos")]
+    FinderTags,
     FileFlags,
+    Mime,
+    Checksum(Algorithm),
+}
+
+/// The parts of a file’s owner that are shown in a combined `user:group`
+/// column. At least one of them is always on.
+#[cfg(unix)]
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub struct OwnerParts {
+    pub user: bool,
+    pub group: bool,
 }
 
 /// Each column can pick its own **Alignment**. Usually, numbers are
 /// right-aligned, and text is left-aligned.
-#[derive(Copy, Clone)]
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
 pub enum Alignment {
     Left,
     Right,
 }
 
+/// Overrides for the alignment of particular columns, by name, which take
+/// precedence over each column’s own alignment. Later overrides win.
+#[derive(PartialEq, Eq, Debug, Default, Clone)]
+pub struct ColumnAlignments(pub Vec<(&'static str, Alignment)>);
+
+impl ColumnAlignments {
+    /// Get the alignment the given column should use, taking any override
+    /// into account.
+    pub fn alignment(&self, column: Column) -> Alignment {
+        self.0
+            .iter()
+            .rev()
+            .find(|(name, _)| *name == column.name())
+            .map_or_else(|| column.alignment(), |(_, alignment)| *alignment)
+    }
+}
+
 impl Column {
+    /// One of each kind of column there is on this system, to find the
+    /// names the user can use to refer to them, such as when overriding
+    /// their alignment.
+    const EVERY: &'static [Self] = &[
+        Self::Depth,
+        Self::Permissions,
+        Self::FileSize,
+        Self::Timestamp(TimeType::Modified),
+        Self::Timestamp(TimeType::Changed),
+        Self::Timestamp(TimeType::Accessed),
+        Self::Timestamp(TimeType::Created),
+        Self::Timestamps(TimeTypes {
+            modified: true,
+            changed: false,
+            accessed: false,
+            created: false,
+        }),
+        #[cfg(unix)]
+        Self::Blocksize,
+        #[cfg(unix)]
+        Self::IoBlocksize,
+        #[cfg(unix)]
+        Self::User,
+        #[cfg(unix)]
+        Self::Group,
+        #[cfg(unix)]
+        Self::Owner(OwnerParts {
+            user: true,
+            group: true,
+        }),
+        #[cfg(unix)]
+        Self::HardLinks,
+        #[cfg(unix)]
+        Self::Inode,
+        Self::GitStatus,
+        Self::SubdirGitRepo(false),
+        #[cfg(unix)]
+        Self::Octal,
+        #[cfg(unix)]
+        Self::SecurityContext,
+        #[cfg(target_os = "macos")]
+        Self::FinderTags,
+        Self::FileFlags,
+        Self::Mime,
+        Self::Checksum(Algorithm::Crc32),
+    ];
+
+    /// The names the user can use to refer to each column, as given by
+    /// `name`.
+    pub fn names() -> impl Iterator<Item = &'static str> {
+        Self::EVERY.iter().copied().map(Self::name)
+    }
+
     /// Get the alignment this column should use.
     #[cfg(unix)]
     pub fn alignment(self) -> Alignment {
         #[allow(clippy::wildcard_in_or_patterns)]
         match self {
-            Self::FileSize | Self::HardLinks | Self::Inode | Self::Blocksize | Self::GitStatus => {
-                Alignment::Right
-            }
+            Self::Depth
+            | Self::FileSize
+            | Self::HardLinks
+            | Self::Inode
+            | Self::Blocksize
+            | Self::IoBlocksize
+            | Self::GitStatus => Alignment::Right,
             Self::Timestamp(_) | _ => Alignment::Left,
         }
     }
@@ -192,28 +479,80 @@ impl Column {
     #[cfg(windows)]
     pub fn alignment(self) -> Alignment {
         match self {
-            Self::FileSize | Self::GitStatus => Alignment::Right,
+            Self::Depth | Self::FileSize | Self::GitStatus => Alignment::Right,
             _ => Alignment::Left,
         }
     }
 
+    /// Get the name used to refer to this column on the command-line.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Depth => "depth",
+            Self::Permissions => "permissions",
+            Self::FileSize => "size",
+            Self::Timestamp(TimeType::Modified) => "modified",
+            Self::Timestamp(TimeType::Changed) => "changed",
+            Self::Timestamp(TimeType::Accessed) => "accessed",
+            Self::Timestamp(TimeType::Created) => "created",
+            Self::Timestamps(_) => "times",
+            #[cfg(unix)]
+            Self::Blocksize => "blocksize",
+            #[cfg(unix)]
+            Self::IoBlocksize => "io-blocksize",
+            #[cfg(unix)]
+            Self::User => "user",
+            #[cfg(unix)]
+            Self::Group => "group",
+            #[cfg(unix)]
+            Self::Owner(_) => "owner",
+            #[cfg(unix)]
+            Self::HardLinks => "links",
+            #[cfg(unix)]
+            Self::Inode => "inode",
+            Self::GitStatus => "git",
+            Self::SubdirGitRepo(_) => "repo",
+            #[cfg(unix)]
+            Self::Octal => "octal",
+            #[cfg(unix)]
+            Self::SecurityContext => "context",
+            #[cfg(target_os = "macos")]
+            Self::FinderTags => "tags",
+            Self::FileFlags => "flags",
+            Self::Mime => "mime",
+            Self::Checksum(_) => "checksum",
+        }
+    }
+
     /// Get the text that should be printed at the top, when the user elects
     /// to have a header row printed.
     pub fn header(self) -> &'static str {
         match self {
+            Self::Depth => "Depth",
             #[cfg(unix)]
             Self::Permissions => "Permissions",
             #[cfg(windows)]
             Self::Permissions => "Mode",
             Self::FileSize => "Size",
             Self::Timestamp(t) => t.header(),
+            Self::Timestamps(_) => "Dates",
             #[cfg(unix)]
             Self::Blocksize => "Blocksize",
             #[cfg(unix)]
+            Self::IoBlocksize => "IO Blocksize",
+            #[cfg(unix)]
             Self::User => "User",
             #[cfg(unix)]
             Self::Group => "Group",
             #[cfg(unix)]
+            Self::Owner(OwnerParts {
+                user: true,
+                group: true,
+            }) => "User:Group",
+            #[cfg(unix)]
+            Self::Owner(OwnerParts { group: false, .. }) => "User",
+            #[cfg(unix)]
+            Self::Owner(OwnerParts { user: false, .. }) => "Group",
+            #[cfg(unix)]
             Self::HardLinks => "Links",
             #[cfg(unix)]
             Self::Inode => "inode",
@@ -223,7 +562,11 @@ impl Column {
             Self::Octal => "Octal",
             #[cfg(unix)]
             Self::SecurityContext => "Security Context",
+            #[cfg(target_os = "macos")]
+            Self::FinderTags => "Tags",
             Self::FileFlags => "Flags",
+            Self::Mime => "MIME Type",
+            Self::Checksum(algorithm) => algorithm.header(),
         }
     }
 }
@@ -243,6 +586,59 @@ pub enum SizeFormat {
 
     /// Do no formatting and just display the size as a number of bytes.
     JustBytes,
+
+    /// Format every size in the same unit, however big or small it is, so
+    /// the numbers in a column can be compared at a glance.
+    FixedUnit(SizeUnit),
+}
+
+/// A unit that every size can be shown in, from `--size-unit`.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum SizeUnit {
+    Kilo,
+    Mega,
+    Giga,
+    Tera,
+    Kibi,
+    Mebi,
+    Gibi,
+    Tebi,
+}
+
+impl SizeUnit {
+    /// The prefix shown after each size.
+    fn prefix(self) -> Prefix {
+        match self {
+            Self::Kilo => Prefix::Kilo,
+            Self::Mega => Prefix::Mega,
+            Self::Giga => Prefix::Giga,
+            Self::Tera => Prefix::Tera,
+            Self::Kibi => Prefix::Kibi,
+            Self::Mebi => Prefix::Mebi,
+            Self::Gibi => Prefix::Gibi,
+            Self::Tebi => Prefix::Tebi,
+        }
+    }
+
+    /// How many bytes there are in one of this unit.
+    fn bytes(self) -> f64 {
+        match self {
+            Self::Kilo => 1e3,
+            Self::Mega => 1e6,
+            Self::Giga => 1e9,
+            Self::Tera => 1e12,
+            Self::Kibi => 1024_f64,
+            Self::Mebi => 1024_f64.powi(2),
+            Self::Gibi => 1024_f64.powi(3),
+            Self::Tebi => 1024_f64.powi(4),
+        }
+    }
+
+    /// The given number of bytes in this unit, even when that’s less than
+    /// one of it.
+    pub fn convert(self, size: u64) -> NumberPrefix<f64> {
+        NumberPrefix::Prefixed(self.prefix(), size as f64 / self.bytes())
+    }
 }
 
 /// Formatting options for user and group.
@@ -254,11 +650,33 @@ pub enum UserFormat {
     Name,
 }
 
+/// Which base to show inode numbers in.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum InodeFormat {
+    /// Plain decimal numbers, as `ls -i` shows them.
+    Decimal,
+    /// Hexadecimal numbers, with a leading `0x`.
+    Hexadecimal,
+}
+
+/// What to show in the size column for directories.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum DirSize {
+    /// Nothing, as a directory has no size of its own.
+    None,
+    /// The size of the directory entry itself, as `ls` shows.
+    Inode,
+    /// The total size of everything inside it, with `--total-size`.
+    Total,
+}
+
 /// Formatting options for group only.
 #[derive(PartialEq, Eq, Debug, Copy, Clone)]
 pub enum GroupFormat {
     /// Numeric or text value
     Regular,
+    /// Show the group’s ID rather than its name
+    Numeric,
     /// Show ":" if user-group value is the same
     Smart,
 }
@@ -291,6 +709,17 @@ impl TimeType {
         }
     }
 
+    /// Returns the short label that goes before this time when several are
+    /// shown together in one column.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Modified => "mod",
+            Self::Changed => "ch",
+            Self::Accessed => "acc",
+            Self::Created => "cr",
+        }
+    }
+
     /// Returns the corresponding time from [File]
     pub fn get_corresponding_time(self, file: &File<'_>) -> Option<NaiveDateTime> {
         match self {
@@ -312,18 +741,6 @@ pub enum FlagsFormat {
     Short,
 }
 
-impl FlagsFormat {
-    pub(crate) fn deduce<V: Vars>(vars: &V) -> FlagsFormat {
-        vars.get(EZA_WINDOWS_ATTRIBUTES)
-            .and_then(|v| match v.to_ascii_lowercase().to_str() {
-                Some("short") => Some(FlagsFormat::Short),
-                Some("long") => Some(FlagsFormat::Long),
-                _ => None,
-            })
-            .unwrap_or_default()
-    }
-}
-
 /// Fields for which of a file’s time fields should be displayed in the
 /// columns output.
 ///
@@ -339,6 +756,21 @@ pub struct TimeTypes {
     pub created:  bool,
 }
 
+impl TimeTypes {
+    /// The time types that are selected, in the order they happen to a
+    /// file.
+    pub fn iter(self) -> impl Iterator<Item = TimeType> {
+        [
+            (self.created, TimeType::Created),
+            (self.modified, TimeType::Modified),
+            (self.changed, TimeType::Changed),
+            (self.accessed, TimeType::Accessed),
+        ]
+        .into_iter()
+        .filter_map(|(selected, time_type)| selected.then_some(time_type))
+    }
+}
+
 impl Default for TimeTypes {
     /// By default, display just the ‘modified’ time. This is the most
     /// common option, which is why it has this shorthand.
@@ -396,16 +828,22 @@ static ENVIRONMENT: Lazy<Environment> = Lazy::new(Environment::load_all);
 
 pub struct Table<'a> {
     columns: Vec<Column>,
+    alignments: Vec<Alignment>,
     theme: &'a Theme,
     env: &'a Environment,
     widths: TableWidths,
     time_format: TimeFormat,
     size_format: SizeFormat,
+    dir_size: DirSize,
     #[cfg(unix)]
     user_format: UserFormat,
     #[cfg(unix)]
     group_format: GroupFormat,
     flags_format: FlagsFormat,
+    #[cfg(unix)]
+    inode_format: InodeFormat,
+    #[cfg(unix)]
+    min_links: Option<f::nlink_t>,
     git: Option<&'a GitCache>,
 }
 
@@ -421,7 +859,15 @@ impl<'a> Table<'a> {
         theme: &'a Theme,
         git_repos: bool,
     ) -> Table<'a> {
-        let columns = options.columns.collect(git.is_some(), git_repos);
+        let mut columns = options.columns.collect(git.is_some(), git_repos);
+        if options.time_format == TimeFormat::Combined {
+            combine_timestamps(&mut columns);
+        }
+
+        let alignments = columns
+            .iter()
+            .map(|c| options.alignments.alignment(*c))
+            .collect();
         let widths = TableWidths::zero(columns.len());
         let env = &*ENVIRONMENT;
 
@@ -431,15 +877,21 @@ impl<'a> Table<'a> {
             theme,
             widths,
             columns,
+            alignments,
             git,
             env,
             time_format: options.time_format.clone(),
             size_format: options.size_format,
+            dir_size: options.dir_size,
             #[cfg(unix)]
             user_format: options.user_format,
             #[cfg(unix)]
             group_format: options.group_format,
             flags_format: options.flags_format,
+            #[cfg(unix)]
+            inode_format: options.inode_format,
+            #[cfg(unix)]
+            min_links: options.min_links,
         }
     }
 
@@ -457,21 +909,35 @@ impl<'a> Table<'a> {
         Row { cells }
     }
 
+    /// Gets the row of cells for a file, which is `depth` levels below the
+    /// files and directories given as arguments.
     pub fn row_for_file(
         &self,
         file: &File<'_>,
+        depth: usize,
         xattrs: bool,
         color_scale_info: Option<ColorScaleInformation>,
     ) -> Row {
         let cells = self
             .columns
             .iter()
-            .map(|c| self.display(file, *c, xattrs, color_scale_info))
+            .map(|c| self.display(file, depth, *c, xattrs, color_scale_info))
             .collect();
 
         Row { cells }
     }
 
+    /// Gets the cell a file would have in the given column, for views that
+    /// lay out the cells themselves.
+    /// The columns in this table, in order.
+    pub fn columns(&self) -> &[Column] {
+        &self.columns
+    }
+
+    pub fn cell_for_file(&self, file: &File<'_>, column: Column) -> TextCell {
+        self.display(file, 0, column, false, None)
+    }
+
     pub fn add_widths(&mut self, row: &Row) {
         self.widths.add_widths(row);
     }
@@ -502,31 +968,57 @@ impl<'a> Table<'a> {
             .map(|p| f::OctalPermissions { permissions: p })
     }
 
+    /// The size to show for a file, where directories show whatever
+    /// `--dir-size` asked for.
+    fn file_size(&self, file: &File<'_>) -> f::Size {
+        if !file.is_directory() {
+            return file.size();
+        }
+
+        match self.dir_size {
+            DirSize::None => f::Size::None,
+            DirSize::Inode => f::Size::Some(file.metadata.len()),
+            DirSize::Total => file.size(),
+        }
+    }
+
     fn display(
         &self,
         file: &File<'_>,
+        depth: usize,
         column: Column,
         xattrs: bool,
         color_scale_info: Option<ColorScaleInformation>,
     ) -> TextCell {
         match column {
+            Column::Depth => TextCell::paint(Style::default(), depth.to_string()),
             Column::Permissions => self.permissions_plus(file, xattrs).render(self.theme),
-            Column::FileSize => file.size().render(
+            Column::FileSize => self.file_size(file).render(
                 self.theme,
                 self.size_format,
                 &self.env.numeric,
                 color_scale_info,
             ),
             #[cfg(unix)]
-            Column::HardLinks => file.links().render(self.theme, &self.env.numeric),
+            Column::HardLinks => file.links().render(
+                self.theme,
+                &self.env.numeric,
+                self.min_links,
+                color_scale_info,
+            ),
             #[cfg(unix)]
-            Column::Inode => file.inode().render(self.theme.ui.inode),
+            Column::Inode => {
+                file.inode()
+                    .render(self.theme.ui.inode, self.inode_format, color_scale_info)
+            }
             #[cfg(unix)]
             Column::Blocksize => {
                 file.blocksize()
                     .render(self.theme, self.size_format, &self.env.numeric)
             }
             #[cfg(unix)]
+            Column::IoBlocksize => file.io_blocksize().render(self.theme),
+            #[cfg(unix)]
             Column::User => {
                 file.user()
                     .render(self.theme, &*self.env.lock_users(), self.user_format)
@@ -540,16 +1032,31 @@ impl<'a> Table<'a> {
                 file.user(),
             ),
             #[cfg(unix)]
+            Column::Owner(parts) => (file.user(), file.group()).render(
+                self.theme,
+                &*self.env.lock_users(),
+                self.user_format,
+                self.group_format,
+                parts,
+            ),
+            #[cfg(unix)]
             Column::SecurityContext => file.security_context().render(self.theme),
+            #[cfg(target_os = "macos")]
+            Column::FinderTags => file.finder_tags().render(self.theme),
             Column::FileFlags => file.flags().render(self.theme.ui.flags, self.flags_format),
+            Column::Mime => file
+                .mime()
+                .render(self.theme.ui.mime, self.theme.ui.punctuation),
+            Column::Checksum(algorithm) => file
+                .checksum(algorithm)
+                .render(self.theme.ui.checksum, self.theme.ui.punctuation),
             Column::GitStatus => self.git_status(file).render(self.theme),
             Column::SubdirGitRepo(status) => self.subdir_git_repo(file, status).render(self.theme),
             #[cfg(unix)]
             Column::Octal => self.octal_permissions(file).render(self.theme.ui.octal),
 
             Column::Timestamp(time_type) => time_type.get_corresponding_time(file).render(
-                if color_scale_info.is_some_and(|csi| csi.options.mode == ColorScaleMode::Gradient)
-                {
+                if color_scale_info.is_some_and(|csi| csi.options.mode != ColorScaleMode::Fixed) {
                     color_scale_info.unwrap().apply_time_gradient(
                         self.theme.ui.date,
                         file,
@@ -561,9 +1068,34 @@ impl<'a> Table<'a> {
                 self.env.time_offset,
                 self.time_format.clone(),
             ),
+            Column::Timestamps(time_types) => self.timestamps(file, time_types),
         }
     }
 
+    /// Renders each of the file’s selected times after its label, such as
+    /// “cr 3d / mod 1h”.
+    fn timestamps(&self, file: &File<'_>, time_types: TimeTypes) -> TextCell {
+        let times = time_types
+            .iter()
+            .map(|time_type| {
+                let time = time_type.get_corresponding_time(file).map_or_else(
+                    || String::from("-"),
+                    |time| {
+                        self.time_format.clone().format(
+                            &DateTime::<FixedOffset>::from_naive_utc_and_offset(
+                                time,
+                                self.env.time_offset,
+                            ),
+                        )
+                    },
+                );
+                format!("{} {time}", time_type.label())
+            })
+            .collect::<Vec<_>>();
+
+        TextCell::paint(self.theme.ui.date, times.join(" / "))
+    }
+
     fn git_status(&self, file: &File<'_>) -> f::Git {
         debug!("Getting Git status for file {:?}", file.path);
 
@@ -589,7 +1121,7 @@ impl<'a> Table<'a> {
         for (n, (this_cell, width)) in iter {
             let padding = width - *this_cell.width;
 
-            match self.columns[n].alignment() {
+            match self.alignments[n] {
                 Alignment::Left => {
                     cell.append(this_cell);
                     cell.add_spaces(padding);
@@ -607,6 +1139,36 @@ impl<'a> Table<'a> {
     }
 }
 
+/// Replaces the separate timestamp columns with a single one showing all of
+/// them, in the place of the first.
+fn combine_timestamps(columns: &mut Vec<Column>) {
+    let mut time_types = TimeTypes {
+        modified: false,
+        changed: false,
+        accessed: false,
+        created: false,
+    };
+    for column in columns.iter() {
+        match column {
+            Column::Timestamp(TimeType::Modified) => time_types.modified = true,
+            Column::Timestamp(TimeType::Changed) => time_types.changed = true,
+            Column::Timestamp(TimeType::Accessed) => time_types.accessed = true,
+            Column::Timestamp(TimeType::Created) => time_types.created = true,
+            _ => {}
+        }
+    }
+
+    let Some(first) = columns
+        .iter()
+        .position(|c| matches!(c, Column::Timestamp(_)))
+    else {
+        return;
+    };
+
+    columns.retain(|c| !matches!(c, Column::Timestamp(_)));
+    columns.insert(first, Column::Timestamps(time_types));
+}
+
 pub struct TableWidths(Vec<usize>);
 
 impl Deref for TableWidths {
@@ -632,3 +1194,389 @@ impl TableWidths {
         self.0.len() + self.0.iter().sum::<usize>()
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fs::test_dir::TestDir;
+    use crate::output::color_scale::{ColorScaleMode, ColorScaleOptions};
+    use crate::theme::{Options as ThemeOptions, UseColours};
+
+    fn options(alignments: ColumnAlignments) -> Options {
+        Options {
+            size_format: SizeFormat::JustBytes,
+            time_format: TimeFormat::DefaultFormat { utc: false },
+            user_format: UserFormat::Numeric,
+            group_format: GroupFormat::Regular,
+            flags_format: FlagsFormat::Long,
+            inode_format: InodeFormat::Decimal,
+            dir_size: DirSize::None,
+            min_links: None,
+            columns: Columns {
+                time_types: TimeTypes {
+                    modified: false,
+                    changed: false,
+                    accessed: false,
+                    created: false,
+                },
+                depth: false,
+                inode: false,
+                links: false,
+                blocksize: false,
+                io_blocksize: false,
+                group: false,
+                git: false,
+                subdir_git_repos: false,
+                subdir_git_repos_no_stat: false,
+                octal: false,
+                security_context: false,
+                file_flags: false,
+                finder_tags: false,
+                mime: false,
+                checksum: None,
+                permissions: false,
+                filesize: true,
+                user: false,
+                combined_owner: false,
+                git_left: false,
+                order: None,
+            },
+            alignments,
+        }
+    }
+
+    /// Renders a single-column table of the given sizes, one line each.
+    fn render_sizes(options: &Options, sizes: &[&'static str]) -> Vec<String> {
+        let theme = ThemeOptions {
+            use_colours: UseColours::Never,
+            colour_scale: ColorScaleOptions {
+                mode: ColorScaleMode::Fixed,
+                min_luminance: 40,
+                max_luminance: 100,
+                size: false,
+                age: false,
+                inode: false,
+                links: false,
+                age_time: None,
+                gradient: None,
+                recent_threshold: None,
+            },
+            definitions: Default::default(),
+        }
+        .to_theme(false);
+        let mut table = Table::new(options, None, &theme, false);
+
+        let rows = sizes
+            .iter()
+            .map(|size| Row {
+                cells: vec![TextCell::paint_str(Style::default(), size)],
+            })
+            .collect::<Vec<_>>();
+        for row in &rows {
+            table.add_widths(row);
+        }
+
+        rows.into_iter()
+            .map(|row| table.render(row).strings().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn size_right_aligned_by_default() {
+        let options = options(ColumnAlignments::default());
+        assert_eq!(render_sizes(&options, &["1", "100"]), vec!["  1 ", "100 "]);
+    }
+
+    #[test]
+    fn size_left_aligned() {
+        let options = options(ColumnAlignments(vec![("size", Alignment::Left)]));
+        assert_eq!(render_sizes(&options, &["1", "100"]), vec!["1   ", "100 "]);
+    }
+
+    /// The names of the columns that would be shown with the given order.
+    fn ordered_names(order: Vec<ColumnKind>, git: bool, enable_git: bool) -> Vec<&'static str> {
+        let mut options = options(ColumnAlignments::default());
+        options.columns.git = git;
+        options.columns.order = Some(order);
+        options
+            .columns
+            .collect(enable_git, false)
+            .into_iter()
+            .map(Column::name)
+            .collect()
+    }
+
+    #[test]
+    fn column_order() {
+        let order = vec![
+            ColumnKind::Timestamp(TimeType::Modified),
+            ColumnKind::Size,
+            ColumnKind::Permissions,
+        ];
+        assert_eq!(
+            ordered_names(order, false, false),
+            vec!["modified", "size", "permissions"]
+        );
+    }
+
+    #[test]
+    fn column_order_git() {
+        let order = vec![ColumnKind::Git, ColumnKind::Size];
+        assert_eq!(ordered_names(order.clone(), false, true), vec!["size"]);
+        assert_eq!(ordered_names(order.clone(), true, false), vec!["size"]);
+        assert_eq!(ordered_names(order, true, true), vec!["git", "size"]);
+    }
+
+    #[test]
+    fn git_next_to_name() {
+        let theme = ThemeOptions {
+            use_colours: UseColours::Never,
+            colour_scale: ColorScaleOptions {
+                mode: ColorScaleMode::Fixed,
+                min_luminance: 40,
+                max_luminance: 100,
+                size: false,
+                age: false,
+                inode: false,
+                links: false,
+                age_time: None,
+                gradient: None,
+                recent_threshold: None,
+            },
+            definitions: Default::default(),
+        }
+        .to_theme(false);
+        let git = std::iter::empty().collect::<GitCache>();
+
+        let mut options = options(ColumnAlignments::default());
+        options.columns.git = true;
+        options.columns.subdir_git_repos = true;
+
+        // The name is written straight after the last of the table’s
+        // columns, so that’s where the Git status should end up.
+        let header = |options: &Options| {
+            let mut table = Table::new(options, Some(&git), &theme, true);
+            let row = table.header_row();
+            table.add_widths(&row);
+            table.render(row).strings().to_string()
+        };
+
+        assert_eq!(header(&options), "Size Git Repo ");
+        options.columns.git_left = true;
+        assert_eq!(header(&options), "Size Repo Git ");
+    }
+
+    #[test]
+    fn combined_times() {
+        let mut options = options(ColumnAlignments::default());
+        options.time_format = TimeFormat::Combined;
+        options.columns.filesize = false;
+        options.columns.time_types.created = true;
+        options.columns.time_types.modified = true;
+
+        let theme = ThemeOptions {
+            use_colours: UseColours::Never,
+            colour_scale: ColorScaleOptions {
+                mode: ColorScaleMode::Fixed,
+                min_luminance: 40,
+                max_luminance: 100,
+                size: false,
+                age: false,
+                inode: false,
+                links: false,
+                age_time: None,
+                gradient: None,
+                recent_threshold: None,
+            },
+            definitions: Default::default(),
+        }
+        .to_theme(false);
+        let table = Table::new(&options, None, &theme, false);
+        assert_eq!(table.header_row().cells.len(), 1);
+
+        let dir = TestDir::new("combined");
+        let path = dir.join("file");
+        std::fs::write(&path, "").unwrap();
+        let file = File::from_args(path, None, None, false, false).unwrap();
+        let row = table.row_for_file(&file, 0, false, None);
+
+        let text = row.cells[0].contents.strings().to_string();
+        assert!(text.starts_with("cr "), "{text}");
+        assert!(text.contains(" / mod "), "{text}");
+        assert!(text.ends_with('s'), "{text}");
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn io_blocksize() {
+        use std::os::unix::fs::MetadataExt;
+
+        let theme = ThemeOptions {
+            use_colours: UseColours::Never,
+            colour_scale: ColorScaleOptions {
+                mode: ColorScaleMode::Fixed,
+                min_luminance: 40,
+                max_luminance: 100,
+                size: false,
+                age: false,
+                inode: false,
+                links: false,
+                age_time: None,
+                gradient: None,
+                recent_threshold: None,
+            },
+            definitions: Default::default(),
+        }
+        .to_theme(false);
+
+        let temp = TestDir::new("io-blocksize");
+        let path = temp.join("file");
+        std::fs::write(&path, "data").unwrap();
+        let file = File::from_args(path.clone(), None, None, false, false).unwrap();
+        let dir = File::from_args(temp.to_path_buf(), None, None, false, false).unwrap();
+        let expected = std::fs::metadata(&path).unwrap().blksize().to_string();
+
+        let mut options = options(ColumnAlignments::default());
+        options.columns.io_blocksize = true;
+        let table = Table::new(&options, None, &theme, false);
+        let file_cell = table.cell_for_file(&file, Column::IoBlocksize);
+        let dir_cell = table.cell_for_file(&dir, Column::IoBlocksize);
+
+        assert_eq!(file_cell.strings().to_string(), expected);
+        assert_eq!(dir_cell.strings().to_string(), "-");
+    }
+
+    #[test]
+    fn checksums() {
+        let theme = ThemeOptions {
+            use_colours: UseColours::Never,
+            colour_scale: ColorScaleOptions {
+                mode: ColorScaleMode::Fixed,
+                min_luminance: 40,
+                max_luminance: 100,
+                size: false,
+                age: false,
+                inode: false,
+                links: false,
+                age_time: None,
+                gradient: None,
+                recent_threshold: None,
+            },
+            definitions: Default::default(),
+        }
+        .to_theme(false);
+
+        let temp = TestDir::new("checksum");
+        let path = temp.join("file");
+        std::fs::write(&path, "123456789").unwrap();
+        let file = File::from_args(path, None, None, false, false).unwrap();
+        let dir = File::from_args(temp.to_path_buf(), None, None, false, false).unwrap();
+
+        let options = options(ColumnAlignments::default());
+        let table = Table::new(&options, None, &theme, false);
+        let crc32 = table.cell_for_file(&file, Column::Checksum(Algorithm::Crc32));
+        let sha256 = table.cell_for_file(&file, Column::Checksum(Algorithm::Sha256));
+        let dir_cell = table.cell_for_file(&dir, Column::Checksum(Algorithm::Sha256));
+
+        assert_eq!(crc32.strings().to_string(), "cbf43926");
+        assert_eq!(
+            sha256.strings().to_string(),
+            "15e2b0d3c33891ebb0f1ef609ec419420c20e320ce94c65fbc8c3312448eb225"
+        );
+        assert_eq!(dir_cell.strings().to_string(), "-");
+    }
+
+    #[test]
+    fn mime_types() {
+        let theme = ThemeOptions {
+            use_colours: UseColours::Never,
+            colour_scale: ColorScaleOptions {
+                mode: ColorScaleMode::Fixed,
+                min_luminance: 40,
+                max_luminance: 100,
+                size: false,
+                age: false,
+                inode: false,
+                links: false,
+                age_time: None,
+                gradient: None,
+                recent_threshold: None,
+            },
+            definitions: Default::default(),
+        }
+        .to_theme(false);
+
+        let dir = TestDir::new("mime");
+        std::fs::write(dir.join("image"), b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR").unwrap();
+        std::fs::write(dir.join("document"), "%PDF-1.4\n%%EOF\n").unwrap();
+        std::fs::write(dir.join("empty"), "").unwrap();
+
+        let options = options(ColumnAlignments::default());
+        let table = Table::new(&options, None, &theme, false);
+        let cell = |path: std::path::PathBuf| {
+            let file = File::from_args(path, None, None, false, false).unwrap();
+            table
+                .cell_for_file(&file, Column::Mime)
+                .strings()
+                .to_string()
+        };
+
+        let png = cell(dir.join("image"));
+        let pdf = cell(dir.join("document"));
+        let empty = cell(dir.join("empty"));
+        let directory = cell(dir.to_path_buf());
+
+        assert_eq!(png, "image/png");
+        assert_eq!(pdf, "application/pdf");
+        assert_eq!(empty, "-");
+        assert_eq!(directory, "-");
+    }
+
+    #[test]
+    fn dir_sizes() {
+        let theme = ThemeOptions {
+            use_colours: UseColours::Never,
+            colour_scale: ColorScaleOptions {
+                mode: ColorScaleMode::Fixed,
+                min_luminance: 40,
+                max_luminance: 100,
+                size: false,
+                age: false,
+                inode: false,
+                links: false,
+                age_time: None,
+                gradient: None,
+                recent_threshold: None,
+            },
+            definitions: Default::default(),
+        }
+        .to_theme(false);
+
+        let path = TestDir::new("dir-size");
+        std::fs::write(path.join("a"), "aaa").unwrap();
+        let dir = File::from_args(path.to_path_buf(), None, None, false, true).unwrap();
+        let inode_size = ENVIRONMENT.numeric.format_int(dir.metadata.len());
+
+        let mut sizes = Vec::new();
+        for dir_size in [DirSize::None, DirSize::Inode, DirSize::Total] {
+            let mut options = options(ColumnAlignments::default());
+            options.dir_size = dir_size;
+            let table = Table::new(&options, None, &theme, false);
+            let cell = table.cell_for_file(&dir, Column::FileSize);
+            sizes.push(cell.strings().to_string());
+        }
+
+        assert_eq!(
+            sizes,
+            vec![String::from("-"), inode_size, String::from("3")]
+        );
+    }
+
+    #[test]
+    fn last_override_wins() {
+        let alignments =
+            ColumnAlignments(vec![("size", Alignment::Left), ("size", Alignment::Right)]);
+        let options = options(alignments);
+        assert_eq!(render_sizes(&options, &["1", "100"]), vec!["  1 ", "100 "]);
+    }
+}
@@ -0,0 +1,313 @@
+//! The template view, which writes one line per file in a format given with
+//! `--format-template`, such as `{name} {size} {mtime}`.
+//!
+//! Each placeholder is replaced by the same cell the details view would show
+//! for that field, so the values are formatted and coloured the same way.
+//! Doubled braces, `{{` and `}}`, stand for the braces themselves.
+
+use std::fmt;
+use std::io::{self, Write};
+
+use crate::fs::feature::git::GitCache;
+use crate::fs::fields as f;
+use crate::fs::filter::FileFilter;
+use crate::fs::File;
+use crate::output::file_name::Options as FileStyle;
+use crate::output::table::{Column, Options as TableOptions, Table, TimeType};
+use crate::theme::Theme;
+
+#[derive(PartialEq, Eq, Debug)]
+pub struct Options {
+    /// The template to fill in for each file.
+    pub template: Template,
+
+    /// How to format each of the fields, as in the details view.
+    pub table: TableOptions,
+}
+
+/// A parsed template: the text between the placeholders, and the fields
+/// they stand for.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Template(Vec<Part>);
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+enum Part {
+    Text(String),
+    Field(Field),
+}
+
+/// A piece of information about a file that a placeholder can stand for.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum Field {
+    Name,
+    Size,
+    Bytes,
+    Perms,
+    Octal,
+    User,
+    Group,
+    Mtime,
+    Inode,
+    Links,
+    Git,
+}
+
+impl Field {
+    /// Every field, by the name used for it in templates.
+    const NAMES: &'static [(&'static str, Self)] = &[
+        ("name", Self::Name),
+        ("size", Self::Size),
+        ("bytes", Self::Bytes),
+        ("perms", Self::Perms),
+        ("octal", Self::Octal),
+        ("user", Self::User),
+        ("group", Self::Group),
+        ("mtime", Self::Mtime),
+        ("inode", Self::Inode),
+        ("links", Self::Links),
+        ("git", Self::Git),
+    ];
+
+    fn from_name(name: &str) -> Option<Self> {
+        Self::NAMES
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, field)| *field)
+    }
+
+    /// The details view column that shows this field, if it has one on
+    /// this platform. The name and byte count are written separately.
+    fn column(self) -> Option<Column> {
+        match self {
+            Self::Size => Some(Column::FileSize),
+            Self::Perms => Some(Column::Permissions),
+            Self::Mtime => Some(Column::Timestamp(TimeType::Modified)),
+            Self::Git => Some(Column::GitStatus),
+            #[cfg(unix)]
+            Self::Octal => Some(Column::Octal),
+            #[cfg(unix)]
+            Self::User => Some(Column::User),
+            #[cfg(unix)]
+            Self::Group => Some(Column::Group),
+            #[cfg(unix)]
+            Self::Inode => Some(Column::Inode),
+            #[cfg(unix)]
+            Self::Links => Some(Column::HardLinks),
+            _ => None,
+        }
+    }
+}
+
+/// A problem with a template, found when parsing it.
+#[derive(PartialEq, Eq, Debug)]
+pub enum TemplateError {
+    /// A placeholder named a field that doesn’t exist.
+    UnknownField(String),
+
+    /// A placeholder was opened with `{` but never closed.
+    Unclosed(String),
+}
+
+impl fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownField(name) => {
+                let names = Field::NAMES
+                    .iter()
+                    .map(|(n, _)| *n)
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                write!(f, "Unknown placeholder {{{name}}} (choices: {names})")
+            }
+            Self::Unclosed(name) => write!(f, "Placeholder {{{name} is missing its closing brace"),
+        }
+    }
+}
+
+impl Template {
+    pub fn parse(input: &str) -> Result<Self, TemplateError> {
+        let mut parts = Vec::new();
+        let mut text = String::new();
+        let mut chars = input.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '{' | '}' if chars.peek() == Some(&c) => {
+                    chars.next();
+                    text.push(c);
+                }
+                '{' => {
+                    let mut name = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => name.push(c),
+                            None => return Err(TemplateError::Unclosed(name)),
+                        }
+                    }
+
+                    let field = Field::from_name(&name).ok_or(TemplateError::UnknownField(name))?;
+                    if !text.is_empty() {
+                        parts.push(Part::Text(std::mem::take(&mut text)));
+                    }
+                    parts.push(Part::Field(field));
+                }
+                c => text.push(c),
+            }
+        }
+
+        if !text.is_empty() {
+            parts.push(Part::Text(text));
+        }
+
+        Ok(Self(parts))
+    }
+
+    /// Whether any of the template’s placeholders stand for the given field.
+    pub fn uses(&self, field: Field) -> bool {
+        self.0.contains(&Part::Field(field))
+    }
+}
+
+pub struct Render<'a> {
+    pub files: Vec<File<'a>>,
+    pub theme: &'a Theme,
+    pub file_style: &'a FileStyle,
+    pub opts: &'a Options,
+    pub filter: &'a FileFilter,
+    pub git: Option<&'a GitCache>,
+}
+
+impl<'a> Render<'a> {
+    pub fn render<W: Write>(mut self, w: &mut W) -> io::Result<()> {
+        self.filter.sort_files(&mut self.files, self.git);
+        let table = Table::new(&self.opts.table, self.git, self.theme, false);
+
+        for file in &self.files {
+            for part in &self.opts.template.0 {
+                match *part {
+                    Part::Text(ref text) => write!(w, "{text}")?,
+                    Part::Field(Field::Name) => {
+                        let name = self
+                            .file_style
+                            .for_file(file, self.theme)
+                            .with_git_ignored(self.git)
+                            .paint();
+                        write!(w, "{}", name.strings())?;
+                    }
+                    Part::Field(Field::Bytes) => match file.size() {
+                        f::Size::Some(bytes) => write!(w, "{bytes}")?,
+                        _ => write!(w, "-")?,
+                    },
+                    Part::Field(field) => match field.column() {
+                        Some(column) => {
+                            write!(w, "{}", table.cell_for_file(file, column).strings())?
+                        }
+                        None => write!(w, "-")?,
+                    },
+                }
+            }
+            writeln!(w)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fs::test_dir::TestDir;
+    use crate::options::{Options as AllOptions, OptionsResult};
+    use crate::output::Mode;
+    use std::ffi::OsStr;
+    use std::path::Path;
+
+    #[test]
+    fn parse_fields() {
+        let template = Template::parse("{name}: {size}").unwrap();
+        assert_eq!(
+            template,
+            Template(vec![
+                Part::Field(Field::Name),
+                Part::Text(String::from(": ")),
+                Part::Field(Field::Size),
+            ])
+        );
+        assert!(template.uses(Field::Size));
+        assert!(!template.uses(Field::Git));
+    }
+
+    #[test]
+    fn parse_braces() {
+        let template = Template::parse("{{{bytes}}}").unwrap();
+        assert_eq!(
+            template,
+            Template(vec![
+                Part::Text(String::from("{")),
+                Part::Field(Field::Bytes),
+                Part::Text(String::from("}")),
+            ])
+        );
+    }
+
+    #[test]
+    fn parse_unknown() {
+        assert_eq!(
+            Template::parse("{name} {colour}"),
+            Err(TemplateError::UnknownField(String::from("colour")))
+        );
+    }
+
+    #[test]
+    fn parse_unclosed() {
+        assert_eq!(
+            Template::parse("{name} {size"),
+            Err(TemplateError::Unclosed(String::from("size")))
+        );
+    }
+
+    /// Lists the given files of a directory with the given template, as
+    /// eza would without colours.
+    fn rendered(dir: &Path, names: &[&str], template: &str) -> String {
+        let args = ["--color=never", "--format-template", template];
+        let OptionsResult::Ok(options, _) = AllOptions::parse(args.iter().map(OsStr::new), &None)
+        else {
+            panic!("Failed to parse {args:?}");
+        };
+        let Mode::Template(ref opts) = options.view.mode else {
+            panic!("Not a template view: {:?}", options.view.mode);
+        };
+
+        let files = names
+            .iter()
+            .map(|name| File::from_args(dir.join(name), None, None, false, false).unwrap())
+            .collect();
+        let theme = options.theme.to_theme(false);
+        let render = Render {
+            files,
+            theme: &theme,
+            file_style: &options.view.file_style,
+            opts,
+            filter: &options.filter,
+            git: None,
+        };
+
+        let mut output = Vec::new();
+        render.render(&mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn names_and_sizes() {
+        let dir = TestDir::new("template");
+        std::fs::write(dir.join("a"), "aaa").unwrap();
+        std::fs::write(dir.join("b"), "b".repeat(1500)).unwrap();
+
+        let sizes = rendered(&dir, &["b", "a"], "{name} is {bytes} bytes ({size})");
+        let braces = rendered(&dir, &["a"], "{{{name}}}");
+
+        assert_eq!(sizes, "a is 3 bytes (3)\nb is 1500 bytes (1.5k)\n");
+        assert_eq!(braces, "{a}\n");
+    }
+}
@@ -27,31 +27,46 @@ pub enum TimeFormat {
     /// The **default format** uses the user’s locale to print month names,
     /// and specifies the timestamp down to the minute for recent times, and
     /// day for older times.
-    DefaultFormat,
+    DefaultFormat { utc: bool },
 
     /// Use the **ISO format**, which specifies the timestamp down to the
     /// minute for recent times, and day for older times. It uses a number
     /// for the month so it doesn’t use the locale.
-    ISOFormat,
+    ISOFormat { utc: bool },
 
     /// Use the **long ISO format**, which specifies the timestamp down to the
     /// minute using only numbers, without needing the locale or year.
-    LongISO,
+    LongISO { utc: bool },
 
     /// Use the **full ISO format**, which specifies the timestamp down to the
     /// millisecond and includes its offset down to the minute. This too uses
     /// only numbers so doesn’t require any special consideration.
-    FullISO,
+    FullISO { utc: bool },
 
     /// Use a relative but fixed width representation.
     Relative,
 
+    /// Use a relative representation with the number right-aligned and the
+    /// unit padded, so a column of them lines up.
+    AlignedRelative,
+
+    /// Use a compact relative representation, such as “3d”, and show all
+    /// the selected timestamps together in one column, each after a label.
+    Combined,
+
     /// Use custom formats, optionally a different custom format can be
     /// specified for recent times, otherwise the same custom format will be
     /// used for both recent and non-recent times.
     Custom {
         non_recent: String,
         recent: Option<String>,
+
+        /// How new a time has to be to count as recent, from
+        /// `EZA_RECENT_THRESHOLD`, instead of being from this year.
+        recent_threshold: Option<Duration>,
+
+        /// Whether to show the time in UTC rather than local time.
+        utc: bool,
     },
 }
 
@@ -59,18 +74,30 @@ impl TimeFormat {
     pub fn format(self, time: &DateTime<FixedOffset>) -> String {
         #[rustfmt::skip]
         return match self {
-            Self::DefaultFormat                 => default(time),
-            Self::ISOFormat                     => iso(time),
-            Self::LongISO                       => long(time),
-            Self::FullISO                       => full(time),
+            Self::DefaultFormat { utc }         => default(&in_zone(time, utc)),
+            Self::ISOFormat { utc }             => iso(&in_zone(time, utc)),
+            Self::LongISO { utc }               => long(&in_zone(time, utc)),
+            Self::FullISO { utc }               => full(&in_zone(time, utc)),
             Self::Relative                      => relative(time),
-            Self::Custom { non_recent, recent } => custom(
-                time, non_recent.as_str(), recent.as_deref()
+            Self::AlignedRelative               => aligned_relative(time),
+            Self::Combined                      => compact_relative(time),
+            Self::Custom { non_recent, recent, recent_threshold, utc } => custom(
+                &in_zone(time, utc), non_recent.as_str(), recent.as_deref(), recent_threshold
             ),
         };
     }
 }
 
+/// The time as it is in UTC if that was asked for, or left in the offset it
+/// came with, which is local time.
+fn in_zone(time: &DateTime<FixedOffset>, utc: bool) -> DateTime<FixedOffset> {
+    if utc {
+        time.with_timezone(&Utc.fix())
+    } else {
+        *time
+    }
+}
+
 fn default(time: &DateTime<FixedOffset>) -> String {
     let month = &*LOCALE.short_month_name(time.month0() as usize);
     let month_width = short_month_padding(*MAX_MONTH_WIDTH, month);
@@ -107,24 +134,95 @@ fn long(time: &DateTime<FixedOffset>) -> String {
 
 // #[allow(trivial_numeric_casts)]
 fn relative(time: &DateTime<FixedOffset>) -> String {
-    timeago::Formatter::new()
-        .ago("")
-        .convert(Duration::from_secs(
-            max(0, Local::now().timestamp() - time.timestamp())
-                // this .unwrap is safe since the call above can never result in a
-                // value < 0
-                .try_into()
-                .unwrap(),
-        ))
+    timeago::Formatter::new().ago("").convert(age(time))
+}
+
+fn aligned_relative(time: &DateTime<FixedOffset>) -> String {
+    align_relative(&relative(time))
+}
+
+fn compact_relative(time: &DateTime<FixedOffset>) -> String {
+    compact_age(age(time))
+}
+
+/// Formats an age as a whole number of its largest unit, with a short
+/// suffix, such as “3d” or “1h”, so several fit side by side.
+fn compact_age(age: Duration) -> String {
+    const MINUTE: u64 = 60;
+    const HOUR: u64 = 60 * MINUTE;
+    const DAY: u64 = 24 * HOUR;
+    const WEEK: u64 = 7 * DAY;
+    const MONTH: u64 = 30 * DAY;
+    const YEAR: u64 = 365 * DAY;
+
+    let secs = age.as_secs();
+    let (unit, suffix) = [
+        (YEAR, "y"),
+        (MONTH, "mo"),
+        (WEEK, "w"),
+        (DAY, "d"),
+        (HOUR, "h"),
+        (MINUTE, "m"),
+    ]
+    .into_iter()
+    .find(|(unit, _)| secs >= *unit)
+    .unwrap_or((1, "s"));
+
+    format!("{}{suffix}", secs / unit)
+}
+
+/// How long ago the given time was, counting times in the future as now.
+fn age(time: &DateTime<FixedOffset>) -> Duration {
+    Duration::from_secs(
+        max(0, Local::now().timestamp() - time.timestamp())
+            // this .unwrap is safe since the call above can never result in a
+            // value < 0
+            .try_into()
+            .unwrap(),
+    )
+}
+
+/// The widest number and unit in a relative time, such as “59 minutes”.
+/// Years can go past two digits, but there won’t be many of those.
+const RELATIVE_NUMBER_WIDTH: usize = 2;
+const RELATIVE_UNIT_WIDTH: usize = 7;
+
+/// Right-aligns the number in a relative time, such as “2 days”, and pads
+/// its unit, so every one of them has the same width. Anything without a
+/// number, such as “now”, is padded to the same width.
+fn align_relative(relative: &str) -> String {
+    match relative.split_once(' ') {
+        Some((number, unit)) => {
+            format!("{number:>RELATIVE_NUMBER_WIDTH$} {unit:<RELATIVE_UNIT_WIDTH$}")
+        }
+        None => format!(
+            "{relative:<width$}",
+            width = RELATIVE_NUMBER_WIDTH + 1 + RELATIVE_UNIT_WIDTH
+        ),
+    }
 }
 
 fn full(time: &DateTime<FixedOffset>) -> String {
     time.format("%Y-%m-%d %H:%M:%S.%f %z").to_string()
 }
 
-fn custom(time: &DateTime<FixedOffset>, non_recent_fmt: &str, recent_fmt: Option<&str>) -> String {
+/// Whether a time counts as recent: no older than the given threshold, or
+/// from this year without one.
+pub fn is_recent(time: &DateTime<FixedOffset>, threshold: Option<Duration>) -> bool {
+    match threshold {
+        Some(threshold) => Utc::now().timestamp() - time.timestamp() <= threshold.as_secs() as i64,
+        None => time.year() == *CURRENT_YEAR,
+    }
+}
+
+fn custom(
+    time: &DateTime<FixedOffset>,
+    non_recent_fmt: &str,
+    recent_fmt: Option<&str>,
+    recent_threshold: Option<Duration>,
+) -> String {
     if let Some(recent_fmt) = recent_fmt {
-        if time.year() == *CURRENT_YEAR {
+        if is_recent(time, recent_threshold) {
             time.format(recent_fmt).to_string()
         } else {
             time.format(non_recent_fmt).to_string()
@@ -153,6 +251,101 @@ static MAX_MONTH_WIDTH: Lazy<usize> = Lazy::new(|| {
 mod test {
     use super::*;
 
+    #[test]
+    fn custom_recent() {
+        let now: DateTime<FixedOffset> = Local::now().into();
+        let format = TimeFormat::Custom {
+            non_recent: String::from("old %Y"),
+            recent: Some(String::from("new %Y")),
+            recent_threshold: None,
+            utc: false,
+        };
+        assert_eq!(format.format(&now), now.format("new %Y").to_string());
+    }
+
+    #[test]
+    fn custom_recent_threshold() {
+        let week = Duration::from_secs(7 * 86_400);
+        let format = TimeFormat::Custom {
+            non_recent: String::from("old"),
+            recent: Some(String::from("new")),
+            recent_threshold: Some(week),
+            utc: false,
+        };
+
+        let now: DateTime<FixedOffset> = Local::now().into();
+        let inside = now - chrono::Duration::days(6);
+        let outside = now - chrono::Duration::days(8);
+        assert_eq!(format.clone().format(&inside), "new");
+        assert_eq!(format.format(&outside), "old");
+    }
+
+    #[test]
+    fn custom_without_recent() {
+        // Without a second line, as with `--no-recent-style`, even a file
+        // from this year gets the first line’s format.
+        let now: DateTime<FixedOffset> = Local::now().into();
+        let format = TimeFormat::Custom {
+            non_recent: String::from("old %Y"),
+            recent: None,
+            recent_threshold: None,
+            utc: false,
+        };
+        assert_eq!(format.format(&now), now.format("old %Y").to_string());
+    }
+
+    #[test]
+    fn utc() {
+        let time = FixedOffset::east_opt(9 * 3600)
+            .unwrap()
+            .with_ymd_and_hms(2023, 9, 30, 1, 30, 0)
+            .unwrap();
+        let local = TimeFormat::FullISO { utc: false };
+        let utc = TimeFormat::FullISO { utc: true };
+        assert_eq!(local.format(&time), "2023-09-30 01:30:00.000000000 +0900");
+        assert_eq!(utc.format(&time), "2023-09-29 16:30:00.000000000 +0000");
+    }
+
+    #[test]
+    fn aligned_relative_ages() {
+        let ages = [5, 45 * 60, 2 * 86_400, 3 * 7 * 86_400, 200 * 86_400, 0].map(|secs| {
+            align_relative(
+                &timeago::Formatter::new()
+                    .ago("")
+                    .convert(Duration::from_secs(secs)),
+            )
+        });
+
+        assert_eq!(
+            ages,
+            [
+                " 5 seconds",
+                "45 minutes",
+                " 2 days   ",
+                " 3 weeks  ",
+                " 6 months ",
+                "now       ",
+            ]
+        );
+    }
+
+    #[test]
+    fn compact_ages() {
+        let ages = [
+            0,
+            59,
+            60,
+            3_600,
+            3 * 86_400,
+            15 * 86_400,
+            40 * 86_400,
+            800 * 86_400,
+        ]
+        .map(|secs| compact_age(Duration::from_secs(secs)));
+
+        assert_eq!(ages, ["0s", "59s", "1m", "1h", "3d", "2w", "1mo", "2y"]);
+    }
+
     #[test]
     fn short_month_width_japanese() {
         let max_month_width = 4;
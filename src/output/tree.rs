@@ -37,6 +37,29 @@
 //! of each file to see if it’s the last one. (As some files may not be
 //! successfully `stat`ted, we don’t know how many files are going to exist in
 //! each directory)
+//!
+//! ## The Indented Format
+//!
+//! With `--tree-format=indent`, a tree is written out in a form that can be
+//! read back in with `--from-tree`. Each line holds one entry, indented by two
+//! spaces for every level of depth, with a trailing `/` for directories:
+//!
+//! ```text
+//!     documents/
+//!       this_file.txt
+//!       features/
+//!         feature_1.rs
+//! ```
+//!
+//! Backslashes, control characters, and a leading space in a name are
+//! escaped with a backslash, and bytes that aren’t valid UTF-8 are written
+//! as `\xNN`, so any file name survives the round trip.
+
+use std::ffi::OsString;
+use std::fmt;
+use std::io;
+
+use super::escape::{name_from_bytes, name_parts, NamePart};
 
 #[derive(PartialEq, Eq, Debug, Copy, Clone)]
 pub enum TreePart {
@@ -171,6 +194,176 @@ impl TreeDepth {
     }
 }
 
+/// One entry of a tree in the indented format.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct IndentedEntry {
+    /// How many directories deep into the tree this entry is.
+    pub depth: usize,
+
+    /// The entry’s file name, unescaped.
+    pub name: OsString,
+
+    /// Whether the entry is a directory, written as a trailing slash.
+    pub is_directory: bool,
+}
+
+impl fmt::Display for IndentedEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:width$}", "", width = self.depth * 2)?;
+
+        let mut first = true;
+        for part in name_parts(&self.name) {
+            let text = match part {
+                NamePart::Text(text) => text,
+                NamePart::Byte(byte) => {
+                    write!(f, "\\x{byte:02x}")?;
+                    first = false;
+                    continue;
+                }
+            };
+
+            for c in text.chars() {
+                match c {
+                    '\\' => f.write_str("\\\\")?,
+                    '\n' => f.write_str("\\n")?,
+                    '\t' => f.write_str("\\t")?,
+                    '\r' => f.write_str("\\r")?,
+                    ' ' if first => f.write_str("\\ ")?,
+                    c if c.is_control() => write!(f, "\\u{{{:x}}}", c as u32)?,
+                    c => write!(f, "{c}")?,
+                }
+                first = false;
+            }
+        }
+
+        if self.is_directory {
+            f.write_str("/")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl IndentedEntry {
+    /// Parses a single line of the indented format, which should have had
+    /// its line ending removed.
+    fn parse(line: &str) -> Result<Self, String> {
+        let content = line.trim_start_matches(' ');
+        let indent = line.len() - content.len();
+        if indent % 2 != 0 {
+            return Err(format!("odd indentation of {indent} spaces"));
+        }
+
+        // The name is built up as bytes, as `\xNN` escapes can stand for
+        // bytes that aren’t valid UTF-8 on their own.
+        let mut name = Vec::new();
+        let mut is_directory = false;
+        let mut chars = content.chars();
+        let push = |name: &mut Vec<u8>, c: char| {
+            name.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes());
+        };
+
+        while let Some(c) = chars.next() {
+            match c {
+                '/' if chars.as_str().is_empty() => is_directory = true,
+                '\\' => match chars.next() {
+                    Some('n') => push(&mut name, '\n'),
+                    Some('t') => push(&mut name, '\t'),
+                    Some('r') => push(&mut name, '\r'),
+                    Some('u') => push(&mut name, Self::parse_codepoint(&mut chars)?),
+                    Some('x') => name.push(Self::parse_byte(&mut chars)?),
+                    Some(e) => push(&mut name, e),
+                    None => return Err("trailing backslash".into()),
+                },
+                c => push(&mut name, c),
+            }
+        }
+
+        if name.is_empty() {
+            return Err("empty name".into());
+        }
+
+        Ok(Self {
+            depth: indent / 2,
+            name: name_from_bytes(name),
+            is_directory,
+        })
+    }
+
+    /// Parses the two hex digits of a `\xNN` escape.
+    fn parse_byte(chars: &mut std::str::Chars<'_>) -> Result<u8, String> {
+        let rest = chars.as_str();
+        let hex = rest
+            .get(..2)
+            .filter(|hex| hex.bytes().all(|b| b.is_ascii_hexdigit()))
+            .ok_or_else(|| String::from("malformed \\x escape"))?;
+
+        let byte = u8::from_str_radix(hex, 16).map_err(|e| e.to_string())?;
+        *chars = rest[2..].chars();
+        Ok(byte)
+    }
+
+    /// Parses the `{hex}` part of a `\u{hex}` escape.
+    fn parse_codepoint(chars: &mut std::str::Chars<'_>) -> Result<char, String> {
+        let rest = chars.as_str();
+        let hex = rest
+            .strip_prefix('{')
+            .and_then(|r| r.split_once('}'))
+            .map(|(hex, _)| hex)
+            .ok_or_else(|| String::from("malformed \\u escape"))?;
+
+        let c = u32::from_str_radix(hex, 16)
+            .ok()
+            .and_then(char::from_u32)
+            .ok_or_else(|| format!("invalid character \\u{{{hex}}}"))?;
+
+        *chars = rest[hex.len() + 2..].chars();
+        Ok(c)
+    }
+}
+
+/// Reads a tree written in the indented format, returning each entry along
+/// with the tree parameters needed to draw it. Blank lines are skipped.
+pub fn read_indented(input: &str) -> io::Result<Vec<(TreeParams, IndentedEntry)>> {
+    let mut entries: Vec<IndentedEntry> = Vec::new();
+
+    for (number, line) in input.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let invalid = |message| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("line {}: {message}", number + 1),
+            )
+        };
+
+        let entry = IndentedEntry::parse(line).map_err(invalid)?;
+        let max_depth = entries.last().map_or(0, |e| e.depth + 1);
+        if entry.depth > max_depth {
+            return Err(invalid(format!(
+                "indented too far, to depth {}",
+                entry.depth
+            )));
+        }
+
+        entries.push(entry);
+    }
+
+    // An entry is the last in its directory if nothing else at its depth
+    // comes before the tree goes back up past it.
+    let params: Vec<_> = (0..entries.len())
+        .map(|index| {
+            let depth = entries[index].depth;
+            let next = entries[index + 1..].iter().find(|e| e.depth <= depth);
+            TreeParams::new(TreeDepth(depth), next.map(|e| e.depth) != Some(depth))
+        })
+        .collect();
+
+    Ok(params.into_iter().zip(entries).collect())
+}
+
 pub struct Iter<I> {
     current_depth: TreeDepth,
     inner: I,
@@ -283,3 +476,119 @@ mod iter_test {
         assert!(iter.next().is_none());
     }
 }
+
+#[cfg(test)]
+mod indented_test {
+    use super::*;
+
+    fn entry(depth: usize, name: &str, is_directory: bool) -> IndentedEntry {
+        IndentedEntry {
+            depth,
+            name: name.into(),
+            is_directory,
+        }
+    }
+
+    fn round_trip(entries: &[IndentedEntry]) -> Vec<IndentedEntry> {
+        let text: String = entries.iter().map(|e| format!("{e}\n")).collect();
+        read_indented(&text)
+            .unwrap()
+            .into_iter()
+            .map(|(_, e)| e)
+            .collect()
+    }
+
+    #[test]
+    fn write() {
+        assert_eq!(entry(0, "documents", true).to_string(), "documents/");
+        assert_eq!(entry(2, "feature.rs", false).to_string(), "    feature.rs");
+    }
+
+    #[test]
+    fn write_escaped() {
+        assert_eq!(entry(0, " a\\b\n", false).to_string(), "\\ a\\\\b\\n");
+        assert_eq!(entry(0, "bell\x07", false).to_string(), "bell\\u{7}");
+    }
+
+    #[test]
+    fn read() {
+        let entries = read_indented("dir/\n  a\n  sub/\n    b\n  c\n").unwrap();
+        let params: Vec<_> = entries.iter().map(|(p, _)| (p.depth.0, p.last)).collect();
+        assert_eq!(
+            params,
+            vec![(0, true), (1, false), (1, false), (2, true), (1, true)]
+        );
+        assert_eq!(entries[2].1, entry(1, "sub", true));
+    }
+
+    #[test]
+    fn round_trip_special_characters() {
+        let entries = vec![
+            entry(0, "root", true),
+            entry(1, "  leading spaces", false),
+            entry(1, "trailing space ", false),
+            entry(1, "back\\slash\\", true),
+            entry(2, "new\nline\ttab\rreturn", false),
+            entry(2, "escape\x1b[31m", false),
+            entry(1, "ünïcödé 🦀", false),
+            entry(0, "\\u{41}", false),
+        ];
+        assert_eq!(round_trip(&entries), entries);
+    }
+
+    #[test]
+    fn blank_lines() {
+        let entries = read_indented("a\n\nb\n").unwrap();
+        assert_eq!(entries.len(), 2);
+        assert!(!entries[0].0.last);
+        assert!(entries[1].0.last);
+    }
+
+    #[test]
+    fn odd_indentation() {
+        assert!(read_indented("a/\n   b\n").is_err());
+    }
+
+    #[test]
+    fn too_deep() {
+        assert!(read_indented("a/\n    b\n").is_err());
+    }
+
+    #[test]
+    fn bad_escape() {
+        assert!(read_indented("a\\").is_err());
+        assert!(read_indented("a\\u{d800}").is_err());
+        assert!(read_indented("a\\u{41").is_err());
+        assert!(read_indented("a\\x4").is_err());
+        assert!(read_indented("a\\xzz").is_err());
+    }
+
+    #[test]
+    fn read_byte_escapes() {
+        let entries = read_indented("caf\\xc3\\xa9\n").unwrap();
+        assert_eq!(entries[0].1, entry(0, "café", false));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn round_trip_invalid_utf8() {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+
+        let invalid = |depth, name: &[u8], is_directory| IndentedEntry {
+            depth,
+            name: OsStr::from_bytes(name).to_owned(),
+            is_directory,
+        };
+
+        let entries = vec![
+            invalid(0, b"caf\xe9", true),
+            invalid(1, b"\xff\xfe", false),
+            invalid(1, b" \xc3", false),
+            invalid(1, b"\\xff", false),
+        ];
+        assert_eq!(entries[0].to_string(), "caf\\xe9/");
+        assert_eq!(entries[3].to_string(), "  \\\\xff");
+        assert_eq!(round_trip(&entries), entries);
+    }
+}
@@ -61,6 +61,7 @@ impl UiStyles {
             links: Links {
                 normal:          Red.bold(),
                 multi_link_file: Red.on(Yellow),
+                min_links_file:  Yellow.on(Red).bold(),
             },
 
             #[rustfmt::skip]
@@ -114,12 +115,18 @@ impl UiStyles {
             blocks: Cyan.normal(),
             octal: Purple.normal(),
             flags: Style::default(),
+            checksum: Style::default(),
+            mime: Style::default(),
             header: Style::default().underline(),
 
             symlink_path: Cyan.normal(),
             control_char: Red.normal(),
             broken_symlink: Red.normal(),
             broken_path_overlay: Style::default().underline(),
+            risky: Black.on(Red),
+            ignored_overlay: Style::default().dimmed(),
+            immutable: Purple.bold().underline(),
+            append_only: Purple.underline(),
         }
     }
 }
@@ -40,6 +40,12 @@ pub enum UseColours {
 
     /// Never display them, even when output is going to a terminal.
     Never,
+
+    /// Write an HTML listing instead, with the colours as inline styles.
+    Html,
+
+    /// Write Pango markup instead, with the colours as span attributes.
+    Pango,
 }
 
 #[derive(PartialEq, Eq, Debug, Default)]
@@ -291,10 +297,49 @@ impl render::GroupColours for Theme {
     fn no_group(&self)   -> Style { self.ui.punctuation }
 }
 
+/// Finder draws its tags in a fixed set of colours, so these aren’t themed.
+#[cfg(target_os = "macos")]
+impl render::TagsColours for Theme {
+    fn tag(&self, colour: crate::fs::fields::TagColour) -> Style {
+        use crate::fs::fields::TagColour;
+        use nu_ansi_term::Color::*;
+
+        if !self.ui.colourful {
+            return Style::default();
+        }
+
+        match colour {
+            TagColour::None => Style::default(),
+            TagColour::Grey => DarkGray.normal(),
+            TagColour::Green => Green.normal(),
+            TagColour::Purple => Purple.normal(),
+            TagColour::Blue => Blue.normal(),
+            TagColour::Yellow => Yellow.normal(),
+            TagColour::Red => Red.normal(),
+            TagColour::Orange => Fixed(208).normal(),
+        }
+    }
+
+    fn no_tags(&self) -> Style {
+        self.ui.punctuation
+    }
+
+    fn control_char(&self) -> Style {
+        self.ui.control_char
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(unix)]
+impl render::OwnerColours for Theme {
+    fn separator(&self) -> Style { self.ui.punctuation }
+}
+
 #[rustfmt::skip]
 impl render::LinksColours for Theme {
     fn normal(&self)           -> Style { self.ui.links.normal }
     fn multi_link_file(&self)  -> Style { self.ui.links.multi_link_file }
+    fn min_links_file(&self)   -> Style { self.ui.links.min_links_file }
 }
 
 #[rustfmt::skip]
@@ -371,6 +416,10 @@ impl FileNameColours for Theme {
     fn broken_control_char(&self) -> Style { apply_overlay(self.ui.control_char,   self.ui.broken_path_overlay) }
     fn executable_file(&self)     -> Style { self.ui.filekinds.executable }
     fn mount_point(&self)         -> Style { self.ui.filekinds.mount_point }
+    fn risky(&self)               -> Style { self.ui.risky }
+    fn git_ignored(&self, base: Style) -> Style { apply_overlay(base, self.ui.ignored_overlay) }
+    fn immutable(&self)           -> Style { self.ui.immutable }
+    fn append_only(&self)         -> Style { self.ui.append_only }
 
     fn colour_file(&self, file: &File<'_>) -> Style {
         self.exts
@@ -564,6 +613,7 @@ mod customs_test {
 
     test!(exa_lc:  ls "", exa "lc=38;5;121"  =>  colours c -> { c.links.normal                          = Fixed(121).normal(); });
     test!(exa_lm:  ls "", exa "lm=38;5;122"  =>  colours c -> { c.links.multi_link_file                 = Fixed(122).normal(); });
+    test!(exa_lt:  ls "", exa "lt=38;5;123"  =>  colours c -> { c.links.min_links_file                  = Fixed(123).normal(); });
 
     test!(exa_ga:  ls "", exa "ga=38;5;123"  =>  colours c -> { c.git.new                               = Fixed(123).normal(); });
     test!(exa_gm:  ls "", exa "gm=38;5;124"  =>  colours c -> { c.git.modified                          = Fixed(124).normal(); });
@@ -582,7 +632,13 @@ mod customs_test {
     test!(exa_cc:  ls "", exa "cc=38;5;134"  =>  colours c -> { c.control_char                          = Fixed(134).normal(); });
     test!(exa_oc:  ls "", exa "oc=38;5;135"  =>  colours c -> { c.octal                                 = Fixed(135).normal(); });
     test!(exa_ff:  ls "", exa "ff=38;5;136"  =>  colours c -> { c.flags                                 = Fixed(136).normal(); });
+    test!(exa_cs:  ls "", exa "cs=38;5;139"  =>  colours c -> { c.checksum                              = Fixed(139).normal(); });
+    test!(exa_mt:  ls "", exa "mt=38;5;140"  =>  colours c -> { c.mime                                  = Fixed(140).normal(); });
     test!(exa_bo:  ls "", exa "bO=4"         =>  colours c -> { c.broken_path_overlay                   = Style::default().underline(); });
+    test!(exa_rk:  ls "", exa "rk=38;5;137"  =>  colours c -> { c.risky                                 = Fixed(137).normal(); });
+    test!(exa_io:  ls "", exa "iO=2"         =>  colours c -> { c.ignored_overlay                       = Style::default().dimmed(); });
+    test!(exa_im:  ls "", exa "Im=1;33"      =>  colours c -> { c.immutable                             = Yellow.bold(); });
+    test!(exa_ap:  ls "", exa "Ap=33"        =>  colours c -> { c.append_only                           = Yellow.normal(); });
 
     test!(exa_mp:  ls "", exa "mp=1;34;4"    =>  colours c -> { c.filekinds.mount_point                 = Blue.bold().underline(); });
     test!(exa_sp:  ls "", exa "sp=1;35;4"    =>  colours c -> { c.filekinds.special                     = Purple.bold().underline(); });
@@ -24,11 +24,17 @@ pub struct UiStyles {
     pub header:       Style,          // hd
     pub octal:        Style,          // oc
     pub flags:        Style,          // ff
+    pub checksum:     Style,          // cs
+    pub mime:         Style,          // mt
 
     pub symlink_path:         Style,  // lp
     pub control_char:         Style,  // cc
     pub broken_symlink:       Style,  // or
     pub broken_path_overlay:  Style,  // bO
+    pub risky:                Style,  // rk
+    pub ignored_overlay:      Style,  // iO
+    pub immutable:            Style,  // Im
+    pub append_only:          Style,  // Ap
 }
 
 #[rustfmt::skip]
@@ -103,6 +109,7 @@ pub struct Users {
 pub struct Links {
     pub normal: Style,           // lc
     pub multi_link_file: Style,  // lm
+    pub min_links_file: Style,   // lt
 }
 
 #[rustfmt::skip]
@@ -234,6 +241,7 @@ impl UiStyles {
 
             "lc" => self.links.normal                   = pair.to_style(),
             "lm" => self.links.multi_link_file          = pair.to_style(),
+            "lt" => self.links.min_links_file           = pair.to_style(),
 
             "ga" => self.git.new                        = pair.to_style(),
             "gm" => self.git.modified                   = pair.to_style(),
@@ -255,9 +263,15 @@ impl UiStyles {
             "hd" => self.header                         = pair.to_style(),
             "oc" => self.octal                          = pair.to_style(),
             "ff" => self.flags                          = pair.to_style(),
+            "cs" => self.checksum                       = pair.to_style(),
+            "mt" => self.mime                           = pair.to_style(),
             "lp" => self.symlink_path                   = pair.to_style(),
             "cc" => self.control_char                   = pair.to_style(),
             "bO" => self.broken_path_overlay            = pair.to_style(),
+            "rk" => self.risky                          = pair.to_style(),
+            "iO" => self.ignored_overlay                = pair.to_style(),
+            "Im" => self.immutable                      = pair.to_style(),
+            "Ap" => self.append_only                    = pair.to_style(),
 
             "mp" => self.filekinds.mount_point          = pair.to_style(),
             "sp" => self.filekinds.special              = pair.to_style(),  // Catch-all for unrecognized file kind